@@ -0,0 +1,258 @@
+use std::fmt;
+
+use ordinator_scheduling_environment::worker_environment::AcceptanceCriterion;
+use rand::Rng;
+
+/// Runtime state of the `AcceptanceCriterion` chosen in the options of an
+/// `Actor`. All objective values given to this type are costs, meaning that
+/// lower is better. See [`crate::traits::ObjectiveValue::cost`].
+pub struct Acceptance
+{
+    criterion: AcceptanceCriterion,
+    temperature: f64,
+    history: Vec<f64>,
+    iteration: usize,
+    record: f64,
+}
+
+impl Acceptance
+{
+    pub fn new(criterion: AcceptanceCriterion) -> Self
+    {
+        let temperature = match &criterion {
+            AcceptanceCriterion::SimulatedAnnealing {
+                initial_temperature,
+                ..
+            } => *initial_temperature,
+            _ => 0.0,
+        };
+
+        Self {
+            criterion,
+            temperature,
+            history: Vec::new(),
+            iteration: 0,
+            record: f64::INFINITY,
+        }
+    }
+
+    pub fn criterion(&self) -> &AcceptanceCriterion
+    {
+        &self.criterion
+    }
+
+    /// The options can change while the `Actor` is running. A new criterion
+    /// resets the temperature, history and record.
+    pub fn update_criterion(&mut self, criterion: &AcceptanceCriterion)
+    {
+        if &self.criterion != criterion {
+            *self = Self::new(criterion.clone());
+        }
+    }
+
+    /// Decides whether the candidate should replace the current solution.
+    /// This has to be called once on every LNS iteration, also when the
+    /// candidate is better, as the cooling schedule, the late acceptance
+    /// history and the record all advance here.
+    pub fn accept(&mut self, current_cost: f64, candidate_cost: f64, rng: &mut impl Rng) -> bool
+    {
+        self.iteration += 1;
+        self.record = self.record.min(current_cost);
+
+        let accepted = match &self.criterion {
+            AcceptanceCriterion::HillClimbing => candidate_cost < current_cost,
+            AcceptanceCriterion::SimulatedAnnealing {
+                cooling_rate,
+                minimum_temperature,
+                ..
+            } => {
+                let delta = candidate_cost - current_cost;
+                let accepted = delta <= 0.0
+                    || (self.temperature > 0.0
+                        && rng.random::<f64>() < (-delta / self.temperature).exp());
+
+                self.temperature = (self.temperature * cooling_rate).max(*minimum_temperature);
+                accepted
+            }
+            AcceptanceCriterion::LateAcceptance { history_length } => {
+                if self.history.is_empty() {
+                    self.history = vec![current_cost; (*history_length).max(1)];
+                }
+                let index = self.iteration % self.history.len();
+
+                let accepted =
+                    candidate_cost <= self.history[index] || candidate_cost <= current_cost;
+
+                self.history[index] = if accepted {
+                    candidate_cost
+                } else {
+                    current_cost
+                };
+                accepted
+            }
+            AcceptanceCriterion::RecordToRecord { .. } => candidate_cost <= self.threshold(),
+        };
+
+        if accepted {
+            self.record = self.record.min(candidate_cost);
+        }
+        accepted
+    }
+
+    /// Advances the criterion for a candidate that replaces the current
+    /// solution regardless of its cost. The late acceptance history and the
+    /// record are kept as if the candidate had been accepted.
+    pub fn force(&mut self, current_cost: f64, candidate_cost: f64, rng: &mut impl Rng)
+    {
+        if self.accept(current_cost, candidate_cost, rng) {
+            return;
+        }
+
+        if let AcceptanceCriterion::LateAcceptance { .. } = &self.criterion {
+            let index = self.iteration % self.history.len();
+            self.history[index] = candidate_cost;
+        }
+        self.record = self.record.min(candidate_cost);
+    }
+
+    /// The cost that a candidate has to beat to be accepted for the threshold
+    /// based criteria.
+    fn threshold(&self) -> f64
+    {
+        match &self.criterion {
+            AcceptanceCriterion::LateAcceptance { .. } => self
+                .history
+                .get(self.iteration % self.history.len().max(1))
+                .copied()
+                .unwrap_or(f64::INFINITY),
+            AcceptanceCriterion::RecordToRecord { deviation } => {
+                self.record + deviation * self.record.abs()
+            }
+            _ => self.record,
+        }
+    }
+}
+
+impl Default for Acceptance
+{
+    fn default() -> Self
+    {
+        Self::new(AcceptanceCriterion::default())
+    }
+}
+
+impl fmt::Debug for Acceptance
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match &self.criterion {
+            AcceptanceCriterion::HillClimbing => f
+                .debug_struct("HillClimbing")
+                .field("record", &self.record)
+                .finish(),
+            AcceptanceCriterion::SimulatedAnnealing { .. } => f
+                .debug_struct("SimulatedAnnealing")
+                .field("temperature", &self.temperature)
+                .field("record", &self.record)
+                .finish(),
+            AcceptanceCriterion::LateAcceptance { history_length } => f
+                .debug_struct("LateAcceptance")
+                .field("history_length", history_length)
+                .field("threshold", &self.threshold())
+                .finish(),
+            AcceptanceCriterion::RecordToRecord { deviation } => f
+                .debug_struct("RecordToRecord")
+                .field("deviation", deviation)
+                .field("threshold", &self.threshold())
+                .field("record", &self.record)
+                .finish(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use ordinator_scheduling_environment::worker_environment::AcceptanceCriterion;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::Acceptance;
+
+    #[test]
+    fn test_hill_climbing_rejects_equal_and_worse()
+    {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut acceptance = Acceptance::default();
+
+        assert!(acceptance.accept(10.0, 9.0, &mut rng));
+        assert!(!acceptance.accept(9.0, 9.0, &mut rng));
+        assert!(!acceptance.accept(9.0, 11.0, &mut rng));
+    }
+
+    #[test]
+    fn test_simulated_annealing_cools_down_to_minimum()
+    {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut acceptance = Acceptance::new(AcceptanceCriterion::SimulatedAnnealing {
+            initial_temperature: 100.0,
+            cooling_rate: 0.5,
+            minimum_temperature: 10.0,
+        });
+
+        for _ in 0..10 {
+            acceptance.accept(10.0, 10.0, &mut rng);
+        }
+
+        assert_eq!(acceptance.temperature, 10.0);
+        assert!(!acceptance.accept(10.0, 1.0e6, &mut rng));
+    }
+
+    #[test]
+    fn test_late_acceptance_compares_against_history()
+    {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut acceptance =
+            Acceptance::new(AcceptanceCriterion::LateAcceptance { history_length: 2 });
+
+        // The history starts out filled with the first current cost.
+        assert!(!acceptance.accept(5.0, 6.0, &mut rng));
+        assert!(acceptance.accept(5.0, 5.0, &mut rng));
+        assert!(!acceptance.accept(5.0, 7.0, &mut rng));
+    }
+
+    #[test]
+    fn test_forced_candidate_advances_the_criterion()
+    {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut acceptance = Acceptance::new(AcceptanceCriterion::SimulatedAnnealing {
+            initial_temperature: 100.0,
+            cooling_rate: 0.5,
+            minimum_temperature: 1.0,
+        });
+
+        acceptance.force(10.0, 1.0e6, &mut rng);
+        assert_eq!(acceptance.temperature, 50.0);
+
+        let mut acceptance =
+            Acceptance::new(AcceptanceCriterion::LateAcceptance { history_length: 1 });
+
+        acceptance.force(5.0, 7.0, &mut rng);
+        // The forced cost is now the late acceptance threshold.
+        assert!(acceptance.accept(7.0, 7.0, &mut rng));
+        assert!(!acceptance.accept(7.0, 8.0, &mut rng));
+    }
+
+    #[test]
+    fn test_record_to_record_allows_deviation_from_record()
+    {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut acceptance =
+            Acceptance::new(AcceptanceCriterion::RecordToRecord { deviation: 0.1 });
+
+        assert!(acceptance.accept(100.0, 109.0, &mut rng));
+        assert!(!acceptance.accept(109.0, 111.0, &mut rng));
+        assert!(acceptance.accept(109.0, 90.0, &mut rng));
+        assert!(!acceptance.accept(90.0, 100.0, &mut rng));
+    }
+}
@@ -2,17 +2,28 @@ use std::fmt::Debug;
 use std::sync::Arc;
 use std::sync::MutexGuard;
 
+use anyhow::Context;
 use anyhow::Result;
 use arc_swap::ArcSwap;
 use arc_swap::Guard;
+use ordinator_configuration::seed::derive_actor_seed;
 use ordinator_orchestrator_actor_traits::Parameters;
 use ordinator_orchestrator_actor_traits::Solution;
 use ordinator_orchestrator_actor_traits::SwapSolution;
 use ordinator_orchestrator_actor_traits::SystemSolutions;
+use ordinator_orchestrator_actor_traits::checkpoint::CheckpointStore;
+use ordinator_orchestrator_actor_traits::checkpoint::scheduling_environment_fingerprint;
 use ordinator_scheduling_environment::SchedulingEnvironment;
 use ordinator_scheduling_environment::worker_environment::resources::Id;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use tracing::Level;
+use tracing::event;
 
+use crate::acceptance::Acceptance;
+use crate::operators::OperatorPortfolio;
 use crate::traits::AbLNSUtils;
+use crate::traits::ObjectiveValue;
 
 // pub type SharedSolution = SharedSolution<
 
@@ -33,9 +44,23 @@ where
     pub id: Id,
     pub solution_intermediate: I,
     pub solution: S,
+    /// The best solution since the objective last changed. This is what the
+    /// actor has published, while `solution` is the working solution.
+    pub best_solution: Option<S>,
     pub parameters: P,
     pub arc_swap_shared_solution: Arc<ArcSwap<Ss>>,
     pub loaded_shared_solution: Guard<Arc<Ss>>,
+    pub acceptance: Acceptance,
+    pub operators: OperatorPortfolio,
+    /// The master seed in the `SystemConfigurations` that `seed` is derived
+    /// from together with the `Id` of the actor.
+    pub master_seed: u64,
+    /// The seed that `rng` was created from.
+    pub seed: u64,
+    pub rng: StdRng,
+    /// The solution from the checkpoint of the asset. It is replayed by
+    /// `ActorBasedLargeNeighborhoodSearch::warm_start_from_checkpoint`.
+    pub warm_start_solution: Option<S>,
 }
 
 // You are designing these all wrong. You have to spend the time that it takes
@@ -54,6 +79,10 @@ where
     parameters: Option<P>,
     arc_swap_shared_solution: Option<Arc<ArcSwap<Ss>>>,
     loaded_shared_solution: Option<Guard<Arc<Ss>>>,
+    master_seed: Option<u64>,
+    checkpoint_store: Option<Arc<CheckpointStore>>,
+    scheduling_environment_fingerprint: Option<u64>,
+    warm_start_solution: Option<S>,
 }
 
 impl<S, P, I, Ss> Algorithm<S, P, I, Ss>
@@ -72,13 +101,61 @@ where
             parameters: None,
             arc_swap_shared_solution: None,
             loaded_shared_solution: None,
+            master_seed: None,
+            checkpoint_store: None,
+            scheduling_environment_fingerprint: None,
+            warm_start_solution: None,
         }
     }
+
+    /// A copy of the `Algorithm` that searches on its own from `seed`. It
+    /// publishes to a private copy of the loaded `SystemSolution`.
+    pub fn fork(&self, seed: u64) -> Self
+    where
+        P: Clone,
+    {
+        let arc_swap_shared_solution =
+            Arc::new(ArcSwap::new(Arc::clone(&self.loaded_shared_solution)));
+        let loaded_shared_solution = arc_swap_shared_solution.load();
+
+        Algorithm {
+            id: self.id.clone(),
+            solution_intermediate: I::default(),
+            solution: self.solution.clone(),
+            best_solution: None,
+            parameters: self.parameters.clone(),
+            arc_swap_shared_solution,
+            loaded_shared_solution,
+            acceptance: Acceptance::default(),
+            operators: OperatorPortfolio::default(),
+            master_seed: self.master_seed,
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+            warm_start_solution: None,
+        }
+    }
+
+    /// Rebuilds the `Parameters` and the initial solution from the
+    /// `SchedulingEnvironment`. The previous solution is returned so that it
+    /// can be replayed with `replay_solution`.
+    pub fn rebuild_parameters(
+        &mut self,
+        scheduling_environment: &MutexGuard<SchedulingEnvironment>,
+    ) -> Result<S>
+    where
+        S: Solution<Parameters = P>,
+    {
+        let parameters = P::from_source(&self.id, scheduling_environment)?;
+        let solution = S::new(&parameters)?;
+        self.parameters = parameters;
+
+        Ok(std::mem::replace(&mut self.solution, solution))
+    }
 }
 impl<S, P, I, Ss> AbLNSUtils for Algorithm<S, P, I, Ss>
 where
     I: Default,
-    S: Solution + Debug + Clone,
+    S: Solution<ObjectiveValue: ObjectiveValue> + Debug + Clone,
     P: Parameters,
     Ss: SystemSolutions,
 {
@@ -99,6 +176,20 @@ where
         self.solution = solution;
     }
 
+    fn best_cost(&self) -> f64
+    {
+        self.best_solution
+            .as_ref()
+            .map_or(f64::INFINITY, |best_solution| {
+                best_solution.objective_value().cost()
+            })
+    }
+
+    fn keep_as_best_solution(&mut self)
+    {
+        self.best_solution = Some(self.solution.clone());
+    }
+
     fn update_objective_value(
         &mut self,
         objective_value: <Self::SolutionType as Solution>::ObjectiveValue,
@@ -106,6 +197,26 @@ where
     {
         self.solution.update_objective_value(objective_value);
     }
+
+    fn acceptance(&mut self) -> &mut Acceptance
+    {
+        &mut self.acceptance
+    }
+
+    fn operators(&mut self) -> &mut OperatorPortfolio
+    {
+        &mut self.operators
+    }
+
+    fn rng(&mut self) -> &mut StdRng
+    {
+        &mut self.rng
+    }
+
+    fn take_warm_start_solution(&mut self) -> Option<S>
+    {
+        self.warm_start_solution.take()
+    }
 }
 
 // Why does this function require a `Alg` that is `Result<Alg>`
@@ -132,13 +243,26 @@ where
         // approach is to make something that can make the whole system
         Algorithm<S, P, I, Ss>: Into<Alg>,
     {
+        let id = self.id.context("Call `id()` build method first")?;
+        let master_seed = self
+            .master_seed
+            .context("Call `master_seed()` build method first")?;
+        let seed = derive_actor_seed(master_seed, &id);
+
         let algorithm_inner = Algorithm {
-            id: self.id.unwrap(),
+            id,
             solution_intermediate: self.solution_intermediate,
             solution: self.solution.unwrap(),
+            best_solution: None,
             parameters: self.parameters.unwrap(),
             arc_swap_shared_solution: self.arc_swap_shared_solution.unwrap(),
             loaded_shared_solution: self.loaded_shared_solution.unwrap(),
+            acceptance: Acceptance::default(),
+            operators: OperatorPortfolio::default(),
+            master_seed,
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+            warm_start_solution: self.warm_start_solution,
         };
 
         Ok(algorithm_inner.into())
@@ -151,6 +275,22 @@ where
         self
     }
 
+    /// The seed of the actor is derived from the `master_seed` and the `Id`.
+    pub fn master_seed(mut self, master_seed: u64) -> Self
+    {
+        self.master_seed = Some(master_seed);
+        self
+    }
+
+    /// With a `CheckpointStore` the actor is warm started from the checkpoint
+    /// of its asset. The checkpoints are written by the `Orchestrator`. Call
+    /// this before `parameters_and_solution()`.
+    pub fn checkpoint_store(mut self, checkpoint_store: Option<Arc<CheckpointStore>>) -> Self
+    {
+        self.checkpoint_store = checkpoint_store;
+        self
+    }
+
     // This is a needless level of indirection. You should be careful of this type
     // of thing. The issue here is what we should do about this.
     // What should happen to this function? I think that the best place to have
@@ -177,6 +317,13 @@ where
         self.solution = Some(S::new(&parameters)?);
         
         self.parameters = Some(parameters);
+
+        if self.checkpoint_store.is_some() {
+            self.scheduling_environment_fingerprint = Some(scheduling_environment_fingerprint(
+                scheduling_environment,
+                self.id.as_ref().expect("Call `id()` build method first").asset(),
+            ));
+        }
         
         Ok(self)
     }
@@ -189,6 +336,18 @@ where
         Ss: SystemSolutions,
     {
         self.arc_swap_shared_solution = Some(shared_solution_arc_swap);
+
+        // A restarted actor continues from its last published solution instead
+        // of starting over from the `Parameters`.
+        if let Some(last_good_solution) = S::from_system_solution(
+            self.id.as_ref().unwrap(),
+            &self.arc_swap_shared_solution.as_ref().unwrap().load(),
+        ) {
+            self.solution = Some(last_good_solution);
+        } else {
+            self.warm_start_solution = self.checkpointed_solution();
+        }
+
         // CRUCIAL INSIGHT
         // The individual solutions should specify how to swap the
         // solution in the [`SystemSolution`]. It is not the task
@@ -215,6 +374,37 @@ where
         
         Ok(self)
     }
+
+    /// The solution of the actor in the checkpoint of its asset. A checkpoint
+    /// that cannot be read is reported and then ignored.
+    fn checkpointed_solution(&self) -> Option<S>
+    {
+        let id = self.id.as_ref().unwrap();
+        let checkpoint_store = self.checkpoint_store.as_ref()?;
+
+        let checkpoint = match checkpoint_store.load::<Ss>(id.asset()) {
+            Ok(checkpoint) => checkpoint?,
+            Err(error) => {
+                event!(Level::WARN, actor = %id, ?error, "ignoring checkpoint");
+                return None;
+            }
+        };
+
+        let scheduling_environment_fingerprint = self
+            .scheduling_environment_fingerprint
+            .expect("Call `parameters_and_solution()` build method first");
+
+        if checkpoint.is_stale(scheduling_environment_fingerprint) {
+            event!(
+                Level::WARN,
+                actor = %id,
+                created_at = %checkpoint.created_at,
+                "stale checkpoint, the SchedulingEnvironment has changed since it was written"
+            );
+        }
+
+        S::from_system_solution(id, &checkpoint.system_solution)
+    }
 }
 
 // TODO [x]
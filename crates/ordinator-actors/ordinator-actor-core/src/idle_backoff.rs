@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+use ordinator_configuration::throttling::Throttling;
+
+use crate::traits::IterationOutcome;
+
+/// Counts the LNS iterations since the last improvement. An actor that has
+/// converged waits between its iterations, and the wait grows the longer the
+/// actor goes without improving.
+#[derive(Debug, Default)]
+pub struct IdleBackoff
+{
+    iterations_without_improvement: u64,
+}
+
+impl IdleBackoff
+{
+    pub fn record(&mut self, iteration_outcome: IterationOutcome)
+    {
+        match iteration_outcome {
+            IterationOutcome::Improved => self.reset(),
+            IterationOutcome::Accepted | IterationOutcome::Rejected => {
+                self.iterations_without_improvement += 1
+            }
+        }
+    }
+
+    /// New messages or changes in the `SystemSolution` can make improvements
+    /// possible again.
+    pub fn reset(&mut self)
+    {
+        self.iterations_without_improvement = 0;
+    }
+
+    /// The wait before the next iteration. `None` means that the actor has not
+    /// converged and should continue right away.
+    pub fn idle(&self, throttling: &Throttling, initial_idle_milliseconds: u64)
+    -> Option<Duration>
+    {
+        self.idle_milliseconds(
+            initial_idle_milliseconds,
+            throttling.idle_iterations,
+            throttling.max_idle_milliseconds,
+        )
+        .map(Duration::from_millis)
+    }
+
+    fn idle_milliseconds(
+        &self,
+        initial_idle_milliseconds: u64,
+        idle_iterations: u64,
+        max_idle_milliseconds: u64,
+    ) -> Option<u64>
+    {
+        let idle_iterations = idle_iterations.max(1);
+
+        let doublings = self.iterations_without_improvement / idle_iterations;
+        if doublings == 0 {
+            return None;
+        }
+
+        Some(
+            initial_idle_milliseconds
+                .saturating_mul(1_u64 << (doublings - 1).min(32))
+                .min(max_idle_milliseconds),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::IdleBackoff;
+    use crate::traits::IterationOutcome;
+
+    #[test]
+    fn test_idle_backoff_grows_until_improvement()
+    {
+        let mut idle_backoff = IdleBackoff::default();
+
+        for _ in 0..9 {
+            idle_backoff.record(IterationOutcome::Rejected);
+        }
+        assert_eq!(idle_backoff.idle_milliseconds(100, 10, 350), None);
+
+        idle_backoff.record(IterationOutcome::Accepted);
+        assert_eq!(idle_backoff.idle_milliseconds(100, 10, 350), Some(100));
+
+        for _ in 0..10 {
+            idle_backoff.record(IterationOutcome::Rejected);
+        }
+        assert_eq!(idle_backoff.idle_milliseconds(100, 10, 350), Some(200));
+
+        for _ in 0..20 {
+            idle_backoff.record(IterationOutcome::Rejected);
+        }
+        assert_eq!(idle_backoff.idle_milliseconds(100, 10, 350), Some(350));
+
+        idle_backoff.record(IterationOutcome::Improved);
+        assert_eq!(idle_backoff.idle_milliseconds(100, 10, 350), None);
+    }
+}
@@ -1,11 +1,18 @@
+pub mod acceptance;
 pub mod algorithm;
+pub mod idle_backoff;
+pub mod operators;
 pub mod traits;
 
 use std::fmt::Debug;
 use std::fmt::{self};
+use std::ops::ControlFlow;
+use std::panic::AssertUnwindSafe;
 use std::panic::Location;
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
 
 use algorithm::AlgorithmBuilder;
 use anyhow::Context;
@@ -14,23 +21,38 @@ use anyhow::anyhow;
 use arc_swap::ArcSwap;
 use colored::Colorize;
 use flume::Receiver;
+use flume::RecvTimeoutError;
+use flume::Selector;
 use flume::Sender;
+use flume::TryRecvError;
+use idle_backoff::IdleBackoff;
 use ordinator_configuration::SystemConfigurations;
+use ordinator_configuration::throttling::DEFAULT_THROTTLING_MILLISECONDS;
+use ordinator_orchestrator_actor_traits::ActorFailure;
 use ordinator_orchestrator_actor_traits::ActorMessage;
 use ordinator_orchestrator_actor_traits::CommandHandler;
 use ordinator_orchestrator_actor_traits::Communication;
+use ordinator_orchestrator_actor_traits::Lifecycle;
 use ordinator_orchestrator_actor_traits::OrchestratorNotifier;
 use ordinator_orchestrator_actor_traits::Parameters;
 use ordinator_orchestrator_actor_traits::Solution;
-use ordinator_orchestrator_actor_traits::StateLink;
 use ordinator_orchestrator_actor_traits::SwapSolution;
 use ordinator_orchestrator_actor_traits::SystemSolutions;
+use ordinator_orchestrator_actor_traits::cpu_budget::CpuBudget;
+use ordinator_orchestrator_actor_traits::domain_event::DomainEventHandler;
+use ordinator_orchestrator_actor_traits::lockstep::Lockstep;
+use ordinator_orchestrator_actor_traits::telemetry::ActorMetrics;
+use ordinator_orchestrator_actor_traits::telemetry::IterationPhase;
 use ordinator_scheduling_environment::SchedulingEnvironment;
 use ordinator_scheduling_environment::worker_environment::resources::Id;
 use serde::Deserialize;
 use serde::Serialize;
+use tracing::Level;
+use tracing::event;
 
 use self::traits::ActorBasedLargeNeighborhoodSearch;
+use self::traits::IterationOutcome;
+use self::traits::IterationReport;
 
 // I do not know if there is
 // TODO [ ] FIX [ ]
@@ -53,13 +75,20 @@ where
     pub actor_id: Id,
     pub scheduling_environment: Arc<Mutex<SchedulingEnvironment>>,
     pub algorithm: Algorithm,
-    pub receiver_from_orchestrator: Receiver<ActorMessage<ActorRequest>>,
-    pub sender_to_orchestrator: Sender<Result<ActorResponse>>,
+    pub receiver_from_orchestrator: Receiver<ActorMessage<ActorRequest, ActorResponse>>,
     pub configurations: Arc<ArcSwap<SystemConfigurations>>,
     pub notify_orchestrator: Arc<dyn OrchestratorNotifier>,
-    pub error_channel: Sender<anyhow::Error>,
+    pub error_channel: Sender<ActorFailure>,
+    pub solution_changes: Receiver<()>,
+    pub cpu_budget: Arc<CpuBudget>,
+    pub metrics: Arc<ActorMetrics>,
+    pub lockstep: Option<Arc<Lockstep>>,
 }
 
+/// How long an actor in a `Lockstep` waits for its turn before it checks its
+/// messages again.
+const LOCKSTEP_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
 // TODO [ ]
 // You should consider making a trait here for the agent. That is the best way
 // of coding this. You are getting the hang of this and that is the most
@@ -80,6 +109,10 @@ where
         // I do not understand what I should be doing here? I think that the best
         // approach is to understand this as well as I can.
 
+        if let Some(lockstep) = &self.lockstep {
+            while !lockstep.wait_for_turn(&self.actor_id, LOCKSTEP_POLL_INTERVAL) {}
+        }
+
         if let Err(actor_error) = self.algorithm.schedule().with_context(|| {
             format!(
                 "{schedule_iteration:#?}\nActor: {}\nLocation: {}",
@@ -87,63 +120,248 @@ where
                 Location::caller(),
             )
         }) {
-            self.error_channel
-                .send(anyhow!(actor_error))
-                .expect("If this happens no amount of error handling will save the program")
+            self.report_error(actor_error);
+        }
+
+        if let Some(lockstep) = &self.lockstep {
+            lockstep.end_turn(&self.actor_id);
         }
 
         schedule_iteration.increment();
 
+        let mut paused = false;
+        let mut idle_backoff = IdleBackoff::default();
+
         loop {
-            while let Ok(message) = self.receiver_from_orchestrator.try_recv() {
-                match self.handle(message) {
-                    Ok(_) => (),
-                    Err(e) => self.error_channel.send(e).expect(
-                        "If this happens no amount of error handling will save the program",
-                    ),
+            // A paused actor blocks on the channel until it is resumed or shut
+            // down, so that it does not spend any CPU on LNS iterations.
+            loop {
+                let message = if paused {
+                    match self.receiver_from_orchestrator.recv() {
+                        Ok(message) => message,
+                        Err(_) => return self.shutdown(Lifecycle::Shutdown),
+                    }
+                } else {
+                    match self.receiver_from_orchestrator.try_recv() {
+                        Ok(message) => message,
+                        Err(TryRecvError::Empty) => break,
+                        Err(TryRecvError::Disconnected) => {
+                            return self.shutdown(Lifecycle::Shutdown);
+                        }
+                    }
+                };
+
+                idle_backoff.reset();
+                if let ControlFlow::Break(lifecycle) = self.dispatch(message, &mut paused) {
+                    return self.shutdown(lifecycle);
                 }
             }
 
-            let sleep_duration = match self
+            // A batch solve stops every actor after the same number of LNS
+            // iterations, the `Orchestrator` shuts it down when all are done.
+            if self
                 .configurations
                 .load()
-                .throttling
-                .get_throttling(&self.actor_id.0)
+                .iteration_limit
+                .is_some_and(|iteration_limit| self.metrics.iterations() >= iteration_limit)
             {
-                Ok(throttle) => throttle,
-                Err(err) => {
-                    self.error_channel
-                        .send(err)
-                        .expect("If error channel is down, everything is down");
-                    panic!("{}", &self.actor_id.0)
+                paused = true;
+                if let Some(lockstep) = &self.lockstep {
+                    lockstep.leave(&self.actor_id);
                 }
-            };
+                self.notify_orchestrator
+                    .iteration_limit_reached(&self.actor_id);
+                continue;
+            }
 
-            std::thread::sleep(std::time::Duration::from_millis(sleep_duration));
+            if self.solution_changes.try_iter().count() > 0 {
+                idle_backoff.reset();
+            }
 
-            if let Err(actor_error) = self
+            if let Some(lockstep) = &self.lockstep
+                && !lockstep.wait_for_turn(&self.actor_id, LOCKSTEP_POLL_INTERVAL)
+            {
+                continue;
+            }
+
+            let iteration_start = Instant::now();
+
+            match self
                 .algorithm
                 // Ahh the issue is that you cannot put this kind of thing in here. The issue comes
                 // from the fact that the. The Actor needs to run this.
                 // Should the Option be removed? Yes
-                .run_lns_iteration()
+                .search_step()
                 .with_context(|| {
                     format!(
                         "{schedule_iteration:#?}\nActor: {}\nLocation: {}",
                         self.actor_id,
                         Location::caller(),
                     )
-                })
-            {
-                self.error_channel
-                    .send(actor_error)
-                    .expect("If this happens no amount of error handling will save the program")
+                }) {
+                Ok(iteration_report) => {
+                    self.record_iteration(&iteration_report);
+                    self.cpu_budget
+                        .set_threads(&self.actor_id, iteration_report.threads);
+                    idle_backoff.record(iteration_report.outcome);
+                    if iteration_report.outcome.is_published() {
+                        self.notify_orchestrator
+                            .notify_system_solution_changed(&self.actor_id);
+                    }
+                }
+                Err(actor_error) => {
+                    self.report_error(actor_error);
+                }
             }
 
             schedule_iteration.increment();
+
+            // The actors of a `Lockstep` take turns instead of resting.
+            if let Some(lockstep) = &self.lockstep {
+                lockstep.end_turn(&self.actor_id);
+                continue;
+            }
+
+            let configurations = self.configurations.load();
+
+            // The rest keeps the actor within its share of the CPU budget of the
+            // asset. Only messages from the `Orchestrator` interrupt it.
+            let rest = self.cpu_budget.rest(
+                &self.actor_id,
+                iteration_start.elapsed(),
+                &configurations.throttling.cpu_budget,
+            );
+            if !rest.is_zero() {
+                match self
+                    .receiver_from_orchestrator
+                    .recv_deadline(Instant::now() + rest)
+                {
+                    Ok(message) => {
+                        idle_backoff.reset();
+                        if let ControlFlow::Break(lifecycle) = self.dispatch(message, &mut paused) {
+                            return self.shutdown(lifecycle);
+                        }
+                    }
+                    Err(RecvTimeoutError::Timeout) => (),
+                    Err(RecvTimeoutError::Disconnected) => {
+                        return self.shutdown(Lifecycle::Shutdown);
+                    }
+                }
+            }
+
+            let initial_idle_milliseconds =
+                match configurations.throttling.get_throttling(&self.actor_id.0) {
+                    Ok(throttle) => throttle,
+                    Err(error) => {
+                        event!(
+                            Level::WARN,
+                            actor = %self.actor_id,
+                            ?error,
+                            "no throttling configured, using the default"
+                        );
+                        DEFAULT_THROTTLING_MILLISECONDS
+                    }
+                };
+
+            // A converged actor waits until a message arrives, another actor
+            // changes the `SystemSolution`, or the idle period runs out.
+            if let Some(idle) =
+                idle_backoff.idle(&configurations.throttling, initial_idle_milliseconds)
+            {
+                let woken = Selector::new()
+                    .recv(&self.receiver_from_orchestrator, |message| {
+                        Some(message.map_err(|_| ()))
+                    })
+                    .recv(&self.solution_changes, |_| None)
+                    .wait_deadline(Instant::now() + idle);
+
+                match woken {
+                    Ok(Some(Ok(message))) => {
+                        idle_backoff.reset();
+                        if let ControlFlow::Break(lifecycle) = self.dispatch(message, &mut paused) {
+                            return self.shutdown(lifecycle);
+                        }
+                    }
+                    Ok(Some(Err(()))) => return self.shutdown(Lifecycle::Shutdown),
+                    Ok(None) => idle_backoff.reset(),
+                    Err(_) => (),
+                }
+            }
         }
     }
 
+    fn record_iteration(&self, iteration_report: &IterationReport)
+    {
+        self.metrics.record_iteration(
+            iteration_report.outcome == IterationOutcome::Improved,
+            iteration_report.outcome.is_published(),
+            iteration_report.current_cost,
+            iteration_report.best_cost,
+        );
+        self.metrics.record_phase(
+            IterationPhase::Unschedule,
+            iteration_report.unschedule_duration,
+        );
+        self.metrics
+            .record_phase(IterationPhase::Schedule, iteration_report.schedule_duration);
+        self.metrics.record_phase(
+            IterationPhase::CalculateObjectiveValue,
+            iteration_report.calculate_objective_value_duration,
+        );
+        self.metrics
+            .record_queue_depth(self.receiver_from_orchestrator.len());
+    }
+
+    /// Handles a single message from the `Orchestrator`. `Break` means that the
+    /// actor has to shut down.
+    fn dispatch(
+        &mut self,
+        message: ActorMessage<ActorRequest, ActorResponse>,
+        paused: &mut bool,
+    ) -> ControlFlow<Lifecycle>
+    {
+        match message {
+            ActorMessage::Lifecycle(Lifecycle::Pause) => *paused = true,
+            ActorMessage::Lifecycle(Lifecycle::Resume) => *paused = false,
+            ActorMessage::Lifecycle(lifecycle @ (Lifecycle::Shutdown | Lifecycle::Abort)) => {
+                return ControlFlow::Break(lifecycle);
+            }
+            ActorMessage::Options(actor_options) => {
+                event!(Level::INFO, actor = %self.actor_id, ?actor_options, "options updated");
+                if let Err(e) = self.algorithm.apply_options(actor_options) {
+                    self.report_error(e)
+                }
+            }
+            message => {
+                if let Err(e) = self.handle(message) {
+                    self.report_error(e)
+                }
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    // The error channel is a rendezvous channel, so this blocks until the
+    // `Orchestrator` has received the error and applied the restart policy.
+    fn report_error(&self, error: anyhow::Error)
+    {
+        self.error_channel
+            .send(ActorFailure {
+                id: self.actor_id.clone(),
+                error,
+            })
+            .expect("If this happens no amount of error handling will save the program")
+    }
+
+    /// The best solution of the actor is already in the `SystemSolution`, so
+    /// the working solution is not published here. The `Orchestrator` writes
+    /// the checkpoint and removes the solution once the thread has been
+    /// joined, so that no LNS iteration can swap it back in after the removal.
+    fn shutdown(&mut self, lifecycle: Lifecycle)
+    {
+        event!(Level::INFO, actor = %self.actor_id, ?lifecycle, "actor shut down");
+    }
+
     pub fn builder() -> ActorBuilder<ActorRequest, ActorResponse, Algorithm>
     {
         ActorBuilder {
@@ -151,7 +369,6 @@ where
             scheduling_environment: None,
             algorithm: None,
             receiver_from_orchestrator: None,
-            sender_to_orchestrator: None,
             configurations: None,
             notify_orchestrator: None,
             communication_for_orchestrator: None,
@@ -171,15 +388,6 @@ where
     type Req = ActorRequest;
     type Res = ActorResponse;
 
-    fn handle_state_link(&mut self, state_link: StateLink) -> Result<Self::Res>
-    {
-        match state_link {
-            StateLink::WorkOrders(_actor_specific) => todo!(),
-            StateLink::WorkerEnvironment => todo!(),
-            StateLink::TimeEnvironment => todo!(),
-        }
-    }
-
     fn handle_request_message(&mut self, _request_message: Self::Req) -> Result<Self::Res>
     {
         // The individual actor has to implement this
@@ -187,6 +395,14 @@ where
     }
 }
 
+// The individual actors implement the handlers of the events routed to them.
+impl<ActorRequest, ActorResponse, Algorithm> DomainEventHandler
+    for Actor<ActorRequest, ActorResponse, Algorithm>
+where
+    Algorithm: ActorBasedLargeNeighborhoodSearch,
+{
+}
+
 pub struct ActorBuilder<ActorRequest, ActorResponse, Algorithm>
 where
     Algorithm: ActorBasedLargeNeighborhoodSearch,
@@ -196,13 +412,12 @@ where
     agent_id: Option<Id>,
     scheduling_environment: Option<Arc<Mutex<SchedulingEnvironment>>>,
     algorithm: Option<Algorithm>,
-    receiver_from_orchestrator: Option<Receiver<ActorMessage<ActorRequest>>>,
-    sender_to_orchestrator: Option<Sender<Result<ActorResponse>>>,
+    receiver_from_orchestrator: Option<Receiver<ActorMessage<ActorRequest, ActorResponse>>>,
     configurations: Option<Arc<ArcSwap<SystemConfigurations>>>,
     notify_orchestrator: Option<Arc<dyn OrchestratorNotifier>>,
     //
     communication_for_orchestrator: Option<Communication<ActorRequest, ActorResponse>>,
-    error_channel: Option<Sender<anyhow::Error>>,
+    error_channel: Option<Sender<ActorFailure>>,
 }
 
 impl<ActorRequest, ActorResponse, SpecificAlgorithm>
@@ -216,24 +431,49 @@ where
 {
     pub fn build(self) -> Result<Communication<ActorRequest, ActorResponse>>
     {
+        let actor_id = self.agent_id.unwrap();
+        let notify_orchestrator = self.notify_orchestrator.unwrap();
+
+        let solution_changes = notify_orchestrator.subscribe_to_system_solution(&actor_id);
+        let cpu_budget = notify_orchestrator.cpu_budget(actor_id.asset())?;
+        let metrics = notify_orchestrator.actor_metrics(&actor_id);
+        let lockstep = notify_orchestrator.lockstep(actor_id.asset());
+
         let mut agent = Actor {
-            actor_id: self.agent_id.unwrap(),
+            actor_id,
             scheduling_environment: self.scheduling_environment.unwrap(),
             algorithm: self.algorithm.unwrap(),
             receiver_from_orchestrator: self.receiver_from_orchestrator.unwrap(),
-            sender_to_orchestrator: self.sender_to_orchestrator.unwrap(),
             configurations: self.configurations.unwrap(),
-            notify_orchestrator: self.notify_orchestrator.unwrap(),
+            notify_orchestrator,
             error_channel: self.error_channel.unwrap(),
+            solution_changes,
+            cpu_budget,
+            metrics,
+            lockstep,
         };
 
         let thread_name = agent.actor_id.to_string();
 
-        std::thread::Builder::new()
+        let join_handle = std::thread::Builder::new()
             .name(thread_name)
-            .spawn(move || agent.run())?;
+            .spawn(move || {
+                // A panic is reported like any other error, so that the restart
+                // policy of the `Orchestrator` also applies to panics.
+                if let Err(error) = catch_panic(|| agent.run()) {
+                    agent.report_error(error);
+                }
+                // The other actors of the `Lockstep` must not wait for a
+                // stopped actor.
+                if let Some(lockstep) = &agent.lockstep {
+                    lockstep.leave(&agent.actor_id);
+                }
+            })?;
+
+        let mut communication = self.communication_for_orchestrator.unwrap();
+        communication.set_join_handle(join_handle);
 
-        Ok(self.communication_for_orchestrator.unwrap())
+        Ok(communication)
     }
 
     pub fn agent_id(mut self, agent_id: Id) -> Self
@@ -273,52 +513,44 @@ where
 
         let algorithm_builder = configure(algorithm_builder)?;
 
-        self.algorithm = Some(SpecificAlgorithm::from(algorithm_builder.build()?));
+        let mut algorithm = SpecificAlgorithm::from(algorithm_builder.build()?);
+
+        if let Some(reconciliation) = algorithm.warm_start_from_checkpoint()? {
+            event!(
+                Level::INFO,
+                actor = ?self.agent_id,
+                ?reconciliation,
+                "warm started from checkpoint"
+            );
+        }
+
+        self.algorithm = Some(algorithm);
 
         Ok(self)
     }
 
     // What is the error here? I think that it has to do with the
     // bounded channel.
-    pub fn communication(mut self, error_channel: Sender<anyhow::Error>) -> Self
+    pub fn communication(mut self, error_channel: Sender<ActorFailure>) -> Self
     {
-        let (sender_to_actor, receiver_from_orchestrator): (
-            flume::Sender<ActorMessage<ActorRequest>>,
-            flume::Receiver<ActorMessage<ActorRequest>>,
-        ) = flume::unbounded();
+        let (sender_to_actor, receiver_from_orchestrator) = flume::unbounded();
 
-        let (sender_to_orchestrator, receiver_from_actor): (
-            flume::Sender<Result<ActorResponse>>,
-            flume::Receiver<Result<ActorResponse>>,
-        ) = flume::unbounded();
-
-        self.communication_for_orchestrator =
-            Some(Communication::new(sender_to_actor, receiver_from_actor));
+        self.communication_for_orchestrator = Some(Communication::new(sender_to_actor));
 
         self.receiver_from_orchestrator = Some(receiver_from_orchestrator);
-        self.sender_to_orchestrator = Some(sender_to_orchestrator);
         self.error_channel = Some(error_channel);
         self
     }
 
     pub fn receiver_from_orchestrator(
         mut self,
-        receiver_from_orchestrator: Receiver<ActorMessage<ActorRequest>>,
+        receiver_from_orchestrator: Receiver<ActorMessage<ActorRequest, ActorResponse>>,
     ) -> Self
     {
         self.receiver_from_orchestrator = Some(receiver_from_orchestrator);
         self
     }
 
-    pub fn sender_to_orchestrator(
-        mut self,
-        sender_to_orchestrator: Sender<Result<ActorResponse>>,
-    ) -> Self
-    {
-        self.sender_to_orchestrator = Some(sender_to_orchestrator);
-        self
-    }
-
     pub fn configurations(mut self, configurations: Arc<ArcSwap<SystemConfigurations>>) -> Self
     {
         self.configurations = Some(configurations);
@@ -372,7 +604,7 @@ impl fmt::Debug for ScheduleIteration
 }
 
 /// This type is the primary message type that all agents should receive.
-/// All agents should have the `StateLink` and each agent then have its own
+/// All agents should handle `DomainEvent`s and each agent then have its own
 /// ActorRequest which is specifically created for each agent.
 // THIS should most likely be removed or refactored.
 #[derive(Debug, Serialize)]
@@ -439,3 +671,39 @@ pub enum ResponseMessage<S, Sc, R, T, C>
     Update,
     Succes,
 }
+
+/// Runs `run` and turns a panic into an error, so that the thread of the actor
+/// unwinds cleanly and can still be joined.
+pub fn catch_panic(run: impl FnOnce()) -> Result<()>
+{
+    std::panic::catch_unwind(AssertUnwindSafe(run)).map_err(|panic| {
+        let message = panic
+            .downcast_ref::<String>()
+            .cloned()
+            .or_else(|| panic.downcast_ref::<&str>().map(|s| s.to_string()))
+            .unwrap_or_default();
+
+        anyhow!("Actor panicked: {message}")
+    })
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::catch_panic;
+
+    #[test]
+    fn test_catch_panic_turns_a_panic_into_an_error()
+    {
+        let thread = std::thread::spawn(|| catch_panic(|| panic!("no capacity left")));
+
+        let error = thread.join().expect("The panic should not reach the thread");
+        assert!(
+            error
+                .unwrap_err()
+                .to_string()
+                .contains("Actor panicked: no capacity left")
+        );
+        assert!(catch_panic(|| ()).is_ok());
+    }
+}
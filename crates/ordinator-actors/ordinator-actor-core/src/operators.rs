@@ -0,0 +1,376 @@
+use std::cmp::Reverse;
+use std::fmt::Debug;
+
+use rand::Rng;
+use rand::seq::IndexedRandom;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::Level;
+use tracing::event;
+
+/// Score given to an operator pair that found a new best solution.
+const SCORE_NEW_BEST: f64 = 33.0;
+/// Score given to an operator pair that improved on the current solution.
+const SCORE_BETTER: f64 = 9.0;
+/// Score given to an operator pair whose worse solution was accepted by the
+/// `AcceptanceCriterion`. This is higher than `SCORE_BETTER` on purpose, as
+/// these are the moves that diversify the search.
+const SCORE_ACCEPTED: f64 = 13.0;
+/// Number of LNS iterations between weight updates.
+const SEGMENT_LENGTH: u64 = 100;
+/// How much the scores of the last segment change the weights.
+const REACTION_FACTOR: f64 = 0.1;
+/// Operators never go below this weight so that they can recover if the
+/// landscape of the problem changes.
+const MINIMUM_WEIGHT: f64 = 0.05;
+
+/// Every destroy and repair operator of an actor implements this. The
+/// operators are plain enums, and the `OperatorPortfolio` only keeps track of
+/// their position in `ALL`.
+pub trait NeighborhoodOperator: Copy + Debug + 'static
+{
+    const ALL: &'static [Self];
+
+    fn name(&self) -> &'static str;
+}
+
+/// Adaptive roulette wheel selection of destroy and repair operators (ALNS).
+/// The actor selects a pair of operators in `unschedule`, and the
+/// `ActorBasedLargeNeighborhoodSearch` loop rewards the pair when it knows
+/// whether the candidate was accepted.
+#[derive(Default)]
+pub struct OperatorPortfolio
+{
+    destroy: Vec<AdaptiveOperator>,
+    repair: Vec<AdaptiveOperator>,
+    selected: Option<(usize, usize)>,
+    best_cost: Option<f64>,
+    iteration: u64,
+}
+
+struct AdaptiveOperator
+{
+    segment_score: f64,
+    segment_uses: u64,
+    statistics: OperatorStatistics,
+}
+
+/// The statistics that are exposed in the status responses of the actors.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct OperatorStatistics
+{
+    pub name: String,
+    pub weight: f64,
+    pub uses: u64,
+    pub new_best: u64,
+    pub improvements: u64,
+    pub accepted: u64,
+    pub total_improvement: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct OperatorPortfolioStatistics
+{
+    pub destroy: Vec<OperatorStatistics>,
+    pub repair: Vec<OperatorStatistics>,
+}
+
+impl OperatorPortfolio
+{
+    pub fn new<D, R>() -> Self
+    where
+        D: NeighborhoodOperator,
+        R: NeighborhoodOperator,
+    {
+        Self {
+            destroy: D::ALL.iter().map(|o| AdaptiveOperator::new(o.name())).collect(),
+            repair: R::ALL.iter().map(|o| AdaptiveOperator::new(o.name())).collect(),
+            selected: None,
+            best_cost: None,
+            iteration: 0,
+        }
+    }
+
+    /// Selects the destroy and repair operator for the next LNS iteration.
+    pub fn select<D, R>(&mut self, rng: &mut impl Rng) -> (D, R)
+    where
+        D: NeighborhoodOperator,
+        R: NeighborhoodOperator,
+    {
+        let destroy = roulette_wheel(&self.destroy, rng);
+        let repair = roulette_wheel(&self.repair, rng);
+        self.selected = Some((destroy, repair));
+
+        (D::ALL[destroy], R::ALL[repair])
+    }
+
+    /// The repair operator of the current LNS iteration. The first operator is
+    /// used if `schedule` is called before any selection was made.
+    pub fn repair<R>(&self) -> R
+    where
+        R: NeighborhoodOperator,
+    {
+        R::ALL[self.selected.map(|(_, repair)| repair).unwrap_or_default()]
+    }
+
+    /// Scores the selected operators based on the outcome of the LNS
+    /// iteration. The costs follow `ObjectiveValue::cost`, lower is better.
+    pub fn reward(&mut self, current_cost: f64, candidate_cost: f64, accepted: bool)
+    {
+        let Some((destroy, repair)) = self.selected.take() else {
+            return;
+        };
+
+        let best_cost = *self.best_cost.get_or_insert(current_cost);
+
+        let score = if candidate_cost < best_cost {
+            self.best_cost = Some(candidate_cost);
+            SCORE_NEW_BEST
+        } else if candidate_cost < current_cost {
+            SCORE_BETTER
+        } else if accepted {
+            SCORE_ACCEPTED
+        } else {
+            0.0
+        };
+
+        for operator in [self.destroy.get_mut(destroy), self.repair.get_mut(repair)]
+            .into_iter()
+            .flatten()
+        {
+            operator.update(score, current_cost - candidate_cost, accepted);
+        }
+
+        self.iteration += 1;
+        if self.iteration.is_multiple_of(SEGMENT_LENGTH) {
+            self.destroy
+                .iter_mut()
+                .chain(self.repair.iter_mut())
+                .for_each(AdaptiveOperator::end_segment);
+
+            event!(
+                Level::INFO,
+                operator_statistics = ?self.statistics()
+            );
+        }
+    }
+
+    pub fn statistics(&self) -> OperatorPortfolioStatistics
+    {
+        OperatorPortfolioStatistics {
+            destroy: self.destroy.iter().map(|o| o.statistics.clone()).collect(),
+            repair: self.repair.iter().map(|o| o.statistics.clone()).collect(),
+        }
+    }
+}
+
+impl AdaptiveOperator
+{
+    fn new(name: &'static str) -> Self
+    {
+        Self {
+            segment_score: 0.0,
+            segment_uses: 0,
+            statistics: OperatorStatistics {
+                name: name.to_string(),
+                weight: 1.0,
+                ..OperatorStatistics::default()
+            },
+        }
+    }
+
+    fn update(&mut self, score: f64, improvement: f64, accepted: bool)
+    {
+        self.segment_score += score;
+        self.segment_uses += 1;
+
+        let statistics = &mut self.statistics;
+        statistics.uses += 1;
+        if score == SCORE_NEW_BEST {
+            statistics.new_best += 1;
+        }
+        if improvement > 0.0 {
+            statistics.improvements += 1;
+            statistics.total_improvement += improvement;
+        }
+        if accepted {
+            statistics.accepted += 1;
+        }
+    }
+
+    fn end_segment(&mut self)
+    {
+        if self.segment_uses != 0 {
+            let weight = &mut self.statistics.weight;
+            *weight = ((1.0 - REACTION_FACTOR) * *weight
+                + REACTION_FACTOR * self.segment_score / self.segment_uses as f64)
+                .max(MINIMUM_WEIGHT);
+        }
+        self.segment_score = 0.0;
+        self.segment_uses = 0;
+    }
+}
+
+/// The priority of a removed element in the priority queue of a repair
+/// operator, where the highest priority is popped first. A repair operator
+/// only uses one of the variants.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RepairPriority
+{
+    Highest(u64),
+    Earliest(Reverse<i64>),
+}
+
+/// Picks `amount` random elements of `candidates`.
+pub fn sample<T: Copy>(candidates: &[T], amount: usize, rng: &mut impl Rng) -> Vec<T>
+{
+    candidates.choose_multiple(rng, amount).copied().collect()
+}
+
+/// Picks `amount` random elements of the `2 * amount` first elements of
+/// `ranked`. Sampling from twice the removal size keeps a destroy operator
+/// that ranks its candidates from removing the exact same elements on every
+/// iteration.
+pub fn sample_from_ranked<T: Copy>(ranked: &[T], amount: usize, rng: &mut impl Rng) -> Vec<T>
+{
+    sample(&ranked[..ranked.len().min(2 * amount)], amount, rng)
+}
+
+/// Orders the scored elements with the highest score first. Ties are broken by
+/// the element itself, so that the order does not depend on a `HashMap`.
+pub fn rank_worst_first<T: Copy + Ord, V: Ord>(mut scored: Vec<(T, V)>) -> Vec<T>
+{
+    scored.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    scored.into_iter().map(|(element, _)| element).collect()
+}
+
+fn roulette_wheel(operators: &[AdaptiveOperator], rng: &mut impl Rng) -> usize
+{
+    let total_weight: f64 = operators.iter().map(|o| o.statistics.weight).sum();
+    if operators.len() <= 1 || total_weight <= 0.0 {
+        return 0;
+    }
+
+    let mut pointer = rng.random_range(0.0..total_weight);
+    for (index, operator) in operators.iter().enumerate() {
+        if pointer < operator.statistics.weight {
+            return index;
+        }
+        pointer -= operator.statistics.weight;
+    }
+    operators.len() - 1
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::cmp::Reverse;
+
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::NeighborhoodOperator;
+    use super::OperatorPortfolio;
+    use super::RepairPriority;
+    use super::SEGMENT_LENGTH;
+    use super::rank_worst_first;
+    use super::sample_from_ranked;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum TestOperator
+    {
+        Good,
+        Bad,
+    }
+
+    impl NeighborhoodOperator for TestOperator
+    {
+        const ALL: &'static [Self] = &[TestOperator::Good, TestOperator::Bad];
+
+        fn name(&self) -> &'static str
+        {
+            match self {
+                TestOperator::Good => "good",
+                TestOperator::Bad => "bad",
+            }
+        }
+    }
+
+    #[test]
+    fn test_operator_weights_follow_improvements()
+    {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut portfolio = OperatorPortfolio::new::<TestOperator, TestOperator>();
+
+        let mut current_cost = 1.0e6;
+        for _ in 0..(10 * SEGMENT_LENGTH) {
+            let (destroy, _) = portfolio.select::<TestOperator, TestOperator>(&mut rng);
+            let candidate_cost = match destroy {
+                TestOperator::Good => current_cost - 1.0,
+                TestOperator::Bad => current_cost + 1.0,
+            };
+            let accepted = candidate_cost < current_cost;
+            portfolio.reward(current_cost, candidate_cost, accepted);
+            if accepted {
+                current_cost = candidate_cost;
+            }
+        }
+
+        let statistics = portfolio.statistics();
+        assert!(statistics.destroy[0].weight > statistics.destroy[1].weight);
+        assert_eq!(statistics.destroy[1].improvements, 0);
+        assert_eq!(
+            statistics.destroy.iter().map(|o| o.uses).sum::<u64>(),
+            10 * SEGMENT_LENGTH
+        );
+    }
+
+    #[test]
+    fn test_repair_defaults_to_first_operator()
+    {
+        let portfolio = OperatorPortfolio::new::<TestOperator, TestOperator>();
+        assert_eq!(portfolio.repair::<TestOperator>(), TestOperator::Good);
+    }
+
+    #[test]
+    fn test_sample_from_ranked_only_picks_from_the_top()
+    {
+        let ranked: Vec<u64> = (0..100).collect();
+
+        for seed in 0..10 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let sampled = sample_from_ranked(&ranked, 5, &mut rng);
+
+            assert_eq!(sampled.len(), 5);
+            assert!(sampled.iter().all(|element| *element < 10));
+        }
+
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(sample_from_ranked(&ranked[..3], 5, &mut rng).len(), 3);
+    }
+
+    #[test]
+    fn test_earliest_repair_priority_pops_the_smallest_first()
+    {
+        let mut priorities = [
+            RepairPriority::Earliest(Reverse(i64::from(i32::MAX) + 1)),
+            RepairPriority::Earliest(Reverse(-3)),
+            RepairPriority::Earliest(Reverse(7)),
+        ];
+        priorities.sort();
+
+        assert_eq!(priorities.last(), Some(&RepairPriority::Earliest(Reverse(-3))));
+        assert_eq!(
+            priorities.first(),
+            Some(&RepairPriority::Earliest(Reverse(i64::from(i32::MAX) + 1)))
+        );
+    }
+
+    #[test]
+    fn test_rank_worst_first_breaks_ties_by_element()
+    {
+        let ranked = rank_worst_first(vec![(3, 10), (1, 5), (2, 10), (4, 0)]);
+
+        assert_eq!(ranked, vec![2, 3, 1, 4]);
+    }
+}
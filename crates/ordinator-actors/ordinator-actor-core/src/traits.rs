@@ -1,14 +1,25 @@
+use std::collections::HashMap;
 use std::fmt::Debug;
+use std::hash::Hash;
 use std::sync::MutexGuard;
+use std::time::Duration;
+use std::time::Instant;
 
 use anyhow::Context;
 use anyhow::Result;
+use ordinator_orchestrator_actor_traits::ActorOptions;
 use ordinator_orchestrator_actor_traits::Solution;
 use ordinator_scheduling_environment::SchedulingEnvironment;
+use ordinator_scheduling_environment::worker_environment::AcceptanceCriterion;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
 use serde::Serialize;
 use tracing::Level;
 use tracing::event;
 
+use crate::acceptance::Acceptance;
+use crate::operators::OperatorPortfolio;
+
 pub type ActorLinkToSchedulingEnvironment<'a> = MutexGuard<'a, SchedulingEnvironment>;
 
 pub trait ActorBasedLargeNeighborhoodSearch
@@ -31,7 +42,7 @@ pub trait ActorBasedLargeNeighborhoodSearch
     // should ideally also go through this functional process on every iteration.
     // but we do not want that
     // ISSUE #129
-    fn run_lns_iteration(&mut self) -> Result<()>
+    fn run_lns_iteration(&mut self) -> Result<IterationReport>
     {
         // The options should be a part of the `Algorithm`... No part of the... It
         // should either be a part of the Algorithm or a Part of the Actor. If
@@ -52,12 +63,19 @@ pub trait ActorBasedLargeNeighborhoodSearch
         // But that means that we cannot code this
         let current_solution = self.algorithm_util_methods().clone_algorithm_solution();
 
+        let current_cost = current_solution.objective_value().cost();
+
+        let phase_start = Instant::now();
         self.unschedule()
             .with_context(|| format!("{current_solution:#?}"))?;
+        let unschedule_duration = phase_start.elapsed();
 
+        let phase_start = Instant::now();
         self.schedule()
             .with_context(|| format!("Could not schedule\n{current_solution:#?}"))?;
+        let schedule_duration = phase_start.elapsed();
 
+        let phase_start = Instant::now();
         let objective_value_type = self.calculate_objective_value().with_context(|| {
             format!(
                 "Could not calculate the objective value\nLocation: {}:{}",
@@ -65,29 +83,98 @@ pub trait ActorBasedLargeNeighborhoodSearch
                 line!()
             )
         })?;
+        let calculate_objective_value_duration = phase_start.elapsed();
 
         event!(
             Level::INFO,
             better_objective = ?objective_value_type
         );
 
-        match objective_value_type {
-            ObjectiveValueType::Better(objective_value) => {
-                self.algorithm_util_methods()
-                    .update_objective_value(objective_value);
-                self.make_atomic_pointer_swap();
+        // The `AcceptanceCriterion` is read from the options on every iteration
+        // so that updated options take effect without rebuilding the `Algorithm`.
+        let acceptance_criterion = self.acceptance_criterion().clone();
+        let mut rng = StdRng::from_rng(self.algorithm_util_methods().rng());
+        let acceptance = self.algorithm_util_methods().acceptance();
+        acceptance.update_criterion(&acceptance_criterion);
+
+        // Every candidate goes through the `Acceptance`, so that the cooling
+        // schedule, the late acceptance history and the record advance on
+        // each iteration, also for improvements and forced moves.
+        let (objective_value, accepted) = match objective_value_type {
+            ObjectiveValueType::Better(objective_value)
+            | ObjectiveValueType::Worse(objective_value) => {
+                let accepted = acceptance.accept(current_cost, objective_value.cost(), &mut rng);
+                (objective_value, accepted)
             }
-            ObjectiveValueType::Worse => self
-                .algorithm_util_methods()
-                .swap_solution(current_solution),
-            ObjectiveValueType::Force => todo!(),
-        }
+            ObjectiveValueType::Force(objective_value) => {
+                acceptance.force(current_cost, objective_value.cost(), &mut rng);
+                (objective_value, true)
+            }
+        };
+        let candidate_cost = objective_value.cost();
 
-        Ok(())
+        event!(
+            Level::INFO,
+            acceptance = ?acceptance,
+            accepted
+        );
+
+        self.algorithm_util_methods()
+            .operators()
+            .reward(current_cost, candidate_cost, accepted);
+
+        // An accepted solution is only the working state of the search. The
+        // other actors see a solution once it improves on the best one, so the
+        // published schedule never gets worse between changes of the objective.
+        let iteration_outcome = if !accepted {
+            self.algorithm_util_methods()
+                .swap_solution(current_solution);
+            IterationOutcome::Rejected
+        } else {
+            self.algorithm_util_methods()
+                .update_objective_value(objective_value);
+            if candidate_cost < self.algorithm_util_methods().best_cost() {
+                self.publish_best_solution();
+                IterationOutcome::Improved
+            } else {
+                IterationOutcome::Accepted
+            }
+        };
+
+        Ok(IterationReport {
+            outcome: iteration_outcome,
+            current_cost: if accepted {
+                candidate_cost
+            } else {
+                current_cost
+            },
+            best_cost: self.algorithm_util_methods().best_cost(),
+            threads: 1,
+            unschedule_duration,
+            schedule_duration,
+            calculate_objective_value_duration,
+        })
+    }
+
+    /// Makes the current solution the best solution and publishes it.
+    fn publish_best_solution(&mut self)
+    {
+        self.algorithm_util_methods().keep_as_best_solution();
+        self.make_atomic_pointer_swap();
+    }
+
+    /// What the `Actor` runs between handling its messages. This is a single
+    /// LNS iteration, unless the actor searches with several workers.
+    fn search_step(&mut self) -> Result<IterationReport>
+    {
+        self.run_lns_iteration()
     }
 
     fn algorithm_util_methods(&mut self) -> &mut Self::Algorithm;
 
+    /// The `AcceptanceCriterion` selected in the options of the actor.
+    fn acceptance_criterion(&self) -> &AcceptanceCriterion;
+
     fn make_atomic_pointer_swap(&mut self);
 
     // So the issue ultimately arises due to you wanting to avoid a state
@@ -126,19 +213,117 @@ pub trait ActorBasedLargeNeighborhoodSearch
             self.schedule().unwrap();
             // We have to determine where the error is located. If this fails we have to go
             // into the crate and start unit testing.
-            self.calculate_objective_value().with_context(|| format!("Could not calculate the objective value after a incorporating state from the system solution\nLocation: {}:{}", file!(), line!()))?;
-            self.make_atomic_pointer_swap();
+            let objective_value = match self.calculate_objective_value().with_context(|| format!("Could not calculate the objective value after a incorporating state from the system solution\nLocation: {}:{}", file!(), line!()))? {
+                ObjectiveValueType::Better(objective_value)
+                | ObjectiveValueType::Worse(objective_value)
+                | ObjectiveValueType::Force(objective_value) => objective_value,
+            };
+
+            // The state of the other actors changes the objective, so the
+            // best solution starts over from the current one.
+            self.algorithm_util_methods()
+                .update_objective_value(objective_value);
+            self.publish_best_solution();
         }
 
         Ok(())
     }
 
     fn incorporate_system_solution(&mut self) -> Result<bool>;
+
+    /// Replaces the options in the parameters of the `Algorithm`. Options
+    /// for another kind of actor are an error.
+    fn update_options(&mut self, actor_options: ActorOptions) -> Result<()>;
+
+    /// Updates the options and re-evaluates the current solution with them,
+    /// so that the published objective value reflects the new weights.
+    fn apply_options(&mut self, actor_options: ActorOptions) -> Result<()>
+    {
+        self.update_options(actor_options)?;
+
+        let objective_value = match self.calculate_objective_value().with_context(|| {
+            format!(
+                "Could not calculate the objective value with the new options\nLocation: {}:{}",
+                file!(),
+                line!()
+            )
+        })? {
+            ObjectiveValueType::Better(objective_value)
+            | ObjectiveValueType::Worse(objective_value)
+            | ObjectiveValueType::Force(objective_value) => objective_value,
+        };
+
+        event!(Level::INFO, objective_value_with_new_options = ?objective_value);
+
+        self.algorithm_util_methods()
+            .update_objective_value(objective_value);
+        self.publish_best_solution();
+        Ok(())
+    }
+
+    /// Replays a checkpointed solution on top of the solution that was built
+    /// from the current `Parameters`. Work orders that no longer exist are
+    /// dropped, and new work orders are left unscheduled for the LNS
+    /// iterations.
+    fn warm_start(
+        &mut self,
+        checkpointed_solution: <Self::Algorithm as AbLNSUtils>::SolutionType,
+    ) -> Result<Reconciliation>;
+
+    /// Warm starts the actor if the `AlgorithmBuilder` found a checkpoint. A
+    /// checkpoint that cannot be replayed is reported, and the actor starts
+    /// from the `Parameters` instead.
+    fn warm_start_from_checkpoint(&mut self) -> Result<Option<Reconciliation>>
+    {
+        let Some(checkpointed_solution) = self.algorithm_util_methods().take_warm_start_solution()
+        else {
+            return Ok(None);
+        };
+
+        self.replay_solution(checkpointed_solution)
+    }
+
+    /// Replays `solution` with `warm_start` and publishes the result. If the
+    /// replay fails the current solution is kept.
+    fn replay_solution(
+        &mut self,
+        solution: <Self::Algorithm as AbLNSUtils>::SolutionType,
+    ) -> Result<Option<Reconciliation>>
+    {
+        let initial_solution = self.algorithm_util_methods().clone_algorithm_solution();
+
+        let reconciliation = match self.warm_start(solution) {
+            Ok(reconciliation) => reconciliation,
+            Err(error) => {
+                event!(Level::WARN, ?error, "could not replay the solution");
+                self.algorithm_util_methods()
+                    .swap_solution(initial_solution);
+                return Ok(None);
+            }
+        };
+
+        let objective_value = match self.calculate_objective_value().with_context(|| {
+            format!(
+                "Could not calculate the objective value of the warm started solution\nLocation: {}:{}",
+                file!(),
+                line!()
+            )
+        })? {
+            ObjectiveValueType::Better(objective_value)
+            | ObjectiveValueType::Worse(objective_value)
+            | ObjectiveValueType::Force(objective_value) => objective_value,
+        };
+
+        self.algorithm_util_methods()
+            .update_objective_value(objective_value);
+        self.publish_best_solution();
+        Ok(Some(reconciliation))
+    }
 }
 
 pub trait AbLNSUtils
 {
-    type SolutionType: Solution + Debug + Clone;
+    type SolutionType: Solution<ObjectiveValue: ObjectiveValue> + Debug + Clone;
 
     fn clone_algorithm_solution(&self) -> Self::SolutionType;
 
@@ -152,14 +337,447 @@ pub trait AbLNSUtils
     );
 
     fn swap_solution(&mut self, solution: Self::SolutionType);
+
+    /// The cost of the best solution since the objective last changed. Only
+    /// the best solution is published to the `SystemSolution`.
+    fn best_cost(&self) -> f64;
+
+    /// Makes a copy of the current solution the best solution.
+    fn keep_as_best_solution(&mut self);
+
+    fn acceptance(&mut self) -> &mut Acceptance;
+
+    fn operators(&mut self) -> &mut OperatorPortfolio;
+
+    /// The seeded random number generator of the actor. All randomness in the
+    /// LNS iterations should be drawn from this to make runs reproducible.
+    fn rng(&mut self) -> &mut StdRng;
+
+    /// The solution of the checkpoint that the actor should be warm started
+    /// from, if the `AlgorithmBuilder` found one.
+    fn take_warm_start_solution(&mut self) -> Option<Self::SolutionType>;
+}
+
+/// How a checkpointed solution was brought in line with the current
+/// `Parameters` of the actor.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Reconciliation
+{
+    /// Entries of the checkpoint that were replayed.
+    pub restored: usize,
+    /// Entries of the checkpoint that no longer exist, or that could not be
+    /// replayed in the current `Parameters`.
+    pub dropped: usize,
+    /// Entries of the `Parameters` that were not in the checkpoint.
+    pub inserted: usize,
+}
+
+impl Reconciliation
+{
+    /// Replays the `checkpointed` entries in the order of their keys, so that
+    /// a warm start is reproducible. `replay` returns false for an entry that
+    /// does not fit the current `Parameters`, whose keys are `current`.
+    pub fn replay<K, V>(
+        current: impl IntoIterator<Item = K>,
+        checkpointed: impl IntoIterator<Item = (K, V)>,
+        mut replay: impl FnMut(K, V) -> Result<bool>,
+    ) -> Result<Reconciliation>
+    where
+        K: Ord,
+    {
+        let mut checkpointed: Vec<(K, V)> = checkpointed.into_iter().collect();
+        checkpointed.sort_by(|(left, _), (right, _)| left.cmp(right));
+
+        let mut reconciliation = Reconciliation {
+            inserted: current
+                .into_iter()
+                .filter(|key| {
+                    checkpointed
+                        .binary_search_by(|(checkpointed_key, _)| checkpointed_key.cmp(key))
+                        .is_err()
+                })
+                .count(),
+            ..Reconciliation::default()
+        };
+
+        for (key, value) in checkpointed {
+            if replay(key, value)? {
+                reconciliation.restored += 1;
+            } else {
+                reconciliation.dropped += 1;
+            }
+        }
+
+        Ok(reconciliation)
+    }
+
+    /// Overwrites the entries of `current` with the `checkpointed` entries of
+    /// the same key. Checkpointed keys that are not in `current` are dropped.
+    pub fn restore<K, V>(current: &mut HashMap<K, V>, checkpointed: HashMap<K, V>) -> Reconciliation
+    where
+        K: Eq + Hash,
+    {
+        let mut reconciliation = Reconciliation {
+            inserted: current
+                .keys()
+                .filter(|key| !checkpointed.contains_key(key))
+                .count(),
+            ..Reconciliation::default()
+        };
+
+        for (key, value) in checkpointed {
+            match current.get_mut(&key) {
+                Some(current_value) => {
+                    *current_value = value;
+                    reconciliation.restored += 1;
+                }
+                None => reconciliation.dropped += 1,
+            }
+        }
+
+        reconciliation
+    }
+}
+
+/// The outcome of a single LNS iteration. Only an `Improved` solution, which
+/// is better than the best solution of the actor, is published to the
+/// `SystemSolution`. An `Accepted` solution is only searched from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IterationOutcome
+{
+    Improved,
+    Accepted,
+    Rejected,
+}
+
+impl IterationOutcome
+{
+    pub fn is_published(&self) -> bool
+    {
+        matches!(self, IterationOutcome::Improved)
+    }
+}
+
+/// What happened in a single LNS iteration, for the telemetry of the actor.
+/// `current_cost` is the cost of the solution that the actor continues from
+/// and `best_cost` the cost of the best solution that it has published.
+#[derive(Debug, Clone, Copy)]
+pub struct IterationReport
+{
+    pub outcome: IterationOutcome,
+    pub current_cost: f64,
+    pub best_cost: f64,
+    /// The number of threads that the iteration ran on.
+    pub threads: usize,
+    pub unschedule_duration: Duration,
+    pub schedule_duration: Duration,
+    pub calculate_objective_value_duration: Duration,
 }
 
 #[derive(Debug)]
 pub enum ObjectiveValueType<O>
 {
     Better(O),
-    Worse,
-    Force,
+    Worse(O),
+    Force(O),
+}
+
+pub trait ObjectiveValue: Serialize
+{
+    /// The objective value as a cost, where lower is better. The actors that
+    /// maximize their objective should return the negated value here. This is
+    /// what the `AcceptanceCriterion` works on.
+    fn cost(&self) -> f64;
 }
 
-pub trait ObjectiveValue: Serialize {}
+#[cfg(test)]
+mod tests
+{
+    use std::collections::HashMap;
+    use std::collections::VecDeque;
+
+    use anyhow::Result;
+    use ordinator_orchestrator_actor_traits::ActorOptions;
+    use ordinator_orchestrator_actor_traits::Solution;
+    use ordinator_scheduling_environment::worker_environment::AcceptanceCriterion;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use serde::Serialize;
+
+    use super::AbLNSUtils;
+    use super::ActorBasedLargeNeighborhoodSearch;
+    use super::IterationOutcome;
+    use super::ObjectiveValue;
+    use super::ObjectiveValueType;
+    use super::Reconciliation;
+    use crate::acceptance::Acceptance;
+    use crate::operators::OperatorPortfolio;
+
+    #[derive(Serialize, Debug, Clone)]
+    struct TestCost(f64);
+
+    impl ObjectiveValue for TestCost
+    {
+        fn cost(&self) -> f64
+        {
+            self.0
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    struct TestSolution(TestCost);
+
+    impl Solution for TestSolution
+    {
+        type ObjectiveValue = TestCost;
+        type Parameters = ();
+
+        fn new(_parameters: &Self::Parameters) -> Result<Self>
+        {
+            Ok(TestSolution(TestCost(100.0)))
+        }
+
+        fn objective_value(&self) -> &Self::ObjectiveValue
+        {
+            &self.0
+        }
+
+        fn update_objective_value(&mut self, other_objective: Self::ObjectiveValue)
+        {
+            self.0 = other_objective;
+        }
+    }
+
+    /// Finds the `candidate_costs` in order and records what it publishes.
+    struct TestSearch
+    {
+        solution: TestSolution,
+        best_solution: Option<TestSolution>,
+        acceptance: Acceptance,
+        operators: OperatorPortfolio,
+        rng: StdRng,
+        acceptance_criterion: AcceptanceCriterion,
+        candidate_costs: VecDeque<f64>,
+        published_costs: Vec<f64>,
+    }
+
+    impl AbLNSUtils for TestSearch
+    {
+        type SolutionType = TestSolution;
+
+        fn clone_algorithm_solution(&self) -> TestSolution
+        {
+            self.solution.clone()
+        }
+
+        fn load_shared_solution(&mut self) {}
+
+        fn update_objective_value(&mut self, objective_value: TestCost)
+        {
+            self.solution.update_objective_value(objective_value);
+        }
+
+        fn swap_solution(&mut self, solution: TestSolution)
+        {
+            self.solution = solution;
+        }
+
+        fn best_cost(&self) -> f64
+        {
+            self.best_solution
+                .as_ref()
+                .map_or(f64::INFINITY, |best_solution| best_solution.0.cost())
+        }
+
+        fn keep_as_best_solution(&mut self)
+        {
+            self.best_solution = Some(self.solution.clone());
+        }
+
+        fn acceptance(&mut self) -> &mut Acceptance
+        {
+            &mut self.acceptance
+        }
+
+        fn operators(&mut self) -> &mut OperatorPortfolio
+        {
+            &mut self.operators
+        }
+
+        fn rng(&mut self) -> &mut StdRng
+        {
+            &mut self.rng
+        }
+
+        fn take_warm_start_solution(&mut self) -> Option<TestSolution>
+        {
+            None
+        }
+    }
+
+    impl ActorBasedLargeNeighborhoodSearch for TestSearch
+    {
+        type Algorithm = TestSearch;
+        type Options = ();
+
+        fn algorithm_util_methods(&mut self) -> &mut Self::Algorithm
+        {
+            self
+        }
+
+        fn acceptance_criterion(&self) -> &AcceptanceCriterion
+        {
+            &self.acceptance_criterion
+        }
+
+        fn make_atomic_pointer_swap(&mut self)
+        {
+            self.published_costs.push(self.solution.0.cost());
+        }
+
+        fn calculate_objective_value(&mut self) -> Result<ObjectiveValueType<TestCost>>
+        {
+            let candidate_cost = self.candidate_costs.pop_front().unwrap();
+            if candidate_cost < self.solution.0.cost() {
+                Ok(ObjectiveValueType::Better(TestCost(candidate_cost)))
+            } else {
+                Ok(ObjectiveValueType::Worse(TestCost(candidate_cost)))
+            }
+        }
+
+        fn schedule(&mut self) -> Result<()>
+        {
+            Ok(())
+        }
+
+        fn unschedule(&mut self) -> Result<()>
+        {
+            Ok(())
+        }
+
+        fn incorporate_system_solution(&mut self) -> Result<bool>
+        {
+            Ok(false)
+        }
+
+        fn update_options(&mut self, _actor_options: ActorOptions) -> Result<()>
+        {
+            Ok(())
+        }
+
+        fn warm_start(&mut self, _checkpointed_solution: TestSolution) -> Result<Reconciliation>
+        {
+            Ok(Reconciliation::default())
+        }
+    }
+
+    #[test]
+    fn test_only_improvements_on_the_best_solution_are_published()
+    {
+        let mut test_search = TestSearch {
+            solution: TestSolution::new(&()).unwrap(),
+            best_solution: None,
+            acceptance: Acceptance::default(),
+            operators: OperatorPortfolio::default(),
+            rng: StdRng::seed_from_u64(0),
+            // The temperature is so high that every worse candidate is accepted.
+            acceptance_criterion: AcceptanceCriterion::SimulatedAnnealing {
+                initial_temperature: 1.0e12,
+                cooling_rate: 1.0,
+                minimum_temperature: 1.0e12,
+            },
+            candidate_costs: VecDeque::from([10.0, 20.0, 15.0, 5.0]),
+            published_costs: vec![],
+        };
+
+        let outcomes: Vec<IterationOutcome> = (0..4)
+            .map(|_| test_search.run_lns_iteration().unwrap().outcome)
+            .collect();
+
+        assert_eq!(
+            outcomes,
+            vec![
+                IterationOutcome::Improved,
+                IterationOutcome::Accepted,
+                IterationOutcome::Accepted,
+                IterationOutcome::Improved,
+            ]
+        );
+        assert_eq!(test_search.published_costs, vec![10.0, 5.0]);
+        assert_eq!(test_search.solution.0.cost(), 5.0);
+        assert_eq!(test_search.best_cost(), 5.0);
+    }
+
+    #[test]
+    fn test_working_solution_continues_from_accepted_worse_candidate()
+    {
+        let mut test_search = TestSearch {
+            solution: TestSolution::new(&()).unwrap(),
+            best_solution: None,
+            acceptance: Acceptance::default(),
+            operators: OperatorPortfolio::default(),
+            rng: StdRng::seed_from_u64(0),
+            acceptance_criterion: AcceptanceCriterion::LateAcceptance { history_length: 2 },
+            candidate_costs: VecDeque::from([10.0, 90.0]),
+            published_costs: vec![],
+        };
+
+        test_search.run_lns_iteration().unwrap();
+        let iteration_report = test_search.run_lns_iteration().unwrap();
+
+        // The late acceptance history still holds the initial cost of 100.
+        assert_eq!(iteration_report.outcome, IterationOutcome::Accepted);
+        assert_eq!(iteration_report.current_cost, 90.0);
+        assert_eq!(test_search.published_costs, vec![10.0]);
+        assert_eq!(test_search.best_cost(), 10.0);
+    }
+
+    #[test]
+    fn test_reconciliation_replays_checkpoint_in_key_order()
+    {
+        let mut replayed = vec![];
+
+        let reconciliation = Reconciliation::replay(
+            [1, 2, 4],
+            [(3, "removed"), (2, "fits"), (1, "does not fit")],
+            |key, value| {
+                replayed.push(key);
+                Ok(value == "fits")
+            },
+        )
+        .unwrap();
+
+        assert_eq!(replayed, vec![1, 2, 3]);
+        assert_eq!(
+            reconciliation,
+            Reconciliation {
+                restored: 1,
+                dropped: 2,
+                inserted: 1,
+            }
+        );
+        assert!(Reconciliation::replay([1], [(1, ())], |_, _| anyhow::bail!("failed")).is_err());
+    }
+
+    // The `SupervisorActor` restores its `Delegate`s like this.
+    #[test]
+    fn test_reconciliation_restores_only_current_keys()
+    {
+        let mut current = HashMap::from([(("OP-01", 10), "assess"), (("OP-02", 10), "assess")]);
+        let checkpointed = HashMap::from([(("OP-01", 10), "assign"), (("OP-03", 10), "assign")]);
+
+        let reconciliation = Reconciliation::restore(&mut current, checkpointed);
+
+        assert_eq!(current[&("OP-01", 10)], "assign");
+        assert_eq!(current[&("OP-02", 10)], "assess");
+        assert!(!current.contains_key(&("OP-03", 10)));
+        assert_eq!(
+            reconciliation,
+            Reconciliation {
+                restored: 1,
+                dropped: 1,
+                inserted: 1,
+            }
+        );
+    }
+}
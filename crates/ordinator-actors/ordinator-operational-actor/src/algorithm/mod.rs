@@ -1,6 +1,7 @@
 pub mod assert_functions;
 pub mod operational_events;
 mod operational_interface;
+pub mod operational_operators;
 pub mod operational_parameter;
 pub mod operational_solution;
 
@@ -10,6 +11,7 @@ use std::sync::Arc;
 
 use anyhow::Context;
 use anyhow::Result;
+use anyhow::bail;
 use anyhow::ensure;
 use assert_functions::OperationalAlgorithmAsserts;
 use chrono::DateTime;
@@ -17,6 +19,8 @@ use chrono::TimeDelta;
 use chrono::Utc;
 use itertools::Itertools;
 use operational_events::OperationalEvents;
+use operational_operators::OperationalDestroyOperator;
+use operational_operators::OperationalRepairOperator;
 use operational_parameter::OperationalParameter;
 use operational_parameter::OperationalParameters;
 use operational_solution::Assignment;
@@ -25,24 +29,29 @@ use operational_solution::OperationalFunctions;
 use operational_solution::OperationalObjectiveValue;
 use operational_solution::OperationalSolution;
 use ordinator_actor_core::algorithm::Algorithm;
+use ordinator_actor_core::operators::OperatorPortfolio;
 use ordinator_actor_core::traits::AbLNSUtils;
 use ordinator_actor_core::traits::ActorBasedLargeNeighborhoodSearch;
 use ordinator_actor_core::traits::ObjectiveValueType;
+use ordinator_actor_core::traits::Reconciliation;
+use ordinator_orchestrator_actor_traits::ActorOptions;
 use ordinator_orchestrator_actor_traits::Solution;
 use ordinator_orchestrator_actor_traits::StrategicInterface;
 use ordinator_orchestrator_actor_traits::SupervisorInterface;
 use ordinator_orchestrator_actor_traits::SystemSolutions;
 use ordinator_orchestrator_actor_traits::TacticalInterface;
-use ordinator_orchestrator_actor_traits::delegate::Delegate;
+use ordinator_orchestrator_actor_traits::marginal_fitness::FitnessCriteria;
 use ordinator_orchestrator_actor_traits::marginal_fitness::MarginalFitness;
 use ordinator_scheduling_environment::time_environment::TimeInterval;
 use ordinator_scheduling_environment::work_order::WorkOrderActivity;
 use ordinator_scheduling_environment::work_order::WorkOrderNumber;
 use ordinator_scheduling_environment::work_order::operation::ActivityNumber;
 use ordinator_scheduling_environment::work_order::operation::Work;
+use ordinator_scheduling_environment::worker_environment::AcceptanceCriterion;
 use ordinator_scheduling_environment::worker_environment::OperationalOptions;
 use ordinator_scheduling_environment::worker_environment::availability::Availability;
-use rand::seq::IndexedRandom;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
 use tracing::Level;
 use tracing::event;
 
@@ -201,7 +210,7 @@ where
         time_delta: TimeDelta,
     )
     {
-        let time_delta_usize = time_delta.num_seconds() as u64;
+        let fitness_criteria = self.fitness_criteria(&work_order_activity_previous, time_delta);
 
         self.0
             .solution
@@ -210,7 +219,67 @@ where
             .find(|oper_sol| oper_sol.0 == work_order_activity_previous)
             .unwrap()
             .1
-            .marginal_fitness = MarginalFitness::Scheduled(time_delta_usize);
+            .marginal_fitness = MarginalFitness::Scheduled(fitness_criteria);
+    }
+}
+
+impl<Ss> OperationalAlgorithm<Ss>
+where
+    Ss: SystemSolutions,
+{
+    /// The bid of the `OperationalActor` on a scheduled activity, see
+    /// `FitnessCriteria`.
+    fn fitness_criteria(
+        &self,
+        work_order_activity: &WorkOrderActivity,
+        idle_time: TimeDelta,
+    ) -> FitnessCriteria
+    {
+        let skill_rank = self
+            .parameters
+            .work_order_parameters
+            .get(work_order_activity)
+            .and_then(|operational_parameter| {
+                self.id
+                    .1
+                    .iter()
+                    .position(|resource| *resource == operational_parameter.resource)
+            })
+            .unwrap_or(self.id.1.len()) as u64;
+
+        let continuity = self
+            .solution
+            .scheduled_work_order_activities
+            .iter()
+            .filter(|(other, _)| other.0 == work_order_activity.0 && other != work_order_activity)
+            .count() as u64;
+
+        // The tactical days start at midnight, so the activity is late if it
+        // finishes after the last of them.
+        let finish_time = self
+            .solution
+            .scheduled_work_order_activities
+            .iter()
+            .find(|(other, _)| other == work_order_activity)
+            .map(|(_, operational_assignment)| operational_assignment.finish_time());
+        let lateness_seconds = self
+            .loaded_shared_solution
+            .tactical_actor_solution()
+            .ok()
+            .and_then(|tactical| tactical.start_and_finish_dates(work_order_activity))
+            .zip(finish_time)
+            .map_or(0, |((_, last_day), finish_time)| {
+                (finish_time - (*last_day + TimeDelta::days(1)))
+                    .num_seconds()
+                    .max(0) as u64
+            });
+
+        FitnessCriteria {
+            idle_seconds: idle_time.num_seconds() as u64,
+            skill_rank,
+            continuity,
+            lateness_seconds,
+        }
     }
 }
 
@@ -270,29 +339,25 @@ where
     {
         let operational_shared_solution = self
             .loaded_shared_solution
-            .supervisor_actor_solutions()
+            // The fact that this error was not propagated with `with_context` caused you a 5 minute
+            // delay and significant redirections.
+            .delegates_for_operational(&self.id)
             .with_context(|| {
                 format!(
                     "SupervisorSolution not available to the OperationalActor:\n{}",
                     self.id
                 )
-            })?
-            // The fact that this error was not propagated with `with_context` caused you a 5 minute
-            // delay and significant redirections.
-            .delegates_for_agent(&self.id);
+            })?;
 
         self.solution
             .scheduled_work_order_activities
-            // We remain all `OperationalSolution` which are not `Delegate::Drop` where
-            // the `Delegate` variant is decided by the
-            .retain(|(woa, _)| {
-                !operational_shared_solution
-                    .get(woa)
-                    .unwrap_or_else(|| {
-                        assert!(*woa == (WorkOrderNumber(0), 0));
-                        &Delegate::Assign
-                    })
-                    .is_drop()
+            // We remain all `OperationalSolution` which are not `Delegate::Drop` or
+            // `Delegate::Done` where the `Delegate` variant is decided by the
+            // supervisor. The supervisor removes the `Delegate`s that it has seen
+            // released in the published solution, which can still be in this one.
+            .retain(|(woa, _)| match operational_shared_solution.get(woa) {
+                Some(delegate) => !delegate.is_drop() && !delegate.is_done(),
+                None => *woa == (WorkOrderNumber(0), 0),
             });
         Ok(true)
     }
@@ -465,7 +530,7 @@ where
             Ok(ObjectiveValueType::Better(new_objective_value))
         } else {
             event!(Level::INFO, operational_objective_value_worse = ?new_objective_value);
-            Ok(ObjectiveValueType::Worse)
+            Ok(ObjectiveValueType::Worse(new_objective_value))
         }
     }
 
@@ -481,11 +546,18 @@ where
         // It determines all work order activities that are either `Delegate::Assess` or
         // `Delegate::Assign`
         // it should be named: `task`?
-        let work_order_activities = &self
+        let delegated_tasks = self
             .loaded_shared_solution
             .supervisor_actor_solutions()
             .with_context(|| "SupervisorSolution is not initialized for the OperationalActor")?
-            .delegated_tasks(&self.id);
+            .values()
+            .flat_map(|supervisor| supervisor.delegated_tasks(&self.id))
+            .collect();
+
+        let mut rng = StdRng::from_rng(&mut self.rng);
+        let repair_operator = self.operators.repair::<OperationalRepairOperator>();
+        let work_order_activities =
+            &self.repair_order(repair_operator, delegated_tasks, &mut rng);
 
         for work_order_activity in work_order_activities {
             let operational_parameter = match self
@@ -658,17 +730,14 @@ where
 
     fn unschedule(&mut self) -> Result<()>
     {
-        let mut rng = rand::rng();
-        let operational_solutions_len = self.solution.scheduled_work_order_activities.len();
+        let mut rng = StdRng::from_rng(&mut self.rng);
+
+        let (destroy_operator, _) = self
+            .operators
+            .select::<OperationalDestroyOperator, OperationalRepairOperator>(&mut rng);
 
         let operational_solutions_filtered: Vec<WorkOrderActivity> =
-            self.solution.scheduled_work_order_activities[1..operational_solutions_len - 1]
-                .choose_multiple(
-                    &mut rng,
-                    self.parameters.options.number_of_removed_activities,
-                )
-                .map(|operational_solution| operational_solution.0)
-                .collect();
+            self.destroy_work_order_activities(destroy_operator, &mut rng);
 
         ensure!(
             (self
@@ -709,6 +778,82 @@ where
     {
         &mut self.0
     }
+
+    fn acceptance_criterion(&self) -> &AcceptanceCriterion
+    {
+        &self.parameters.options.acceptance_criterion
+    }
+
+    fn update_options(&mut self, actor_options: ActorOptions) -> Result<()>
+    {
+        match actor_options {
+            ActorOptions::Operational(operational_options) => {
+                self.parameters.options = operational_options;
+                Ok(())
+            }
+            actor_options => bail!(
+                "OperationalActor {} cannot use {:?} options",
+                self.id,
+                actor_options.actor_kind()
+            ),
+        }
+    }
+
+    fn warm_start(&mut self, checkpointed_solution: OperationalSolution) -> Result<Reconciliation>
+    {
+        // The unavailability events at `(WorkOrderNumber(0), 0)` always come from
+        // the current `Availability`.
+        let mut checkpointed_work_order_activities: Vec<_> = checkpointed_solution
+            .scheduled_work_order_activities
+            .into_iter()
+            .filter(|(work_order_activity, _)| work_order_activity.0 != WorkOrderNumber(0))
+            .collect();
+        checkpointed_work_order_activities
+            .sort_by_key(|(_, operational_assignment)| operational_assignment.start_time());
+
+        let mut reconciliation = Reconciliation {
+            inserted: self
+                .parameters
+                .work_order_parameters
+                .keys()
+                .filter(|work_order_activity| {
+                    !checkpointed_work_order_activities
+                        .iter()
+                        .any(|(checkpointed, _)| checkpointed == *work_order_activity)
+                })
+                .count(),
+            ..Reconciliation::default()
+        };
+
+        for (work_order_activity, operational_assignment) in checkpointed_work_order_activities {
+            if !self
+                .parameters
+                .work_order_parameters
+                .contains_key(&work_order_activity)
+                || !is_assignments_in_bounds(
+                    &operational_assignment.assignments,
+                    &self.parameters.availability,
+                )
+            {
+                reconciliation.dropped += 1;
+                continue;
+            }
+
+            self.solution
+                .try_insert(work_order_activity, operational_assignment.assignments);
+
+            if self
+                .solution
+                .is_operational_solution_already_scheduled(work_order_activity)
+            {
+                reconciliation.restored += 1;
+            } else {
+                reconciliation.dropped += 1;
+            }
+        }
+
+        Ok(reconciliation)
+    }
 }
 
 impl<Ss> OperationalAlgorithm<Ss>
@@ -795,7 +940,7 @@ where
         Ok(assigned_work)
     }
 
-    fn unschedule_single_work_order_activity(
+    pub(crate) fn unschedule_single_work_order_activity(
         &mut self,
         work_order_and_activity_number: WorkOrderActivity,
     ) -> Result<()>
@@ -1205,9 +1350,16 @@ where
     Ss: SystemSolutions,
 {
     fn from(
-        value: Algorithm<OperationalSolution, OperationalParameters, FillinOperationalEvents, Ss>,
+        mut value: Algorithm<
+            OperationalSolution,
+            OperationalParameters,
+            FillinOperationalEvents,
+            Ss,
+        >,
     ) -> Self
     {
+        value.operators =
+            OperatorPortfolio::new::<OperationalDestroyOperator, OperationalRepairOperator>();
         OperationalAlgorithm(value)
     }
 }
@@ -2,8 +2,10 @@ use chrono::NaiveTime;
 use chrono::TimeDelta;
 use ordinator_scheduling_environment::time_environment::TimeInterval;
 use ordinator_scheduling_environment::work_order::WorkOrderActivity;
+use serde::Deserialize;
+use serde::Serialize;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum OperationalEvents {
     WrenchTime((TimeInterval, WorkOrderActivity)),
     Break(TimeInterval),
@@ -1,6 +1,11 @@
+use std::collections::HashMap;
+
+use chrono::DateTime;
+use chrono::Utc;
 use ordinator_orchestrator_actor_traits::OperationalInterface;
 use ordinator_orchestrator_actor_traits::marginal_fitness::MarginalFitness;
 use ordinator_scheduling_environment::work_order::WorkOrderActivity;
+use ordinator_scheduling_environment::work_order::WorkOrderNumber;
 
 use super::operational_solution::OperationalSolution;
 
@@ -29,4 +34,18 @@ impl OperationalInterface for OperationalSolution {
             .find(|woa_ass| woa_ass.0 == *work_order_activity)
             .map(|woa_ass| &woa_ass.1.marginal_fitness)
     }
+
+    fn scheduled_intervals(&self) -> HashMap<WorkOrderActivity, (DateTime<Utc>, DateTime<Utc>)> {
+        // `WorkOrderNumber(0)` marks the unavailability of the technician.
+        self.scheduled_work_order_activities
+            .iter()
+            .filter(|(work_order_activity, _)| work_order_activity.0 != WorkOrderNumber(0))
+            .filter_map(|(work_order_activity, operational_assignment)| {
+                let assignments = &operational_assignment.assignments;
+                let start = assignments.iter().map(|assignment| assignment.start).min()?;
+                let finish = assignments.iter().map(|assignment| assignment.finish).max()?;
+                Some((*work_order_activity, (start, finish)))
+            })
+            .collect()
+    }
 }
@@ -0,0 +1,151 @@
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+use ordinator_actor_core::operators::NeighborhoodOperator;
+use ordinator_orchestrator_actor_traits::SystemSolutions;
+use ordinator_scheduling_environment::work_order::WorkOrderActivity;
+use ordinator_scheduling_environment::work_order::operation::Work;
+use rand::Rng;
+use rand::seq::IndexedRandom;
+use rand::seq::SliceRandom;
+
+use super::OperationalAlgorithm;
+
+/// The destroy operators of the `OperationalAlgorithm`. Each of them removes up
+/// to `number_of_removed_activities` work order activities. The first and last
+/// entries of the `OperationalSolution` are never removed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OperationalDestroyOperator
+{
+    Random,
+    /// Removes a random work order activity together with the other
+    /// activities of the same work order.
+    RelatedByWorkOrder,
+    /// Removes a block of work order activities that are next to each other in
+    /// time, which frees a single larger gap in the schedule.
+    RelatedByTime,
+}
+
+/// The repair operators decide the order in which the delegated work order
+/// activities are inserted in `schedule`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OperationalRepairOperator
+{
+    SortedOrder,
+    LargestWorkFirst,
+    RandomOrder,
+}
+
+impl NeighborhoodOperator for OperationalDestroyOperator
+{
+    const ALL: &'static [Self] = &[
+        OperationalDestroyOperator::Random,
+        OperationalDestroyOperator::RelatedByWorkOrder,
+        OperationalDestroyOperator::RelatedByTime,
+    ];
+
+    fn name(&self) -> &'static str
+    {
+        match self {
+            OperationalDestroyOperator::Random => "random",
+            OperationalDestroyOperator::RelatedByWorkOrder => "related_by_work_order",
+            OperationalDestroyOperator::RelatedByTime => "related_by_time",
+        }
+    }
+}
+
+impl NeighborhoodOperator for OperationalRepairOperator
+{
+    const ALL: &'static [Self] = &[
+        OperationalRepairOperator::SortedOrder,
+        OperationalRepairOperator::LargestWorkFirst,
+        OperationalRepairOperator::RandomOrder,
+    ];
+
+    fn name(&self) -> &'static str
+    {
+        match self {
+            OperationalRepairOperator::SortedOrder => "sorted_order",
+            OperationalRepairOperator::LargestWorkFirst => "largest_work_first",
+            OperationalRepairOperator::RandomOrder => "random_order",
+        }
+    }
+}
+
+impl<Ss> OperationalAlgorithm<Ss>
+where
+    Ss: SystemSolutions,
+{
+    pub(crate) fn destroy_work_order_activities(
+        &self,
+        destroy_operator: OperationalDestroyOperator,
+        rng: &mut impl Rng,
+    ) -> Vec<WorkOrderActivity>
+    {
+        let number_of_removed_activities = self.parameters.options.number_of_removed_activities;
+
+        let scheduled = &self.solution.scheduled_work_order_activities;
+        let candidates: Vec<WorkOrderActivity> = match scheduled.len() {
+            0..=2 => vec![],
+            len => scheduled[1..len - 1].iter().map(|(woa, _)| *woa).collect(),
+        };
+
+        match destroy_operator {
+            OperationalDestroyOperator::Random => candidates
+                .choose_multiple(rng, number_of_removed_activities)
+                .copied()
+                .collect(),
+            OperationalDestroyOperator::RelatedByWorkOrder => {
+                let Some(seed) = candidates.choose(rng) else {
+                    return vec![];
+                };
+
+                let mut related = candidates
+                    .iter()
+                    .filter(|woa| woa.0 == seed.0)
+                    .copied()
+                    .collect::<Vec<_>>();
+                related.truncate(number_of_removed_activities);
+                related
+            }
+            OperationalDestroyOperator::RelatedByTime => {
+                if candidates.is_empty() {
+                    return vec![];
+                }
+                let window = number_of_removed_activities.min(candidates.len());
+                let start = rng.random_range(0..=candidates.len() - window);
+
+                candidates[start..start + window].to_vec()
+            }
+        }
+    }
+
+    /// Orders the delegated work order activities for insertion.
+    pub(crate) fn repair_order(
+        &self,
+        repair_operator: OperationalRepairOperator,
+        work_order_activities: HashSet<WorkOrderActivity>,
+        rng: &mut impl Rng,
+    ) -> Vec<WorkOrderActivity>
+    {
+        let mut work_order_activities: Vec<_> = work_order_activities.into_iter().collect();
+        work_order_activities.sort();
+
+        match repair_operator {
+            OperationalRepairOperator::SortedOrder => (),
+            OperationalRepairOperator::LargestWorkFirst => {
+                work_order_activities.sort_by_key(|woa| {
+                    Reverse(
+                        self.parameters
+                            .work_order_parameters
+                            .get(woa)
+                            .map(|operational_parameter| operational_parameter.work)
+                            .unwrap_or(Work::from(0.0)),
+                    )
+                });
+            }
+            OperationalRepairOperator::RandomOrder => work_order_activities.shuffle(rng),
+        }
+        work_order_activities
+    }
+}
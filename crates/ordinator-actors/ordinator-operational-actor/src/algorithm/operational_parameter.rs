@@ -13,6 +13,7 @@ use ordinator_scheduling_environment::work_order::operation::Work;
 use ordinator_scheduling_environment::worker_environment::OperationalOptions;
 use ordinator_scheduling_environment::worker_environment::availability::Availability;
 use ordinator_scheduling_environment::worker_environment::resources::Id;
+use ordinator_scheduling_environment::worker_environment::resources::Resources;
 
 pub struct OperationalParameters
 {
@@ -48,6 +49,7 @@ impl Parameters for OperationalParameters
                 let operational_parameter_option = OperationalParameter::new(
                     operation.operation_info.work_remaining,
                     operation.operation_analytic.preparation_time,
+                    operation.resource,
                 );
 
                 // Are we mutating this function?
@@ -94,11 +96,7 @@ impl Parameters for OperationalParameters
                 .operational_configuration
                 .toolbox_interval
                 .clone(),
-            options: OperationalOptions {
-                number_of_removed_activities: operational_configuration
-                    .operational_options
-                    .number_of_removed_activities,
-            },
+            options: operational_configuration.operational_options.clone(),
         })
     }
 
@@ -119,6 +117,7 @@ pub struct OperationalParameter
     // TODO: INCLUDE PREPARATION
     pub _preparation: Work,
     pub operation_time_delta: TimeDelta,
+    pub resource: Resources,
     // start_window: DateTime<Utc>,
     // end_window: DateTime<Utc>,
     // pub delegated: Delegate,
@@ -130,6 +129,7 @@ impl OperationalParameter
     pub fn new(
         work: Work,
         _preparation: Work,
+        resource: Resources,
         // start_window: DateTime<Utc>,
         // end_window: DateTime<Utc>,
         // delegated: Delegate,
@@ -145,6 +145,7 @@ impl OperationalParameter
             work,
             _preparation,
             operation_time_delta,
+            resource,
             // start_window,
             // end_window,
             // delegated,
@@ -16,6 +16,7 @@ use ordinator_scheduling_environment::work_order::WorkOrderActivity;
 use ordinator_scheduling_environment::work_order::WorkOrderNumber;
 use ordinator_scheduling_environment::worker_environment::availability::Availability;
 use ordinator_scheduling_environment::worker_environment::resources::Id;
+use serde::Deserialize;
 use serde::Serialize;
 
 // This is for the `constracts`, `conversions`, and the `orchstrator` to handle.
@@ -27,10 +28,17 @@ use super::operational_parameter::OperationalParameters;
 
 /// You want this to be a struct so that you can implement methods and
 /// formatting and logging.
-#[derive(Serialize, Copy, PartialEq, PartialOrd, Ord, Eq, Debug, Default, Clone)]
+#[derive(Serialize, Deserialize, Copy, PartialEq, PartialOrd, Ord, Eq, Debug, Default, Clone)]
 pub struct OperationalObjectiveValue(pub u64);
 
-impl ObjectiveValue for OperationalObjectiveValue {}
+impl ObjectiveValue for OperationalObjectiveValue
+{
+    // The operational actor maximizes its share of wrench time.
+    fn cost(&self) -> f64
+    {
+        -(self.0 as f64)
+    }
+}
 
 impl From<u64> for OperationalObjectiveValue
 {
@@ -40,7 +48,7 @@ impl From<u64> for OperationalObjectiveValue
     }
 }
 
-#[derive(PartialEq, Eq, Debug, Default, Clone)]
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
 pub struct OperationalSolution
 {
     pub objective_value: OperationalObjectiveValue,
@@ -78,6 +86,11 @@ impl Solution for OperationalSolution
         })
     }
 
+    fn objective_value(&self) -> &Self::ObjectiveValue
+    {
+        &self.objective_value
+    }
+
     fn update_objective_value(&mut self, other_objective_value: Self::ObjectiveValue)
     {
         self.objective_value = other_objective_value;
@@ -106,6 +119,11 @@ where
     {
         system_solution.operational_swap(id, solution);
     }
+
+    fn from_system_solution(id: &Id, system_solution: &Ss) -> Option<Self>
+    {
+        system_solution.operational_actor_solutions(id).ok().cloned()
+    }
 }
 
 #[allow(dead_code)]
@@ -243,7 +261,7 @@ impl OperationalFunctions for OperationalSolution
     }
 }
 
-#[derive(PartialEq, Eq, Clone, Debug)]
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
 pub struct OperationalAssignment
 {
     // This is an auxilliary objective value. Where should it lie to solve this issue? You
@@ -283,7 +301,7 @@ impl OperationalAssignment
 // This kind of behavior should be part of the `SharedSolutionTrait`
 // The issue here is that the code is not ready for use. We have to
 // change the different
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct Assignment
 {
     pub operational_events: OperationalEvents,
@@ -386,18 +404,24 @@ impl Assignment
 #[cfg(test)]
 mod tests
 {
+    use ordinator_orchestrator_actor_traits::marginal_fitness::FitnessCriteria;
     use ordinator_orchestrator_actor_traits::marginal_fitness::MarginalFitness;
 
     #[test]
     fn test_marginal_fitness_debug()
     {
-        let marginal_fitness = MarginalFitness::Scheduled(3600);
+        let marginal_fitness = MarginalFitness::Scheduled(FitnessCriteria {
+            idle_seconds: 3600,
+            skill_rank: 0,
+            continuity: 2,
+            lateness_seconds: 0,
+        });
 
         let formatted_marginal_fitness = format!("{marginal_fitness:?}");
 
         assert_eq!(
             formatted_marginal_fitness,
-            "MarginalFitness::Scheduled(3600, 1, 0)"
+            "MarginalFitness::Scheduled(3600, 1, 0, skill_rank: 0, continuity: 2, lateness: 0)"
         );
     }
 }
@@ -3,10 +3,8 @@ use anyhow::ensure;
 use chrono::TimeDelta;
 use colored::Colorize;
 use ordinator_actor_core::algorithm::Algorithm;
-use ordinator_orchestrator_actor_traits::SupervisorInterface;
 use ordinator_orchestrator_actor_traits::SystemSolutions;
 use ordinator_orchestrator_actor_traits::delegate::Delegate;
-use ordinator_orchestrator_actor_traits::marginal_fitness::MarginalFitness;
 
 use super::algorithm::FillinOperationalEvents;
 use super::algorithm::operational_parameter::OperationalParameters;
@@ -32,8 +30,7 @@ where
     {
         for delegate in self
             .loaded_shared_solution
-            .supervisor_actor_solutions()?
-            .delegates_for_agent(&self.id)
+            .delegates_for_operational(&self.id)?
             .values()
         {
             ensure!(delegate != &Delegate::Unassign)
@@ -61,8 +58,12 @@ where
                 .sum();
 
             ensure!(
-                assignments[1].1.marginal_fitness
-                    == MarginalFitness::Scheduled(combined_non_productive.num_seconds() as u64),
+                assignments[1]
+                    .1
+                    .marginal_fitness
+                    .criteria()
+                    .map(|fitness_criteria| fitness_criteria.idle_seconds)
+                    == Some(combined_non_productive.num_seconds() as u64),
                 format!(
                     "{}\n{}\n\n{}\n{}\n\n{}\n{}\n{}",
                     format!("{:<10}: {:?}", "Activity", assignments[1].0)
@@ -83,7 +84,7 @@ where
                     format!(
                         "{:<18}: {:?}",
                         "Calculated first",
-                        MarginalFitness::Scheduled(combined_non_productive.num_seconds() as u64)
+                        combined_non_productive.num_seconds() as u64
                     )
                     .bright_purple(),
                     format!(
@@ -21,6 +21,7 @@ use ordinator_actor_core::algorithm::Algorithm;
 use ordinator_actor_core::traits::ActorBasedLargeNeighborhoodSearch;
 use ordinator_configuration::SystemConfigurations;
 use ordinator_orchestrator_actor_traits::ActorFactory;
+use ordinator_orchestrator_actor_traits::ActorFailure;
 use ordinator_orchestrator_actor_traits::Communication;
 use ordinator_orchestrator_actor_traits::CommandHandler;
 use ordinator_orchestrator_actor_traits::OrchestratorNotifier;
@@ -74,7 +75,7 @@ where
         shared_solution_arc_swap: Arc<ArcSwap<Ss>>,
         notify_orchestrator: Arc<dyn OrchestratorNotifier>,
         system_configurations: Arc<ArcSwap<SystemConfigurations>>,
-        error_channel: Sender<anyhow::Error>,
+        error_channel: Sender<ActorFailure>,
     ) -> Result<Self::Communication>
     where
         Ss: SystemSolutions<Operational = OperationalSolution> + Send + Sync + 'static,
@@ -84,10 +85,12 @@ where
             + From<Algorithm<OperationalSolution, OperationalParameters, FillinOperationalEvents, Ss>>,
     {
         Actor::<OperationalRequestMessage, OperationalResponseMessage, OperationalAlgorithm<Ss>>::builder()
-        .agent_id(Id::new("OperationalAgent", vec![], vec![id.asset().clone()]))
+        .agent_id(id.clone())
         .scheduling_environment(Arc::clone(&scheduling_environment_guard))
         .algorithm(|ab| {
-            ab.id(id)
+            ab.master_seed(system_configurations.load().master_seed)
+                .id(id)
+                .checkpoint_store(notify_orchestrator.checkpoint_store())
                 // So this function returns a `Result`
                 .parameters_and_solution(
                     &scheduling_environment_guard.lock().unwrap(),
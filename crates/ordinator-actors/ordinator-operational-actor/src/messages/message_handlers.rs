@@ -1,18 +1,23 @@
+use std::sync::Arc;
+
 use anyhow::Result;
-use anyhow::bail;
-use ordinator_orchestrator_actor_traits::ActorSpecific;
+use ordinator_actor_core::traits::ActorBasedLargeNeighborhoodSearch;
+use ordinator_orchestrator_actor_traits::ActorError;
 use ordinator_orchestrator_actor_traits::CommandHandler;
-use ordinator_orchestrator_actor_traits::StateLink;
 use ordinator_orchestrator_actor_traits::SupervisorInterface;
 use ordinator_orchestrator_actor_traits::SystemSolutions;
-use tracing::Level;
-use tracing::event;
+use ordinator_orchestrator_actor_traits::domain_event::DomainEventHandler;
+use ordinator_scheduling_environment::work_order::WorkOrderActivity;
+use ordinator_scheduling_environment::work_order::WorkOrderNumber;
+use ordinator_scheduling_environment::work_order::operation::ActivityNumber;
+use ordinator_scheduling_environment::worker_environment::resources::Id;
 
 use super::OperationalRequestMessage;
 use super::OperationalResponseMessage;
 use super::requests::OperationalSchedulingRequest;
 use super::responses::OperationalResponseStatus;
 use crate::OperationalActor;
+use crate::algorithm::operational_parameter::OperationalParameter;
 use crate::algorithm::operational_solution::OperationalSolution;
 
 // Was this actually needed? I am not really sure here I believe that
@@ -24,27 +29,6 @@ where
     type Req = OperationalRequestMessage;
     type Res = OperationalResponseMessage;
 
-    fn handle_state_link(&mut self, state_link: StateLink) -> Result<OperationalResponseMessage>
-    {
-        event!(
-            Level::INFO,
-            self.algorithm.operational_parameters =
-                self.algorithm.parameters.work_order_parameters.len()
-        );
-        match state_link {
-            StateLink::WorkOrders(ActorSpecific::Strategic(changed_work_orders)) => {
-                // TODO:
-                event!(Level::ERROR, unhandled_work_orders = ?changed_work_orders);
-                bail!("IMPLEMENT STATELINK FOR THE OPERATIONAL AGENT");
-            }
-            // Here you should make a clear separation between the different
-            // ways
-            StateLink::WorkerEnvironment => todo!(),
-
-            StateLink::TimeEnvironment => todo!(),
-        }
-    }
-
     fn handle_request_message(
         &mut self,
         request: OperationalRequestMessage,
@@ -71,7 +55,11 @@ where
                     .algorithm
                     .loaded_shared_solution
                     .supervisor_actor_solutions()?
-                    .count_delegate_types(&self.actor_id);
+                    .values()
+                    .map(|supervisor| supervisor.count_delegate_types(&self.actor_id))
+                    .fold((0, 0, 0), |(assign, assess, unassign), counts| {
+                        (assign + counts.0, assess + counts.1, unassign + counts.2)
+                    });
 
                 // Remember that the business types should not be the same type as the
                 // algorithm types. That is crucial to understand in all this.
@@ -84,6 +72,8 @@ where
                     assess,
                     unassign,
                     self.algorithm.solution.objective_value,
+                    self.algorithm.operators.statistics(),
+                    self.algorithm.master_seed,
                 );
                 Ok(OperationalResponseMessage::Status(
                     operational_response_status,
@@ -134,3 +124,105 @@ where
         }
     }
 }
+
+impl<Ss> DomainEventHandler for OperationalActor<Ss>
+where
+    Ss: SystemSolutions<Operational = OperationalSolution>,
+{
+    fn work_order_created(&mut self, work_order_numbers: Vec<WorkOrderNumber>) -> Result<()>
+    {
+        self.update_operational_parameters(work_order_numbers, None)
+    }
+
+    fn work_order_updated(&mut self, work_order_numbers: Vec<WorkOrderNumber>) -> Result<()>
+    {
+        self.update_operational_parameters(work_order_numbers, None)
+    }
+
+    fn work_order_closed(&mut self, work_order_numbers: Vec<WorkOrderNumber>) -> Result<()>
+    {
+        let scheduled_work_order_activities: Vec<WorkOrderActivity> = self
+            .algorithm
+            .solution
+            .scheduled_work_order_activities
+            .iter()
+            .map(|(work_order_activity, _)| *work_order_activity)
+            .filter(|work_order_activity| work_order_numbers.contains(&work_order_activity.0))
+            .collect();
+
+        for work_order_activity in scheduled_work_order_activities {
+            self.algorithm
+                .unschedule_single_work_order_activity(work_order_activity)?;
+        }
+        self.algorithm
+            .parameters
+            .work_order_parameters
+            .retain(|work_order_activity, _| !work_order_numbers.contains(&work_order_activity.0));
+
+        self.algorithm.calculate_objective_value()?;
+        self.algorithm.publish_best_solution();
+        Ok(())
+    }
+
+    fn operation_progress_confirmed(&mut self, work_order_activity: WorkOrderActivity)
+    -> Result<()>
+    {
+        self.update_operational_parameters(vec![work_order_activity.0], Some(work_order_activity.1))
+    }
+
+    /// The assignments are replayed on the new `Availability`, assignments
+    /// that no longer fit are left for the LNS iterations.
+    fn worker_availability_changed(&mut self, _id: Id) -> Result<()>
+    {
+        let scheduling_environment = Arc::clone(&self.scheduling_environment);
+        let previous_solution = self
+            .algorithm
+            .rebuild_parameters(&scheduling_environment.lock().unwrap())?;
+        self.algorithm.replay_solution(previous_solution)?;
+        Ok(())
+    }
+}
+
+impl<Ss> OperationalActor<Ss>
+where
+    Ss: SystemSolutions<Operational = OperationalSolution>,
+{
+    /// Operations without remaining work keep their `OperationalParameter`
+    /// until the supervisor marks their `Delegate` as done.
+    fn update_operational_parameters(
+        &mut self,
+        work_order_numbers: Vec<WorkOrderNumber>,
+        activity_number: Option<ActivityNumber>,
+    ) -> Result<()>
+    {
+        let scheduling_environment = Arc::clone(&self.scheduling_environment);
+        let scheduling_environment_guard = scheduling_environment.lock().unwrap();
+
+        for work_order_number in work_order_numbers {
+            let work_order = scheduling_environment_guard
+                .work_orders
+                .inner
+                .get(&work_order_number)
+                .ok_or(ActorError::UnknownWorkOrder(work_order_number))?;
+
+            for (work_order_activity_number, operation) in &work_order.operations.0 {
+                if activity_number.is_some_and(|number| number != *work_order_activity_number) {
+                    continue;
+                }
+                let Ok(operational_parameter) = OperationalParameter::new(
+                    operation.operation_info.work_remaining,
+                    operation.operation_analytic.preparation_time,
+                    operation.resource,
+                ) else {
+                    continue;
+                };
+
+                self.algorithm.parameters.work_order_parameters.insert(
+                    (work_order_number, *work_order_activity_number),
+                    operational_parameter,
+                );
+            }
+        }
+        Ok(())
+    }
+}
@@ -80,6 +80,8 @@ pub struct OperationalResponseStatus {
     assess_number_of_activities: u64,
     unassign_number_of_activities: u64,
     objective: OperationalObjectiveValue,
+    operator_statistics: OperatorPortfolioStatistics,
+    master_seed: u64,
 }
 
 impl OperationalResponseStatus {
@@ -89,6 +91,8 @@ impl OperationalResponseStatus {
         assess_number_of_activities: u64,
         unassign_number_of_activities: u64,
         objective: OperationalObjectiveValue,
+        operator_statistics: OperatorPortfolioStatistics,
+        master_seed: u64,
     ) -> Self {
         Self {
             id,
@@ -96,10 +100,14 @@ impl OperationalResponseStatus {
             assess_number_of_activities,
             unassign_number_of_activities,
             objective,
+            operator_statistics,
+            master_seed,
         }
     }
 }
 
+use ordinator_actor_core::operators::OperatorPortfolioStatistics;
+
 use crate::algorithm::operational_solution::OperationalObjectiveValue;
 
 #[derive(Serialize)]
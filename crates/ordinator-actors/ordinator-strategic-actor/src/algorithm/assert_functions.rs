@@ -6,14 +6,13 @@ use anyhow::bail;
 use anyhow::ensure;
 use ordinator_actor_core::algorithm::Algorithm;
 use ordinator_orchestrator_actor_traits::SystemSolutions;
-use ordinator_scheduling_environment::work_order::WorkOrderNumber;
 use ordinator_scheduling_environment::work_order::operation::Work;
 use ordinator_scheduling_environment::worker_environment::resources::Resources;
-use priority_queue::PriorityQueue;
 use strum::IntoEnumIterator;
 use tracing::Level;
 use tracing::event;
 
+use super::RepairQueue;
 use super::strategic_parameters::StrategicParameters;
 use super::strategic_resources::StrategicResources;
 use super::strategic_solution::StrategicSolution;
@@ -29,7 +28,7 @@ pub trait StrategicAssertions {
 }
 
 impl<Ss> StrategicAssertions
-    for Algorithm<StrategicSolution, StrategicParameters, PriorityQueue<WorkOrderNumber, u64>, Ss>
+    for Algorithm<StrategicSolution, StrategicParameters, RepairQueue, Ss>
 where
     Ss: SystemSolutions,
 {
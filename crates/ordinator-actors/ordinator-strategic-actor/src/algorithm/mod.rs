@@ -1,10 +1,11 @@
 pub mod assert_functions;
 pub mod strategic_interface;
+pub mod strategic_operators;
 pub mod strategic_parameters;
+pub mod strategic_portfolio;
 pub mod strategic_resources;
 pub mod strategic_solution;
 
-use std::any::type_name;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::ops::Deref;
@@ -19,26 +20,35 @@ use anyhow::ensure;
 use itertools::Itertools;
 use ordinator_actor_core::algorithm::Algorithm;
 use ordinator_actor_core::algorithm::LoadOperation;
+use ordinator_actor_core::operators::OperatorPortfolio;
+use ordinator_actor_core::operators::RepairPriority;
 use ordinator_actor_core::traits::AbLNSUtils;
 use ordinator_actor_core::traits::ActorBasedLargeNeighborhoodSearch;
+use ordinator_actor_core::traits::IterationReport;
 use ordinator_actor_core::traits::ObjectiveValueType;
+use ordinator_actor_core::traits::Reconciliation;
+use ordinator_orchestrator_actor_traits::ActorError;
+use ordinator_orchestrator_actor_traits::ActorOptions;
 use ordinator_orchestrator_actor_traits::Parameters;
 use ordinator_orchestrator_actor_traits::Solution;
 use ordinator_orchestrator_actor_traits::SystemSolutions;
 use ordinator_orchestrator_actor_traits::TacticalInterface;
-use ordinator_scheduling_environment::time_environment::TimeEnvironment;
 use ordinator_scheduling_environment::time_environment::period::Period;
 use ordinator_scheduling_environment::work_order::WorkOrderNumber;
 use ordinator_scheduling_environment::work_order::operation::Work;
+use ordinator_scheduling_environment::worker_environment::AcceptanceCriterion;
 use ordinator_scheduling_environment::worker_environment::StrategicOptions;
 use ordinator_scheduling_environment::worker_environment::resources::Resources;
 use priority_queue::PriorityQueue;
 use rand::distr::weighted::Weight;
+use rand::SeedableRng;
 use rand::prelude::SliceRandom;
-use rand::seq::IndexedRandom;
+use rand::rngs::StdRng;
+use strategic_operators::StrategicDestroyOperator;
+use strategic_operators::StrategicRepairOperator;
 use strategic_parameters::StrategicClustering;
 use strategic_parameters::StrategicParameters;
-use strategic_parameters::WorkOrderParameter;
+use strategic_portfolio::StrategicPortfolio;
 use strategic_resources::OperationalResource;
 use strategic_resources::StrategicResources;
 use strategic_solution::StrategicObjectiveValue;
@@ -53,6 +63,9 @@ use crate::messages::requests::StrategicRequestScheduling;
 use crate::messages::responses::StrategicResponseResources;
 use crate::messages::responses::StrategicResponseScheduling;
 
+/// The removed work orders waiting to be repaired.
+pub type RepairQueue = PriorityQueue<WorkOrderNumber, RepairPriority>;
+
 // How would it look like here if you made it generic? impl
 // Algorithm<StrategicSolution, StrategicParameters, StrategicAssertions> {
 //
@@ -65,21 +78,21 @@ use crate::messages::responses::StrategicResponseScheduling;
 
 
 pub struct StrategicAlgorithm<Ss>(
-    pub Algorithm<StrategicSolution, StrategicParameters, PriorityQueue<WorkOrderNumber, u64>, Ss>,
+    pub Algorithm<StrategicSolution, StrategicParameters, RepairQueue, Ss>,
+    pub StrategicPortfolio<Ss>,
 )
 where
     StrategicSolution: Solution,
     StrategicParameters: Parameters,
     Ss: SystemSolutions,
-    Algorithm<StrategicSolution, StrategicParameters, PriorityQueue<WorkOrderNumber, u64>, Ss>:
+    Algorithm<StrategicSolution, StrategicParameters, RepairQueue, Ss>:
         AbLNSUtils;
 
 impl<Ss> Deref for StrategicAlgorithm<Ss>
 where
     Ss: SystemSolutions,
 {
-    type Target =
-        Algorithm<StrategicSolution, StrategicParameters, PriorityQueue<WorkOrderNumber, u64>, Ss>;
+    type Target = Algorithm<StrategicSolution, StrategicParameters, RepairQueue, Ss>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
@@ -96,16 +109,23 @@ where
 
 impl<Ss> ActorBasedLargeNeighborhoodSearch for StrategicAlgorithm<Ss>
 where
-    Algorithm<StrategicSolution, StrategicParameters, PriorityQueue<WorkOrderNumber, u64>, Ss>:
+    Algorithm<StrategicSolution, StrategicParameters, RepairQueue, Ss>:
         AbLNSUtils<SolutionType = StrategicSolution>,
     StrategicSolution: Solution,
     StrategicParameters: Parameters,
     Ss: SystemSolutions<Strategic = StrategicSolution>,
 {
-    type Algorithm =
-        Algorithm<StrategicSolution, StrategicParameters, PriorityQueue<WorkOrderNumber, u64>, Ss>;
+    type Algorithm = Algorithm<StrategicSolution, StrategicParameters, RepairQueue, Ss>;
     type Options = StrategicOptions;
 
+    fn search_step(&mut self) -> Result<IterationReport> {
+        if self.parameters.strategic_options.portfolio.number_of_workers > 1 {
+            return self.run_portfolio_round();
+        }
+        self.discard_portfolio_workers();
+        self.run_lns_iteration()
+    }
+
     fn incorporate_system_solution(&mut self) -> Result<bool> {
         let mut work_order_numbers: Vec<ForcedWorkOrder> = vec![];
         let mut state_change = false;
@@ -215,7 +235,7 @@ where
             Ok(ObjectiveValueType::Better(strategic_objective_value))
         } else {
             event!(Level::INFO, strategic_objective_value_worse = ?strategic_objective_value);
-            Ok(ObjectiveValueType::Worse)
+            Ok(ObjectiveValueType::Worse(strategic_objective_value))
         }
     }
 
@@ -264,48 +284,24 @@ where
     }
 
     fn unschedule(&mut self) -> Result<()> {
-        let mut rng = rand::rng();
-        let strategic_work_orders = &self.solution.strategic_scheduled_work_orders;
-
-        let strategic_parameters = &self.parameters.strategic_work_order_parameters;
-
-        let mut filtered_keys: Vec<_> = strategic_work_orders
-            .iter()
-            .filter(|(won, _)| {
-                strategic_parameters
-                    .get(won)
-                    .unwrap()
-                    .locked_in_period
-                    .is_none()
-            })
-            .map(|(&won, _)| won)
-            .collect();
+        let mut rng = StdRng::from_rng(&mut self.rng);
 
-        filtered_keys.sort();
+        let (destroy_operator, repair_operator) = self
+            .operators
+            .select::<StrategicDestroyOperator, StrategicRepairOperator>(&mut rng);
 
-        let sampled_work_order_keys = filtered_keys
-            .choose_multiple(
-                &mut rng,
-                self.parameters
-                    .strategic_options
-                    .number_of_removed_work_orders,
-            )
-            .collect::<Vec<_>>()
-            .clone();
+        let sampled_work_order_keys = self
+            .destroy_work_orders(destroy_operator, &mut rng)
+            .with_context(|| format!("Could not destroy with: {destroy_operator:?}"))?;
 
         // assert!(self.solution.scheduled_periods.values().all(|per| per.is_some()));
-        for work_order_number in sampled_work_order_keys {
+        for work_order_number in &sampled_work_order_keys {
 
             self.unschedule_specific_work_order(*work_order_number)
                 .with_context(|| format!("Could not unschedule: {work_order_number:?}\nLocation: {}", Location::caller()))?;
 
-            let weight = self
-                .parameters
-                .strategic_work_order_parameters
-                .get(work_order_number)
-                .context("Parameters should always be available")?
-                .weight;
-            self.solution_intermediate.push(*work_order_number, weight);
+            let priority = self.repair_priority(repair_operator, work_order_number, &mut rng)?;
+            self.solution_intermediate.push(*work_order_number, priority);
         }
         Ok(())
     }
@@ -313,6 +309,80 @@ where
     fn algorithm_util_methods(&mut self) -> &mut Self::Algorithm {
         &mut self.0
     }
+
+    fn acceptance_criterion(&self) -> &AcceptanceCriterion {
+        &self.parameters.strategic_options.acceptance_criterion
+    }
+
+    fn update_options(&mut self, actor_options: ActorOptions) -> Result<()> {
+        match actor_options {
+            ActorOptions::Strategic(strategic_options) => {
+                self.parameters.strategic_options = strategic_options;
+                Ok(())
+            }
+            actor_options => bail!(
+                "StrategicActor {} cannot use {:?} options",
+                self.id,
+                actor_options.actor_kind()
+            ),
+        }
+    }
+
+    fn warm_start(&mut self, checkpointed_solution: StrategicSolution) -> Result<Reconciliation> {
+        let current: Vec<WorkOrderNumber> = self
+            .parameters
+            .strategic_work_order_parameters
+            .keys()
+            .copied()
+            .collect();
+
+        // The loadings are not taken from the checkpoint, they are rebuilt by
+        // scheduling each work order again with the current parameters.
+        Reconciliation::replay(
+            current,
+            checkpointed_solution.strategic_scheduled_work_orders,
+            |work_order_number, period| {
+                let is_current = self
+                    .solution
+                    .strategic_scheduled_work_orders
+                    .contains_key(&work_order_number);
+
+                match warm_start_action(is_current, period, &self.parameters.strategic_periods) {
+                    WarmStartAction::Keep => Ok(true),
+                    WarmStartAction::Drop => Ok(false),
+                    WarmStartAction::Schedule(period) => Ok(self
+                        .schedule_strategic_work_order(work_order_number, &period)
+                        .with_context(|| {
+                            format!("{work_order_number:?} could not be replayed in {period}")
+                        })?
+                        .is_none()),
+                }
+            },
+        )
+    }
+}
+
+/// What the warm start does with a work order of a checkpoint.
+#[derive(Debug, PartialEq, Eq)]
+enum WarmStartAction {
+    /// The work order stays unscheduled.
+    Keep,
+    Schedule(Period),
+    /// The work order or its period is no longer in the `StrategicParameters`.
+    Drop,
+}
+
+fn warm_start_action(
+    is_current: bool,
+    checkpointed_period: Option<Period>,
+    strategic_periods: &[Period],
+) -> WarmStartAction {
+    match checkpointed_period {
+        _ if !is_current => WarmStartAction::Drop,
+        None => WarmStartAction::Keep,
+        Some(period) if strategic_periods.contains(&period) => WarmStartAction::Schedule(period),
+        Some(_) => WarmStartAction::Drop,
+    }
 }
 
 impl<Ss> StrategicAlgorithm<Ss>
@@ -620,7 +690,7 @@ pub trait StrategicUtils {
     );
 
     fn determine_best_permutation(
-        &self,
+        &mut self,
         work_load: HashMap<Resources, Work>,
         period: &Period,
         schedule: ScheduleWorkOrder,
@@ -791,6 +861,7 @@ where
         // it would not make sense for this kind of function. I believe that the best
         // decision here is to make a function that lets us manually update and change a
         // value.
+        let changed_periods: Vec<Period> = strategic_resources.0.keys().cloned().collect();
         for (period, operational_resources) in strategic_resources.0 {
             for (operational_id, loading) in operational_resources {
                 match load_operation {
@@ -835,6 +906,10 @@ where
                 }
             }
         }
+
+        for period in &changed_periods {
+            self.update_overloaded_resource_periods(period);
+        }
     }
 
     /// This was such a stupid direction to take the code in.
@@ -845,12 +920,12 @@ where
     /// * If true, return the loading that should be put into the
     ///   StrategicSolution::strategic_loadings.
     fn determine_best_permutation(
-        &self,
+        &mut self,
         work_load: HashMap<Resources, Work>,
         period: &Period,
         schedule: ScheduleWorkOrder,
     ) -> Result<Option<StrategicResources>> {
-        let mut rng = rand::rng();
+        let mut rng = StdRng::from_rng(&mut self.rng);
         let mut best_total_excess = Work::from(-999999999.0);
         let mut best_work_order_resource_loadings = StrategicResources::default();
 
@@ -890,15 +965,19 @@ where
         let mut error_for_unschedule = HashSet::new();
         let mut store_strategic_resources_options = vec![];
         for _ in 0..10 {
+            // Sorted before the shuffle, as the `HashMap` order differs between
+            // runs and would break the seeded `rng`.
             let mut technician_permutation = difference_resources
                 .clone()
-                .into_values()
+                .into_iter()
+                .sorted_by(|a, b| a.0.cmp(&b.0))
+                .map(|(_, operational_resource)| operational_resource)
                 .collect::<Vec<_>>();
             technician_permutation.shuffle(&mut rng);
 
             // Perform 10 different work_load permutations
             for _ in 0..10 {
-                let mut work_load_permutation = work_load.clone().into_iter().collect::<Vec<_>>();
+                let mut work_load_permutation = work_load.clone().into_iter().sorted_by_key(|(resource, _)| *resource).collect::<Vec<_>>();
 
 
                 work_load_permutation.shuffle(&mut rng);
@@ -1613,6 +1692,8 @@ where
         &mut self,
         strategic_scheduling_request: StrategicRequestScheduling,
     ) -> Result<StrategicResponseScheduling> {
+        self.update_portfolio_workers();
+
         match strategic_scheduling_request {
             StrategicRequestScheduling::Schedule(schedule_work_order) => {
                 let period = self
@@ -1621,12 +1702,7 @@ where
                     .iter()
                     .find(|period| period.period_string() == schedule_work_order.period_string())
                     .cloned()
-                    .with_context(|| {
-                        format!(
-                            "period: {:?} does not exist",
-                            schedule_work_order.period_string()
-                        )
-                    })?;
+                    .ok_or_else(|| ActorError::UnknownPeriod(schedule_work_order.period_string()))?;
 
                 let mut number_of_work_orders = 0;
                 for work_order_number in schedule_work_order.work_order_number {
@@ -1634,7 +1710,7 @@ where
                         .parameters
                         .strategic_work_order_parameters
                         .get_mut(&work_order_number)
-                        .unwrap();
+                        .ok_or(ActorError::UnknownWorkOrder(work_order_number))?;
                     if strategic_parameter.excluded_periods.contains(&period) {
                         strategic_parameter.excluded_periods.remove(&period);
                     }
@@ -1657,35 +1733,49 @@ where
                     .find(|period| {
                         period.period_string() == exclude_from_period.period_string().clone()
                     })
-                    .with_context(|| {
-                        format!(
-                            "{} was not found in the {}",
-                            exclude_from_period.period_string,
-                            std::any::type_name::<TimeEnvironment>()
-                        )
-                    })
-                    .cloned()?;
+                    .cloned()
+                    .ok_or_else(|| ActorError::UnknownPeriod(exclude_from_period.period_string()))?;
 
-                let mut number_of_work_orders = 0;
-                for work_order_number in exclude_from_period.work_order_number {
-                    let solution = self
+                // Every work order is validated before any of them is excluded.
+                for work_order_number in &exclude_from_period.work_order_number {
+                    let work_order_number = *work_order_number;
+                    let strategic_parameter = self
+                        .parameters
+                        .strategic_work_order_parameters
+                        .get(&work_order_number)
+                        .ok_or(ActorError::UnknownWorkOrder(work_order_number))?;
+
+                    if let Some(Some(scheduled_period)) = self
                         .solution
                         .strategic_scheduled_work_orders
                         .get(&work_order_number)
-                        .as_ref()
-                        .unwrap()
-                        .as_ref()
-                        .unwrap()
-                        .clone();
-
-                    let strategic_parameter = self
-                            .parameters
-                            .strategic_work_order_parameters
-                            .get_mut(&work_order_number)
-                            .with_context(|| format!("The {:?} was not found in the {:#?}. The {:#?} should have been initialized at creation.", work_order_number, type_name::<WorkOrderParameter>(), type_name::<WorkOrderParameter>()))?;
+                        && strategic_parameter.excluded_periods.contains(scheduled_period)
+                    {
+                        bail!(ActorError::InvariantViolated(format!(
+                            "{work_order_number:?} is scheduled in its excluded period {}",
+                            scheduled_period.period_string()
+                        )));
+                    }
 
-                    assert!(!strategic_parameter.excluded_periods.contains(&solution));
+                    // A work order has to be schedulable in at least one period.
+                    if self.parameters.strategic_periods.iter().all(|strategic_period| {
+                        strategic_period == &period
+                            || strategic_parameter.excluded_periods.contains(strategic_period)
+                    }) {
+                        bail!(ActorError::InfeasibleLock(format!(
+                            "excluding {work_order_number:?} from {} leaves no period to schedule it in",
+                            period.period_string()
+                        )));
+                    }
+                }
 
+                let mut number_of_work_orders = 0;
+                for work_order_number in exclude_from_period.work_order_number {
+                    let strategic_parameter = self
+                        .parameters
+                        .strategic_work_order_parameters
+                        .get_mut(&work_order_number)
+                        .expect("The WorkOrderParameter was validated above");
                     strategic_parameter.excluded_periods.insert(period.clone());
 
                     // assert!(!strategic_parameter.excluded_periods.contains(self.solution.
@@ -1736,7 +1826,10 @@ where
         }
     }
 
-    fn unschedule_specific_work_order(&mut self, work_order_number: WorkOrderNumber) -> Result<()> {
+    pub(crate) fn unschedule_specific_work_order(
+        &mut self,
+        work_order_number: WorkOrderNumber,
+    ) -> Result<()> {
         let unschedule_from_period = self
             .solution
             .strategic_scheduled_work_orders
@@ -1754,7 +1847,8 @@ where
                 .parameters
                 .strategic_work_order_parameters
                 .get(&work_order_number)
-                .unwrap();
+                .unwrap()
+                .clone();
 
             let work_load = strategic_parameter.work_load.clone();
 
@@ -1793,28 +1887,32 @@ where
                 .is_none()
             {
                 let strategic_work_order_weight = strategic_parameter.weight;
-                self.solution_intermediate
-                    .push(*work_order_number, strategic_work_order_weight);
+                self.solution_intermediate.push(
+                    *work_order_number,
+                    RepairPriority::Highest(strategic_work_order_weight),
+                );
             }
         }
     }
 }
 
 impl<Ss>
-    From<Algorithm<StrategicSolution, StrategicParameters, PriorityQueue<WorkOrderNumber, u64>, Ss>>
+    From<Algorithm<StrategicSolution, StrategicParameters, RepairQueue, Ss>>
     for StrategicAlgorithm<Ss>
 where
     Ss: SystemSolutions,
 {
     fn from(
-        value: Algorithm<
+        mut value: Algorithm<
             StrategicSolution,
             StrategicParameters,
-            PriorityQueue<WorkOrderNumber, u64>,
+            RepairQueue,
             Ss,
         >,
     ) -> Self {
-        StrategicAlgorithm(value)
+        value.operators =
+            OperatorPortfolio::new::<StrategicDestroyOperator, StrategicRepairOperator>();
+        StrategicAlgorithm(value, StrategicPortfolio::default())
     }
 }
 
@@ -1825,6 +1923,7 @@ mod tests {
 
     use rand::SeedableRng;
     use rand::rngs::StdRng;
+    use rand::seq::IndexedRandom;
     use strategic_parameters::WorkOrderParameter;
 
     use super::*;
@@ -2815,4 +2914,32 @@ mod tests {
         assert_eq!(period_1, period_2);
         assert_eq!(period_1, period_1.clone());
     }
+
+    #[test]
+    fn test_warm_start_action() {
+        let period = Period::from_str("2023-W47-48").unwrap();
+        let rolled_period = Period::from_str("2023-W45-46").unwrap();
+        let strategic_periods = vec![period.clone()];
+
+        assert_eq!(
+            warm_start_action(true, Some(period.clone()), &strategic_periods),
+            WarmStartAction::Schedule(period.clone())
+        );
+        assert_eq!(
+            warm_start_action(true, None, &strategic_periods),
+            WarmStartAction::Keep
+        );
+        assert_eq!(
+            warm_start_action(true, Some(rolled_period), &strategic_periods),
+            WarmStartAction::Drop
+        );
+        assert_eq!(
+            warm_start_action(false, Some(period), &strategic_periods),
+            WarmStartAction::Drop
+        );
+        assert_eq!(
+            warm_start_action(false, None, &strategic_periods),
+            WarmStartAction::Drop
+        );
+    }
 }
@@ -0,0 +1,412 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use anyhow::Context;
+use anyhow::Result;
+use ordinator_actor_core::operators::NeighborhoodOperator;
+use ordinator_actor_core::operators::RepairPriority;
+use ordinator_actor_core::operators::rank_worst_first;
+use ordinator_actor_core::operators::sample;
+use ordinator_actor_core::operators::sample_from_ranked;
+use ordinator_orchestrator_actor_traits::SystemSolutions;
+use ordinator_scheduling_environment::time_environment::period::Period;
+use ordinator_scheduling_environment::work_order::WorkOrderNumber;
+use ordinator_scheduling_environment::work_order::operation::Work;
+use ordinator_scheduling_environment::worker_environment::resources::Resources;
+use rand::Rng;
+use rand::seq::IndexedRandom;
+use strum::IntoEnumIterator;
+
+use super::StrategicAlgorithm;
+use super::calculate_period_difference;
+use super::strategic_parameters::ClusteringValue;
+use super::strategic_resources::StrategicResources;
+
+/// The destroy operators of the `StrategicAlgorithm`. Each of them removes up
+/// to `number_of_removed_work_orders` work orders that are not locked in a
+/// period.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StrategicDestroyOperator
+{
+    Random,
+    /// Removes the work orders that contribute the most to the urgency
+    /// objective, meaning the ones scheduled furthest past their latest period.
+    WorstUrgency,
+    /// Removes a random work order together with the work orders that have the
+    /// highest clustering value with it. The clustering is derived from the
+    /// functional locations.
+    RelatedByFunctionalLocation,
+    /// Removes random work orders from a single period.
+    RelatedByPeriod,
+    /// Removes work orders that load a resource in a period where the loading
+    /// exceeds the capacity.
+    OverloadedResourcePeriod,
+}
+
+/// The repair operators decide the order in which the removed work orders are
+/// popped from the priority queue in `schedule`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StrategicRepairOperator
+{
+    GreedyByWeight,
+    EarliestLatestPeriod,
+    RandomOrder,
+}
+
+impl NeighborhoodOperator for StrategicDestroyOperator
+{
+    const ALL: &'static [Self] = &[
+        StrategicDestroyOperator::Random,
+        StrategicDestroyOperator::WorstUrgency,
+        StrategicDestroyOperator::RelatedByFunctionalLocation,
+        StrategicDestroyOperator::RelatedByPeriod,
+        StrategicDestroyOperator::OverloadedResourcePeriod,
+    ];
+
+    fn name(&self) -> &'static str
+    {
+        match self {
+            StrategicDestroyOperator::Random => "random",
+            StrategicDestroyOperator::WorstUrgency => "worst_urgency",
+            StrategicDestroyOperator::RelatedByFunctionalLocation => {
+                "related_by_functional_location"
+            }
+            StrategicDestroyOperator::RelatedByPeriod => "related_by_period",
+            StrategicDestroyOperator::OverloadedResourcePeriod => "overloaded_resource_period",
+        }
+    }
+}
+
+impl NeighborhoodOperator for StrategicRepairOperator
+{
+    const ALL: &'static [Self] = &[
+        StrategicRepairOperator::GreedyByWeight,
+        StrategicRepairOperator::EarliestLatestPeriod,
+        StrategicRepairOperator::RandomOrder,
+    ];
+
+    fn name(&self) -> &'static str
+    {
+        match self {
+            StrategicRepairOperator::GreedyByWeight => "greedy_by_weight",
+            StrategicRepairOperator::EarliestLatestPeriod => "earliest_latest_period",
+            StrategicRepairOperator::RandomOrder => "random_order",
+        }
+    }
+}
+
+impl<Ss> StrategicAlgorithm<Ss>
+where
+    Ss: SystemSolutions,
+{
+    pub(crate) fn destroy_work_orders(
+        &self,
+        destroy_operator: StrategicDestroyOperator,
+        rng: &mut impl Rng,
+    ) -> Result<Vec<WorkOrderNumber>>
+    {
+        let number_of_removed_work_orders = self
+            .parameters
+            .strategic_options
+            .number_of_removed_work_orders;
+
+        let mut candidates: Vec<WorkOrderNumber> = self
+            .solution
+            .strategic_scheduled_work_orders
+            .keys()
+            .filter(|won| {
+                self.parameters
+                    .strategic_work_order_parameters
+                    .get(won)
+                    .is_some_and(|strategic_parameter| {
+                        strategic_parameter.locked_in_period.is_none()
+                    })
+            })
+            .copied()
+            .collect();
+
+        // Sorted so that a seeded rng always removes the same work orders.
+        candidates.sort();
+
+        let work_order_numbers = match destroy_operator {
+            StrategicDestroyOperator::Random => {
+                sample(&candidates, number_of_removed_work_orders, rng)
+            }
+            StrategicDestroyOperator::WorstUrgency => {
+                let urgencies = candidates
+                    .iter()
+                    .map(|won| Ok((*won, self.urgency_contribution(won)?)))
+                    .collect::<Result<Vec<_>>>()?;
+
+                let worst = rank_worst_first(urgencies);
+
+                sample_from_ranked(&worst, number_of_removed_work_orders, rng)
+            }
+            StrategicDestroyOperator::RelatedByFunctionalLocation => related_by_clustering(
+                &candidates,
+                &self.parameters.strategic_clustering.inner,
+                number_of_removed_work_orders,
+                rng,
+            ),
+            StrategicDestroyOperator::RelatedByPeriod => {
+                let periods_in_use = self
+                    .parameters
+                    .strategic_periods
+                    .iter()
+                    .filter(|period| {
+                        candidates.iter().any(|won| self.scheduled_period(won) == Some(period))
+                    })
+                    .collect::<Vec<_>>();
+
+                match periods_in_use.choose(rng) {
+                    Some(period) => {
+                        let in_period = candidates
+                            .iter()
+                            .filter(|won| self.scheduled_period(won) == Some(*period))
+                            .copied()
+                            .collect::<Vec<_>>();
+
+                        sample(&in_period, number_of_removed_work_orders, rng)
+                    }
+                    None => sample(&candidates, number_of_removed_work_orders, rng),
+                }
+            }
+            StrategicDestroyOperator::OverloadedResourcePeriod => {
+                let overloaded_cells = self
+                    .solution
+                    .overloaded_resource_periods
+                    .iter()
+                    .collect::<Vec<_>>();
+
+                match overloaded_cells.choose(rng) {
+                    Some((period, resource)) => {
+                        let in_cell = candidates
+                            .iter()
+                            .filter(|won| self.scheduled_period(won) == Some(period))
+                            .filter(|won| {
+                                self.parameters
+                                    .strategic_work_order_parameters
+                                    .get(won)
+                                    .and_then(|sp| sp.work_load.get(resource))
+                                    .is_some_and(|work| *work > Work::from(0.0))
+                            })
+                            .copied()
+                            .collect::<Vec<_>>();
+
+                        sample(&in_cell, number_of_removed_work_orders, rng)
+                    }
+                    None => sample(&candidates, number_of_removed_work_orders, rng),
+                }
+            }
+        };
+
+        Ok(work_order_numbers)
+    }
+
+    /// The priority that a removed work order gets in the priority queue. The
+    /// queue pops the highest priority first.
+    pub(crate) fn repair_priority(
+        &self,
+        repair_operator: StrategicRepairOperator,
+        work_order_number: &WorkOrderNumber,
+        rng: &mut impl Rng,
+    ) -> Result<RepairPriority>
+    {
+        let strategic_parameter = self
+            .parameters
+            .strategic_work_order_parameters
+            .get(work_order_number)
+            .context("Parameters should always be available")?;
+
+        Ok(match repair_operator {
+            StrategicRepairOperator::GreedyByWeight => {
+                RepairPriority::Highest(strategic_parameter.weight)
+            }
+            StrategicRepairOperator::EarliestLatestPeriod => RepairPriority::Earliest(Reverse(
+                i64::from(*strategic_parameter.latest_period.id()),
+            )),
+            StrategicRepairOperator::RandomOrder => RepairPriority::Highest(rng.random()),
+        })
+    }
+
+    fn scheduled_period(&self, work_order_number: &WorkOrderNumber) -> Option<&Period>
+    {
+        self.solution
+            .strategic_scheduled_work_orders
+            .get(work_order_number)
+            .and_then(|period| period.as_ref())
+    }
+
+    /// The same penalty as the one used in `determine_urgency`.
+    fn urgency_contribution(&self, work_order_number: &WorkOrderNumber) -> Result<u64>
+    {
+        let strategic_parameter = self
+            .parameters
+            .strategic_work_order_parameters
+            .get(work_order_number)
+            .context("Parameters should always be available")?;
+
+        let scheduled_period = match self.scheduled_period(work_order_number) {
+            Some(period) => period,
+            None => self
+                .parameters
+                .strategic_periods
+                .last()
+                .context("There should always be a last period")?,
+        };
+
+        Ok(
+            calculate_period_difference(scheduled_period, &strategic_parameter.latest_period)
+                * strategic_parameter.weight,
+        )
+    }
+
+    /// Checks the loading against the capacity of every resource in the
+    /// `period`, so that `OverloadedResourcePeriod` does not have to check
+    /// all periods on every iteration.
+    pub(crate) fn update_overloaded_resource_periods(&mut self, period: &Period)
+    {
+        for resource in Resources::iter() {
+            let cell = (period.clone(), resource);
+            if is_overloaded(
+                &self.solution.strategic_loadings,
+                &self.parameters.strategic_capacity,
+                period,
+                &resource,
+            ) {
+                self.solution.overloaded_resource_periods.insert(cell);
+            } else {
+                self.solution.overloaded_resource_periods.remove(&cell);
+            }
+        }
+    }
+
+    /// Used when the capacities change, as that does not go through the
+    /// loadings.
+    pub(crate) fn update_all_overloaded_resource_periods(&mut self)
+    {
+        for period in self.parameters.strategic_periods.clone() {
+            self.update_overloaded_resource_periods(&period);
+        }
+    }
+}
+
+/// A period that is missing from the `StrategicResources` counts as zero.
+fn is_overloaded(
+    loadings: &StrategicResources,
+    capacity: &StrategicResources,
+    period: &Period,
+    resource: &Resources,
+) -> bool
+{
+    let loading = loadings
+        .aggregated_capacity_by_period_and_resource(period, resource)
+        .unwrap_or_default();
+    let capacity = capacity
+        .aggregated_capacity_by_period_and_resource(period, resource)
+        .unwrap_or_default();
+
+    loading > capacity
+}
+
+fn related_by_clustering(
+    candidates: &[WorkOrderNumber],
+    clustering: &HashMap<(WorkOrderNumber, WorkOrderNumber), ClusteringValue>,
+    amount: usize,
+    rng: &mut impl Rng,
+) -> Vec<WorkOrderNumber>
+{
+    let Some(seed) = candidates.choose(rng).copied() else {
+        return vec![];
+    };
+
+    let mut related = candidates
+        .iter()
+        .filter(|won| **won != seed)
+        .map(|won| {
+            let clustering_value = clustering
+                .get(&(seed, *won))
+                .or_else(|| clustering.get(&(*won, seed)))
+                .copied()
+                .unwrap_or_default();
+            (*won, clustering_value)
+        })
+        .collect::<Vec<_>>();
+
+    related.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    std::iter::once(seed)
+        .chain(related.into_iter().map(|(won, _)| won))
+        .take(amount)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    use ordinator_scheduling_environment::time_environment::period::Period;
+    use ordinator_scheduling_environment::work_order::WorkOrderNumber;
+    use ordinator_scheduling_environment::work_order::operation::Work;
+    use ordinator_scheduling_environment::worker_environment::resources::Resources;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::is_overloaded;
+    use super::related_by_clustering;
+    use crate::algorithm::strategic_resources::OperationalResource;
+    use crate::algorithm::strategic_resources::StrategicResources;
+
+    #[test]
+    fn test_related_by_clustering_removes_most_related()
+    {
+        let candidates = [
+            WorkOrderNumber(2100000001),
+            WorkOrderNumber(2100000002),
+            WorkOrderNumber(2100000003),
+        ];
+
+        let mut clustering = HashMap::new();
+        clustering.insert((candidates[0], candidates[1]), 1);
+        clustering.insert((candidates[2], candidates[0]), 10);
+        clustering.insert((candidates[1], candidates[2]), 1);
+
+        for seed in 0..10 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let removed = related_by_clustering(&candidates, &clustering, 2, &mut rng);
+
+            assert_eq!(removed.len(), 2);
+            if removed[0] == candidates[0] {
+                assert_eq!(removed[1], candidates[2]);
+            }
+            if removed[0] == candidates[2] {
+                assert_eq!(removed[1], candidates[0]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_overloaded_compares_loading_with_capacity()
+    {
+        let period = Period::from_str("2026-W41-42").unwrap();
+        let missing_period = Period::from_str("2026-W43-44").unwrap();
+
+        let mut capacity = StrategicResources::default();
+        capacity.insert_operational_resource(
+            period.clone(),
+            OperationalResource::new("OP_TEST_0", Work::from(40.0), vec![Resources::MtnMech]),
+        );
+
+        let mut loadings = StrategicResources::default();
+        loadings.insert_operational_resource(
+            period.clone(),
+            OperationalResource::new("OP_TEST_0", Work::from(50.0), vec![Resources::MtnMech]),
+        );
+
+        assert!(is_overloaded(&loadings, &capacity, &period, &Resources::MtnMech));
+        assert!(!is_overloaded(&loadings, &capacity, &period, &Resources::MtnElec));
+        assert!(!is_overloaded(&loadings, &capacity, &missing_period, &Resources::MtnMech));
+    }
+}
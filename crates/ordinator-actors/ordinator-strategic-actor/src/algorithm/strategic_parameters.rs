@@ -5,6 +5,7 @@ use std::sync::MutexGuard;
 use anyhow::Context;
 use anyhow::Result;
 use anyhow::bail;
+use ordinator_orchestrator_actor_traits::ActorError;
 use ordinator_orchestrator_actor_traits::Parameters;
 use ordinator_scheduling_environment::Asset;
 use ordinator_scheduling_environment::SchedulingEnvironment;
@@ -16,6 +17,7 @@ use ordinator_scheduling_environment::work_order::WorkOrderConfigurations;
 use ordinator_scheduling_environment::work_order::WorkOrderNumber;
 use ordinator_scheduling_environment::work_order::WorkOrders;
 use ordinator_scheduling_environment::work_order::operation::Work;
+use ordinator_scheduling_environment::work_order::planner_decision::PlannerDecision;
 use ordinator_scheduling_environment::worker_environment::StrategicOptions;
 use ordinator_scheduling_environment::worker_environment::resources::Id;
 use ordinator_scheduling_environment::worker_environment::resources::Resources;
@@ -23,7 +25,7 @@ use serde::Serialize;
 
 use super::StrategicResources;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct StrategicParameters
 {
     pub strategic_work_order_parameters: HashMap<WorkOrderNumber, WorkOrderParameter>,
@@ -102,6 +104,7 @@ impl Parameters for StrategicParameters
                             work_order_configurations,
                             material_to_period,
                         )?
+                        .with_planner_decision(&wo.planner_decision, strategic_periods)
                         .build(),
                 ))
             })
@@ -227,10 +230,7 @@ impl StrategicParameters
             .get_mut(&work_order_number)
         {
             Some(optimized_work_order) => optimized_work_order,
-            None => bail!(
-                "Work order number {:?} not found in optimized work orders",
-                work_order_number
-            ),
+            None => bail!(ActorError::UnknownWorkOrder(work_order_number)),
         };
         optimized_work_order.locked_in_period = Some(period);
         Ok(())
@@ -380,6 +380,40 @@ impl WorkOrderParameterBuilder
         Ok(self)
     }
 
+    /// The `PlannerDecision` takes precedence over the locked in and excluded
+    /// periods derived from the `WorkOrder`. Decisions on periods that are no
+    /// longer in `periods` are ignored.
+    pub fn with_planner_decision(
+        mut self,
+        planner_decision: &PlannerDecision,
+        periods: &[Period],
+    ) -> Self
+    {
+        self.excluded_periods.extend(
+            planner_decision
+                .excluded_periods
+                .iter()
+                .filter(|period| periods.contains(period))
+                .cloned(),
+        );
+
+        if self
+            .locked_in_period
+            .as_ref()
+            .is_some_and(|period| self.excluded_periods.contains(period))
+        {
+            self.locked_in_period = None;
+        }
+
+        if let Some(locked_in_period) = &planner_decision.locked_in_period
+            && periods.contains(locked_in_period)
+        {
+            self.excluded_periods.remove(locked_in_period);
+            self.locked_in_period = Some(locked_in_period.clone());
+        }
+        self
+    }
+
     pub fn build(self) -> WorkOrderParameter
     {
         if let Some(ref locked_in_period) = self.locked_in_period {
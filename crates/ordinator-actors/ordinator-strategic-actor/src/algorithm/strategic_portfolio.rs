@@ -0,0 +1,433 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use ordinator_actor_core::traits::AbLNSUtils;
+use ordinator_actor_core::traits::ActorBasedLargeNeighborhoodSearch;
+use ordinator_actor_core::traits::IterationOutcome;
+use ordinator_actor_core::traits::IterationReport;
+use ordinator_actor_core::traits::ObjectiveValue;
+use ordinator_orchestrator_actor_traits::SystemSolutions;
+use ordinator_scheduling_environment::worker_environment::StrategicOptions;
+use rand::Rng;
+
+use super::StrategicAlgorithm;
+use super::strategic_parameters::StrategicParameters;
+use super::strategic_solution::StrategicSolution;
+
+/// The workers of the parallel multi-start search of the `StrategicActor`.
+/// They are forked from the `StrategicAlgorithm` on the first round of the
+/// portfolio. When the `StrategicParameters` change, the next round copies
+/// them and the `StrategicSolution` into every worker.
+pub struct StrategicPortfolio<Ss>
+where
+    Ss: SystemSolutions,
+{
+    workers: Vec<StrategicAlgorithm<Ss>>,
+    parameters_changed: bool,
+}
+
+impl<Ss> Default for StrategicPortfolio<Ss>
+where
+    Ss: SystemSolutions,
+{
+    fn default() -> Self
+    {
+        Self {
+            workers: vec![],
+            parameters_changed: false,
+        }
+    }
+}
+
+impl<Ss> StrategicAlgorithm<Ss>
+where
+    Ss: SystemSolutions,
+{
+    /// Planner locks and changed work orders have to reach every worker. They
+    /// are copied into the workers at the start of the next round, so several
+    /// changes between two rounds are only copied once.
+    pub fn update_portfolio_workers(&mut self)
+    {
+        self.1.parameters_changed = true;
+    }
+
+    pub fn discard_portfolio_workers(&mut self)
+    {
+        self.1.workers.clear();
+        self.1.parameters_changed = false;
+    }
+}
+
+impl<Ss> StrategicAlgorithm<Ss>
+where
+    Ss: SystemSolutions<Strategic = StrategicSolution>,
+    Self:
+        ActorBasedLargeNeighborhoodSearch<Algorithm: AbLNSUtils<SolutionType = StrategicSolution>>,
+{
+    /// Runs `exchange_interval` LNS iterations on every worker in parallel.
+    /// The best solution of the workers is published if it improves on the
+    /// current solution, and every worker that is behind continues from it.
+    pub fn run_portfolio_round(&mut self) -> Result<IterationReport>
+    {
+        self.update_based_on_system_solution().with_context(|| {
+            format!(
+                "Could not update the Algorithm state based on SystemSolution\nLocation: {}:{}",
+                file!(),
+                line!()
+            )
+        })?;
+
+        let strategic_options = self.0.parameters.strategic_options.clone();
+        if self.1.workers.len() != strategic_options.portfolio.number_of_workers {
+            self.fork_portfolio_workers(strategic_options.portfolio.number_of_workers);
+        }
+
+        if self.1.parameters_changed {
+            synchronize_workers(
+                &self.0.parameters,
+                &self.0.solution,
+                self.1
+                    .workers
+                    .iter_mut()
+                    .map(|worker| (&mut worker.0.parameters, &mut worker.0.solution)),
+            );
+            for worker in &mut self.1.workers {
+                worker.0.solution_intermediate.clear();
+                worker.0.best_solution = None;
+            }
+            self.1.parameters_changed = false;
+        }
+
+        // The workers see the same `SystemSolution` as the `StrategicAlgorithm`,
+        // and options that were updated since the last round.
+        for (index, worker) in self.1.workers.iter_mut().enumerate() {
+            worker.0.parameters.strategic_options = worker_options(&strategic_options, index);
+            worker
+                .0
+                .arc_swap_shared_solution
+                .store(Arc::clone(&self.0.loaded_shared_solution));
+        }
+
+        let exchange_interval = strategic_options.portfolio.exchange_interval;
+        let worker_reports = std::thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .1
+                .workers
+                .iter_mut()
+                .map(|worker| {
+                    scope.spawn(move || {
+                        (0..exchange_interval)
+                            .map(|_| worker.run_lns_iteration())
+                            .collect::<Result<Vec<_>>>()
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                })
+                .collect::<Result<Vec<_>>>()
+        })
+        .context("A worker of the strategic portfolio failed")?;
+
+        // The workers publish into their own `SystemSolution`, so only the
+        // global best of the round reaches the `Orchestrator`.
+        let global_best = global_best(
+            &self.0.solution,
+            self.1
+                .workers
+                .iter()
+                .map(|worker| (&worker.0.solution, worker.0.best_solution.as_ref())),
+        )
+        .cloned();
+
+        let improved = global_best.is_some();
+        if let Some(global_best) = global_best {
+            self.0.solution = global_best;
+            self.publish_best_solution();
+        }
+
+        for worker in &mut self.1.workers {
+            if self.0.solution.objective_value.objective_value
+                < worker.0.solution.objective_value.objective_value
+            {
+                worker.0.solution = self.0.solution.clone();
+                worker.0.solution_intermediate.clear();
+            }
+        }
+
+        let mut iteration_report = IterationReport {
+            outcome: if improved {
+                IterationOutcome::Improved
+            } else {
+                IterationOutcome::Rejected
+            },
+            current_cost: self.0.solution.objective_value.cost(),
+            best_cost: self.0.best_cost(),
+            threads: self.1.workers.len(),
+            unschedule_duration: Duration::ZERO,
+            schedule_duration: Duration::ZERO,
+            calculate_objective_value_duration: Duration::ZERO,
+        };
+
+        // The phases are timed as the sum over the workers, that is the CPU time
+        // that the round spent in them.
+        for worker_report in worker_reports.iter().flatten() {
+            iteration_report.unschedule_duration += worker_report.unschedule_duration;
+            iteration_report.schedule_duration += worker_report.schedule_duration;
+            iteration_report.calculate_objective_value_duration +=
+                worker_report.calculate_objective_value_duration;
+        }
+
+        Ok(iteration_report)
+    }
+
+    fn fork_portfolio_workers(&mut self, number_of_workers: usize)
+    {
+        self.1.workers = (0..number_of_workers)
+            .map(|_| {
+                let seed = self.0.rng.random();
+                StrategicAlgorithm::from(self.0.fork(seed))
+            })
+            .collect();
+        self.1.parameters_changed = false;
+    }
+}
+
+/// The best solution of the workers, if its objective value is lower than the
+/// objective value of the `current` solution. Each worker is given as its
+/// working solution and its best solution. The working solution has usually
+/// moved on from the best solution, and is only used by a worker that has not
+/// kept a best solution since its parameters changed.
+fn global_best<'a>(
+    current: &StrategicSolution,
+    workers: impl IntoIterator<Item = (&'a StrategicSolution, Option<&'a StrategicSolution>)>,
+) -> Option<&'a StrategicSolution>
+{
+    workers
+        .into_iter()
+        .map(|(solution, best_solution)| best_solution.unwrap_or(solution))
+        .enumerate()
+        .min_by_key(|(index, solution)| (solution.objective_value.objective_value, *index))
+        .filter(|(_, solution)| {
+            solution.objective_value.objective_value < current.objective_value.objective_value
+        })
+        .map(|(_, solution)| solution)
+}
+
+fn synchronize_workers<'a>(
+    parameters: &StrategicParameters,
+    solution: &StrategicSolution,
+    workers: impl IntoIterator<Item = (&'a mut StrategicParameters, &'a mut StrategicSolution)>,
+)
+{
+    for (worker_parameters, worker_solution) in workers {
+        worker_parameters.clone_from(parameters);
+        worker_solution.clone_from(solution);
+    }
+}
+
+/// Worker `index` cycles through the `acceptance_criteria` of the portfolio.
+fn worker_options(strategic_options: &StrategicOptions, index: usize) -> StrategicOptions
+{
+    let mut worker_options = strategic_options.clone();
+    if let Some(acceptance_criterion) = strategic_options
+        .portfolio
+        .acceptance_criteria
+        .iter()
+        .cycle()
+        .nth(index)
+    {
+        worker_options.acceptance_criterion = acceptance_criterion.clone();
+    }
+    worker_options
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::collections::HashMap;
+    use std::collections::HashSet;
+    use std::str::FromStr;
+
+    use anyhow::Result;
+    use ordinator_orchestrator_actor_traits::Solution;
+    use ordinator_scheduling_environment::time_environment::period::Period;
+    use ordinator_scheduling_environment::work_order::WorkOrderNumber;
+    use ordinator_scheduling_environment::worker_environment::AcceptanceCriterion;
+    use ordinator_scheduling_environment::worker_environment::StrategicOptions;
+    use ordinator_scheduling_environment::worker_environment::StrategicPortfolioOptions;
+
+    use super::global_best;
+    use super::synchronize_workers;
+    use super::worker_options;
+    use crate::algorithm::strategic_parameters::StrategicClustering;
+    use crate::algorithm::strategic_parameters::StrategicParameters;
+    use crate::algorithm::strategic_parameters::WorkOrderParameter;
+    use crate::algorithm::strategic_resources::StrategicResources;
+    use crate::algorithm::strategic_solution::StrategicSolution;
+
+    fn strategic_options() -> StrategicOptions
+    {
+        StrategicOptions {
+            number_of_removed_work_orders: 10,
+            urgency_weight: 1,
+            resource_penalty_weight: 1,
+            clustering_weight: 1,
+            acceptance_criterion: AcceptanceCriterion::HillClimbing,
+            portfolio: StrategicPortfolioOptions {
+                number_of_workers: 3,
+                exchange_interval: 10,
+                acceptance_criteria: vec![],
+            },
+        }
+    }
+
+    fn strategic_parameters() -> StrategicParameters
+    {
+        StrategicParameters {
+            strategic_work_order_parameters: HashMap::new(),
+            strategic_capacity: StrategicResources::default(),
+            strategic_clustering: StrategicClustering {
+                inner: HashMap::new(),
+            },
+            period_locks: HashSet::new(),
+            strategic_periods: vec![],
+            strategic_options: strategic_options(),
+        }
+    }
+
+    fn solution(objective_value: u64) -> Result<StrategicSolution>
+    {
+        let mut solution = StrategicSolution::new(&strategic_parameters())?;
+        solution.objective_value.objective_value = objective_value;
+        Ok(solution)
+    }
+
+    fn objective_value(solution: Option<&StrategicSolution>) -> Option<u64>
+    {
+        solution.map(|solution| solution.objective_value.objective_value)
+    }
+
+    #[test]
+    fn test_only_the_global_best_is_published() -> Result<()>
+    {
+        let workers = [solution(90)?, solution(70)?, solution(80)?];
+        let best_objective_value = |current: u64| -> Result<Option<u64>> {
+            Ok(objective_value(global_best(
+                &solution(current)?,
+                workers.iter().map(|worker| (worker, None)),
+            )))
+        };
+
+        assert_eq!(best_objective_value(100)?, Some(70));
+        assert_eq!(best_objective_value(70)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_the_best_solution_of_a_worker_is_adopted() -> Result<()>
+    {
+        // The first worker found 60 during the round but moved on to 95.
+        let (first_solution, first_best_solution) = (solution(95)?, solution(60)?);
+        let (second_solution, second_best_solution) = (solution(70)?, solution(70)?);
+
+        let global_best = global_best(
+            &solution(100)?,
+            [
+                (&first_solution, Some(&first_best_solution)),
+                (&second_solution, Some(&second_best_solution)),
+            ],
+        );
+
+        assert_eq!(global_best, Some(&first_best_solution));
+        Ok(())
+    }
+
+    #[test]
+    fn test_planner_lock_reaches_every_worker() -> Result<()>
+    {
+        let work_order_number = WorkOrderNumber(2100000001);
+        let period = Period::from_str("2023-W47-48").unwrap();
+
+        let mut parameters = StrategicParameters {
+            strategic_work_order_parameters: HashMap::from([(
+                work_order_number,
+                WorkOrderParameter::default(),
+            )]),
+            strategic_capacity: StrategicResources::default(),
+            strategic_clustering: StrategicClustering {
+                inner: HashMap::new(),
+            },
+            period_locks: HashSet::new(),
+            strategic_periods: vec![period.clone()],
+            strategic_options: strategic_options(),
+        };
+        let mut solution = StrategicSolution::new(&parameters)?;
+        let mut workers = vec![(parameters.clone(), solution.clone()); 3];
+
+        parameters
+            .strategic_work_order_parameters
+            .get_mut(&work_order_number)
+            .unwrap()
+            .locked_in_period = Some(period.clone());
+        solution
+            .strategic_scheduled_work_orders
+            .insert(work_order_number, Some(period.clone()));
+
+        synchronize_workers(
+            &parameters,
+            &solution,
+            workers
+                .iter_mut()
+                .map(|(worker_parameters, worker_solution)| (worker_parameters, worker_solution)),
+        );
+
+        for (worker_parameters, worker_solution) in &workers {
+            assert_eq!(
+                worker_parameters.strategic_work_order_parameters[&work_order_number]
+                    .locked_in_period,
+                Some(period.clone())
+            );
+            assert_eq!(
+                worker_solution.strategic_scheduled_work_orders[&work_order_number],
+                Some(period.clone())
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_worker_options_cycle_acceptance_criteria()
+    {
+        let mut strategic_options = strategic_options();
+
+        assert_eq!(
+            worker_options(&strategic_options, 2).acceptance_criterion,
+            AcceptanceCriterion::HillClimbing
+        );
+
+        let late_acceptance = AcceptanceCriterion::LateAcceptance { history_length: 5 };
+        strategic_options.portfolio.acceptance_criteria =
+            vec![AcceptanceCriterion::HillClimbing, late_acceptance.clone()];
+
+        assert_eq!(
+            worker_options(&strategic_options, 0).acceptance_criterion,
+            AcceptanceCriterion::HillClimbing
+        );
+        assert_eq!(
+            worker_options(&strategic_options, 1).acceptance_criterion,
+            late_acceptance
+        );
+        assert_eq!(
+            worker_options(&strategic_options, 2).acceptance_criterion,
+            AcceptanceCriterion::HillClimbing
+        );
+    }
+}
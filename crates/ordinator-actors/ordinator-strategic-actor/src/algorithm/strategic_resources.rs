@@ -19,7 +19,10 @@ use serde::Serialize;
 // be in the shared types. You should not deserialize this. You cannot
 // code software with this mentality.
 #[derive(Default, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
-pub struct StrategicResources(pub HashMap<Period, HashMap<OperationalId, OperationalResource>>);
+pub struct StrategicResources(
+    #[serde(with = "serde_json_any_key::any_key_map")]
+    pub HashMap<Period, HashMap<OperationalId, OperationalResource>>,
+);
 
 impl<'a> From<(&MutexGuard<'a, SchedulingEnvironment>, &Id)> for StrategicResources
 {
@@ -1,8 +1,10 @@
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::fmt::Debug;
 
 use anyhow::Result;
 use colored::Colorize;
+use ordinator_actor_core::traits::ObjectiveValue;
 use ordinator_orchestrator_actor_traits::Solution;
 use ordinator_orchestrator_actor_traits::SwapSolution;
 use ordinator_orchestrator_actor_traits::SystemSolutions;
@@ -10,6 +12,7 @@ use ordinator_scheduling_environment::time_environment::period::Period;
 use ordinator_scheduling_environment::work_order::WorkOrderNumber;
 use ordinator_scheduling_environment::work_order::operation::Work;
 use ordinator_scheduling_environment::worker_environment::StrategicOptions;
+use ordinator_scheduling_environment::worker_environment::resources::Resources;
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -17,12 +20,16 @@ use super::strategic_parameters::StrategicParameters;
 use super::strategic_resources::OperationalResource;
 use super::strategic_resources::StrategicResources;
 
-#[derive(PartialEq, Eq, Clone)]
+#[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct StrategicSolution
 {
     pub objective_value: StrategicObjectiveValue,
     pub strategic_scheduled_work_orders: HashMap<WorkOrderNumber, Option<Period>>,
     pub strategic_loadings: StrategicResources,
+    /// The periods and resources where the loading exceeds the capacity. It
+    /// is updated together with the `strategic_loadings`.
+    #[serde(skip)]
+    pub overloaded_resource_periods: BTreeSet<(Period, Resources)>,
 }
 impl Debug for StrategicSolution
 {
@@ -83,6 +90,14 @@ impl StrategicObjectiveValue
             - self.clustering_value.0 as u64 * self.clustering_value.1;
     }
 }
+impl ObjectiveValue for StrategicObjectiveValue
+{
+    fn cost(&self) -> f64
+    {
+        self.objective_value as f64
+    }
+}
+
 impl Solution for StrategicSolution
 {
     type ObjectiveValue = StrategicObjectiveValue;
@@ -137,9 +152,15 @@ impl Solution for StrategicSolution
             objective_value: strategic_objective_value,
             strategic_scheduled_work_orders,
             strategic_loadings,
+            overloaded_resource_periods: BTreeSet::new(),
         })
     }
 
+    fn objective_value(&self) -> &Self::ObjectiveValue
+    {
+        &self.objective_value
+    }
+
     fn update_objective_value(&mut self, other_objective_value: Self::ObjectiveValue)
     {
         self.objective_value = other_objective_value;
@@ -158,4 +179,12 @@ where
     {
         system_solution.strategic_swap(id, solution);
     }
+
+    fn from_system_solution(
+        _id: &ordinator_scheduling_environment::worker_environment::resources::Id,
+        system_solution: &Ss,
+    ) -> Option<Self>
+    {
+        system_solution.strategic().ok().cloned()
+    }
 }
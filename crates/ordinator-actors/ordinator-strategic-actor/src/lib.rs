@@ -8,6 +8,7 @@ use std::ops::DerefMut;
 use std::sync::Arc;
 use std::sync::Mutex;
 
+use algorithm::RepairQueue;
 use algorithm::StrategicAlgorithm;
 use algorithm::strategic_parameters::StrategicParameters;
 use algorithm::strategic_solution::StrategicSolution;
@@ -21,14 +22,13 @@ use ordinator_actor_core::algorithm::Algorithm;
 use ordinator_actor_core::traits::ActorBasedLargeNeighborhoodSearch;
 use ordinator_configuration::SystemConfigurations;
 use ordinator_orchestrator_actor_traits::ActorFactory;
+use ordinator_orchestrator_actor_traits::ActorFailure;
 use ordinator_orchestrator_actor_traits::CommandHandler;
 use ordinator_orchestrator_actor_traits::Communication;
 use ordinator_orchestrator_actor_traits::OrchestratorNotifier;
 use ordinator_orchestrator_actor_traits::SystemSolutions;
 use ordinator_scheduling_environment::SchedulingEnvironment;
-use ordinator_scheduling_environment::work_order::WorkOrderNumber;
 use ordinator_scheduling_environment::worker_environment::resources::Id;
-use priority_queue::PriorityQueue;
 
 pub struct StrategicActor<Ss>(
     Actor<StrategicRequestMessage, StrategicResponseMessage, StrategicAlgorithm<Ss>>,
@@ -72,7 +72,7 @@ where
         shared_solution_arc_swap: Arc<ArcSwap<Ss>>,
         notify_orchestrator: Arc<dyn OrchestratorNotifier>,
         system_configurations: Arc<ArcSwap<SystemConfigurations>>,
-        error_channel: Sender<anyhow::Error>,
+        error_channel: Sender<ActorFailure>,
     ) -> Result<<Self as ActorFactory<Ss>>::Communication>
     where
         Ss: SystemSolutions<Strategic = StrategicSolution> + Send + Sync + 'static,
@@ -83,7 +83,7 @@ where
                 Algorithm<
                     StrategicSolution,
                     StrategicParameters,
-                    PriorityQueue<WorkOrderNumber, u64>,
+                    RepairQueue,
                     Ss,
                 >,
             >,
@@ -93,7 +93,9 @@ where
         .agent_id(id.clone())
         .scheduling_environment(Arc::clone(&scheduling_environment_guard))
         .algorithm(|ab| {
-            ab.id(id)
+            ab.master_seed(system_configurations.load().master_seed)
+                .id(id)
+                .checkpoint_store(notify_orchestrator.checkpoint_store())
                 // So this function returns a `Result`
                 .parameters_and_solution(&scheduling_environment_guard.lock().unwrap())?
                 .arc_swap_shared_solution(shared_solution_arc_swap)
@@ -1,14 +1,19 @@
 use std::any::type_name;
+use std::sync::Arc;
 
 use anyhow::Context;
 use anyhow::Result;
 use anyhow::bail;
 use colored::Colorize;
 use ordinator_actor_core::traits::ActorBasedLargeNeighborhoodSearch;
-use ordinator_orchestrator_actor_traits::ActorSpecific;
+use ordinator_orchestrator_actor_traits::ActorError;
 use ordinator_orchestrator_actor_traits::CommandHandler;
-use ordinator_orchestrator_actor_traits::StateLink;
 use ordinator_orchestrator_actor_traits::SystemSolutions;
+use ordinator_orchestrator_actor_traits::domain_event::DomainEventHandler;
+use ordinator_scheduling_environment::audit_log::SchedulingEnvironmentMutation;
+use ordinator_scheduling_environment::work_order::WorkOrderActivity;
+use ordinator_scheduling_environment::work_order::WorkOrderNumber;
+use ordinator_scheduling_environment::worker_environment::resources::Id;
 use tracing::Level;
 use tracing::event;
 
@@ -58,7 +63,7 @@ where
                             .collect::<Vec<_>>()
                             .contains(&period)
                         {
-                            bail!("Period not found in the the scheduling environment".to_string());
+                            bail!(ActorError::UnknownPeriod(period));
                         }
 
                         // let work_orders_by_period: HashMap<WorkOrderNumber, WorkOrderResponse> =
@@ -101,56 +106,30 @@ where
 
                         bail!("The endpoints are being refactored")
                     }
-                    // This should be created in a different way. I think that you should rely
-                    // on a lot of `From` implementations here. I think that is the best approach
-                    // to fixing this issue.
-                    StrategicStatusMessage::WorkOrder(_work_order_number) => {
-                        // TODO [ ]
-                        // Make a `From` implementation.
-                        // let strategic_solution_for_specific_work_order = self
-                        //     .algorithm
-                        //     .solution
-                        //     .strategic_scheduled_work_orders
-                        //     .get(&work_order_number)
-                        //     .with_context(|| format!("{:?} not found in", work_order_number,))?;
-
-                        // let strategic_parameter = self
-                        //     .algorithm
-                        //     .parameters
-                        //     .strategic_work_order_parameters
-                        //     .get(&work_order_number)
-                        //     .with_context(|| {
-                        //         format!(
-                        //             "{:?} does not have a {}",
-                        //             work_order_number,
-                        //             std::any::type_name::<WorkOrderParameter>(),
-                        //         )
-                        //     })?;
-
-                        // let locked_in_period = &strategic_parameter.locked_in_period;
-                        // let excluded_from_period = &strategic_parameter.excluded_periods;
-
-                        // let strategic_api_solution = StrategicApiSolution {
-                        //     solution: strategic_solution_for_specific_work_order.clone(),
-                        //     locked_in_period: locked_in_period.clone(),
-                        //     excluded_from_period: excluded_from_period.clone(),
-                        // };
-
-                        // let work_orders_in_period =
-                        //     WorkOrdersStatus::SingleSolution(strategic_api_solution);
-
-                        // let strategic_response_message =
-                        //     StrategicResponseMessage::WorkOrder(work_orders_in_period);
-
-                        // Ok(strategic_response_message)
-                        todo!()
+                    StrategicStatusMessage::WorkOrders(work_order_numbers) => {
+                        let strategic_work_order_parameters = work_order_numbers
+                            .into_iter()
+                            .filter_map(|work_order_number| {
+                                self.algorithm
+                                    .parameters
+                                    .strategic_work_order_parameters
+                                    .get(&work_order_number)
+                                    .map(|work_order_parameter| {
+                                        (work_order_number, work_order_parameter.clone())
+                                    })
+                            })
+                            .collect();
+
+                        Ok(StrategicResponseMessage::WorkOrders(
+                            strategic_work_order_parameters,
+                        ))
                     }
                 }
             }
             ordinator_actor_core::RequestMessage::Scheduling(scheduling_message) => {
                 let scheduling_output: StrategicResponseScheduling = self
                     .algorithm
-                    .update_scheduling_state(scheduling_message)
+                    .update_scheduling_state(scheduling_message.clone())
                     .with_context(|| {
                         format!(
                             "{} was not Resolved",
@@ -162,6 +141,8 @@ where
                         )
                     })?;
 
+                self.record_planner_decision(&scheduling_message)?;
+
                 self.algorithm.calculate_objective_value()?;
                 event!(Level::INFO, strategic_objective_value = ?self.algorithm.solution.objective_value);
                 Ok(StrategicResponseMessage::Scheduling(scheduling_output))
@@ -205,164 +186,17 @@ where
             ordinator_actor_core::RequestMessage::SchedulingEnvironment(
                 strategic_scheduling_environment_commands,
             ) => match strategic_scheduling_environment_commands {
-                StrategicSchedulingEnvironmentCommands::UserStatus(strategic_user_status_codes) => {
-                    let scheduling_environment_lock =
-                        &mut self.scheduling_environment.lock().unwrap();
-
-                    for work_order_number in &strategic_user_status_codes.work_order_numbers {
-                        let work_order = scheduling_environment_lock
-                            .work_orders
-                            .inner
-                            .get_mut(work_order_number)
-                            .with_context(|| {
-                                format!(
-                                    "{:?} is not found for {:?}",
-                                    work_order_number,
-                                    self.actor_id.asset()
-                                )
-                            })?;
-
-                        // This should ideally be encapsulated into the a method on the WorkOrder
-                        // that accepts a StrategicUserStatusCodes
-                        let user_status_codes =
-                            &mut work_order.work_order_analytic.user_status_codes;
-
-                        if let Some(sece) = strategic_user_status_codes.sece {
-                            user_status_codes.sece = sece;
-                        }
-                        if let Some(sch) = strategic_user_status_codes.sch {
-                            user_status_codes.sch = sch;
-                        }
-                        if let Some(awsc) = strategic_user_status_codes.awsc {
-                            user_status_codes.awsc = awsc;
-                        }
-
-                        // Should this be handeled here? I think that the best
-                        // approach is to simple update
-                        // the scheduling environment and then the
-                        // algorithm should handle the rest. The issue is that
-                        // you are leaking internals in
-                        // the system and that is making it
-                        // impossible for you to understand the systems
-                        // behavior. Ideally the the
-                        // only think that you change is scheduling
-                        // environment and then the parameters are derived from
-                        // this afterwards. I do not see
-                        // that there is a better way of
-                        // doing the project.
-
-                        // QUESTION
-                        // Should these message handlers mutate self? No I do
-                        // not think so. What should
-                        // happen then instead?
-                        // This should happen in a different message.
-                        // FIX THIS should happen in the internal logic of the
-                        // `StrategicAlgorithm`! That is
-                        // the best way of doing all this. I think that the best
-                        // approach here is to make the
-                        // system work so that the `Actor`s cannot ever modify
-                        // anything inside of the
-                        // `Algorithm` here you will need to create an interface
-                        // through which they are
-                        // allowed to communicate together.
-                        //
-                        //
-                        // This means that writing `self.algorithm.<field or
-                        // method>` is a complete no go.
-                        // I do not see another way around it. You have to
-                        // *encapsulate* the `Algorithm`
-                        // it becomes impossible to handle the coding thing
-                        // otherwise, too much state.
-                        // That also raises the question of how we should handle
-                        // the `SchedulingEnvironment`.
-                        // There is a question or whether we should make it
-                        // in the orchestrator... Hmm I think that you should
-                        // maybe create an `InteractWithSchedulingEnvironment`
-                        // that each of the different `Actor` should implement.
-                        // Yes that is a good idea. Then the public
-                        // interface for the Actor will include that and then
-                        // the message handler can only extract that
-                        // from the interface.
-                        //
-                        // I think that this is the best way of doing it.
-                        //
-                        //     let last_period =
-                        //         self.algorithm.parameters.strategic_periods.
-                        // last().cloned();
-
-                        //     let unscheduled_period = self
-                        //         .algorithm
-                        //         .solution
-                        //         .strategic_scheduled_work_orders
-                        //         .insert(*work_order_number,
-                        // last_period.clone())
-                        //         .expect("WorkOrderNumber should always be
-                        // present")         .expect(
-                        //             "All WorkOrders should be scheduled in
-                        // between ScheduleIteration loops",
-                        //         );
-
-                        //     let work_load = self
-                        //         .algorithm
-                        //         .parameters
-                        //         .strategic_work_order_parameters
-                        //         .get(work_order_number)
-                        //         .unwrap()
-                        //         .work_load
-                        //         .clone();
-
-                        //     let unscheduled_resources = self
-                        //         .algorithm
-                        //         .determine_best_permutation(
-                        //             work_load.clone(),
-                        //             &unscheduled_period,
-                        //             ScheduleWorkOrder::Unschedule,
-                        //         )
-                        //         .with_context(|| {
-                        //             format!(
-                        //                 "{:?}\nin period {:?}\ncould not be
-                        // {:?}",
-                        // work_order_number,
-                        //                 unscheduled_period,
-                        //                 ScheduleWorkOrder::Unschedule
-                        //             )
-                        //         })?
-                        //         .expect("It should always be possible to
-                        // release resources");
-
-                        //     self.algorithm
-                        //         .update_loadings(unscheduled_resources,
-                        // LoadOperation::Sub);
-
-                        //     let scheduled_resources = self
-                        //         .algorithm
-                        //         .determine_best_permutation(
-                        //             work_load,
-                        //             &last_period.unwrap(),
-                        //             ScheduleWorkOrder::Forced,
-                        //         )
-                        //         .with_context(|| {
-                        //             format!(
-                        //                 "{:?}\nin period {:?}\ncould not be
-                        // {:?}",
-                        // work_order_number,
-                        //                 unscheduled_period,
-                        //                 ScheduleWorkOrder::Forced
-                        //             )
-                        //         })?
-                        //         .expect("It should always be possible to
-                        // release resources");
-
-                        //     self.algorithm
-                        //         .update_loadings(scheduled_resources,
-                        // LoadOperation::Add);
-                        // FIX
-                    }
-
-                    // Signal Orchestrator that the it should tell all actor to update work orders
+                StrategicSchedulingEnvironmentCommands::UserStatus {
+                    user,
+                    user_status_codes,
+                } => {
+                    // The `SchedulingEnvironment` is only changed through the
+                    // `Orchestrator`, which also signals all the actors to update
+                    // the work orders.
                     self.notify_orchestrator
-                        .notify_all_agents_of_work_order_change(
-                            strategic_user_status_codes.work_order_numbers,
+                        .mutate_scheduling_environment(
+                            SchedulingEnvironmentMutation::UserStatus(user_status_codes),
+                            &user,
                             self.actor_id.asset(),
                         )
                         .context("Could not notify Orchestrator")?;
@@ -376,67 +210,199 @@ where
 
         strategic_response
     }
+}
 
-    fn handle_state_link(&mut self, msg: StateLink) -> Result<StrategicResponseMessage>
+impl<Ss> DomainEventHandler for StrategicActor<Ss>
+where
+    Ss: SystemSolutions<Strategic = StrategicSolution>,
+{
+    fn work_order_created(&mut self, work_order_numbers: Vec<WorkOrderNumber>) -> Result<()>
     {
-        match msg {
-            StateLink::WorkOrders(agent_specific) => {
-                match agent_specific {
-                    ActorSpecific::Strategic(changed_work_orders) => {
-                        for work_order_number in changed_work_orders {
-                            let scheduling_environment_guard =
-                                self.scheduling_environment.lock().unwrap();
-                            let work_order = scheduling_environment_guard
-                                .work_orders
-                                .inner
-                                .get(&work_order_number)
-                                .with_context(|| {
-                                    format!(
-                                        "{work_order_number:?} is not present in SchedulingEnvironment",
-                                        
-                                    )
-                                })?;
-                            let actor_specification = scheduling_environment_guard.worker_environment.actor_specification.get(self.actor_id.asset()).expect("Missing Asset for ActorSpecification");
-                            let work_order_configurations = &actor_specification.work_order_configurations;
-                            let material_to_period = &actor_specification.material_to_period;
-
-                            let strategic_parameter = WorkOrderParameter::builder()
-                                .with_scheduling_environment(
-                                    work_order,
-                                    &scheduling_environment_guard
-                                        .time_environment
-                                        .periods,
-                                    work_order_configurations,
-                                    material_to_period,
-                                )?
-                                .build();
-
-                            drop(scheduling_environment_guard);
-                            self.algorithm
-                                .parameters
-                                .strategic_work_order_parameters
-                                .insert(work_order_number, strategic_parameter);
-                        }
-                    }
-                }
+        self.update_work_order_parameters(work_order_numbers)
+    }
+
+    fn work_order_updated(&mut self, work_order_numbers: Vec<WorkOrderNumber>) -> Result<()>
+    {
+        self.update_work_order_parameters(work_order_numbers)
+    }
+
+    fn work_order_closed(&mut self, work_order_numbers: Vec<WorkOrderNumber>) -> Result<()>
+    {
+        for work_order_number in work_order_numbers {
+            if !self
+                .algorithm
+                .solution
+                .strategic_scheduled_work_orders
+                .contains_key(&work_order_number)
+            {
+                continue;
+            }
+            self.algorithm
+                .unschedule_specific_work_order(work_order_number)?;
+            self.algorithm
+                .solution
+                .strategic_scheduled_work_orders
+                .remove(&work_order_number);
+            self.algorithm
+                .parameters
+                .strategic_work_order_parameters
+                .remove(&work_order_number);
+        }
+        self.algorithm.update_portfolio_workers();
+        self.algorithm.calculate_objective_value()?;
+        self.algorithm.publish_best_solution();
+        Ok(())
+    }
+
+    fn operation_progress_confirmed(&mut self, work_order_activity: WorkOrderActivity)
+    -> Result<()>
+    {
+        self.update_work_order_parameters(vec![work_order_activity.0])
+    }
+
+    fn worker_availability_changed(&mut self, _id: Id) -> Result<()>
+    {
+        let scheduling_environment_guard = self.scheduling_environment.lock().unwrap();
+        let strategic_resources =
+            StrategicResources::from((&scheduling_environment_guard, &self.actor_id));
+        drop(scheduling_environment_guard);
+
+        self.algorithm
+            .parameters
+            .strategic_capacity
+            .update_resource_capacities(strategic_resources)
+            .context("Could not update the StrategicResources")?;
+        self.algorithm.update_all_overloaded_resource_periods();
+        Ok(())
+    }
+
+    /// The capacities are rebuilt, as the operational actor can bring a
+    /// resource that has no capacity yet.
+    fn worker_added(&mut self, _id: Id) -> Result<()>
+    {
+        self.rebuild_and_replay()
+    }
+
+    fn worker_removed(&mut self, _id: Id) -> Result<()>
+    {
+        self.rebuild_and_replay()
+    }
+
+    fn material_date_changed(&mut self, work_order_numbers: Vec<WorkOrderNumber>) -> Result<()>
+    {
+        self.update_work_order_parameters(work_order_numbers)
+    }
+
+    fn period_rolled(&mut self) -> Result<()>
+    {
+        self.rebuild_and_replay()
+    }
+}
+
+impl<Ss> StrategicActor<Ss>
+where
+    Ss: SystemSolutions<Strategic = StrategicSolution>,
+{
+    fn rebuild_and_replay(&mut self) -> Result<()>
+    {
+        let scheduling_environment = Arc::clone(&self.scheduling_environment);
+        let previous_solution = self
+            .algorithm
+            .rebuild_parameters(&scheduling_environment.lock().unwrap())?;
+        self.algorithm.update_portfolio_workers();
+        self.algorithm.replay_solution(previous_solution)?;
+        self.algorithm.update_all_overloaded_resource_periods();
+        Ok(())
+    }
 
-                Ok(StrategicResponseMessage::StateLink)
+    /// Stores the locked in or excluded period on the `WorkOrder`s, so that it
+    /// is persisted and survives a rebuild of the `WorkOrderParameter`s.
+    fn record_planner_decision(&self, scheduling_message: &StrategicRequestScheduling)
+    -> Result<()>
+    {
+        let (StrategicRequestScheduling::Schedule(schedule_change)
+        | StrategicRequestScheduling::ExcludeFromPeriod(schedule_change)) = scheduling_message;
+
+        let period = self
+            .scheduling_environment
+            .lock()
+            .unwrap()
+            .time_environment
+            .periods
+            .iter()
+            .find(|period| period.period_string() == schedule_change.period_string)
+            .cloned()
+            .ok_or_else(|| ActorError::UnknownPeriod(schedule_change.period_string()))?;
+
+        let work_order_numbers = schedule_change.work_order_number.clone();
+        let mutation = match scheduling_message {
+            StrategicRequestScheduling::Schedule(_) => {
+                SchedulingEnvironmentMutation::LockInPeriod {
+                    work_order_numbers,
+                    period,
+                }
             }
-            StateLink::WorkerEnvironment => {
-                let scheduling_environment_guard = self.scheduling_environment.lock().unwrap();
-                let strategic_resources =
-                    StrategicResources::from((&scheduling_environment_guard, &self.actor_id));
-                drop(scheduling_environment_guard);
-
-                self.algorithm
-                    .parameters
-                    .strategic_capacity
-                    .update_resource_capacities(strategic_resources)
-                    .expect("Could not update the StrategicResources");
-
-                Ok(StrategicResponseMessage::StateLink)
+            StrategicRequestScheduling::ExcludeFromPeriod(_) => {
+                SchedulingEnvironmentMutation::ExcludeFromPeriod {
+                    work_order_numbers,
+                    period,
+                }
             }
-            StateLink::TimeEnvironment => todo!(),
+        };
+
+        self.notify_orchestrator
+            .mutate_scheduling_environment(mutation, &schedule_change.user, self.actor_id.asset())
+            .context("Could not notify Orchestrator")?;
+        Ok(())
+    }
+
+    /// Rebuilds the `WorkOrderParameter`s from the `SchedulingEnvironment`.
+    /// New work orders start out unscheduled.
+    fn update_work_order_parameters(
+        &mut self,
+        work_order_numbers: Vec<WorkOrderNumber>,
+    ) -> Result<()>
+    {
+        for work_order_number in work_order_numbers {
+            let scheduling_environment_guard = self.scheduling_environment.lock().unwrap();
+            let work_order = scheduling_environment_guard
+                .work_orders
+                .inner
+                .get(&work_order_number)
+                .ok_or(ActorError::UnknownWorkOrder(work_order_number))?;
+            let actor_specification = scheduling_environment_guard
+                .worker_environment
+                .actor_specification
+                .get(self.actor_id.asset())
+                .expect("Missing Asset for ActorSpecification");
+            let work_order_configurations = &actor_specification.work_order_configurations;
+            let material_to_period = &actor_specification.material_to_period;
+
+            let strategic_parameter = WorkOrderParameter::builder()
+                .with_scheduling_environment(
+                    work_order,
+                    &scheduling_environment_guard.time_environment.periods,
+                    work_order_configurations,
+                    material_to_period,
+                )?
+                .with_planner_decision(
+                    &work_order.planner_decision,
+                    &scheduling_environment_guard.time_environment.periods,
+                )
+                .build();
+
+            drop(scheduling_environment_guard);
+            self.algorithm
+                .parameters
+                .strategic_work_order_parameters
+                .insert(work_order_number, strategic_parameter);
+            self.algorithm
+                .solution
+                .strategic_scheduled_work_orders
+                .entry(work_order_number)
+                .or_insert(None);
         }
+        self.algorithm.update_portfolio_workers();
+        Ok(())
     }
 }
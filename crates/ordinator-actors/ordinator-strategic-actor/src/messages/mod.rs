@@ -2,13 +2,17 @@ pub mod message_handlers;
 pub mod requests;
 pub mod responses;
 
+use std::collections::HashMap;
+
 use ordinator_actor_core::RequestMessage;
+use ordinator_scheduling_environment::work_order::WorkOrderNumber;
 use ordinator_scheduling_environment::work_order::work_order_analytic::status_codes::StrategicUserStatusCodes;
 use serde::Deserialize;
 use serde::Serialize;
 
 use self::requests::*;
 use self::responses::*;
+use crate::algorithm::strategic_parameters::WorkOrderParameter;
 
 pub type StrategicRequestMessage = RequestMessage<
     StrategicStatusMessage,
@@ -23,18 +27,23 @@ pub type StrategicRequestMessage = RequestMessage<
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub enum StrategicSchedulingEnvironmentCommands
 {
-    UserStatus(StrategicUserStatusCodes),
+    /// The `user` is recorded in the audit log.
+    UserStatus
+    {
+        user: String,
+        user_status_codes: StrategicUserStatusCodes,
+    },
 }
 
 #[derive(Serialize)]
 #[allow(clippy::large_enum_variant)]
 pub enum StrategicResponseMessage
 {
-    StateLink,
     Status(StrategicResponseStatus),
     Scheduling(StrategicResponseScheduling),
     Resources(StrategicResponseResources),
     Periods(StrategicResponsePeriods),
+    WorkOrders(HashMap<WorkOrderNumber, WorkOrderParameter>),
     Success,
 }
 
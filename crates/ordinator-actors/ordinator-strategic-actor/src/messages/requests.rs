@@ -58,11 +58,13 @@ impl StrategicRequestScheduling
     pub fn new_single_work_order(
         work_order_number: Vec<WorkOrderNumber>,
         period_string: String,
+        user: String,
     ) -> Self
     {
         Self::Schedule(ScheduleChange {
             work_order_number,
             period_string,
+            user,
         })
     }
 }
@@ -72,15 +74,19 @@ pub struct ScheduleChange
 {
     pub work_order_number: Vec<WorkOrderNumber>,
     pub period_string: String,
+    /// The planner that is recorded in the audit log.
+    pub user: String,
 }
 
 impl ScheduleChange
 {
-    pub fn new(work_order_number: Vec<WorkOrderNumber>, period_string: String) -> Self
+    pub fn new(work_order_number: Vec<WorkOrderNumber>, period_string: String, user: String)
+    -> Self
     {
         Self {
             work_order_number,
             period_string,
+            user,
         }
     }
 
@@ -95,7 +101,8 @@ pub enum StrategicStatusMessage
 {
     General,
     Period(String),
-    WorkOrder(WorkOrderNumber),
+    /// The `WorkOrderParameter`s of the work orders.
+    WorkOrders(Vec<WorkOrderNumber>),
 }
 
 impl StrategicStatusMessage
@@ -113,8 +120,8 @@ impl Display for StrategicStatusMessage
         match self {
             StrategicStatusMessage::General => write!(f, "general"),
             StrategicStatusMessage::Period(period) => write!(f, "period: {period}",),
-            StrategicStatusMessage::WorkOrder(work_order_number) => {
-                write!(f, "{work_order_number:?}",)
+            StrategicStatusMessage::WorkOrders(work_order_numbers) => {
+                write!(f, "{work_order_numbers:?}",)
             }
         }
     }
@@ -1,3 +1,4 @@
+use ordinator_actor_core::operators::OperatorPortfolioStatistics;
 use ordinator_orchestrator_actor_traits::SystemSolutions;
 use ordinator_scheduling_environment::time_environment::period::Period;
 use serde::Deserialize;
@@ -66,6 +67,8 @@ pub struct StrategicResponseStatus
     pub strategic_objective_value: usize,
     pub number_of_strategic_work_orders: usize,
     pub number_of_periods: usize,
+    pub operator_statistics: OperatorPortfolioStatistics,
+    pub master_seed: u64,
 }
 
 impl<Ss> From<&mut StrategicActor<Ss>> for StrategicResponseStatus
@@ -92,6 +95,8 @@ where
             asset: asset.clone(),
             strategic_objective_value: value.algorithm.solution.objective_value.objective_value
                 as usize,
+            operator_statistics: value.algorithm.operators.statistics(),
+            master_seed: value.algorithm.master_seed,
         }
     }
 }
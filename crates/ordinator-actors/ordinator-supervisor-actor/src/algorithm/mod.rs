@@ -1,5 +1,6 @@
 pub mod assert_functions;
 pub mod supervisor_interface;
+pub mod supervisor_operators;
 pub mod supervisor_parameters;
 pub mod supervisor_solution;
 
@@ -10,25 +11,38 @@ use std::sync::Arc;
 
 use anyhow::Context;
 use anyhow::Result;
+use anyhow::bail;
 use anyhow::ensure;
 use ordinator_actor_core::algorithm::Algorithm;
+use ordinator_actor_core::operators::OperatorPortfolio;
 use ordinator_actor_core::traits::AbLNSUtils;
 use ordinator_actor_core::traits::ActorBasedLargeNeighborhoodSearch;
 use ordinator_actor_core::traits::ObjectiveValueType;
+use ordinator_actor_core::traits::Reconciliation;
+use ordinator_orchestrator_actor_traits::ActorOptions;
+use ordinator_orchestrator_actor_traits::OperationalInterface;
 use ordinator_orchestrator_actor_traits::Parameters;
 use ordinator_orchestrator_actor_traits::Solution;
 use ordinator_orchestrator_actor_traits::StrategicInterface;
 use ordinator_orchestrator_actor_traits::SwapSolution;
 use ordinator_orchestrator_actor_traits::SystemSolutions;
 use ordinator_orchestrator_actor_traits::delegate::Delegate;
+use ordinator_orchestrator_actor_traits::delegate::DelegateCause;
 use ordinator_orchestrator_actor_traits::marginal_fitness::MarginalFitness;
+use ordinator_scheduling_environment::work_order::WorkOrderActivity;
 use ordinator_scheduling_environment::work_order::WorkOrderNumber;
 use ordinator_scheduling_environment::work_order::operation::ActivityNumber;
 use ordinator_scheduling_environment::work_order::operation::Work;
+use ordinator_scheduling_environment::worker_environment::AcceptanceCriterion;
 use ordinator_scheduling_environment::worker_environment::SupervisorOptions;
-use rand::rng;
-use rand::seq::IndexedRandom;
+use ordinator_scheduling_environment::worker_environment::resources::Id;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use supervisor_operators::SupervisorDestroyOperator;
+use supervisor_operators::SupervisorRepairOperator;
 use supervisor_parameters::SupervisorParameters;
+use supervisor_solution::SupervisorObjectiveValue;
 use supervisor_solution::SupervisorSolution;
 #[allow(unused_imports)]
 use tracing::Level;
@@ -49,7 +63,89 @@ where
     ) -> Result<()>
     {
         self.solution
-            .turn_work_order_into_delegate_assess(work_order_number);
+            .turn_work_order_into_delegate_assess(work_order_number)
+    }
+}
+
+impl<Ss> SupervisorAlgorithm<Ss>
+where
+    Ss: SystemSolutions,
+{
+    /// Moves the `Delegate`s along with the rest of the `SystemSolution`.
+    /// Work orders that leave the supervisor periods are dropped instead of
+    /// removed, so that the `OperationalActor`s also release them, and the
+    /// dropped `Delegate`s are removed once that has happened. The `Done`
+    /// `Delegate`s of these work orders are removed in the same way.
+    fn update_delegates(&mut self, strategic_work_orders: &HashSet<WorkOrderNumber>) -> Result<()>
+    {
+        let all_operational: HashSet<Id> = self
+            .loaded_shared_solution
+            .all_operational()
+            .into_iter()
+            .collect();
+
+        for key in self.solution.sorted_keys(|key, delegate| {
+            !strategic_work_orders.contains(&key.1.0) && !delegate.is_drop() && !delegate.is_done()
+        }) {
+            self.solution
+                .transition(&key, Delegate::Drop, DelegateCause::StrategicPeriodMoved)?;
+        }
+
+        for key in self.solution.sorted_keys(|key, delegate| {
+            strategic_work_orders.contains(&key.1.0) && delegate.is_drop()
+        }) {
+            self.solution.transition(
+                &key,
+                Delegate::Assess,
+                DelegateCause::StrategicPeriodMoved,
+            )?;
+        }
+
+        let work_completed = |key: &(Id, WorkOrderActivity)| {
+            self.parameters
+                .supervisor_parameter(&key.1)
+                .is_ok_and(|supervisor_parameter| supervisor_parameter.work_remaining.is_zero())
+        };
+        for key in self
+            .solution
+            .sorted_keys(|key, delegate| !delegate.is_done() && work_completed(key))
+        {
+            self.solution
+                .transition(&key, Delegate::Done, DelegateCause::WorkCompleted)?;
+        }
+
+        for key in self.solution.sorted_keys(|key, delegate| {
+            !all_operational.contains(&key.0)
+                && matches!(
+                    delegate,
+                    Delegate::Assign | Delegate::Unassign | Delegate::Fixed
+                )
+        }) {
+            self.solution.transition(
+                &key,
+                Delegate::Assess,
+                DelegateCause::OperationalUnavailable,
+            )?;
+        }
+
+        let released =
+            self.solution
+                .sorted_keys(|(operational_id, work_order_activity), delegate| {
+                    (delegate.is_drop()
+                        || (delegate.is_done()
+                            && !strategic_work_orders.contains(&work_order_activity.0)))
+                        && !self
+                            .loaded_shared_solution
+                            .operational_actor_solutions(operational_id)
+                            .is_ok_and(|operational_solution| {
+                                operational_solution
+                                    .marginal_fitness_for_operational_actor(work_order_activity)
+                                    .is_some()
+                            })
+                });
+        for key in &released {
+            self.solution.operational_state_machine.remove(key);
+        }
         Ok(())
     }
 }
@@ -120,27 +216,34 @@ where
             intermediate = 0.0;
         };
 
-        let objective_value = (intermediate * 1000.0) as u64;
+        let objective_value = SupervisorObjectiveValue((intermediate * 1000.0) as u64);
 
         if self.solution.objective_value < objective_value {
             event!(
                 Level::INFO,
-                supervisor_objective_value_better = objective_value
+                supervisor_objective_value_better = objective_value.0
             );
             Ok(ObjectiveValueType::Better(objective_value))
         } else {
             event!(
                 Level::INFO,
-                supervisor_objective_value_worse = objective_value
+                supervisor_objective_value_worse = objective_value.0
             );
-            Ok(ObjectiveValueType::Worse)
+            Ok(ObjectiveValueType::Worse(objective_value))
         }
     }
 
     fn schedule(&mut self) -> Result<()>
     {
+        let mut rng = StdRng::from_rng(&mut self.rng);
+        let repair_operator = self.operators.repair::<SupervisorRepairOperator>();
+
         // What is the criteria for handling this in practice?
-        for work_order_activity in &self.solution.get_work_order_activities() {
+        let mut work_order_activities: Vec<_> =
+            self.solution.get_work_order_activities().into_iter().collect();
+        work_order_activities.sort();
+
+        for work_order_activity in &work_order_activities {
             let number = self
                 .parameters
                 .supervisor_work_orders
@@ -159,23 +262,39 @@ where
             operational_status_by_work_order_activity
                 .retain(|(_, _, mar_fit)| matches!(mar_fit, MarginalFitness::Scheduled(_)));
 
-            operational_status_by_work_order_activity.sort_by_key(|(_agent_id, _, mar_fit)| {
-                match mar_fit {
-                    MarginalFitness::Scheduled(auxillary_operational_objective) => {
-                        *auxillary_operational_objective
-                    }
-                    MarginalFitness::None => panic!(),
+            // The statuses come out of a `HashMap`, sorting on the actor name keeps
+            // the seeded runs reproducible.
+            operational_status_by_work_order_activity
+                .sort_by(|(agent_id_a, _, _), (agent_id_b, _, _)| agent_id_a.0.cmp(&agent_id_b.0));
+
+            match repair_operator {
+                SupervisorRepairOperator::BestMarginalFitness => {
+                    let weights = &self.parameters.options.marginal_fitness_weights;
+                    // Only the scheduled bids are retained above, an
+                    // unscheduled bid would rank last.
+                    operational_status_by_work_order_activity.sort_by_key(
+                        |(_agent_id, _, mar_fit)| match mar_fit {
+                            MarginalFitness::Scheduled(fitness_criteria) => {
+                                fitness_criteria.score(weights)
+                            }
+                            MarginalFitness::None => i64::MAX,
+                        },
+                    );
+                }
+                SupervisorRepairOperator::RandomOrder => {
+                    operational_status_by_work_order_activity.shuffle(&mut rng);
                 }
-            });
+            }
 
             if !operational_status_by_work_order_activity.is_empty() {};
 
             let number_of_assigned = operational_status_by_work_order_activity
                 .iter()
-                .filter(|(_, delegate, _)| *delegate == Delegate::Assign)
+                .filter(|(_, delegate, _)| delegate.is_assigned())
                 .count() as u64;
 
-            let mut remaining_to_assign = number - number_of_assigned;
+            // Planner fixed activities can exceed the number of people needed.
+            let mut remaining_to_assign = number.saturating_sub(number_of_assigned);
 
             event!(Level::DEBUG, remaining_to_assign = ?remaining_to_assign);
             for (agent_id, delegate_status, _marginal_fitness) in
@@ -185,19 +304,14 @@ where
                     continue;
                 }
 
-                let solution =
-                    self.solution
-                        .operational_state_machine
-                        .get_mut(&(agent_id.clone(), *work_order_activity)).expect("This value should always be present. Check the generation of keys and values if this fails");
-
+                let key = (agent_id.clone(), *work_order_activity);
                 if remaining_to_assign >= 1 {
                     remaining_to_assign -= 1;
-                    solution.state_change_to_assign();
+                    self.solution
+                        .transition(&key, Delegate::Assign, DelegateCause::Auction)?;
                 } else {
-                    if delegate_status == Delegate::Assign {
-                        continue;
-                    }
-                    solution.state_change_to_unassign();
+                    self.solution
+                        .transition(&key, Delegate::Unassign, DelegateCause::Auction)?;
                 }
             }
         }
@@ -206,18 +320,15 @@ where
 
     fn unschedule(&mut self) -> Result<()>
     {
-        let mut rng = rng();
-        let work_order_numbers = self.solution.get_assigned_and_unassigned_work_orders();
-
-        let sampled_work_order_numbers = work_order_numbers
-            .choose_multiple(
-                &mut rng,
-                self.parameters.options.number_of_unassigned_work_orders,
-            )
-            .collect::<Vec<_>>()
-            .clone();
-
-        for work_order_number in sampled_work_order_numbers {
+        let mut rng = StdRng::from_rng(&mut self.rng);
+
+        let (destroy_operator, _) = self
+            .operators
+            .select::<SupervisorDestroyOperator, SupervisorRepairOperator>(&mut rng);
+
+        let sampled_work_order_numbers = self.destroy_work_orders(destroy_operator, &mut rng);
+
+        for work_order_number in &sampled_work_order_numbers {
             self.unschedule_specific_work_order(*work_order_number)
                 .with_context(|| {
                     format!("Could not unschedule work_order_number: {work_order_number:?}")
@@ -304,17 +415,15 @@ where
             .cloned()
             .collect::<HashSet<_>>();
 
-        self.solution
-            .operational_state_machine
-            .retain(|id_woa, _| strategic_activities_hash_set.contains(&id_woa.1.0));
+        self.update_delegates(&strategic_activities_hash_set)?;
 
         let value = self
             .solution
-            .operational_state_machine
-            .iter()
-            .map(|e| e.0.1.0)
+            .get_work_order_activities()
+            .into_iter()
+            .map(|(work_order_number, _)| work_order_number)
             .collect::<HashSet<_>>();
-        ensure!(strategic_activities_hash_set == value);
+        ensure!(value.is_subset(&strategic_activities_hash_set));
         Ok(true)
     }
 
@@ -322,6 +431,36 @@ where
     {
         &mut self.0
     }
+
+    fn acceptance_criterion(&self) -> &AcceptanceCriterion
+    {
+        &self.parameters.options.acceptance_criterion
+    }
+
+    fn update_options(&mut self, actor_options: ActorOptions) -> Result<()>
+    {
+        match actor_options {
+            ActorOptions::Supervisor(supervisor_options) => {
+                self.parameters.options = supervisor_options;
+                Ok(())
+            }
+            actor_options => bail!(
+                "SupervisorActor {} cannot use {:?} options",
+                self.id,
+                actor_options.actor_kind()
+            ),
+        }
+    }
+
+    fn warm_start(&mut self, checkpointed_solution: SupervisorSolution) -> Result<Reconciliation>
+    {
+        // Only the `Delegate`s of operational actors and work order activities
+        // that are still in the `SupervisorParameters` are restored.
+        Ok(Reconciliation::restore(
+            &mut self.solution.operational_state_machine,
+            checkpointed_solution.operational_state_machine,
+        ))
+    }
 }
 
 fn is_assigned_part_of_all(
@@ -359,8 +498,171 @@ impl<Ss> From<Algorithm<SupervisorSolution, SupervisorParameters, (), Ss>>
 where
     Ss: SystemSolutions,
 {
-    fn from(value: Algorithm<SupervisorSolution, SupervisorParameters, (), Ss>) -> Self
+    fn from(mut value: Algorithm<SupervisorSolution, SupervisorParameters, (), Ss>) -> Self
     {
+        value.operators =
+            OperatorPortfolio::new::<SupervisorDestroyOperator, SupervisorRepairOperator>();
         SupervisorAlgorithm(value)
     }
 }
+
+#[cfg(test)]
+mod tests
+{
+    use std::collections::HashMap;
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    use arc_swap::ArcSwap;
+    use ordinator_actor_core::acceptance::Acceptance;
+    use ordinator_actor_core::algorithm::Algorithm;
+    use ordinator_actor_core::operators::OperatorPortfolio;
+    use ordinator_operational_actor::algorithm::operational_solution::OperationalAssignment;
+    use ordinator_operational_actor::algorithm::operational_solution::OperationalSolution;
+    use ordinator_orchestrator_actor_traits::SystemSolution;
+    use ordinator_orchestrator_actor_traits::SystemSolutions;
+    use ordinator_orchestrator_actor_traits::delegate::Delegate;
+    use ordinator_scheduling_environment::Asset;
+    use ordinator_scheduling_environment::work_order::WorkOrderActivity;
+    use ordinator_scheduling_environment::work_order::WorkOrderNumber;
+    use ordinator_scheduling_environment::work_order::operation::Work;
+    use ordinator_scheduling_environment::worker_environment::SupervisorOptions;
+    use ordinator_scheduling_environment::worker_environment::resources::Id;
+    use ordinator_scheduling_environment::worker_environment::resources::Resources;
+    use ordinator_strategic_actor::algorithm::strategic_solution::StrategicSolution;
+    use ordinator_tactical_actor::algorithm::tactical_solution::TacticalSolution;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    use super::SupervisorAlgorithm;
+    use super::supervisor_parameters::SupervisorParameter;
+    use super::supervisor_parameters::SupervisorParameters;
+    use super::supervisor_solution::SupervisorSolution;
+
+    type TestSystemSolution = SystemSolution<
+        StrategicSolution,
+        TacticalSolution,
+        SupervisorSolution,
+        OperationalSolution,
+    >;
+
+    const IN_PERIODS: WorkOrderNumber = WorkOrderNumber(1);
+    const LEFT_PERIODS: WorkOrderNumber = WorkOrderNumber(2);
+
+    fn supervisor_algorithm(
+        delegates: HashMap<(Id, WorkOrderActivity), Delegate>,
+        completed: &[WorkOrderActivity],
+        system_solution: TestSystemSolution,
+    ) -> SupervisorAlgorithm<TestSystemSolution>
+    {
+        let mut supervisor_work_orders: HashMap<_, HashMap<_, _>> = HashMap::new();
+        for (_, (work_order_number, activity_number)) in delegates.keys() {
+            let work_remaining = if completed.contains(&(*work_order_number, *activity_number)) {
+                Work::from(0.0)
+            } else {
+                Work::from(10.0)
+            };
+            supervisor_work_orders
+                .entry(*work_order_number)
+                .or_default()
+                .insert(
+                    *activity_number,
+                    SupervisorParameter::new(
+                        Resources::MtnMech,
+                        1,
+                        Work::from(10.0),
+                        work_remaining,
+                    ),
+                );
+        }
+
+        let arc_swap_shared_solution = Arc::new(ArcSwap::from_pointee(system_solution));
+        let loaded_shared_solution = arc_swap_shared_solution.load();
+        SupervisorAlgorithm(Algorithm {
+            id: Id::new("SUP-01", vec![], vec![Asset::Test]),
+            solution_intermediate: (),
+            solution: SupervisorSolution {
+                operational_state_machine: delegates,
+                ..Default::default()
+            },
+            best_solution: None,
+            parameters: SupervisorParameters {
+                supervisor_work_orders,
+                supervisor_periods: vec![],
+                operational_ids: vec![],
+                options: SupervisorOptions {
+                    number_of_unassigned_work_orders: 1,
+                    acceptance_criterion: Default::default(),
+                    marginal_fitness_weights: Default::default(),
+                },
+            },
+            arc_swap_shared_solution,
+            loaded_shared_solution,
+            acceptance: Acceptance::default(),
+            operators: OperatorPortfolio::default(),
+            master_seed: 0,
+            seed: 0,
+            rng: StdRng::seed_from_u64(0),
+            warm_start_solution: None,
+        })
+    }
+
+    #[test]
+    fn test_update_delegates()
+    {
+        let available = Id::new("OP-01-001", vec![Resources::MtnMech], vec![Asset::Test]);
+        let missing = Id::new("OP-01-002", vec![Resources::MtnMech], vec![Asset::Test]);
+
+        // The available operational actor still has one of the activities of
+        // the work order that left the supervisor periods in its solution.
+        let mut system_solution = TestSystemSolution::new();
+        system_solution.operational_swap(
+            &available,
+            OperationalSolution {
+                objective_value: Default::default(),
+                scheduled_work_order_activities: vec![(
+                    (LEFT_PERIODS, 10),
+                    OperationalAssignment::new(vec![]),
+                )],
+            },
+        );
+
+        let delegates = HashMap::from([
+            ((available.clone(), (IN_PERIODS, 10)), Delegate::Assign),
+            ((available.clone(), (IN_PERIODS, 20)), Delegate::Assign),
+            ((missing.clone(), (IN_PERIODS, 30)), Delegate::Assign),
+            ((missing.clone(), (IN_PERIODS, 40)), Delegate::Assess),
+            ((available.clone(), (LEFT_PERIODS, 10)), Delegate::Assign),
+            ((available.clone(), (LEFT_PERIODS, 20)), Delegate::Unassign),
+            ((available.clone(), (LEFT_PERIODS, 30)), Delegate::Done),
+        ]);
+        let mut supervisor_algorithm =
+            supervisor_algorithm(delegates, &[(IN_PERIODS, 20)], system_solution);
+
+        supervisor_algorithm
+            .update_delegates(&HashSet::from([IN_PERIODS]))
+            .unwrap();
+
+        assert_eq!(
+            supervisor_algorithm.solution.operational_state_machine,
+            HashMap::from([
+                ((available.clone(), (IN_PERIODS, 10)), Delegate::Assign),
+                ((available.clone(), (IN_PERIODS, 20)), Delegate::Done),
+                ((missing.clone(), (IN_PERIODS, 30)), Delegate::Assess),
+                ((missing, (IN_PERIODS, 40)), Delegate::Assess),
+                ((available.clone(), (LEFT_PERIODS, 10)), Delegate::Drop),
+            ])
+        );
+
+        // The work order comes back into the supervisor periods before the
+        // operational actor has released it.
+        supervisor_algorithm
+            .update_delegates(&HashSet::from([IN_PERIODS, LEFT_PERIODS]))
+            .unwrap();
+        assert_eq!(
+            supervisor_algorithm.solution.operational_state_machine
+                [&(available, (LEFT_PERIODS, 10))],
+            Delegate::Assess
+        );
+    }
+}
@@ -23,7 +23,7 @@ impl SupervisorInterface for SupervisorSolution {
         self.operational_state_machine
             .iter()
             .filter(|(id_woa, del)| {
-                &id_woa.0 == operational_agent && (del.is_assign() || del.is_assess())
+                &id_woa.0 == operational_agent && (del.is_assigned() || del.is_assess())
             })
             .map(|(id_woa, _)| id_woa.1)
             .collect::<HashSet<_>>()
@@ -37,13 +37,16 @@ impl SupervisorInterface for SupervisorSolution {
         for delegate in self.delegates_for_agent(operational_agent).values() {
             match delegate {
                 Delegate::Assess => count_assess += 1,
-                Delegate::Assign => count_assign += 1,
+                Delegate::Assign | Delegate::Fixed => count_assign += 1,
                 Delegate::Unassign => count_unassign += 1,
                 Delegate::Drop => (),
                 Delegate::Done => (),
-                Delegate::Fixed => (),
             }
         }
         (count_assign, count_assess, count_unassign)
     }
+
+    fn all_delegates(&self) -> &HashMap<(Id, WorkOrderActivity), Delegate> {
+        &self.operational_state_machine
+    }
 }
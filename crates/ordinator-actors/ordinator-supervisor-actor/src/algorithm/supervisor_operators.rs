@@ -0,0 +1,170 @@
+use ordinator_actor_core::operators::NeighborhoodOperator;
+use ordinator_actor_core::operators::sample;
+use ordinator_actor_core::operators::sample_from_ranked;
+use ordinator_orchestrator_actor_traits::SystemSolutions;
+use ordinator_scheduling_environment::work_order::WorkOrderNumber;
+use ordinator_scheduling_environment::worker_environment::resources::Resources;
+use rand::Rng;
+use rand::seq::IndexedRandom;
+
+use super::SupervisorAlgorithm;
+
+/// The destroy operators of the `SupervisorAlgorithm`. Each of them turns up
+/// to `number_of_unassigned_work_orders` work orders back into
+/// `Delegate::Assess`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SupervisorDestroyOperator
+{
+    Random,
+    /// Removes work orders that have an activity requiring a single random
+    /// resource.
+    RelatedByResource,
+    /// Removes the work orders where the fewest activities have the required
+    /// number of operational actors assigned.
+    LeastCovered,
+}
+
+/// The repair operators decide in which order the `OperationalActor`s are
+/// offered a work order activity in `schedule`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SupervisorRepairOperator
+{
+    BestMarginalFitness,
+    RandomOrder,
+}
+
+impl NeighborhoodOperator for SupervisorDestroyOperator
+{
+    const ALL: &'static [Self] = &[
+        SupervisorDestroyOperator::Random,
+        SupervisorDestroyOperator::RelatedByResource,
+        SupervisorDestroyOperator::LeastCovered,
+    ];
+
+    fn name(&self) -> &'static str
+    {
+        match self {
+            SupervisorDestroyOperator::Random => "random",
+            SupervisorDestroyOperator::RelatedByResource => "related_by_resource",
+            SupervisorDestroyOperator::LeastCovered => "least_covered",
+        }
+    }
+}
+
+impl NeighborhoodOperator for SupervisorRepairOperator
+{
+    const ALL: &'static [Self] = &[
+        SupervisorRepairOperator::BestMarginalFitness,
+        SupervisorRepairOperator::RandomOrder,
+    ];
+
+    fn name(&self) -> &'static str
+    {
+        match self {
+            SupervisorRepairOperator::BestMarginalFitness => "best_marginal_fitness",
+            SupervisorRepairOperator::RandomOrder => "random_order",
+        }
+    }
+}
+
+impl<Ss> SupervisorAlgorithm<Ss>
+where
+    Ss: SystemSolutions,
+{
+    pub(crate) fn destroy_work_orders(
+        &self,
+        destroy_operator: SupervisorDestroyOperator,
+        rng: &mut impl Rng,
+    ) -> Vec<WorkOrderNumber>
+    {
+        let number_of_unassigned_work_orders =
+            self.parameters.options.number_of_unassigned_work_orders;
+
+        let mut candidates = self.solution.get_assigned_and_unassigned_work_orders();
+
+        // Sorted so that a seeded rng always removes the same work orders.
+        candidates.sort();
+        candidates.dedup();
+
+        match destroy_operator {
+            SupervisorDestroyOperator::Random => {
+                sample(&candidates, number_of_unassigned_work_orders, rng)
+            }
+            SupervisorDestroyOperator::RelatedByResource => {
+                let mut resources = candidates
+                    .iter()
+                    .flat_map(|won| self.resources_of_work_order(won))
+                    .collect::<Vec<_>>();
+                resources.sort();
+                resources.dedup();
+
+                match resources.choose(rng) {
+                    Some(resource) => {
+                        let related = candidates
+                            .iter()
+                            .filter(|won| self.resources_of_work_order(won).contains(resource))
+                            .copied()
+                            .collect::<Vec<_>>();
+
+                        sample(&related, number_of_unassigned_work_orders, rng)
+                    }
+                    None => sample(&candidates, number_of_unassigned_work_orders, rng),
+                }
+            }
+            SupervisorDestroyOperator::LeastCovered => {
+                let mut coverage = candidates
+                    .iter()
+                    .map(|won| (*won, self.coverage(won)))
+                    .collect::<Vec<_>>();
+
+                coverage.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+
+                let least_covered = coverage
+                    .iter()
+                    .map(|(won, _)| *won)
+                    .collect::<Vec<_>>();
+
+                sample_from_ranked(&least_covered, number_of_unassigned_work_orders, rng)
+            }
+        }
+    }
+
+    fn resources_of_work_order(&self, work_order_number: &WorkOrderNumber) -> Vec<Resources>
+    {
+        self.parameters
+            .supervisor_work_orders
+            .get(work_order_number)
+            .map(|activities| activities.values().map(|sp| sp.resource).collect())
+            .unwrap_or_default()
+    }
+
+    /// The share of the activities of the work order that have enough
+    /// `OperationalActor`s in `Delegate::Assign`.
+    fn coverage(&self, work_order_number: &WorkOrderNumber) -> f64
+    {
+        let Some(activities) = self.parameters.supervisor_work_orders.get(work_order_number)
+        else {
+            return 1.0;
+        };
+
+        if activities.is_empty() {
+            return 1.0;
+        }
+
+        let covered = activities
+            .iter()
+            .filter(|(activity_number, supervisor_parameter)| {
+                let assigned = self
+                    .solution
+                    .get_iter()
+                    .filter(|((_, woa), delegate)| {
+                        *woa == (*work_order_number, **activity_number) && delegate.is_assign()
+                    })
+                    .count() as u64;
+                assigned >= supervisor_parameter.number
+            })
+            .count();
+
+        covered as f64 / activities.len() as f64
+    }
+}
@@ -80,6 +80,7 @@ impl Parameters for SupervisorParameters
                             op.resource,
                             op.operation_info.number,
                             op.operation_info.work,
+                            op.operation_info.work_remaining,
                         ),
                     )
                 })
@@ -149,11 +150,22 @@ impl SupervisorParameters
     // overwhelmed again. Relax
     pub(crate) fn create_and_insert_supervisor_parameter(
         &mut self,
-        _operation: &Operation,
-        _work_order_activity: &WorkOrderActivity,
+        operation: &Operation,
+        work_order_activity: &WorkOrderActivity,
     )
     {
-        // DEBUG: Make assertions here!
+        self.supervisor_work_orders
+            .entry(work_order_activity.0)
+            .or_default()
+            .insert(
+                work_order_activity.1,
+                SupervisorParameter::new(
+                    operation.resource,
+                    operation.operation_info.number,
+                    operation.operation_info.work,
+                    operation.operation_info.work_remaining,
+                ),
+            );
     }
 }
 
@@ -163,16 +175,23 @@ pub struct SupervisorParameter
     pub resource: Resources,
     pub number: NumberOfPeople,
     pub work: Work,
+    pub work_remaining: Work,
 }
 
 impl SupervisorParameter
 {
-    pub fn new(resource: Resources, number: NumberOfPeople, work: Work) -> Self
+    pub fn new(
+        resource: Resources,
+        number: NumberOfPeople,
+        work: Work,
+        work_remaining: Work,
+    ) -> Self
     {
         Self {
             resource,
             number,
             work,
+            work_remaining,
         }
     }
 }
@@ -2,26 +2,44 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::sync::Arc;
 
+use anyhow::Context;
 use anyhow::Result;
 use arc_swap::Guard;
+use ordinator_actor_core::traits::ObjectiveValue;
 use ordinator_orchestrator_actor_traits::OperationalInterface;
 use ordinator_orchestrator_actor_traits::Solution;
 use ordinator_orchestrator_actor_traits::SwapSolution;
 use ordinator_orchestrator_actor_traits::SystemSolutions;
 use ordinator_orchestrator_actor_traits::delegate::Delegate;
+use ordinator_orchestrator_actor_traits::delegate::DelegateCause;
 use ordinator_orchestrator_actor_traits::marginal_fitness::MarginalFitness;
 use ordinator_scheduling_environment::work_order::WorkOrderActivity;
 use ordinator_scheduling_environment::work_order::WorkOrderNumber;
 use ordinator_scheduling_environment::worker_environment::resources::Id;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::Level;
+use tracing::event;
 
 use super::supervisor_parameters::SupervisorParameters;
 
-pub type SupervisorObjectiveValue = u64;
+#[derive(Serialize, Deserialize, Copy, PartialEq, PartialOrd, Ord, Eq, Debug, Default, Clone)]
+pub struct SupervisorObjectiveValue(pub u64);
 
-#[derive(PartialEq, Eq, Debug, Default, Clone)]
+impl ObjectiveValue for SupervisorObjectiveValue
+{
+    // The supervisor maximizes the share of assigned work order activities.
+    fn cost(&self) -> f64
+    {
+        -(self.0 as f64)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
 pub struct SupervisorSolution
 {
     pub(crate) objective_value: SupervisorObjectiveValue,
+    #[serde(with = "serde_json_any_key::any_key_map")]
     pub(crate) operational_state_machine: HashMap<(Id, WorkOrderActivity), Delegate>,
 }
 
@@ -66,6 +84,11 @@ impl Solution for SupervisorSolution
         })
     }
 
+    fn objective_value(&self) -> &Self::ObjectiveValue
+    {
+        &self.objective_value
+    }
+
     fn update_objective_value(&mut self, other_objective_value: Self::ObjectiveValue)
     {
         self.objective_value = other_objective_value;
@@ -80,6 +103,11 @@ where
     {
         system_solution.supervisor_swap(id, solution);
     }
+
+    fn from_system_solution(id: &Id, system_solution: &Ss) -> Option<Self>
+    {
+        system_solution.supervisor_actor_solution(id).ok().cloned()
+    }
 }
 /// The SupervisorSolution is a state machine that keeps track of all the
 /// states of the operational agents. It is a solution representation of
@@ -88,12 +116,109 @@ where
 /// We should be careful about how we implement this system.
 impl SupervisorSolution
 {
-    pub fn turn_work_order_into_delegate_assess(&mut self, work_order_number: WorkOrderNumber)
+    /// Changes the `Delegate` of the operational actor for the activity and
+    /// logs the transition with its `DelegateCause`.
+    pub fn transition(
+        &mut self,
+        key: &(Id, WorkOrderActivity),
+        to: Delegate,
+        cause: DelegateCause,
+    ) -> Result<()>
+    {
+        let delegate = self
+            .operational_state_machine
+            .get_mut(key)
+            .with_context(|| format!("{key:?} is not delegated by the SupervisorActor"))?;
+        let from = delegate
+            .transition(to.clone(), cause)
+            .with_context(|| format!("Illegal Delegate transition for {key:?}"))?;
+
+        match cause {
+            // The LNS transitions are too frequent to be logged at the info level.
+            DelegateCause::Auction | DelegateCause::Unschedule => event!(
+                Level::DEBUG,
+                operational_id = %key.0,
+                work_order_activity = ?key.1,
+                ?from,
+                ?to,
+                ?cause,
+                "delegate transition"
+            ),
+            _ => event!(
+                Level::INFO,
+                operational_id = %key.0,
+                work_order_activity = ?key.1,
+                ?from,
+                ?to,
+                ?cause,
+                "delegate transition"
+            ),
+        }
+        Ok(())
+    }
+
+    /// The keys of the `Delegate`s that match the predicate, sorted so that
+    /// the transitions are made in the same order on every run.
+    pub(crate) fn sorted_keys(
+        &self,
+        predicate: impl Fn(&(Id, WorkOrderActivity), &Delegate) -> bool,
+    ) -> Vec<(Id, WorkOrderActivity)>
+    {
+        let mut keys: Vec<_> = self
+            .operational_state_machine
+            .iter()
+            .filter(|(key, delegate)| predicate(key, delegate))
+            .map(|(key, _)| key.clone())
+            .collect();
+        keys.sort_by(|a, b| (&a.0.0, a.1).cmp(&(&b.0.0, b.1)));
+        keys
+    }
+
+    /// Releases the assigned and unassigned activities of the work order for a
+    /// new auction. `Fixed`, `Drop` and `Done` are kept.
+    pub fn turn_work_order_into_delegate_assess(
+        &mut self,
+        work_order_number: WorkOrderNumber,
+    ) -> Result<()>
     {
+        let keys = self.sorted_keys(|key, delegate| {
+            key.1.0 == work_order_number
+                && matches!(delegate, Delegate::Assign | Delegate::Unassign)
+        });
+
+        for key in &keys {
+            self.transition(key, Delegate::Assess, DelegateCause::Unschedule)?;
+        }
+        Ok(())
+    }
+
+    /// Releases the activities of an operational actor that was removed. The
+    /// other operational actors are assessed again for the activities that it
+    /// was assigned, and its own `Delegate`s are removed.
+    pub fn release_operational(&mut self, operational_id: &Id) -> Result<()>
+    {
+        let released: HashSet<WorkOrderActivity> = self
+            .operational_state_machine
+            .iter()
+            .filter(|(key, delegate)| &key.0 == operational_id && delegate.is_assigned())
+            .map(|(key, _)| key.1)
+            .collect();
+
+        let keys = self.sorted_keys(|key, delegate| {
+            (&key.0 == operational_id
+                && matches!(
+                    delegate,
+                    Delegate::Assign | Delegate::Unassign | Delegate::Fixed
+                ))
+                || (released.contains(&key.1) && delegate == &Delegate::Unassign)
+        });
+        for key in &keys {
+            self.transition(key, Delegate::Assess, DelegateCause::OperationalUnavailable)?;
+        }
+
         self.operational_state_machine
-            .iter_mut()
-            .filter(|(key, _)| key.1.0 == work_order_number)
-            .for_each(|(_, delegate)| *delegate = Delegate::Assess)
+            .retain(|key, _| &key.0 != operational_id);
+        Ok(())
     }
 
     pub fn count_unique_woa(&self) -> usize
@@ -108,7 +233,7 @@ impl SupervisorSolution
     {
         self.operational_state_machine
             .iter()
-            .filter(|(_, val)| val.is_assign())
+            .filter(|(_, val)| val.is_assigned())
             .map(|(key, _)| key.1)
             .collect()
     }
@@ -179,12 +304,59 @@ impl SupervisorSolution
             .collect()
     }
 
+    /// The activities that are still open for delegation, that is not dropped
+    /// from the supervisor periods or done.
     pub(crate) fn get_work_order_activities(&self) -> HashSet<WorkOrderActivity>
     {
         self.operational_state_machine
-            .keys()
-            .map(|(_, woa)| woa)
+            .iter()
+            .filter(|(_, delegate)| !delegate.is_drop() && !delegate.is_done())
+            .map(|((_, woa), _)| woa)
             .cloned()
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests
+{
+    use std::collections::HashMap;
+
+    use ordinator_orchestrator_actor_traits::delegate::Delegate;
+    use ordinator_scheduling_environment::work_order::WorkOrderNumber;
+    use ordinator_scheduling_environment::worker_environment::resources::Id;
+
+    use super::SupervisorSolution;
+
+    #[test]
+    fn test_release_operational_reassesses_its_activities()
+    {
+        let removed = Id::new("OP-01", vec![], vec![]);
+        let remaining = Id::new("OP-02", vec![], vec![]);
+        let assigned = (WorkOrderNumber(1), 10);
+        let assessed = (WorkOrderNumber(1), 20);
+        let kept = (WorkOrderNumber(2), 10);
+
+        let mut supervisor_solution = SupervisorSolution {
+            operational_state_machine: HashMap::from([
+                ((removed.clone(), assigned), Delegate::Assign),
+                ((remaining.clone(), assigned), Delegate::Unassign),
+                ((removed.clone(), assessed), Delegate::Assess),
+                ((remaining.clone(), assessed), Delegate::Assess),
+                ((remaining.clone(), kept), Delegate::Assign),
+            ]),
+            ..Default::default()
+        };
+
+        supervisor_solution.release_operational(&removed).unwrap();
+
+        assert_eq!(
+            supervisor_solution.operational_state_machine,
+            HashMap::from([
+                ((remaining.clone(), assigned), Delegate::Assess),
+                ((remaining.clone(), assessed), Delegate::Assess),
+                ((remaining, kept), Delegate::Assign),
+            ])
+        );
+    }
+}
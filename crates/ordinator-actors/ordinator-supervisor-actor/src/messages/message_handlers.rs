@@ -1,12 +1,18 @@
-use std::collections::HashSet;
+use std::sync::Arc;
 
 use anyhow::Context;
 use anyhow::Result;
 use anyhow::bail;
-use ordinator_orchestrator_actor_traits::ActorSpecific;
+use ordinator_actor_core::traits::ActorBasedLargeNeighborhoodSearch;
+use ordinator_orchestrator_actor_traits::ActorError;
 use ordinator_orchestrator_actor_traits::CommandHandler;
-use ordinator_orchestrator_actor_traits::StateLink;
 use ordinator_orchestrator_actor_traits::SystemSolutions;
+use ordinator_orchestrator_actor_traits::delegate::Delegate;
+use ordinator_orchestrator_actor_traits::delegate::DelegateCause;
+use ordinator_orchestrator_actor_traits::domain_event::DomainEventHandler;
+use ordinator_scheduling_environment::work_order::WorkOrderActivity;
+use ordinator_scheduling_environment::work_order::WorkOrderNumber;
+use ordinator_scheduling_environment::work_order::operation::ActivityNumber;
 use ordinator_scheduling_environment::worker_environment::resources::Id;
 use tracing::Level;
 use tracing::event;
@@ -16,6 +22,7 @@ use super::SupervisorResponseMessage;
 use crate::SupervisorActor;
 use crate::algorithm::supervisor_parameters::SupervisorParameters;
 use crate::algorithm::supervisor_solution::SupervisorSolution;
+use crate::messages::requests::SupervisorRequestScheduling;
 use crate::messages::responses::SupervisorResponseScheduling;
 use crate::messages::responses::SupervisorResponseStatus;
 
@@ -26,80 +33,6 @@ where
     type Req = SupervisorRequestMessage;
     type Res = SupervisorResponseMessage;
 
-    fn handle_state_link(&mut self, state_link: StateLink) -> Result<Self::Res>
-    {
-        match state_link {
-            StateLink::WorkOrders(agent_specific) => match agent_specific {
-                ActorSpecific::Strategic(changed_work_orders) => {
-                    // It is beginning to seem a little horrible that the self. here holds both the
-                    // `scheduling_environment` and the `algorithm`. There is a
-                    // couple of issues here relating to how we interact
-                    // with the algorithm. I
-                    let work_orders = {
-                        let scheduling_environment_guard =
-                            self.scheduling_environment.lock().unwrap();
-
-                        scheduling_environment_guard.work_orders.inner.clone()
-                    };
-
-                    for work_order_number in changed_work_orders {
-                        let work_order =
-                            work_orders.get(&work_order_number).with_context(|| {
-                                format!(
-                                    "{:?} should always be present in {}",
-                                    work_order_number,
-                                    std::any::type_name::<SupervisorParameters>()
-                                )
-                            })?;
-                        // TODO [ ]
-                        // You need to take a clear stance on this in the code. Should you make an
-                        // API for this? Of course you should.
-                        //
-                        // This is written so sloppy.
-                        // I can sense that we should instead think about the data flow in
-                        // the program. That probably has a higher chance of success. Yes.
-                        for (activity_number, operation) in &work_order.operations.0 {
-                            self.algorithm
-                                .parameters
-                                .create_and_insert_supervisor_parameter(
-                                    operation,
-                                    &(work_order_number, *activity_number),
-                                )
-                        }
-                    }
-                    Ok(SupervisorResponseMessage::StateLink)
-                }
-            },
-            StateLink::WorkerEnvironment => {
-                let scheduling_environment_guard = self.scheduling_environment.lock().unwrap();
-
-                let operational_agents = scheduling_environment_guard
-                    .worker_environment
-                    .actor_specification
-                    .get(self.actor_id.asset())
-                    .unwrap()
-                    .operational
-                    .iter()
-                    .map(|e| &e.id)
-                    .collect::<HashSet<&Id>>();
-
-                event!(
-                    Level::ERROR,
-                    does_state_ids_and_addr_ids_match = self
-                        .algorithm
-                        .loaded_shared_solution
-                        .all_operational()
-                        .iter()
-                        .eq(operational_agents.iter().copied()),
-                    "Check this error later. FIX: YOU SHOULD call '.send()' instead of '.do_send()' and use the lldb debugger to trace the flow."
-                );
-
-                Ok(SupervisorResponseMessage::StateLink)
-            }
-            StateLink::TimeEnvironment => todo!(),
-        }
-    }
-
     fn handle_request_message(
         &mut self,
         supervisor_request_message: SupervisorRequestMessage,
@@ -108,9 +41,42 @@ where
         event!(Level::WARN, "start_of_supervisor_handler");
 
         match supervisor_request_message {
-            SupervisorRequestMessage::Scheduling(_scheduling_message) => Ok(
-                SupervisorResponseMessage::Scheduling(SupervisorResponseScheduling {}),
-            ),
+            SupervisorRequestMessage::Scheduling(scheduling_message) => {
+                let (supervisor_scheduling_message, to, cause) = match scheduling_message {
+                    SupervisorRequestScheduling::Fix(supervisor_scheduling_message) => (
+                        supervisor_scheduling_message,
+                        Delegate::Fixed,
+                        DelegateCause::PlannerFixed,
+                    ),
+                    SupervisorRequestScheduling::Release(supervisor_scheduling_message) => (
+                        supervisor_scheduling_message,
+                        Delegate::Assess,
+                        DelegateCause::PlannerReleased,
+                    ),
+                };
+                let key = (
+                    supervisor_scheduling_message.id_operational,
+                    supervisor_scheduling_message.work_order_activity,
+                );
+                if !self
+                    .algorithm
+                    .solution
+                    .operational_state_machine
+                    .contains_key(&key)
+                {
+                    return Err(ActorError::InvalidRequest(format!(
+                        "{:?} is not delegated to {} by SupervisorActor {}",
+                        key.1, key.0, self.actor_id
+                    ))
+                    .into());
+                }
+
+                self.algorithm.solution.transition(&key, to, cause)?;
+                self.algorithm.publish_best_solution();
+                Ok(SupervisorResponseMessage::Scheduling(
+                    SupervisorResponseScheduling {},
+                ))
+            }
             SupervisorRequestMessage::Update => {
                 bail!(
                     "IMPLEMENT update logic for Supervisor for Asset: {:?}",
@@ -126,7 +92,9 @@ where
                 let supervisor_status = SupervisorResponseStatus {
                     supervisor_resource: self.algorithm.parameters.operational_ids.clone(),
                     delegated_work_order_activities: self.algorithm.solution.count_unique_woa(),
-                    objective: self.algorithm.solution.objective_value,
+                    objective: self.algorithm.solution.objective_value.0,
+                    operator_statistics: self.algorithm.operators.statistics(),
+                    master_seed: self.algorithm.master_seed,
                 };
                 event!(Level::WARN, "after creation of the supervisor_status");
 
@@ -135,3 +103,114 @@ where
         }
     }
 }
+
+impl<Ss> DomainEventHandler for SupervisorActor<Ss>
+where
+    Ss: SystemSolutions<Supervisor = SupervisorSolution>,
+{
+    fn work_order_created(&mut self, work_order_numbers: Vec<WorkOrderNumber>) -> Result<()>
+    {
+        self.update_supervisor_parameters(work_order_numbers, None)
+    }
+
+    fn work_order_updated(&mut self, work_order_numbers: Vec<WorkOrderNumber>) -> Result<()>
+    {
+        self.update_supervisor_parameters(work_order_numbers, None)
+    }
+
+    /// The `OperationalActor`s release the activities of a closed work order
+    /// once their `Delegate`s are done.
+    fn work_order_closed(&mut self, work_order_numbers: Vec<WorkOrderNumber>) -> Result<()>
+    {
+        for key in self.algorithm.solution.sorted_keys(|key, delegate| {
+            work_order_numbers.contains(&key.1.0) && !delegate.is_done()
+        }) {
+            self.algorithm.solution.transition(
+                &key,
+                Delegate::Done,
+                DelegateCause::WorkCompleted,
+            )?;
+        }
+        self.algorithm.publish_best_solution();
+        Ok(())
+    }
+
+    fn operation_progress_confirmed(&mut self, work_order_activity: WorkOrderActivity)
+    -> Result<()>
+    {
+        self.update_supervisor_parameters(vec![work_order_activity.0], Some(work_order_activity.1))
+    }
+
+    /// The new operational actor is assessed for every activity that it has
+    /// the skills for.
+    fn worker_added(&mut self, _id: Id) -> Result<()>
+    {
+        self.rebuild_and_replay()
+    }
+
+    /// The activities of the removed operational actor are auctioned to the
+    /// remaining operational actors.
+    fn worker_removed(&mut self, id: Id) -> Result<()>
+    {
+        self.algorithm.solution.release_operational(&id)?;
+        self.rebuild_and_replay()
+    }
+
+    fn period_rolled(&mut self) -> Result<()>
+    {
+        self.rebuild_and_replay()
+    }
+}
+
+impl<Ss> SupervisorActor<Ss>
+where
+    Ss: SystemSolutions<Supervisor = SupervisorSolution>,
+{
+    fn rebuild_and_replay(&mut self) -> Result<()>
+    {
+        let scheduling_environment = Arc::clone(&self.scheduling_environment);
+        let previous_solution = self
+            .algorithm
+            .rebuild_parameters(&scheduling_environment.lock().unwrap())?;
+        self.algorithm.replay_solution(previous_solution)?;
+        Ok(())
+    }
+
+    /// Recreates the `SupervisorParameter`s of every operation, or of a single
+    /// `ActivityNumber`, of the work orders. The `Delegate`s follow when the
+    /// `SystemSolution` is incorporated.
+    fn update_supervisor_parameters(
+        &mut self,
+        work_order_numbers: Vec<WorkOrderNumber>,
+        activity_number: Option<ActivityNumber>,
+    ) -> Result<()>
+    {
+        let work_orders = {
+            let scheduling_environment_guard = self.scheduling_environment.lock().unwrap();
+
+            scheduling_environment_guard.work_orders.inner.clone()
+        };
+
+        for work_order_number in work_order_numbers {
+            let work_order = work_orders.get(&work_order_number).with_context(|| {
+                format!(
+                    "{:?} should always be present in {}",
+                    work_order_number,
+                    std::any::type_name::<SupervisorParameters>()
+                )
+            })?;
+            for (work_order_activity_number, operation) in &work_order.operations.0 {
+                if activity_number.is_some_and(|number| number != *work_order_activity_number) {
+                    continue;
+                }
+                self.algorithm
+                    .parameters
+                    .create_and_insert_supervisor_parameter(
+                        operation,
+                        &(work_order_number, *work_order_activity_number),
+                    )
+            }
+        }
+        Ok(())
+    }
+}
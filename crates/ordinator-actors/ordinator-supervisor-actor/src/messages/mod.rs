@@ -33,7 +33,6 @@ pub enum SupervisorType
 #[derive(Serialize)]
 pub enum SupervisorResponseMessage
 {
-    StateLink,
     Status(SupervisorResponseStatus),
     Scheduling(SupervisorResponseScheduling),
     Resources(SupervisorResponseResources),
@@ -26,7 +26,17 @@ pub enum SupervisorStatusMessage
 {
     General,
 }
-pub enum SupervisorRequestScheduling {}
+/// Planner decisions on the delegation of a `WorkOrderActivity`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "scheduling_message_type")]
+pub enum SupervisorRequestScheduling
+{
+    /// Fixes the activity to the operational actor, the LNS will not release
+    /// it.
+    Fix(SupervisorSchedulingMessage),
+    /// Releases a fixed activity for a new auction.
+    Release(SupervisorSchedulingMessage),
+}
 pub enum SupervisorRequestResource {}
 pub enum SupervisorTimeRequest {}
 pub enum SupervisorSchedulingEnvironmentCommands {}
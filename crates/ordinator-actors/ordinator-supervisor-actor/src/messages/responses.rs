@@ -1,3 +1,4 @@
+use ordinator_actor_core::operators::OperatorPortfolioStatistics;
 use ordinator_scheduling_environment::worker_environment::resources::Id;
 use serde::Serialize;
 
@@ -13,6 +14,8 @@ pub struct SupervisorResponseStatus
     pub supervisor_resource: Vec<Id>,
     pub delegated_work_order_activities: usize,
     pub objective: u64,
+    pub operator_statistics: OperatorPortfolioStatistics,
+    pub master_seed: u64,
 }
 
 #[derive(Serialize)]
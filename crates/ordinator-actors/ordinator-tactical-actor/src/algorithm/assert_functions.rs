@@ -6,14 +6,13 @@ use anyhow::bail;
 use colored::Colorize;
 use ordinator_orchestrator_actor_traits::SystemSolutions;
 use ordinator_scheduling_environment::time_environment::day::Day;
-use ordinator_scheduling_environment::work_order::WorkOrderNumber;
 use ordinator_scheduling_environment::work_order::operation::Work;
 use ordinator_scheduling_environment::worker_environment::resources::Resources;
-use priority_queue::PriorityQueue;
 use strum::IntoEnumIterator;
 use tracing::Level;
 use tracing::event;
 
+use super::RepairQueue;
 use super::Algorithm;
 use super::tactical_parameters::TacticalParameters;
 use super::tactical_solution::TacticalSolution;
@@ -32,7 +31,7 @@ pub trait TacticalAssertions
 type Type = TacticalParameters;
 
 impl<Ss> TacticalAssertions
-    for Algorithm<TacticalSolution, Type, PriorityQueue<WorkOrderNumber, u64>, Ss>
+    for Algorithm<TacticalSolution, Type, RepairQueue, Ss>
 where
     Ss: SystemSolutions,
 {
@@ -1,5 +1,6 @@
 mod assert_functions;
 pub mod tactical_interface;
+pub mod tactical_operators;
 pub mod tactical_parameters;
 pub mod tactical_resources;
 pub mod tactical_solution;
@@ -17,9 +18,13 @@ use chrono::NaiveDate;
 use chrono::TimeDelta;
 use ordinator_actor_core::algorithm::Algorithm;
 use ordinator_actor_core::algorithm::LoadOperation;
+use ordinator_actor_core::operators::OperatorPortfolio;
+use ordinator_actor_core::operators::RepairPriority;
 use ordinator_actor_core::traits::AbLNSUtils;
 use ordinator_actor_core::traits::ActorBasedLargeNeighborhoodSearch;
 use ordinator_actor_core::traits::ObjectiveValueType;
+use ordinator_actor_core::traits::Reconciliation;
+use ordinator_orchestrator_actor_traits::ActorOptions;
 use ordinator_orchestrator_actor_traits::Parameters;
 use ordinator_orchestrator_actor_traits::Solution;
 use ordinator_orchestrator_actor_traits::StrategicInterface;
@@ -29,11 +34,14 @@ use ordinator_scheduling_environment::time_environment::day::Day;
 use ordinator_scheduling_environment::work_order::WorkOrderNumber;
 use ordinator_scheduling_environment::work_order::operation::ActivityNumber;
 use ordinator_scheduling_environment::work_order::operation::Work;
+use ordinator_scheduling_environment::worker_environment::AcceptanceCriterion;
 use ordinator_scheduling_environment::worker_environment::TacticalOptions;
 use ordinator_scheduling_environment::worker_environment::resources::Resources;
 use priority_queue::PriorityQueue;
-use rand::rng;
-use rand::seq::IndexedRandom;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use tactical_operators::TacticalDestroyOperator;
+use tactical_operators::TacticalRepairOperator;
 use tactical_solution::TacticalObjectiveValue;
 use tactical_solution::TacticalScheduledOperations;
 use tactical_solution::TacticalSolution;
@@ -44,9 +52,12 @@ use self::assert_functions::TacticalAssertions;
 use self::tactical_parameters::TacticalParameters;
 use self::tactical_solution::OperationSolution;
 
+/// The removed work orders waiting to be repaired.
+pub type RepairQueue = PriorityQueue<WorkOrderNumber, RepairPriority>;
+
 // If you had a single crate you should simply call thie
 pub struct TacticalAlgorithm<Ss>(
-    Algorithm<TacticalSolution, TacticalParameters, PriorityQueue<WorkOrderNumber, u64>, Ss>,
+    Algorithm<TacticalSolution, TacticalParameters, RepairQueue, Ss>,
 )
 where
     TacticalSolution: Solution,
@@ -202,14 +213,13 @@ where
 
 impl<Ss> ActorBasedLargeNeighborhoodSearch for TacticalAlgorithm<Ss>
 where
-    Algorithm<TacticalSolution, TacticalParameters, PriorityQueue<WorkOrderNumber, u64>, Ss>:
+    Algorithm<TacticalSolution, TacticalParameters, RepairQueue, Ss>:
         AbLNSUtils<SolutionType = TacticalSolution>,
     TacticalSolution: Solution,
     TacticalParameters: Parameters,
     Ss: SystemSolutions<Tactical = TacticalSolution>,
 {
-    type Algorithm =
-        Algorithm<TacticalSolution, TacticalParameters, PriorityQueue<WorkOrderNumber, u64>, Ss>;
+    type Algorithm = Algorithm<TacticalSolution, TacticalParameters, RepairQueue, Ss>;
     type Options = TacticalOptions;
 
     fn incorporate_system_solution(&mut self) -> Result<bool>
@@ -259,7 +269,7 @@ where
             Ok(ObjectiveValueType::Better(tactical_objective_value))
         } else {
             event!(Level::INFO, tactical_objective_value_worse = ?tactical_objective_value);
-            Ok(ObjectiveValueType::Worse)
+            Ok(ObjectiveValueType::Worse(tactical_objective_value))
         }
     }
 
@@ -274,18 +284,21 @@ where
         self.asset_that_loading_matches_scheduled()
             .with_context(|| format!("TESTING_ASSERTION\nfile: {}\nline: {}", file!(), line!()))?;
 
-        for (work_order_number, solution) in &self.solution.tactical_work_orders.0.clone() {
-            let tactical_parameter = self
-                .parameters
-                .tactical_work_orders
-                .get(work_order_number)
-                .expect("TacticalParameter should ALWAYS be available for a TacticalSolution")
-                .clone();
+        let mut rng = StdRng::from_rng(&mut self.rng);
+        let repair_operator = self.operators.repair::<TacticalRepairOperator>();
+
+        // Sorted so that work orders with the same priority are always popped in
+        // the same order for a given seed.
+        let mut tactical_work_orders: Vec<_> =
+            self.solution.tactical_work_orders.0.clone().into_iter().collect();
+        tactical_work_orders.sort_by_key(|(work_order_number, _)| *work_order_number);
 
+        for (work_order_number, solution) in &tactical_work_orders {
             // All the work orders that does not have a solution gets pushed to the queue.
             if matches!(solution, WhereIsWorkOrder::NotScheduled) {
-                self.solution_intermediate
-                    .push(*work_order_number, tactical_parameter.weight);
+                let priority =
+                    self.repair_priority(repair_operator, work_order_number, &mut rng)?;
+                self.solution_intermediate.push(*work_order_number, priority);
             }
         }
 
@@ -487,25 +500,19 @@ where
     // Remember that you should only change one thing at a time.
     fn unschedule(&mut self) -> Result<()>
     {
-        let mut rng = rng();
-        let work_order_numbers: Vec<WorkOrderNumber> = self
-            .solution
-            .tactical_work_orders
-            .0
-            .clone()
-            .into_keys()
-            .collect();
-
-        let random_work_order_numbers = work_order_numbers.choose_multiple(
-            &mut rng,
-            self.parameters
-                .tactical_options
-                .number_of_removed_work_orders,
-        );
+        let mut rng = StdRng::from_rng(&mut self.rng);
+
+        let (destroy_operator, _) = self
+            .operators
+            .select::<TacticalDestroyOperator, TacticalRepairOperator>(&mut rng);
+
+        let random_work_order_numbers = self
+            .destroy_work_orders(destroy_operator, &mut rng)
+            .with_context(|| format!("Could not destroy with: {destroy_operator:?}"))?;
 
         // How can you make something that will allow us to catch the
         // error instantneously?
-        for work_order_number in random_work_order_numbers {
+        for work_order_number in &random_work_order_numbers {
             self.unschedule_specific_work_order(*work_order_number)
                 .with_context(|| {
                     format!(
@@ -523,6 +530,99 @@ where
     {
         &mut self.0
     }
+
+    fn acceptance_criterion(&self) -> &AcceptanceCriterion
+    {
+        &self.parameters.tactical_options.acceptance_criterion
+    }
+
+    fn update_options(&mut self, actor_options: ActorOptions) -> Result<()>
+    {
+        match actor_options {
+            ActorOptions::Tactical(tactical_options) => {
+                self.parameters.tactical_options = tactical_options;
+                Ok(())
+            }
+            actor_options => bail!(
+                "TacticalActor {} cannot use {:?} options",
+                self.id,
+                actor_options.actor_kind()
+            ),
+        }
+    }
+
+    fn warm_start(&mut self, checkpointed_solution: TacticalSolution) -> Result<Reconciliation>
+    {
+        let current: Vec<WorkOrderNumber> =
+            self.parameters.tactical_work_orders.keys().copied().collect();
+
+        let reconciliation = Reconciliation::replay(
+            current,
+            checkpointed_solution.tactical_work_orders.0,
+            |work_order_number, where_is_work_order| {
+                let is_current = self
+                    .solution
+                    .tactical_work_orders
+                    .0
+                    .contains_key(&work_order_number);
+
+                match warm_start_action(is_current, where_is_work_order, |operation_solutions| {
+                    self.fits_parameters(&work_order_number, operation_solutions)
+                }) {
+                    WarmStartAction::Keep => (),
+                    WarmStartAction::Release => self
+                        .solution
+                        .release_from_tactical_solution(&work_order_number),
+                    WarmStartAction::Insert(operation_solutions) => {
+                        self.update_loadings(&operation_solutions, LoadOperation::Add)?;
+                        self.solution
+                            .tactical_insert_work_order(work_order_number, operation_solutions);
+                    }
+                    WarmStartAction::Drop => return Ok(false),
+                }
+                Ok(true)
+            },
+        )?;
+
+        self.asset_that_loading_matches_scheduled()
+            .with_context(|| format!("TESTING_ASSERTION\nfile: {}\nline: {}", file!(), line!()))?;
+
+        Ok(reconciliation)
+    }
+}
+
+/// What the warm start does with a work order of a checkpoint.
+#[derive(Debug, PartialEq, Eq)]
+enum WarmStartAction<T>
+{
+    /// The work order stays unscheduled.
+    Keep,
+    /// The work order is released back to the strategic actor.
+    Release,
+    Insert(T),
+    /// The work order is gone or its operations no longer fit the
+    /// `TacticalParameters`.
+    Drop,
+}
+
+fn warm_start_action<T>(
+    is_current: bool,
+    where_is_work_order: WhereIsWorkOrder<T>,
+    fits: impl FnOnce(&T) -> bool,
+) -> WarmStartAction<T>
+{
+    if !is_current {
+        return WarmStartAction::Drop;
+    }
+
+    match where_is_work_order {
+        WhereIsWorkOrder::NotScheduled => WarmStartAction::Keep,
+        WhereIsWorkOrder::Strategic => WarmStartAction::Release,
+        WhereIsWorkOrder::Tactical(operation_solutions) if fits(&operation_solutions) => {
+            WarmStartAction::Insert(operation_solutions)
+        }
+        WhereIsWorkOrder::Tactical(_) => WarmStartAction::Drop,
+    }
 }
 
 enum LoopState
@@ -535,8 +635,7 @@ impl<Ss> Deref for TacticalAlgorithm<Ss>
 where
     Ss: SystemSolutions,
 {
-    type Target =
-        Algorithm<TacticalSolution, TacticalParameters, PriorityQueue<WorkOrderNumber, u64>, Ss>;
+    type Target = Algorithm<TacticalSolution, TacticalParameters, RepairQueue, Ss>;
 
     fn deref(&self) -> &Self::Target
     {
@@ -606,6 +705,36 @@ where
         }
     }
 
+    /// A checkpointed work order can only be replayed if its operations and
+    /// days still exist in the `TacticalParameters`.
+    fn fits_parameters(
+        &self,
+        work_order_number: &WorkOrderNumber,
+        operation_solutions: &TacticalScheduledOperations,
+    ) -> bool
+    {
+        let Some(tactical_parameter) = self.parameters.tactical_work_orders.get(work_order_number)
+        else {
+            return false;
+        };
+
+        operation_solutions
+            .0
+            .iter()
+            .all(|(activity_number, operation_solution)| {
+                tactical_parameter
+                    .tactical_operation_parameters
+                    .get(activity_number)
+                    .is_some_and(|operation_parameters| {
+                        operation_parameters.resource == operation_solution.resource
+                    })
+                    && operation_solution
+                        .scheduled
+                        .iter()
+                        .all(|(day, _)| self.capacity(&operation_solution.resource, day).is_ok())
+            })
+    }
+
     fn remaining_capacity(&self, resource: &Resources, day: &Day) -> Option<Work>
     {
         let remaining_capacity = self.capacity(resource, day).ok()? - self.loading(resource, day);
@@ -650,20 +779,22 @@ enum OperationDifference
     DiffDay,
 }
 impl<Ss>
-    From<Algorithm<TacticalSolution, TacticalParameters, PriorityQueue<WorkOrderNumber, u64>, Ss>>
+    From<Algorithm<TacticalSolution, TacticalParameters, RepairQueue, Ss>>
     for TacticalAlgorithm<Ss>
 where
     Ss: SystemSolutions,
 {
     fn from(
-        value: Algorithm<
+        mut value: Algorithm<
             TacticalSolution,
             TacticalParameters,
-            PriorityQueue<WorkOrderNumber, u64>,
+            RepairQueue,
             Ss,
         >,
     ) -> Self
     {
+        value.operators =
+            OperatorPortfolio::new::<TacticalDestroyOperator, TacticalRepairOperator>();
         TacticalAlgorithm(value)
     }
 }
@@ -674,7 +805,11 @@ pub mod tests
     use ordinator_scheduling_environment::work_order::operation::Work;
     use ordinator_scheduling_environment::worker_environment::resources::Id;
 
+    use ordinator_orchestrator_actor_traits::WhereIsWorkOrder;
+
+    use crate::algorithm::WarmStartAction;
     use crate::algorithm::determine_load;
+    use crate::algorithm::warm_start_action;
 
     #[test]
     fn test_determine_load_1()
@@ -745,4 +880,29 @@ pub mod tests
     // absolutely. I do not see anyother way, as the `objective value` may
     // always be dependent on the other `Solution`s.
     // GOOD a decision was made here.
+
+    #[test]
+    fn test_warm_start_action()
+    {
+        assert_eq!(
+            warm_start_action(true, WhereIsWorkOrder::Tactical(1), |_| true),
+            WarmStartAction::Insert(1)
+        );
+        assert_eq!(
+            warm_start_action(true, WhereIsWorkOrder::Tactical(1), |_| false),
+            WarmStartAction::Drop
+        );
+        assert_eq!(
+            warm_start_action(true, WhereIsWorkOrder::<u32>::Strategic, |_| true),
+            WarmStartAction::Release
+        );
+        assert_eq!(
+            warm_start_action(true, WhereIsWorkOrder::<u32>::NotScheduled, |_| true),
+            WarmStartAction::Keep
+        );
+        assert_eq!(
+            warm_start_action(false, WhereIsWorkOrder::Tactical(1), |_| true),
+            WarmStartAction::Drop
+        );
+    }
 }
@@ -0,0 +1,294 @@
+use std::cmp::Reverse;
+
+use anyhow::Context;
+use anyhow::Result;
+use chrono::NaiveDate;
+use chrono::TimeDelta;
+use ordinator_actor_core::operators::NeighborhoodOperator;
+use ordinator_actor_core::operators::RepairPriority;
+use ordinator_actor_core::operators::rank_worst_first;
+use ordinator_actor_core::operators::sample;
+use ordinator_actor_core::operators::sample_from_ranked;
+use ordinator_orchestrator_actor_traits::SystemSolutions;
+use ordinator_orchestrator_actor_traits::WhereIsWorkOrder;
+use ordinator_scheduling_environment::time_environment::day::Day;
+use ordinator_scheduling_environment::work_order::WorkOrderNumber;
+use ordinator_scheduling_environment::worker_environment::resources::Resources;
+use rand::Rng;
+use rand::seq::IndexedRandom;
+
+use super::TacticalAlgorithm;
+use super::tactical_solution::TacticalScheduledOperations;
+
+/// The destroy operators of the `TacticalAlgorithm`. Each of them removes up
+/// to `number_of_removed_work_orders` work orders.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TacticalDestroyOperator
+{
+    Random,
+    /// Removes the work orders that finish the latest compared to their
+    /// earliest allowed start date, weighted by the work order weight.
+    WorstTardiness,
+    /// Removes random work orders that have load on a single day.
+    RelatedByDay,
+    /// Removes work orders that load a resource on a day where the loading
+    /// exceeds the capacity.
+    OverloadedResourceDay,
+}
+
+/// The repair operators decide the order in which the removed work orders are
+/// popped from the priority queue in `schedule`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TacticalRepairOperator
+{
+    GreedyByWeight,
+    EarliestStartDate,
+    RandomOrder,
+}
+
+impl NeighborhoodOperator for TacticalDestroyOperator
+{
+    const ALL: &'static [Self] = &[
+        TacticalDestroyOperator::Random,
+        TacticalDestroyOperator::WorstTardiness,
+        TacticalDestroyOperator::RelatedByDay,
+        TacticalDestroyOperator::OverloadedResourceDay,
+    ];
+
+    fn name(&self) -> &'static str
+    {
+        match self {
+            TacticalDestroyOperator::Random => "random",
+            TacticalDestroyOperator::WorstTardiness => "worst_tardiness",
+            TacticalDestroyOperator::RelatedByDay => "related_by_day",
+            TacticalDestroyOperator::OverloadedResourceDay => "overloaded_resource_day",
+        }
+    }
+}
+
+impl NeighborhoodOperator for TacticalRepairOperator
+{
+    const ALL: &'static [Self] = &[
+        TacticalRepairOperator::GreedyByWeight,
+        TacticalRepairOperator::EarliestStartDate,
+        TacticalRepairOperator::RandomOrder,
+    ];
+
+    fn name(&self) -> &'static str
+    {
+        match self {
+            TacticalRepairOperator::GreedyByWeight => "greedy_by_weight",
+            TacticalRepairOperator::EarliestStartDate => "earliest_start_date",
+            TacticalRepairOperator::RandomOrder => "random_order",
+        }
+    }
+}
+
+impl<Ss> TacticalAlgorithm<Ss>
+where
+    Ss: SystemSolutions,
+{
+    pub(crate) fn destroy_work_orders(
+        &self,
+        destroy_operator: TacticalDestroyOperator,
+        rng: &mut impl Rng,
+    ) -> Result<Vec<WorkOrderNumber>>
+    {
+        let number_of_removed_work_orders = self
+            .parameters
+            .tactical_options
+            .number_of_removed_work_orders;
+
+        let mut candidates: Vec<WorkOrderNumber> = self
+            .solution
+            .tactical_work_orders
+            .0
+            .keys()
+            .copied()
+            .collect();
+
+        // Sorted so that a seeded rng always removes the same work orders.
+        candidates.sort();
+
+        let work_order_numbers = match destroy_operator {
+            TacticalDestroyOperator::Random => {
+                sample(&candidates, number_of_removed_work_orders, rng)
+            }
+            TacticalDestroyOperator::WorstTardiness => {
+                let tardiness = candidates
+                    .iter()
+                    .map(|won| Ok((*won, self.tardiness_contribution(won)?)))
+                    .collect::<Result<Vec<_>>>()?;
+
+                let worst = rank_worst_first(tardiness);
+
+                sample_from_ranked(&worst, number_of_removed_work_orders, rng)
+            }
+            TacticalDestroyOperator::RelatedByDay => {
+                match self.parameters.tactical_days.choose(rng) {
+                    Some(day) => {
+                        let on_day = candidates
+                            .iter()
+                            .filter(|won| {
+                                self.scheduled_operations(won).is_some_and(|operations| {
+                                    operations.0.values().any(|operation| {
+                                        operation.scheduled.iter().any(|(d, _)| d == day)
+                                    })
+                                })
+                            })
+                            .copied()
+                            .collect::<Vec<_>>();
+
+                        sample(&on_day, number_of_removed_work_orders, rng)
+                    }
+                    None => sample(&candidates, number_of_removed_work_orders, rng),
+                }
+            }
+            TacticalDestroyOperator::OverloadedResourceDay => {
+                let overloaded_cells = self.overloaded_resource_days()?;
+
+                match overloaded_cells.choose(rng) {
+                    Some((resource, day)) => {
+                        let in_cell = candidates
+                            .iter()
+                            .filter(|won| {
+                                self.scheduled_operations(won).is_some_and(|operations| {
+                                    operations.0.values().any(|operation| {
+                                        &operation.resource == resource
+                                            && operation.scheduled.iter().any(|(d, _)| d == day)
+                                    })
+                                })
+                            })
+                            .copied()
+                            .collect::<Vec<_>>();
+
+                        sample(&in_cell, number_of_removed_work_orders, rng)
+                    }
+                    None => sample(&candidates, number_of_removed_work_orders, rng),
+                }
+            }
+        };
+
+        Ok(work_order_numbers)
+    }
+
+    /// The priority that an unscheduled work order gets in the priority queue.
+    /// The queue pops the highest priority first.
+    pub(crate) fn repair_priority(
+        &self,
+        repair_operator: TacticalRepairOperator,
+        work_order_number: &WorkOrderNumber,
+        rng: &mut impl Rng,
+    ) -> Result<RepairPriority>
+    {
+        let tactical_parameter = self
+            .parameters
+            .tactical_work_orders
+            .get(work_order_number)
+            .context("TacticalParameter should ALWAYS be available for a TacticalSolution")?;
+
+        Ok(match repair_operator {
+            TacticalRepairOperator::GreedyByWeight => {
+                RepairPriority::Highest(tactical_parameter.weight)
+            }
+            TacticalRepairOperator::EarliestStartDate => {
+                let days_from_ce = chrono::Datelike::num_days_from_ce(
+                    &tactical_parameter.earliest_allowed_start_date,
+                );
+                RepairPriority::Earliest(Reverse(i64::from(days_from_ce)))
+            }
+            TacticalRepairOperator::RandomOrder => RepairPriority::Highest(rng.random()),
+        })
+    }
+
+    fn scheduled_operations(
+        &self,
+        work_order_number: &WorkOrderNumber,
+    ) -> Option<&TacticalScheduledOperations>
+    {
+        match self.solution.tactical_work_orders.0.get(work_order_number) {
+            Some(WhereIsWorkOrder::Tactical(operations)) => Some(operations),
+            _ => None,
+        }
+    }
+
+    /// A simplified version of `determine_tardiness` that only uses the
+    /// parameters of the `TacticalAlgorithm`, so that it does not have to load
+    /// the strategic solution for every work order.
+    fn tardiness_contribution(&self, work_order_number: &WorkOrderNumber) -> Result<u64>
+    {
+        let tactical_parameter = self
+            .parameters
+            .tactical_work_orders
+            .get(work_order_number)
+            .context("TacticalParameter should ALWAYS be available for a TacticalSolution")?;
+
+        let Some(last_day) = self.scheduled_operations(work_order_number).and_then(|operations| {
+            operations
+                .0
+                .values()
+                .filter_map(|operation| operation.scheduled.last())
+                .map(|(day, _)| day.date().date_naive())
+                .max()
+        }) else {
+            return Ok(0);
+        };
+
+        Ok(weighted_tardiness(
+            last_day,
+            tactical_parameter.earliest_allowed_start_date,
+            tactical_parameter.weight,
+        ))
+    }
+
+    fn overloaded_resource_days(&self) -> Result<Vec<(Resources, Day)>>
+    {
+        let mut resources: Vec<_> = self
+            .parameters
+            .tactical_capacity
+            .resources
+            .keys()
+            .collect();
+        resources.sort();
+
+        let mut overloaded_cells = vec![];
+        for resource in resources {
+            for day in &self.parameters.tactical_days {
+                if self.loading(resource, day) > self.capacity(resource, day)? {
+                    overloaded_cells.push((*resource, day.clone()));
+                }
+            }
+        }
+        Ok(overloaded_cells)
+    }
+}
+
+/// Finishing before the earliest allowed start date does not count as
+/// negative tardiness.
+fn weighted_tardiness(last_day: NaiveDate, earliest_allowed_start_date: NaiveDate, weight: u64)
+-> u64
+{
+    let day_difference = (last_day - earliest_allowed_start_date).max(TimeDelta::zero());
+
+    weight * day_difference.num_days() as u64
+}
+
+#[cfg(test)]
+mod tests
+{
+    use chrono::NaiveDate;
+
+    use super::weighted_tardiness;
+
+    #[test]
+    fn test_weighted_tardiness_ignores_early_work_orders()
+    {
+        let earliest_allowed_start_date = NaiveDate::from_ymd_opt(2026, 10, 12).unwrap();
+
+        let late = NaiveDate::from_ymd_opt(2026, 10, 15).unwrap();
+        let early = NaiveDate::from_ymd_opt(2026, 10, 10).unwrap();
+
+        assert_eq!(weighted_tardiness(late, earliest_allowed_start_date, 100), 300);
+        assert_eq!(weighted_tardiness(early, earliest_allowed_start_date, 100), 0);
+    }
+}
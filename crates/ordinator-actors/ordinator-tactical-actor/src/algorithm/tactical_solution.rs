@@ -5,6 +5,7 @@ use std::fmt::Display;
 use anyhow::Context;
 use anyhow::Result;
 use anyhow::bail;
+use ordinator_actor_core::traits::ObjectiveValue;
 use ordinator_orchestrator_actor_traits::Solution;
 use ordinator_orchestrator_actor_traits::SwapSolution;
 use ordinator_orchestrator_actor_traits::SystemSolutions;
@@ -51,7 +52,15 @@ impl TacticalObjectiveValue
     }
 }
 
-#[derive(PartialEq, Eq, Debug, Clone)]
+impl ObjectiveValue for TacticalObjectiveValue
+{
+    fn cost(&self) -> f64
+    {
+        self.objective_value as f64
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
 pub struct TacticalSolution
 {
     pub(crate) objective_value: TacticalObjectiveValue,
@@ -94,6 +103,11 @@ impl Solution for TacticalSolution
         })
     }
 
+    fn objective_value(&self) -> &Self::ObjectiveValue
+    {
+        &self.objective_value
+    }
+
     fn update_objective_value(&mut self, other_objective_value: Self::ObjectiveValue)
     {
         self.objective_value = other_objective_value;
@@ -108,6 +122,11 @@ where
     {
         system_solution.tactical_swap(id, solution);
     }
+
+    fn from_system_solution(_id: &Id, system_solution: &Ss) -> Option<Self>
+    {
+        system_solution.tactical_actor_solution().ok().cloned()
+    }
 }
 
 impl TacticalSolution
@@ -160,7 +179,7 @@ impl TacticalSolution
 }
 // This is part of the solution. I think that you should rewrite the trait here
 // so that you can work with the
-#[derive(PartialEq, Eq, Debug, Default, Clone)]
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
 pub struct TacticalScheduledWorkOrders(
     pub HashMap<WorkOrderNumber, WhereIsWorkOrder<TacticalScheduledOperations>>,
 );
@@ -211,7 +230,7 @@ impl TacticalScheduledWorkOrders
     }
 }
 
-#[derive(PartialEq, Eq, Debug, Default, Clone)]
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
 pub struct TacticalScheduledOperations(pub BTreeMap<ActivityNumber, OperationSolution>);
 
 //
@@ -267,7 +286,7 @@ impl TacticalSolutionBuilder
         }
     }
 }
-#[derive(Hash, PartialEq, PartialOrd, Ord, Eq, Clone, Debug, Serialize)]
+#[derive(Hash, PartialEq, PartialOrd, Ord, Eq, Clone, Debug, Serialize, Deserialize)]
 pub struct OperationSolution
 {
     pub scheduled: Vec<(Day, Work)>,
@@ -6,6 +6,7 @@ use std::ops::DerefMut;
 use std::sync::Arc;
 use std::sync::Mutex;
 
+use algorithm::RepairQueue;
 use algorithm::TacticalAlgorithm;
 use algorithm::tactical_parameters::TacticalParameters;
 use algorithm::tactical_solution::TacticalSolution;
@@ -19,14 +20,13 @@ use ordinator_actor_core::algorithm::Algorithm;
 use ordinator_actor_core::traits::ActorBasedLargeNeighborhoodSearch;
 use ordinator_configuration::SystemConfigurations;
 use ordinator_orchestrator_actor_traits::ActorFactory;
+use ordinator_orchestrator_actor_traits::ActorFailure;
 use ordinator_orchestrator_actor_traits::Communication;
 use ordinator_orchestrator_actor_traits::CommandHandler;
 use ordinator_orchestrator_actor_traits::OrchestratorNotifier;
 use ordinator_orchestrator_actor_traits::SystemSolutions;
 use ordinator_scheduling_environment::SchedulingEnvironment;
-use ordinator_scheduling_environment::work_order::WorkOrderNumber;
 use ordinator_scheduling_environment::worker_environment::resources::Id;
-use priority_queue::PriorityQueue;
 
 pub struct TacticalActor<Ss>(
     Actor<TacticalRequestMessage, TacticalResponseMessage, TacticalAlgorithm<Ss>>,
@@ -69,7 +69,7 @@ where
             Algorithm<
                 TacticalSolution,
                 TacticalParameters,
-                PriorityQueue<WorkOrderNumber, u64>,
+                RepairQueue,
                 Ss,
             >,
         >,
@@ -82,11 +82,11 @@ where
         shared_solution_arc_swap: Arc<ArcSwap<Ss>>,
         notify_orchestrator: Arc<dyn OrchestratorNotifier>,
         system_configurations: Arc<ArcSwap<SystemConfigurations>>,
-        error_channel: Sender<anyhow::Error>,
+        error_channel: Sender<ActorFailure>,
     ) -> Result<Self::Communication>
     {
         Actor::<TacticalRequestMessage, TacticalResponseMessage, TacticalAlgorithm<Ss>>::builder()
-            .agent_id(Id::new("TacticalAgent", vec![], vec![id.asset().clone()]))
+            .agent_id(id.clone())
             .scheduling_environment(Arc::clone(&scheduling_environment_guard))
             // TODO
             // Make a builder here!
@@ -96,7 +96,9 @@ where
             // I think that taking a short break is a good idea.
             // The issue is that you do not understand `Fn` traits well enough
             .algorithm(|ab| {
-                ab.id(id)
+                ab.master_seed(system_configurations.load().master_seed)
+                    .id(id)
+                    .checkpoint_store(notify_orchestrator.checkpoint_store())
                     // So this function returns a `Result`
                     .parameters_and_solution(&scheduling_environment_guard.lock().unwrap())?
                     .arc_swap_shared_solution(shared_solution_arc_swap)
@@ -1,13 +1,19 @@
+use std::sync::Arc;
+
 use anyhow::Context;
 use anyhow::Result;
-use ordinator_orchestrator_actor_traits::ActorSpecific;
+use ordinator_actor_core::traits::ActorBasedLargeNeighborhoodSearch;
 use ordinator_orchestrator_actor_traits::CommandHandler;
-use ordinator_orchestrator_actor_traits::StateLink;
 use ordinator_orchestrator_actor_traits::SystemSolutions;
 use ordinator_orchestrator_actor_traits::WhereIsWorkOrder;
+use ordinator_orchestrator_actor_traits::domain_event::DomainEventHandler;
+use ordinator_scheduling_environment::work_order::WorkOrderActivity;
+use ordinator_scheduling_environment::work_order::WorkOrderNumber;
+use ordinator_scheduling_environment::worker_environment::resources::Id;
 
 use super::TacticalRequestMessage;
 use super::TacticalResponseMessage;
+use super::responses::TacticalResponseStatus;
 use crate::TacticalActor;
 use crate::algorithm::tactical_parameters::TacticalParameters;
 use crate::algorithm::tactical_parameters::create_tactical_parameter;
@@ -33,9 +39,13 @@ where
     {
         match tactical_request {
             TacticalRequestMessage::Status(_tactical_status_message) => {
-                // let status_message = self.status().unwrap();
-                // Ok(TacticalResponseMessage::Status(status_message))
-                todo!()
+                let tactical_status = TacticalResponseStatus {
+                    objective: self.algorithm.solution.objective_value.objective_value,
+                    time_horizon: self.algorithm.parameters.tactical_days.clone(),
+                    operator_statistics: self.algorithm.operators.statistics(),
+                    master_seed: self.algorithm.master_seed,
+                };
+                Ok(TacticalResponseMessage::Status(tactical_status))
             }
             TacticalRequestMessage::Scheduling(_tactical_scheduling_message) => {
                 todo!()
@@ -63,94 +73,162 @@ where
             }
         }
     }
+}
 
-    fn handle_state_link(&mut self, state_link: StateLink) -> Result<Self::Res>
+impl<Ss> DomainEventHandler for TacticalActor<Ss>
+where
+    Ss: SystemSolutions<Tactical = TacticalSolution>,
+{
+    fn work_order_created(&mut self, work_order_numbers: Vec<WorkOrderNumber>) -> Result<()>
     {
-        match state_link {
-            StateLink::WorkOrders(agent_specific) => match agent_specific {
-                ActorSpecific::Strategic(changed_work_orders) => {
-                    let scheduling_environment_guard = self.scheduling_environment.lock().unwrap();
-
-                    let work_orders = &scheduling_environment_guard.work_orders.inner.clone();
-                    let work_order_configurations = &scheduling_environment_guard
-                        .worker_environment
-                        .actor_specification
-                        .get(self.actor_id.asset())
-                        .unwrap()
-                        .work_order_configurations
-                        .clone();
-
-                    drop(scheduling_environment_guard);
-                    for work_order_number in changed_work_orders {
-                        let work_order =
-                            work_orders.get(&work_order_number).with_context(|| {
-                                format!(
-                                    "{:?} should always be present in {}",
-                                    work_order_number,
-                                    std::any::type_name::<TacticalParameters>()
-                                )
-                            })?;
-
-                        // FIX
-                        // The solution should also be updated here. Think about how you can make
-                        // this generic.
-                        // QUESTION
-                        // Is this a good way of coding the program? I think that there is common
-                        // behavior here that we are going to have to
-                        // exploit to make sense of this. You are not creating this in the best
-                        // possible way at the moment I think. There is a
-                        // better approach for dealing with this.
-                        //
-                        // You should wrap this up in the `Interface`
-
-                        let tactical_parameter =
-                            create_tactical_parameter(work_order, work_order_configurations)?;
-
-                        // It is only the algorithm that can modify parameters. Not the the Actor
-                        // directly you should fix this issue soon. What
-                        // about the code. You should make the interface
-                        // here for interacting with the algorithm.
-                        self.algorithm
-                            .parameters
-                            .tactical_work_orders
-                            .insert(work_order_number, tactical_parameter);
-
-                        self.algorithm
-                            .solution
-                            .tactical_work_orders
-                            .0
-                            .insert(work_order_number, WhereIsWorkOrder::NotScheduled);
-                    }
-                    Ok(TacticalResponseMessage::FreeStringResponse(
-                        "Updated StateLink::WorkOrders".to_string(),
-                    ))
-                }
-            },
-            StateLink::WorkerEnvironment => {
-                let scheduling_environment_guard = self.scheduling_environment.lock().unwrap();
-
-                // The issue here is that `from` does not consume the value. But instead work
-                // with the reference.
-                let tactical_resources =
-                    TacticalResources::from((&scheduling_environment_guard, &self.actor_id));
-                drop(scheduling_environment_guard);
+        self.update_tactical_parameters(work_order_numbers)
+    }
 
-                self.algorithm
-                    .parameters
-                    .tactical_capacity
-                    .update_resources(tactical_resources);
+    fn work_order_updated(&mut self, work_order_numbers: Vec<WorkOrderNumber>) -> Result<()>
+    {
+        self.update_tactical_parameters(work_order_numbers)
+    }
 
-                // TODO [ ]
-                // Turn this into a JSON
-                Ok(TacticalResponseMessage::FreeStringResponse(
-                    "Updated StateLink::WorkerEnvironment".to_string(),
-                ))
+    fn work_order_closed(&mut self, work_order_numbers: Vec<WorkOrderNumber>) -> Result<()>
+    {
+        for work_order_number in work_order_numbers {
+            if let Some(WhereIsWorkOrder::Tactical(_)) = self
+                .algorithm
+                .solution
+                .tactical_work_orders
+                .0
+                .get(&work_order_number)
+            {
+                self.algorithm
+                    .unschedule_specific_work_order(work_order_number)?;
             }
+            self.algorithm
+                .solution
+                .tactical_work_orders
+                .0
+                .remove(&work_order_number);
+            self.algorithm
+                .parameters
+                .tactical_work_orders
+                .remove(&work_order_number);
+        }
+        self.algorithm.calculate_objective_value()?;
+        self.algorithm.publish_best_solution();
+        Ok(())
+    }
 
-            StateLink::TimeEnvironment => {
-                todo!()
+    fn operation_progress_confirmed(&mut self, work_order_activity: WorkOrderActivity)
+    -> Result<()>
+    {
+        self.update_tactical_parameters(vec![work_order_activity.0])
+    }
+
+    fn worker_availability_changed(&mut self, _id: Id) -> Result<()>
+    {
+        let scheduling_environment_guard = self.scheduling_environment.lock().unwrap();
+
+        // The issue here is that `from` does not consume the value. But instead work
+        // with the reference.
+        let tactical_resources =
+            TacticalResources::from((&scheduling_environment_guard, &self.actor_id));
+        drop(scheduling_environment_guard);
+
+        self.algorithm
+            .parameters
+            .tactical_capacity
+            .update_resources(tactical_resources);
+        Ok(())
+    }
+
+    /// The capacities are rebuilt, as the operational actor can bring a
+    /// resource that has no capacity yet.
+    fn worker_added(&mut self, _id: Id) -> Result<()>
+    {
+        self.rebuild_and_replay()
+    }
+
+    fn worker_removed(&mut self, _id: Id) -> Result<()>
+    {
+        self.rebuild_and_replay()
+    }
+
+    fn period_rolled(&mut self) -> Result<()>
+    {
+        self.rebuild_and_replay()
+    }
+}
+
+impl<Ss> TacticalActor<Ss>
+where
+    Ss: SystemSolutions<Tactical = TacticalSolution>,
+{
+    fn rebuild_and_replay(&mut self) -> Result<()>
+    {
+        let scheduling_environment = Arc::clone(&self.scheduling_environment);
+        let previous_solution = self
+            .algorithm
+            .rebuild_parameters(&scheduling_environment.lock().unwrap())?;
+        self.algorithm.replay_solution(previous_solution)?;
+        Ok(())
+    }
+
+    /// Work orders that the `TacticalActor` has scheduled are unscheduled
+    /// before their `TacticalParameter` is replaced.
+    fn update_tactical_parameters(&mut self, work_order_numbers: Vec<WorkOrderNumber>)
+    -> Result<()>
+    {
+        let scheduling_environment_guard = self.scheduling_environment.lock().unwrap();
+
+        let work_orders = &scheduling_environment_guard.work_orders.inner.clone();
+        let work_order_configurations = &scheduling_environment_guard
+            .worker_environment
+            .actor_specification
+            .get(self.actor_id.asset())
+            .unwrap()
+            .work_order_configurations
+            .clone();
+
+        drop(scheduling_environment_guard);
+        for work_order_number in work_order_numbers {
+            let work_order = work_orders.get(&work_order_number).with_context(|| {
+                format!(
+                    "{:?} should always be present in {}",
+                    work_order_number,
+                    std::any::type_name::<TacticalParameters>()
+                )
+            })?;
+
+            let tactical_parameter =
+                create_tactical_parameter(work_order, work_order_configurations)?;
+
+            if let Some(WhereIsWorkOrder::Tactical(_)) = self
+                .algorithm
+                .solution
+                .tactical_work_orders
+                .0
+                .get(&work_order_number)
+            {
+                self.algorithm
+                    .unschedule_specific_work_order(work_order_number)?;
             }
+
+            // It is only the algorithm that can modify parameters. Not the the Actor
+            // directly you should fix this issue soon. What
+            // about the code. You should make the interface
+            // here for interacting with the algorithm.
+            self.algorithm
+                .parameters
+                .tactical_work_orders
+                .insert(work_order_number, tactical_parameter);
+
+            self.algorithm
+                .solution
+                .tactical_work_orders
+                .0
+                .entry(work_order_number)
+                .or_insert(WhereIsWorkOrder::NotScheduled);
         }
+        Ok(())
     }
 }
 
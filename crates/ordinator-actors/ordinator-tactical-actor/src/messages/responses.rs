@@ -1,3 +1,4 @@
+use ordinator_actor_core::operators::OperatorPortfolioStatistics;
 use ordinator_scheduling_environment::time_environment::day::Day;
 use serde::Deserialize;
 use serde::Serialize;
@@ -7,8 +8,10 @@ pub struct TacticalResponseScheduling {}
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TacticalResponseStatus {
-    objective: u64,
-    time_horizon: Vec<Day>,
+    pub objective: u64,
+    pub time_horizon: Vec<Day>,
+    pub operator_statistics: OperatorPortfolioStatistics,
+    pub master_seed: u64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -56,18 +56,19 @@ pub async fn operational_handler_for_operational_agent(
     let operational_request_message =
         OperationalRequestMessage::Status(OperationalStatusRequest::General);
     // OperationalStatusMessage
-    let hash_map = orchestrator.actor_registries.lock().unwrap();
-    let communication = hash_map
+    let requester = orchestrator
+        .actor_registries
+        .lock()
+        .unwrap()
         .get(&asset)
         .expect("This error should be handled higher up")
         .get_operational_addr(&technician_id)
-        .context("OperationalCommunication not found")?;
+        .context("OperationalCommunication not found")?
+        .requester();
 
-    communication
-        .from_agent(operational_request_message)
-        .context("Could not await the message sending, theard problems are the most likely")?;
-
-    let response = communication.from_actor();
+    let response = requester
+        .request(operational_request_message, orchestrator.request_timeout())
+        .await?;
 
     Ok(Json(response))
 }
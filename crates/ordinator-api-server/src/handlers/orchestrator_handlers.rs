@@ -1,9 +1,10 @@
 use std::sync::Arc;
 
+use anyhow::Context;
 use axum::Json;
 use axum::body::Bytes;
-use axum::debug_handler;
 use axum::extract::Path;
+use axum::extract::Query;
 use axum::extract::State;
 use axum::http::HeaderMap;
 use axum::http::StatusCode;
@@ -11,10 +12,18 @@ use axum::http::header;
 use axum::response::IntoResponse;
 use axum::response::Response;
 use axum::response::Result;
+use ordinator_orchestrator::ActorLifecycleRequest;
 use ordinator_orchestrator::Asset;
+use ordinator_orchestrator::InputOperational;
+use ordinator_orchestrator::InputSupervisor;
 use ordinator_orchestrator::Orchestrator;
 use ordinator_orchestrator::OrchestratorRequest;
 use ordinator_orchestrator::TotalSystemSolution;
+use ordinator_orchestrator::actor_options::ActorOptionsUpdate;
+use ordinator_orchestrator::source_changes::SourceChange;
+use serde::Deserialize;
+
+use crate::routes::api::AppError;
 
 // This should be deleted and replaced with the other handler. I do not
 // see a different way around it.
@@ -33,13 +42,11 @@ use ordinator_orchestrator::TotalSystemSolution;
 pub async fn scheduler_excel_export(
     State(orchestrator): State<Arc<Orchestrator<TotalSystemSolution>>>,
     Path(asset): Path<Asset>,
-) -> Result<Response>
+) -> Result<Response, AppError>
 {
     let mut headers = HeaderMap::new();
 
-    let (buffer, http_header) = orchestrator
-        .export_xlsx_solution(asset)
-        .expect("Could not export xlsx");
+    let (buffer, http_header) = orchestrator.export_xlsx_solution(asset)?;
 
     headers.insert(
         header::CONTENT_TYPE,
@@ -48,7 +55,12 @@ pub async fn scheduler_excel_export(
             .unwrap(),
     );
 
-    headers.insert(header::CONTENT_DISPOSITION, http_header.parse().unwrap());
+    headers.insert(
+        header::CONTENT_DISPOSITION,
+        http_header
+            .parse()
+            .with_context(|| format!("Invalid Content-Disposition header {http_header}"))?,
+    );
 
     // let http_response = HttpResponse::Ok()
     //     .content_type("application/vnd.openxmlformats-officedocument.
@@ -104,31 +116,189 @@ pub async fn orchestrator_status(
     Ok(Json(orchestrator.actor_registries.lock().unwrap().len()).into_response())
 }
 
-pub async fn get_days(
-    orchestrator: State<Arc<Orchestrator<TotalSystemSolution>>>,
+pub async fn actor_lifecycle(
+    State(orchestrator): State<Arc<Orchestrator<TotalSystemSolution>>>,
+    Path((asset, actor_id, actor_lifecycle_request)): Path<(Asset, String, ActorLifecycleRequest)>,
+) -> Result<Response, AppError>
+{
+    let response = orchestrator
+        .handle(OrchestratorRequest::ActorLifecycle(
+            asset,
+            actor_id,
+            actor_lifecycle_request,
+        ))
+        .await?;
+
+    Ok(Json(response).into_response())
+}
+
+pub async fn update_actor_options(
+    State(orchestrator): State<Arc<Orchestrator<TotalSystemSolution>>>,
+    Path((asset, actor_id)): Path<(Asset, String)>,
+    Json(actor_options_update): Json<ActorOptionsUpdate>,
+) -> Result<Response, AppError>
+{
+    let response = orchestrator
+        .handle(OrchestratorRequest::UpdateActorOptions(
+            asset,
+            actor_id,
+            actor_options_update,
+        ))
+        .await?;
+
+    Ok(Json(response).into_response())
+}
+
+pub async fn actor_options_history(
+    State(orchestrator): State<Arc<Orchestrator<TotalSystemSolution>>>,
+    Path(asset): Path<Asset>,
 ) -> Result<Response>
 {
-    let json = Json(OrchestratorRequest::GetPeriods);
-    orchestrator_helper(orchestrator, json).await
+    Ok(Json(orchestrator.actor_options_history(&asset)).into_response())
 }
 
-/// This is a helper function for routing message for the orchestrator. Each
-/// `handler` function should ideally use this to send a [`OrchestratorRequest`]
-/// to the orchestrator.
-#[debug_handler]
-pub async fn orchestrator_helper(
+/// The telemetry of all actors in the Prometheus text format.
+pub async fn metrics(State(orchestrator): State<Arc<Orchestrator<TotalSystemSolution>>>)
+-> Response
+{
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        orchestrator.metrics_registry.render(),
+    )
+        .into_response()
+}
+
+pub async fn supervision_report(
     State(orchestrator): State<Arc<Orchestrator<TotalSystemSolution>>>,
-    Json(orchestrator_request): Json<OrchestratorRequest>,
+    Path(asset): Path<Asset>,
 ) -> Result<Response>
+{
+    Ok(Json(orchestrator.supervision_report(&asset)).into_response())
+}
+
+#[derive(Deserialize)]
+pub struct SolutionChangesQuery
+{
+    #[serde(default)]
+    since: u64,
+}
+
+/// The changes of the `SystemSolution` of the asset after version `since`.
+pub async fn solution_changes(
+    State(orchestrator): State<Arc<Orchestrator<TotalSystemSolution>>>,
+    Path(asset): Path<Asset>,
+    Query(query): Query<SolutionChangesQuery>,
+) -> Result<Response, AppError>
+{
+    let solution_diffs = orchestrator.solution_changes_since(&asset, query.since)?;
+
+    Ok(Json(solution_diffs).into_response())
+}
+
+/// Applies the changes from the source systems to the asset in order.
+pub async fn source_changes(
+    State(orchestrator): State<Arc<Orchestrator<TotalSystemSolution>>>,
+    Path(asset): Path<Asset>,
+    Json(source_changes): Json<Vec<SourceChange>>,
+) -> Result<Response, AppError>
+{
+    let number_of_source_changes = source_changes.len();
+    for source_change in source_changes {
+        orchestrator.apply_source_change(source_change, &asset)?;
+    }
+
+    Ok(Json(format!(
+        "{number_of_source_changes} source changes applied to Asset {asset}"
+    ))
+    .into_response())
+}
+
+/// Validates the published `SystemSolution` of the asset.
+pub async fn consistency_report(
+    State(orchestrator): State<Arc<Orchestrator<TotalSystemSolution>>>,
+    Path(asset): Path<Asset>,
+) -> Result<Response, AppError>
+{
+    Ok(Json(orchestrator.validate_consistency(&asset)?).into_response())
+}
+
+pub async fn create_supervisor_actor(
+    State(orchestrator): State<Arc<Orchestrator<TotalSystemSolution>>>,
+    Path(asset): Path<Asset>,
+    Json(input_supervisor): Json<InputSupervisor>,
+) -> Result<Response, AppError>
 {
     let response = orchestrator
-        // So all the handling logic should reside in the Orchestrator itself.
-        .handle(orchestrator_request)
-        .await
-        .unwrap();
-    // This is horrible. I do not think that you should work on this
-    //so late in the evening. Instead focus on doing
-    //what actually Json(response)
+        .handle(OrchestratorRequest::CreateSupervisorAgent(
+            asset,
+            input_supervisor,
+        ))
+        .await?;
+
+    Ok(Json(response).into_response())
+}
+
+pub async fn create_operational_actor(
+    State(orchestrator): State<Arc<Orchestrator<TotalSystemSolution>>>,
+    Path(asset): Path<Asset>,
+    Json(input_operational): Json<InputOperational>,
+) -> Result<Response, AppError>
+{
+    let response = orchestrator
+        .handle(OrchestratorRequest::CreateOperationalAgent(
+            asset,
+            input_operational,
+        ))
+        .await?;
+
+    Ok(Json(response).into_response())
+}
+
+pub async fn delete_operational_actor(
+    State(orchestrator): State<Arc<Orchestrator<TotalSystemSolution>>>,
+    Path((asset, actor_id)): Path<(Asset, String)>,
+) -> Result<Response, AppError>
+{
+    let response = orchestrator
+        .handle(OrchestratorRequest::DeleteOperationalAgent(asset, actor_id))
+        .await?;
+
+    Ok(Json(response).into_response())
+}
+
+/// Which of the available assets have a running scheduling system.
+pub async fn asset_statuses(
+    State(orchestrator): State<Arc<Orchestrator<TotalSystemSolution>>>,
+) -> Result<Response>
+{
+    Ok(Json(orchestrator.asset_statuses()).into_response())
+}
+
+pub async fn start_asset(
+    State(orchestrator): State<Arc<Orchestrator<TotalSystemSolution>>>,
+    Path(asset): Path<Asset>,
+) -> Result<Response, AppError>
+{
+    orchestrator.start_asset(&asset)?;
+
+    Ok(Json(format!("Asset {asset} started")).into_response())
+}
+
+pub async fn stop_asset(
+    State(orchestrator): State<Arc<Orchestrator<TotalSystemSolution>>>,
+    Path(asset): Path<Asset>,
+) -> Result<Response, AppError>
+{
+    orchestrator.stop_asset(&asset)?;
+
+    Ok(Json(format!("Asset {asset} stopped")).into_response())
+}
+
+pub async fn get_days(
+    State(orchestrator): State<Arc<Orchestrator<TotalSystemSolution>>>,
+) -> Result<Response, AppError>
+{
+    let response = orchestrator.handle(OrchestratorRequest::GetPeriods).await?;
 
     Ok(Json(response).into_response())
 }
@@ -3,26 +3,109 @@ use std::sync::Arc;
 use axum::Json;
 use axum::debug_handler;
 use axum::extract::Path;
+use axum::extract::Query;
 use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::response::Response;
 use axum::response::Result;
+use ordinator_orchestrator::Asset;
 use ordinator_orchestrator::Orchestrator;
+use ordinator_orchestrator::OrchestratorRequest;
+use ordinator_orchestrator::Resources;
 use ordinator_orchestrator::TotalSystemSolution;
 use ordinator_orchestrator::WorkOrderNumber;
+use ordinator_orchestrator::work_order_status::WorkOrderStatusFilter;
+use serde::Deserialize;
+
+use crate::routes::api::AppError;
+
+#[derive(Deserialize)]
+pub struct WorkOrdersQuery
+{
+    asset: Asset,
+    #[serde(default)]
+    status: Option<String>,
+    #[serde(default)]
+    resource: Option<Resources>,
+}
+
+#[derive(Deserialize)]
+pub struct PlannerQuery
+{
+    asset: Asset,
+    user: String,
+}
 
 // This is a handler. Not a `Route` you should change that. Keep working.
 #[debug_handler]
 pub async fn get_scheduler_work_orders(
-    State(_orchestrator): State<Arc<Orchestrator<TotalSystemSolution>>>,
+    State(orchestrator): State<Arc<Orchestrator<TotalSystemSolution>>>,
     Path(i): Path<u64>,
-) -> Result<Json<WorkOrderNumber>>
+) -> Result<Response, AppError>
 {
-    // This should go into the handler, directory. There is no other way around it
-    // REMEMBER: You should only wrap method calls that the Orchestrator exposes.
-    //
-    // WARN: You are beginning to feel drained again. You should grap something to
-    // eat again.
-    Ok(Json(WorkOrderNumber(i)))
-
-    // TODO [ ] M
-    // orchestrator.get_work_order(id)
+    let response = orchestrator
+        .handle(OrchestratorRequest::GetWorkOrderStatus(WorkOrderNumber(i)))
+        .await?;
+
+    Ok(Json(response).into_response())
+}
+
+/// The work orders of the asset in every scheduling layer, filtered by a
+/// status code and a resource.
+pub async fn get_scheduler_work_orders_state(
+    State(orchestrator): State<Arc<Orchestrator<TotalSystemSolution>>>,
+    Query(query): Query<WorkOrdersQuery>,
+) -> Result<Response, AppError>
+{
+    let work_order_status_filter = WorkOrderStatusFilter {
+        status: query.status,
+        resource: query.resource,
+    };
+    let response = orchestrator
+        .handle(OrchestratorRequest::GetWorkOrdersState(
+            query.asset,
+            work_order_status_filter,
+        ))
+        .await?;
+
+    Ok(Json(response).into_response())
+}
+
+/// Every change that the planners made to the work order, in order.
+pub async fn get_scheduler_work_order_history(
+    State(orchestrator): State<Arc<Orchestrator<TotalSystemSolution>>>,
+    Path(i): Path<u64>,
+) -> Result<Response, AppError>
+{
+    let response = orchestrator
+        .handle(OrchestratorRequest::GetWorkOrderHistory(WorkOrderNumber(i)))
+        .await?;
+
+    Ok(Json(response).into_response())
+}
+
+/// Reverts the last locks, exclusions and user status codes that the user set
+/// on the asset.
+pub async fn undo_planner_command(
+    State(orchestrator): State<Arc<Orchestrator<TotalSystemSolution>>>,
+    Query(query): Query<PlannerQuery>,
+) -> Result<Response, AppError>
+{
+    let response = orchestrator
+        .handle(OrchestratorRequest::Undo(query.asset, query.user))
+        .await?;
+
+    Ok(Json(response).into_response())
+}
+
+pub async fn redo_planner_command(
+    State(orchestrator): State<Arc<Orchestrator<TotalSystemSolution>>>,
+    Query(query): Query<PlannerQuery>,
+) -> Result<Response, AppError>
+{
+    let response = orchestrator
+        .handle(OrchestratorRequest::Redo(query.asset, query.user))
+        .await?;
+
+    Ok(Json(response).into_response())
 }
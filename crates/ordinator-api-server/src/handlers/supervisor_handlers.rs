@@ -1,7 +1,6 @@
 use std::sync::Arc;
 
 use anyhow::Context;
-use anyhow::anyhow;
 use axum::Json;
 use axum::debug_handler;
 use axum::extract::Path;
@@ -9,7 +8,9 @@ use axum::extract::State;
 use axum::response::Result;
 use ordinator_orchestrator::Asset;
 use ordinator_orchestrator::Orchestrator;
+use ordinator_orchestrator::OrchestratorError;
 use ordinator_orchestrator::SupervisorRequestMessage;
+use ordinator_orchestrator::SupervisorRequestScheduling;
 use ordinator_orchestrator::SupervisorResponseMessage;
 use ordinator_orchestrator::SupervisorStatusMessage::General;
 use ordinator_orchestrator::TotalSystemSolution;
@@ -22,31 +23,80 @@ pub async fn status(
     Path((asset, supervisor_id)): Path<(Asset, String)>,
 ) -> Result<Json<SupervisorResponseMessage>, AppError>
 {
-    let lock = orchestrator.actor_registries.lock().unwrap();
-    let supervisor_agent_senders = &lock
-        .get(&asset)
-        .with_context(|| format!("Asset {asset} is not present in the ActorRegistry"))
-        .unwrap()
-        .supervisor_agent_senders;
-
-    let supervisor_id = supervisor_agent_senders
-        .keys()
-        .find(|e| e.0 == supervisor_id)
-        .ok_or(AppError::Anyhow(anyhow!("Supervisor Not found")))?;
-
-    let communication = supervisor_agent_senders
-        .get(supervisor_id)
-        .with_context(|| {
-            format!(
-                "Supervisor {supervisor_id} on Asset {asset} is not present in the ActorRegistry"
-            )
-        })?;
-
-    communication
-        .from_agent(SupervisorRequestMessage::Status(General))
-        .unwrap();
-
-    Ok(Json(communication.from_actor()))
+    let response = request_supervisor(
+        &orchestrator,
+        &asset,
+        &supervisor_id,
+        SupervisorRequestMessage::Status(General),
+    )
+    .await?;
+
+    Ok(Json(response))
+}
+
+/// Fixes or releases the delegation of a work order activity to an
+/// operational actor.
+#[debug_handler]
+pub async fn scheduling(
+    State(orchestrator): State<Arc<Orchestrator<TotalSystemSolution>>>,
+    Path((asset, supervisor_id)): Path<(Asset, String)>,
+    Json(supervisor_request_scheduling): Json<SupervisorRequestScheduling>,
+) -> Result<Json<SupervisorResponseMessage>, AppError>
+{
+    let response = request_supervisor(
+        &orchestrator,
+        &asset,
+        &supervisor_id,
+        SupervisorRequestMessage::Scheduling(supervisor_request_scheduling),
+    )
+    .await?;
+
+    Ok(Json(response))
+}
+
+async fn request_supervisor(
+    orchestrator: &Orchestrator<TotalSystemSolution>,
+    asset: &Asset,
+    supervisor_id: &str,
+    supervisor_request_message: SupervisorRequestMessage,
+) -> Result<SupervisorResponseMessage, AppError>
+{
+    // The lock on the registries is released before awaiting the reply.
+    let requester = {
+        let lock = orchestrator.actor_registries.lock().unwrap();
+        let supervisor_agent_senders = &lock
+            .get(asset)
+            .ok_or_else(|| {
+                OrchestratorError::ActorUnavailable(format!(
+                    "The scheduling system for Asset {asset}"
+                ))
+            })?
+            .supervisor_agent_senders;
+
+        let supervisor_id = supervisor_agent_senders
+            .keys()
+            .find(|e| e.0 == supervisor_id)
+            .ok_or_else(|| {
+                OrchestratorError::ActorUnavailable(format!(
+                    "Supervisor {supervisor_id} on Asset {asset}"
+                ))
+            })?;
+
+        supervisor_agent_senders
+            .get(supervisor_id)
+            .with_context(|| {
+                format!(
+                    "Supervisor {supervisor_id} on Asset {asset} is not present in the ActorRegistry"
+                )
+            })?
+            .requester()
+    };
+
+    let response = requester
+        .request(supervisor_request_message, orchestrator.request_timeout())
+        .await?;
+
+    Ok(response)
 }
 
 // _ISSUE_ #000 means unassigned
@@ -8,10 +8,13 @@ use axum::response::Response;
 use axum::response::Result;
 use ordinator_orchestrator::Asset;
 use ordinator_orchestrator::Orchestrator;
+use ordinator_orchestrator::OrchestratorError;
 use ordinator_orchestrator::SystemSolutions;
 use ordinator_orchestrator::TacticalRequestMessage;
 use ordinator_orchestrator::TacticalStatusMessage;
 
+use crate::routes::api::AppError;
+
 // So each handler should construct a specific message. That is the key point
 // here. This function uses the orchestrator to send any kind of message. Which
 // way is the correct one here?
@@ -30,12 +33,17 @@ where
 {
     let message = TacticalRequestMessage::Status(TacticalStatusMessage::General);
 
-    let hash_map = orchestrator.actor_registries.lock().unwrap();
-    let actor_registry_for_asset = &hash_map
-        .get(&asset)
+    let requester = orchestrator
+        .actor_registries
+        .lock()
         .unwrap()
-        // .with_context(|| format!("Asset {} not initialized", &asset))?
-        .tactical_agent_sender;
+        .get(&asset)
+        .ok_or_else(|| {
+            OrchestratorError::ActorUnavailable(format!("The scheduling system for Asset {asset}"))
+        })
+        .map_err(AppError::from)?
+        .tactical_agent_sender
+        .requester();
 
     // We should use the
     // ESSAY: How to handle the string here? I think that the best approach is to
@@ -43,13 +51,10 @@ where
     // an interface to the `Actor` that makes it so that only the
     // `ActorMessage::Request` can be chosen. That means that what really has to
     // change is the way that `Communication is implemented`
-    actor_registry_for_asset.from_agent(message).unwrap();
-
-    let response = actor_registry_for_asset
-        .receiver_from_actor
-        .recv()
-        .unwrap()
-        .unwrap();
+    let response = requester
+        .request(message, orchestrator.request_timeout())
+        .await
+        .map_err(AppError::from)?;
 
     Ok(Json(response).into_response())
 }
@@ -15,7 +15,7 @@ use anyhow::Context;
 use anyhow::Result;
 use axum::Router;
 use axum::routing::get;
-use ordinator_orchestrator::Asset;
+use handlers::orchestrator_handlers::metrics;
 // use std::fs::File;
 // use std::io::Read;
 use ordinator_orchestrator::Orchestrator;
@@ -37,10 +37,10 @@ async fn main() -> Result<()>
         JoinHandle<Result<()>>,
     ) = Orchestrator::new().context("Orchestrator could not be created")?;
 
-    // WARN: Manually add `Asset`s here. Everything added here should be done from
-    // the API in actual production. So this is only a temporary solution.
-
-    orchestrator.asset_factory(&Asset::DF)?;
+    // The remaining available `Asset`s are started through the API.
+    orchestrator
+        .start_configured_assets()
+        .context("Could not start the configured assets")?;
 
     // WARN
 
@@ -86,6 +86,7 @@ async fn main() -> Result<()>
         .nest_service("/scheduler", scheduler_files)
         .nest_service("/supervisor", supervisor_files)
         .route("/hello", get(|| async { "Hello, world!" }))
+        .route("/metrics", get(metrics))
         .with_state(orchestrator);
 
     let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
@@ -3,6 +3,9 @@ pub mod v1;
 use axum::Json;
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
+use ordinator_orchestrator::ActorError;
+use ordinator_orchestrator::OrchestratorError;
+use ordinator_orchestrator::RequestError;
 use serde_json::json;
 use thiserror::Error;
 
@@ -11,18 +14,283 @@ pub enum AppError
 {
     #[error(transparent)]
     Anyhow(#[from] anyhow::Error),
+    #[error(transparent)]
+    Request(#[from] RequestError),
+    #[error(transparent)]
+    Actor(#[from] ActorError),
+    #[error(transparent)]
+    Orchestrator(#[from] OrchestratorError),
 }
 
-impl IntoResponse for AppError
+impl AppError
 {
-    fn into_response(self) -> axum::response::Response
+    fn status_code_and_code(&self) -> (StatusCode, &'static str)
     {
         match self {
-            AppError::Anyhow(error) => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({ "error" : error.to_string()})),
-            )
-                .into_response(),
+            AppError::Anyhow(error) => error
+                .chain()
+                .find_map(|cause| {
+                    cause
+                        .downcast_ref::<ActorError>()
+                        .map(actor_error_status_code_and_code)
+                        .or_else(|| {
+                            cause
+                                .downcast_ref::<OrchestratorError>()
+                                .map(orchestrator_error_status_code_and_code)
+                        })
+                })
+                .unwrap_or((StatusCode::INTERNAL_SERVER_ERROR, "internal")),
+            AppError::Request(error) => match error.actor_error() {
+                Some(actor_error) => actor_error_status_code_and_code(actor_error),
+                None => {
+                    let status_code = match error {
+                        RequestError::ActorStopped => StatusCode::SERVICE_UNAVAILABLE,
+                        RequestError::TimedOut(_) => StatusCode::GATEWAY_TIMEOUT,
+                        RequestError::Rejected(_) => StatusCode::UNPROCESSABLE_ENTITY,
+                    };
+                    (status_code, error.code())
+                }
+            },
+            AppError::Actor(error) => actor_error_status_code_and_code(error),
+            AppError::Orchestrator(error) => orchestrator_error_status_code_and_code(error),
         }
     }
 }
+
+fn actor_error_status_code_and_code(actor_error: &ActorError) -> (StatusCode, &'static str)
+{
+    let status_code = match actor_error {
+        ActorError::InvalidRequest(_) => StatusCode::BAD_REQUEST,
+        ActorError::UnknownWorkOrder(_) | ActorError::UnknownPeriod(_) => StatusCode::NOT_FOUND,
+        ActorError::InfeasibleLock(_) => StatusCode::CONFLICT,
+        ActorError::InvariantViolated(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status_code, actor_error.code())
+}
+
+fn orchestrator_error_status_code_and_code(
+    orchestrator_error: &OrchestratorError,
+) -> (StatusCode, &'static str)
+{
+    let status_code = match orchestrator_error {
+        OrchestratorError::InvalidRequest(_) => StatusCode::BAD_REQUEST,
+        OrchestratorError::UnknownWorkOrder(_) => StatusCode::NOT_FOUND,
+        OrchestratorError::ActorUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+        OrchestratorError::UnknownActor(_) => StatusCode::NOT_FOUND,
+        OrchestratorError::IllegalTransition(_) => StatusCode::CONFLICT,
+        OrchestratorError::InvariantViolated(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    (status_code, orchestrator_error.code())
+}
+
+impl IntoResponse for AppError
+{
+    fn into_response(self) -> axum::response::Response
+    {
+        let (status_code, code) = self.status_code_and_code();
+        let message = match &self {
+            AppError::Anyhow(error) => format!("{error:#}"),
+            AppError::Request(error) => error.to_string(),
+            AppError::Actor(error) => error.to_string(),
+            AppError::Orchestrator(error) => error.to_string(),
+        };
+
+        (
+            status_code,
+            Json(json!({ "error" : message, "code" : code })),
+        )
+            .into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use anyhow::Context;
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+    use ordinator_orchestrator::ActorError;
+    use ordinator_orchestrator::ActorLifecycleRequest;
+    use ordinator_orchestrator::Asset;
+    use ordinator_orchestrator::Id;
+    use ordinator_orchestrator::OrchestratorError;
+    use ordinator_orchestrator::RequestError;
+    use ordinator_orchestrator::WorkOrderNumber;
+
+    use super::AppError;
+
+    async fn status_code_and_body(app_error: AppError) -> (StatusCode, serde_json::Value)
+    {
+        let response = app_error.into_response();
+        let status_code = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status_code, serde_json::from_slice(&body).unwrap())
+    }
+
+    async fn assert_status_code_and_code(app_error: AppError, status_code: StatusCode, code: &str)
+    {
+        let (actual_status_code, body) = status_code_and_body(app_error).await;
+        assert_eq!(actual_status_code, status_code);
+        assert_eq!(body["code"], code);
+        assert!(body["error"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_request_is_bad_request()
+    {
+        assert_status_code_and_code(
+            ActorError::InvalidRequest("no work orders".to_string()).into(),
+            StatusCode::BAD_REQUEST,
+            "invalid_request",
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_unknown_work_order_is_not_found()
+    {
+        assert_status_code_and_code(
+            ActorError::UnknownWorkOrder(WorkOrderNumber(2100000001)).into(),
+            StatusCode::NOT_FOUND,
+            "unknown_work_order",
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_unknown_period_is_not_found()
+    {
+        assert_status_code_and_code(
+            ActorError::UnknownPeriod("2023-W47-48".to_string()).into(),
+            StatusCode::NOT_FOUND,
+            "unknown_period",
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_infeasible_lock_is_conflict()
+    {
+        assert_status_code_and_code(
+            ActorError::InfeasibleLock("no capacity".to_string()).into(),
+            StatusCode::CONFLICT,
+            "infeasible_lock",
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_invariant_violated_is_internal_server_error()
+    {
+        assert_status_code_and_code(
+            ActorError::InvariantViolated("loadings do not match".to_string()).into(),
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "invariant_violated",
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_orchestrator_errors()
+    {
+        assert_status_code_and_code(
+            OrchestratorError::InvalidRequest("DF is not a valid Asset".to_string()).into(),
+            StatusCode::BAD_REQUEST,
+            "invalid_request",
+        )
+        .await;
+        assert_status_code_and_code(
+            OrchestratorError::UnknownWorkOrder(WorkOrderNumber(2100000001)).into(),
+            StatusCode::NOT_FOUND,
+            "unknown_work_order",
+        )
+        .await;
+        assert_status_code_and_code(
+            OrchestratorError::ActorUnavailable("OP-01-001".to_string()).into(),
+            StatusCode::SERVICE_UNAVAILABLE,
+            "actor_unavailable",
+        )
+        .await;
+        assert_status_code_and_code(
+            OrchestratorError::UnknownActor("OP-01-001 on Asset DF".to_string()).into(),
+            StatusCode::NOT_FOUND,
+            "unknown_actor",
+        )
+        .await;
+        assert_status_code_and_code(
+            OrchestratorError::IllegalTransition("OP-01-001 is stopped".to_string()).into(),
+            StatusCode::CONFLICT,
+            "illegal_transition",
+        )
+        .await;
+        assert_status_code_and_code(
+            OrchestratorError::InvariantViolated("missing solution".to_string()).into(),
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "invariant_violated",
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_errors_are_found_behind_context()
+    {
+        let error = Err::<(), _>(ActorError::InfeasibleLock("no capacity".to_string()))
+            .context("Could not lock the work order")
+            .unwrap_err();
+        assert_status_code_and_code(
+            RequestError::Rejected(error).into(),
+            StatusCode::CONFLICT,
+            "infeasible_lock",
+        )
+        .await;
+
+        let error = Err::<(), _>(OrchestratorError::ActorUnavailable("OP-01-001".to_string()))
+            .context("Could not update the options")
+            .unwrap_err();
+        assert_status_code_and_code(
+            error.into(),
+            StatusCode::SERVICE_UNAVAILABLE,
+            "actor_unavailable",
+        )
+        .await;
+
+        assert_status_code_and_code(
+            anyhow::anyhow!("unexpected").into(),
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "internal",
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_failing_lifecycle_requests()
+    {
+        let id = Id::new("OP-01-001", vec![], vec![Asset::DF]);
+
+        let error = ActorLifecycleRequest::Resume
+            .check_transition(&id, false)
+            .context("Could not apply Resume")
+            .unwrap_err();
+        assert_status_code_and_code(error.into(), StatusCode::CONFLICT, "illegal_transition").await;
+
+        assert!(
+            ActorLifecycleRequest::Restart
+                .check_transition(&id, false)
+                .is_ok()
+        );
+        assert!(
+            ActorLifecycleRequest::Pause
+                .check_transition(&id, true)
+                .is_ok()
+        );
+
+        let error = Err::<(), _>(OrchestratorError::UnknownActor(
+            "OP-99-999 on Asset DF".to_string(),
+        ))
+        .context("Could not apply Stop")
+        .unwrap_err();
+        assert_status_code_and_code(error.into(), StatusCode::NOT_FOUND, "unknown_actor").await;
+    }
+}
@@ -1,13 +1,28 @@
 use std::sync::Arc;
 
 use axum::Router;
+use axum::routing::delete;
 use axum::routing::get;
+use axum::routing::post;
 use ordinator_orchestrator::Orchestrator;
 use ordinator_orchestrator::TotalSystemSolution;
 
+use crate::handlers::orchestrator_handlers::actor_lifecycle;
+use crate::handlers::orchestrator_handlers::actor_options_history;
+use crate::handlers::orchestrator_handlers::asset_statuses;
+use crate::handlers::orchestrator_handlers::consistency_report;
+use crate::handlers::orchestrator_handlers::create_operational_actor;
+use crate::handlers::orchestrator_handlers::create_supervisor_actor;
+use crate::handlers::orchestrator_handlers::delete_operational_actor;
 use crate::handlers::orchestrator_handlers::get_days;
 use crate::handlers::orchestrator_handlers::orchestrator_status;
 use crate::handlers::orchestrator_handlers::scheduler_excel_export;
+use crate::handlers::orchestrator_handlers::solution_changes;
+use crate::handlers::orchestrator_handlers::source_changes;
+use crate::handlers::orchestrator_handlers::start_asset;
+use crate::handlers::orchestrator_handlers::stop_asset;
+use crate::handlers::orchestrator_handlers::supervision_report;
+use crate::handlers::orchestrator_handlers::update_actor_options;
 
 pub async fn export_xlsx(
     state: Arc<Orchestrator<TotalSystemSolution>>,
@@ -26,5 +41,24 @@ pub async fn orchestrator_api_scope(
     Router::new()
         .route("/", get(orchestrator_status))
         .route("/number_of_days", get(get_days))
+        .route("/assets", get(asset_statuses))
+        .route("/assets/{asset}/start", post(start_asset))
+        .route("/assets/{asset}/stop", post(stop_asset))
+        .route("/actor/{asset}/supervisor", post(create_supervisor_actor))
+        .route("/actor/{asset}/operational", post(create_operational_actor))
+        .route(
+            "/actor/{asset}/operational/{actor_id}",
+            delete(delete_operational_actor),
+        )
+        .route(
+            "/actor/{asset}/{actor_id}/{lifecycle}",
+            post(actor_lifecycle),
+        )
+        .route("/options/{asset}", get(actor_options_history))
+        .route("/options/{asset}/{actor_id}", post(update_actor_options))
+        .route("/supervision/{asset}", get(supervision_report))
+        .route("/solution_changes/{asset}", get(solution_changes))
+        .route("/consistency/{asset}", get(consistency_report))
+        .route("/source_changes/{asset}", post(source_changes))
         .with_state(state)
 }
@@ -2,10 +2,15 @@ use std::sync::Arc;
 
 use axum::Router;
 use axum::routing::get;
+use axum::routing::post;
 use ordinator_orchestrator::Orchestrator;
 use ordinator_orchestrator::TotalSystemSolution;
 
+use crate::handlers::strategic_handlers::get_scheduler_work_order_history;
 use crate::handlers::strategic_handlers::get_scheduler_work_orders;
+use crate::handlers::strategic_handlers::get_scheduler_work_orders_state;
+use crate::handlers::strategic_handlers::redo_planner_command;
+use crate::handlers::strategic_handlers::undo_planner_command;
 
 // TODO [x]
 // The main idea is to replace all the.
@@ -15,7 +20,14 @@ pub async fn scheduler_nest(
 {
     Router::new()
         .without_v07_checks()
+        .route("/work_orders", get(get_scheduler_work_orders_state))
         .route("/work_orders/{id}", get(get_scheduler_work_orders))
+        .route(
+            "/work_orders/{id}/history",
+            get(get_scheduler_work_order_history),
+        )
+        .route("/undo", post(undo_planner_command))
+        .route("/redo", post(redo_planner_command))
         .with_state(state)
 }
 
@@ -2,9 +2,11 @@ use std::sync::Arc;
 
 use axum::Router;
 use axum::routing::get;
+use axum::routing::post;
 use ordinator_orchestrator::Orchestrator;
 use ordinator_orchestrator::TotalSystemSolution;
 
+use crate::handlers::supervisor_handlers::scheduling;
 use crate::handlers::supervisor_handlers::status;
 
 pub async fn supervisor_routes(
@@ -13,6 +15,7 @@ pub async fn supervisor_routes(
 {
     Router::new()
         .route("/{asset}/{supervisor_id}", get(status))
+        .route("/{asset}/{supervisor_id}/scheduling", post(scheduling))
         .with_state(state)
 
     // TODO [ ] Put these into the handler
@@ -0,0 +1,45 @@
+use anyhow::Result;
+use anyhow::ensure;
+use ordinator_scheduling_environment::Asset;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AssetsConfiguration
+{
+    pub available: Vec<Asset>,
+    pub started: Vec<Asset>,
+}
+
+impl AssetsConfiguration
+{
+    pub fn validate(&self) -> Result<()>
+    {
+        for asset in &self.started {
+            ensure!(
+                self.available.contains(asset),
+                "Asset {asset} is started but not available in the assets configuration"
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use ordinator_scheduling_environment::Asset;
+
+    use super::AssetsConfiguration;
+
+    #[test]
+    fn test_started_assets_are_available()
+    {
+        let mut assets_configuration: AssetsConfiguration =
+            toml::from_str("available = [\"DF\", \"HB\"]\nstarted = [\"DF\"]").unwrap();
+        assert!(assets_configuration.validate().is_ok());
+
+        assets_configuration.started.push(Asset::TE);
+        assert!(assets_configuration.validate().is_err());
+    }
+}
@@ -0,0 +1,19 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CheckpointConfiguration
+{
+    pub enabled: bool,
+    pub interval_seconds: u64,
+}
+
+impl CheckpointConfiguration
+{
+    pub fn interval(&self) -> Duration
+    {
+        Duration::from_secs(self.interval_seconds)
+    }
+}
@@ -0,0 +1,18 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CommunicationConfiguration
+{
+    pub request_timeout_milliseconds: u64,
+}
+
+impl CommunicationConfiguration
+{
+    pub fn request_timeout(&self) -> Duration
+    {
+        Duration::from_millis(self.request_timeout_milliseconds)
+    }
+}
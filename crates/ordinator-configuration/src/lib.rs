@@ -1,19 +1,33 @@
+pub mod assets;
+pub mod checkpoint;
+pub mod communication;
 mod material;
 mod resources;
-mod throttling;
+pub mod seed;
+pub mod supervision;
+pub mod throttling;
 pub mod time_input;
 pub mod toml_baptiste;
 mod user_interface;
+pub mod validation;
 
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use anyhow::Context;
 use anyhow::Result;
 use arc_swap::ArcSwap;
+use assets::AssetsConfiguration;
+use checkpoint::CheckpointConfiguration;
+use communication::CommunicationConfiguration;
 use ordinator_scheduling_environment::SystemConfigurationTrait;
+use seed::Seed;
+use supervision::RestartPolicies;
 use throttling::Throttling;
 use toml_baptiste::BaptisteToml;
 use user_interface::EventColors;
+use validation::ValidationConfiguration;
 
 // QUESTION
 // How should this be handled?
@@ -28,12 +42,25 @@ use user_interface::EventColors;
 // WARN
 // Remember! You have a single source of all configurations here,
 // so there is no reason to question that in the system.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SystemConfigurations {
     pub data_locations: BaptisteToml,
+    pub assets: AssetsConfiguration,
     pub throttling: Throttling,
+    pub restart_policies: RestartPolicies,
+    pub communication: CommunicationConfiguration,
+    pub checkpoint: CheckpointConfiguration,
+    pub validation: ValidationConfiguration,
     pub user_interface: EventColors,
     pub database_config: PathBuf,
+    pub master_seed: u64,
+    /// The number of LNS iterations after which every actor pauses. It is
+    /// only set by a batch solve.
+    pub iteration_limit: Option<u64>,
+    /// The actors of an asset take turns on their LNS iterations in a fixed
+    /// order, so that the run is reproducible from the `master_seed`. It is
+    /// only set by a batch solve.
+    pub lockstep: bool,
 }
 
 impl SystemConfigurationTrait for SystemConfigurations {}
@@ -43,32 +70,81 @@ impl SystemConfigurationTrait for SystemConfigurations {}
 // revisit it.
 impl SystemConfigurations {
     pub fn read_all_configs() -> Result<Arc<ArcSwap<SystemConfigurations>>> {
+        let database_path_string =
+            &dotenvy::var("DATABASE_PATH").expect("Could not read database path");
+
+        Self::read_configs(
+            Path::new("./configuration"),
+            Path::new(database_path_string),
+        )
+    }
+
+    /// Like `read_all_configs()`, but from the `configuration_directory` and
+    /// with the database at `database_path`.
+    pub fn read_configs(
+        configuration_directory: &Path,
+        database_path: &Path,
+    ) -> Result<Arc<ArcSwap<SystemConfigurations>>> {
+        let read = |file: &str| {
+            let path = configuration_directory.join(file);
+            std::fs::read_to_string(&path)
+                .with_context(|| format!("Could not read {}", path.display()))
+        };
+
         let baptiste_data_locations_contents =
-            std::fs::read_to_string("./configuration/data_locations/baptiste_data_locations.toml")
-                .unwrap();
+            read("data_locations/baptiste_data_locations.toml").unwrap();
         let data_locations = toml::from_str(&baptiste_data_locations_contents).unwrap();
 
-        let throttling_contents =
-            std::fs::read_to_string("./configuration/throttling/throttling.toml").unwrap();
+        let assets_contents = read("assets/assets.toml")?;
+        let assets: AssetsConfiguration =
+            toml::from_str(&assets_contents).context("Could not parse assets.toml")?;
+        assets.validate()?;
+
+        let throttling_contents = read("throttling/throttling.toml").unwrap();
         let throttling: Throttling = toml::from_str(&throttling_contents).unwrap();
 
-        let event_colors_contents =
-            std::fs::read_to_string("./configuration/user_interface/event_colors.toml").unwrap();
-        let event_colors: EventColors = toml::from_str(&event_colors_contents).unwrap();
+        let restart_policies_contents = read("supervision/supervision.toml")?;
+        let restart_policies: RestartPolicies = toml::from_str(&restart_policies_contents)
+            .context("Could not parse supervision.toml")?;
 
-        let database_path_string =
-            &dotenvy::var("DATABASE_PATH").expect("Could not read database path");
+        let communication_contents = read("communication/communication.toml")?;
+        let communication: CommunicationConfiguration = toml::from_str(&communication_contents)
+            .context("Could not parse communication.toml")?;
+
+        let checkpoint_contents = read("checkpoint/checkpoint.toml")?;
+        let checkpoint: CheckpointConfiguration =
+            toml::from_str(&checkpoint_contents).context("Could not parse checkpoint.toml")?;
+
+        let validation_contents = read("validation/validation.toml")?;
+        let validation: ValidationConfiguration =
+            toml::from_str(&validation_contents).context("Could not parse validation.toml")?;
+
+        let event_colors_contents = read("user_interface/event_colors.toml").unwrap();
+        let event_colors: EventColors = toml::from_str(&event_colors_contents).unwrap();
 
-        let database_path = std::path::Path::new(database_path_string);
+        // The seed file is optional, a missing file gives a random master seed.
+        let seed: Seed = match read("seed/seed.toml") {
+            Ok(seed_contents) => toml::from_str(&seed_contents)?,
+            Err(_) => Seed::default(),
+        };
+        let master_seed = seed.master_seed.unwrap_or_else(rand::random);
 
         // I believe that it is the best appraoch here to make sure that the
         // `Configurations` are always created wrapped. Then you will never
         // make the mistake, of accessing wild and stray configurations.
         Ok(Arc::new(ArcSwap::new(Arc::new(SystemConfigurations {
             data_locations,
+            assets,
             throttling,
+            restart_policies,
+            communication,
+            checkpoint,
+            validation,
             user_interface: event_colors,
             database_config: database_path.to_owned(),
+            master_seed,
+            iteration_limit: None,
+            lockstep: false,
         }))))
         // TODO [ ]
         // Integrate this if you have issues with data initialization
@@ -0,0 +1,62 @@
+use ordinator_scheduling_environment::worker_environment::resources::Id;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// The master seed of the system. Leaving out `master_seed` in the toml file
+/// makes the system draw a random seed at startup, which is logged so that the
+/// run can be reproduced afterwards.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Seed
+{
+    pub master_seed: Option<u64>,
+}
+
+/// Derives the seed of a single actor from the master seed and the actor
+/// `Id`. This has to be stable across runs, builds and platforms, so the
+/// `DefaultHasher` is not used here.
+pub fn derive_actor_seed(master_seed: u64, id: &Id) -> u64
+{
+    // FNV-1a over the `Id` followed by a splitmix64 finalizer on the
+    // combination with the master seed.
+    let id_hash = id
+        .to_string()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+
+    let mut z = master_seed ^ id_hash.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests
+{
+    use ordinator_scheduling_environment::Asset;
+    use ordinator_scheduling_environment::worker_environment::resources::Id;
+    use ordinator_scheduling_environment::worker_environment::resources::Resources;
+
+    use super::derive_actor_seed;
+
+    #[test]
+    fn test_derive_actor_seed_is_deterministic_and_distinct()
+    {
+        let strategic = Id::new("StrategicActor", vec![], vec![Asset::Test]);
+        let operational = Id::new("OP-01-001", vec![Resources::MtnMech], vec![Asset::Test]);
+
+        assert_eq!(
+            derive_actor_seed(42, &strategic),
+            derive_actor_seed(42, &strategic)
+        );
+        assert_ne!(
+            derive_actor_seed(42, &strategic),
+            derive_actor_seed(42, &operational)
+        );
+        assert_ne!(
+            derive_actor_seed(42, &strategic),
+            derive_actor_seed(43, &strategic)
+        );
+    }
+}
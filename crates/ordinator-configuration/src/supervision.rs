@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// The restart policies of the `Orchestrator` for each kind of actor.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RestartPolicies
+{
+    pub strategic: RestartPolicy,
+    pub tactical: RestartPolicy,
+    pub supervisor: RestartPolicy,
+    pub operational: RestartPolicy,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartStrategy
+{
+    OneForOne,
+    OneForAll,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RestartPolicy
+{
+    pub strategy: RestartStrategy,
+    pub max_restarts: usize,
+    pub window_seconds: u64,
+    pub initial_backoff_milliseconds: u64,
+    pub max_backoff_milliseconds: u64,
+}
+
+impl RestartPolicy
+{
+    pub fn window(&self) -> Duration
+    {
+        Duration::from_secs(self.window_seconds)
+    }
+
+    /// The backoff doubles for every restart that is already inside of the
+    /// window.
+    pub fn backoff(&self, restarts_in_window: usize) -> Duration
+    {
+        let backoff = self
+            .initial_backoff_milliseconds
+            .saturating_mul(1_u64 << restarts_in_window.min(32))
+            .min(self.max_backoff_milliseconds);
+
+        Duration::from_millis(backoff)
+    }
+}
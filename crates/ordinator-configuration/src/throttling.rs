@@ -3,27 +3,112 @@ use anyhow::anyhow;
 use serde::Deserialize;
 use serde::Serialize;
 
-#[derive(Serialize, Deserialize, Debug)]
+/// Used for actors whose id does not map to a configured throttling.
+pub const DEFAULT_THROTTLING_MILLISECONDS: u64 = 100;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Throttling
 {
     strategic_throttling: u64,
     tactical_throttling: u64,
     supervisor_throttling: u64,
     operational_throttling: u64,
+    pub idle_iterations: u64,
+    pub max_idle_milliseconds: u64,
+    pub cpu_budget: CpuBudgetConfiguration,
 }
 
-impl Throttling
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CpuBudgetConfiguration
 {
-    pub fn get_throttling(&self, actor: &str) -> Result<u64>
+    pub cores_per_asset: f64,
+    pub strategic_share: f64,
+    pub tactical_share: f64,
+    pub supervisor_share: f64,
+    pub operational_share: f64,
+}
+
+/// The kinds of actors that share a throttling.
+#[derive(Clone, Copy)]
+enum ThrottlingKind
+{
+    Strategic,
+    Tactical,
+    Supervisor,
+    Operational,
+}
+
+impl ThrottlingKind
+{
+    fn from_actor(actor: &str) -> Result<Self>
     {
         match actor {
-            s if s.to_lowercase().starts_with("strategic") => Ok(self.strategic_throttling),
-            s if s.to_lowercase().starts_with("tactical") => Ok(self.tactical_throttling),
-            s if s.to_lowercase().starts_with("main") => Ok(self.supervisor_throttling),
-            s if s.to_lowercase().starts_with("supervisor") => Ok(self.supervisor_throttling),
-            s if s.to_lowercase().starts_with("operational") => Ok(self.operational_throttling),
-            s if s.starts_with("OP") => Ok(self.operational_throttling),
+            s if s.to_lowercase().starts_with("strategic") => Ok(ThrottlingKind::Strategic),
+            s if s.to_lowercase().starts_with("tactical") => Ok(ThrottlingKind::Tactical),
+            s if s.to_lowercase().starts_with("main") => Ok(ThrottlingKind::Supervisor),
+            s if s.to_lowercase().starts_with("supervisor") => Ok(ThrottlingKind::Supervisor),
+            s if s.to_lowercase().starts_with("operational") => Ok(ThrottlingKind::Operational),
+            s if s.starts_with("OP") => Ok(ThrottlingKind::Operational),
             _ => Err(anyhow!("wrong key to access actor throttling logic")),
         }
     }
 }
+
+impl Throttling
+{
+    pub fn get_throttling(&self, actor: &str) -> Result<u64>
+    {
+        Ok(match ThrottlingKind::from_actor(actor)? {
+            ThrottlingKind::Strategic => self.strategic_throttling,
+            ThrottlingKind::Tactical => self.tactical_throttling,
+            ThrottlingKind::Supervisor => self.supervisor_throttling,
+            ThrottlingKind::Operational => self.operational_throttling,
+        })
+    }
+
+    /// Sets the throttling of every actor of the same kind as `actor`.
+    pub fn set_throttling(&mut self, actor: &str, throttling: u64) -> Result<()>
+    {
+        let throttling_field = match ThrottlingKind::from_actor(actor)? {
+            ThrottlingKind::Strategic => &mut self.strategic_throttling,
+            ThrottlingKind::Tactical => &mut self.tactical_throttling,
+            ThrottlingKind::Supervisor => &mut self.supervisor_throttling,
+            ThrottlingKind::Operational => &mut self.operational_throttling,
+        };
+        *throttling_field = throttling;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::CpuBudgetConfiguration;
+    use super::Throttling;
+
+    #[test]
+    fn test_set_throttling_uses_the_same_keys_as_get_throttling()
+    {
+        let mut throttling = Throttling {
+            strategic_throttling: 100,
+            tactical_throttling: 100,
+            supervisor_throttling: 100,
+            operational_throttling: 100,
+            idle_iterations: 50,
+            max_idle_milliseconds: 5000,
+            cpu_budget: CpuBudgetConfiguration {
+                cores_per_asset: 2.0,
+                strategic_share: 0.35,
+                tactical_share: 0.25,
+                supervisor_share: 0.1,
+                operational_share: 0.3,
+            },
+        };
+
+        throttling.set_throttling("OP-01-001", 250).unwrap();
+
+        assert_eq!(throttling.get_throttling("operational").unwrap(), 250);
+        assert_eq!(throttling.get_throttling("main").unwrap(), 100);
+        assert!(throttling.set_throttling("unknown", 250).is_err());
+    }
+}
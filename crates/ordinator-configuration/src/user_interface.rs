@@ -5,7 +5,7 @@ use serde::Deserialize;
 use serde::Serialize;
 
 // This should be a configuration type not a backend type!
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct EventColors {
     wrench_time: (u8, u8, u8),
     work_break: (u8, u8, u8),
@@ -0,0 +1,18 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ValidationConfiguration
+{
+    pub interval_seconds: u64,
+}
+
+impl ValidationConfiguration
+{
+    pub fn interval(&self) -> Duration
+    {
+        Duration::from_secs(self.interval_seconds)
+    }
+}
@@ -1,9 +1,20 @@
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 
+use anyhow::Context;
+use anyhow::Result;
+use chrono::DateTime;
+use chrono::Utc;
+use ordinator_orchestrator_actor_traits::delegate::Delegate;
+use ordinator_scheduling_environment::audit_log::SchedulingEnvironmentEvent;
+use ordinator_scheduling_environment::time_environment::MaterialToPeriod;
 use ordinator_scheduling_environment::time_environment::day::Day;
 use ordinator_scheduling_environment::time_environment::period::Period;
+use ordinator_scheduling_environment::work_order::WorkOrder;
+use ordinator_scheduling_environment::work_order::WorkOrderConfigurations;
 use ordinator_scheduling_environment::work_order::WorkOrderNumber;
+use ordinator_scheduling_environment::work_order::operation::ActivityNumber;
 use ordinator_scheduling_environment::work_order::operation::Work;
 use ordinator_scheduling_environment::work_order::work_order_analytic::status_codes::SystemStatusCodes;
 use ordinator_scheduling_environment::work_order::work_order_analytic::status_codes::UserStatusCodes;
@@ -26,6 +37,8 @@ use serde::Serialize;
 pub enum OrchestratorResponse
 {
     WorkOrderStatus(WorkOrdersStatus),
+    WorkOrderHistory(Vec<SchedulingEnvironmentEvent>),
+    SchedulingEnvironmentEvent(SchedulingEnvironmentEvent),
     RequestStatus(String),
     Periods(Vec<Period>),
     Days(Vec<Day>),
@@ -56,16 +69,45 @@ pub struct WorkOrderResponse
     api_solution: ApiSolution,
 }
 
-#[derive(Serialize)]
+impl WorkOrderResponse
+{
+    pub fn new(
+        work_order: &WorkOrder,
+        api_solution: ApiSolution,
+        periods: &[Period],
+        work_order_configurations: &WorkOrderConfigurations,
+        material_to_period: &MaterialToPeriod,
+    ) -> Result<Self>
+    {
+        Ok(Self {
+            earliest_period: work_order
+                .earliest_allowed_start_period(periods, material_to_period)
+                .clone(),
+            work_order_info: work_order.work_order_info.clone(),
+            vendor: work_order.vendor(),
+            weight: work_order
+                .work_order_value(work_order_configurations)
+                .with_context(|| format!("Could not calculate the weight of {work_order}"))?,
+            work_order_work_load: work_order.work_order_load()?,
+            system_status_codes: work_order.work_order_analytic.system_status_codes.clone(),
+            user_status_codes: work_order.work_order_analytic.user_status_codes.clone(),
+            api_solution,
+        })
+    }
+}
+
+/// The work order in the solution of every scheduling layer. The supervisor
+/// and operational solutions are keyed by the technician of each activity.
+#[derive(Serialize, Default)]
 pub struct ApiSolution
 {
-    pub strategic: String,   //ApiStrategic,
-    pub tactical: String,    //ApiTactical,
-    pub supervisor: String,  //HashMap<Id, ApiSupervisor>,
-    pub operational: String, //HashMap<Id, ApiOperational>,
+    pub strategic: StrategicApiSolution,
+    pub tactical: BTreeMap<ActivityNumber, Day>,
+    pub supervisor: BTreeMap<ActivityNumber, BTreeMap<String, Delegate>>,
+    pub operational: BTreeMap<ActivityNumber, BTreeMap<String, OperationalApiSolution>>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Default)]
 pub struct StrategicApiSolution
 {
     pub solution: Option<Period>,
@@ -74,31 +116,10 @@ pub struct StrategicApiSolution
 }
 
 #[derive(Serialize)]
-#[allow(dead_code)]
-struct ApiStrategic
-{
-    solution_data: String,
-}
-
-#[derive(Serialize)]
-#[allow(dead_code)]
-struct ApiTactical
-{
-    solution_data: String,
-}
-
-#[derive(Serialize)]
-#[allow(dead_code)]
-struct ApiSupervisor
-{
-    solution_data: String,
-}
-
-#[derive(Serialize)]
-#[allow(dead_code)]
-struct ApiOperational
+pub struct OperationalApiSolution
 {
-    solution_data: String,
+    pub start: DateTime<Utc>,
+    pub finish: DateTime<Utc>,
 }
 
 // TODO [ ]
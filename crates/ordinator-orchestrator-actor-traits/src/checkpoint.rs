@@ -0,0 +1,233 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::sync::Arc;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::ensure;
+use chrono::DateTime;
+use chrono::Utc;
+use ordinator_scheduling_environment::Asset;
+use ordinator_scheduling_environment::SchedulingEnvironment;
+use redb::Database;
+use redb::ReadableDatabase;
+use redb::TableDefinition;
+use redb::TableError;
+use serde::Deserialize;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// Has to be bumped whenever the layout of the solutions changes, so that an
+/// old checkpoint is ignored instead of misread.
+pub const CHECKPOINT_FORMAT_VERSION: u32 = 2;
+
+/// A snapshot of the `SystemSolution` of an asset. It holds the solutions of
+/// all the actors of the asset, so every actor can be warm started from it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Checkpoint<Ss>
+{
+    pub format_version: u32,
+    pub asset: Asset,
+    pub created_at: DateTime<Utc>,
+    pub scheduling_environment_fingerprint: u64,
+    pub system_solution: Ss,
+}
+
+impl<Ss> Checkpoint<Ss>
+{
+    /// A stale checkpoint was made from another `SchedulingEnvironment`, so
+    /// its solutions have to be reconciled with the current `Parameters`.
+    pub fn is_stale(&self, scheduling_environment_fingerprint: u64) -> bool
+    {
+        self.scheduling_environment_fingerprint != scheduling_environment_fingerprint
+    }
+}
+
+const CHECKPOINT_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("checkpoints");
+
+/// Writes and reads the checkpoints of every asset in the database of the
+/// orchestrator, next to the `SchedulingEnvironment` that they were made
+/// from.
+#[derive(Debug)]
+pub struct CheckpointStore
+{
+    database: Arc<Database>,
+}
+
+impl CheckpointStore
+{
+    pub fn new(database: Arc<Database>) -> Self
+    {
+        Self { database }
+    }
+
+    /// The checkpoint is written in a single write transaction, so that a
+    /// crash during the write never leaves a truncated checkpoint and
+    /// concurrent writers of the same asset do not interleave.
+    pub fn save<Ss>(
+        &self,
+        asset: &Asset,
+        scheduling_environment_fingerprint: u64,
+        system_solution: &Ss,
+    ) -> Result<()>
+    where
+        Ss: Serialize,
+    {
+        let checkpoint = Checkpoint {
+            format_version: CHECKPOINT_FORMAT_VERSION,
+            asset: asset.clone(),
+            created_at: Utc::now(),
+            scheduling_environment_fingerprint,
+            system_solution,
+        };
+
+        let bytes = rmp_serde::to_vec_named(&checkpoint)
+            .with_context(|| format!("Could not serialize the checkpoint of {asset}"))?;
+
+        let write_transaction = self.database.begin_write()?;
+        write_transaction
+            .open_table(CHECKPOINT_TABLE)?
+            .insert(asset.to_string().as_str(), bytes.as_slice())
+            .with_context(|| format!("Could not write the checkpoint of {asset}"))?;
+        write_transaction
+            .commit()
+            .with_context(|| format!("Could not commit the checkpoint of {asset}"))?;
+
+        Ok(())
+    }
+
+    /// `None` if the asset has no checkpoint. A checkpoint that cannot be
+    /// read with the current solution types is an error.
+    pub fn load<Ss>(&self, asset: &Asset) -> Result<Option<Checkpoint<Ss>>>
+    where
+        Ss: DeserializeOwned,
+    {
+        let read_transaction = self.database.begin_read()?;
+        let checkpoint_table = match read_transaction.open_table(CHECKPOINT_TABLE) {
+            Ok(checkpoint_table) => checkpoint_table,
+            Err(TableError::TableDoesNotExist(_)) => return Ok(None),
+            Err(error) => return Err(error.into()),
+        };
+
+        let Some(bytes) = checkpoint_table
+            .get(asset.to_string().as_str())
+            .with_context(|| format!("Could not read the checkpoint of {asset}"))?
+        else {
+            return Ok(None);
+        };
+
+        let checkpoint: Checkpoint<Ss> = rmp_serde::from_slice(bytes.value())
+            .with_context(|| format!("The checkpoint of {asset} is not compatible"))?;
+
+        ensure!(
+            checkpoint.format_version == CHECKPOINT_FORMAT_VERSION,
+            "The checkpoint of {} has format version {} but version {} is required",
+            asset,
+            checkpoint.format_version,
+            CHECKPOINT_FORMAT_VERSION
+        );
+        ensure!(
+            checkpoint.asset == *asset,
+            "The checkpoint stored for {} belongs to asset {}",
+            asset,
+            checkpoint.asset
+        );
+
+        Ok(Some(checkpoint))
+    }
+}
+
+/// Identifies the `SchedulingEnvironment` that a checkpoint of the `Asset`
+/// was made from by its work orders, periods, days and actors. Changes inside
+/// a work order do not change the fingerprint, they are reconciled by the warm
+/// start of each actor.
+pub fn scheduling_environment_fingerprint(
+    scheduling_environment: &SchedulingEnvironment,
+    asset: &Asset,
+) -> u64
+{
+    let mut work_order_numbers: Vec<_> = scheduling_environment
+        .work_orders
+        .inner
+        .keys()
+        .collect();
+    work_order_numbers.sort();
+
+    let mut hasher = DefaultHasher::new();
+    work_order_numbers.hash(&mut hasher);
+    scheduling_environment
+        .time_environment
+        .periods
+        .hash(&mut hasher);
+    scheduling_environment.time_environment.days.hash(&mut hasher);
+
+    if let Some(actor_specifications) = scheduling_environment
+        .worker_environment
+        .actor_specification
+        .get(asset)
+    {
+        actor_specifications.strategic.id.hash(&mut hasher);
+        actor_specifications.tactical.id.hash(&mut hasher);
+        for input_supervisor in &actor_specifications.supervisors {
+            input_supervisor.id.hash(&mut hasher);
+        }
+        for input_operational in &actor_specifications.operational {
+            input_operational.id.hash(&mut hasher);
+        }
+    }
+
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use ordinator_scheduling_environment::Asset;
+    use redb::Database;
+
+    use super::CheckpointStore;
+
+    #[test]
+    fn test_checkpoint_store_round_trip()
+    {
+        let path = std::env::temp_dir().join(format!(
+            "ordinator_checkpoint_test_{}.redb",
+            std::process::id()
+        ));
+        let checkpoint_store = CheckpointStore::new(Arc::new(Database::create(&path).unwrap()));
+
+        assert!(
+            checkpoint_store
+                .load::<HashMap<(u64, u64), String>>(&Asset::Test)
+                .unwrap()
+                .is_none()
+        );
+
+        // Keys that are not strings are the reason for not using JSON here.
+        let system_solution = HashMap::from([((1, 10), "assigned".to_string())]);
+        checkpoint_store
+            .save(&Asset::Test, 42, &system_solution)
+            .unwrap();
+
+        let checkpoint = checkpoint_store
+            .load::<HashMap<(u64, u64), String>>(&Asset::Test)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(checkpoint.system_solution, system_solution);
+        assert!(!checkpoint.is_stale(42));
+        assert!(checkpoint.is_stale(43));
+        assert!(
+            checkpoint_store
+                .load::<Vec<String>>(&Asset::Test)
+                .is_err()
+        );
+
+        drop(checkpoint_store);
+        std::fs::remove_file(path).unwrap();
+    }
+}
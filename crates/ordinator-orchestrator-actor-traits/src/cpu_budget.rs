@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::Duration;
+
+use ordinator_configuration::throttling::CpuBudgetConfiguration;
+use ordinator_scheduling_environment::worker_environment::resources::Id;
+
+use crate::ActorKind;
+
+/// The CPU budget of a single asset. Each actor gets a share of the cores of
+/// the asset, and rests after every LNS iteration so that it stays within its
+/// share on average. An actor that runs on several threads takes one share
+/// of its `ActorKind` per thread.
+#[derive(Debug, Default)]
+pub struct CpuBudget
+{
+    actors: RwLock<HashMap<Id, (ActorKind, usize)>>,
+}
+
+impl CpuBudget
+{
+    pub fn register(&self, id: Id, actor_kind: ActorKind)
+    {
+        self.actors.write().unwrap().insert(id, (actor_kind, 1));
+    }
+
+    /// Sets the number of threads that the actor runs its iterations on.
+    pub fn set_threads(&self, id: &Id, threads: usize)
+    {
+        let threads = threads.max(1);
+        if self
+            .actors
+            .read()
+            .unwrap()
+            .get(id)
+            .is_none_or(|(_, registered_threads)| *registered_threads == threads)
+        {
+            return;
+        }
+
+        if let Some((_, registered_threads)) = self.actors.write().unwrap().get_mut(id) {
+            *registered_threads = threads;
+        }
+    }
+
+    pub fn deregister(&self, id: &Id)
+    {
+        self.actors.write().unwrap().remove(id);
+    }
+
+    /// The number of cores that the actor may use over all its threads. An
+    /// actor that is not registered is not limited.
+    pub fn allowance(&self, id: &Id, configuration: &CpuBudgetConfiguration) -> f64
+    {
+        let actors = self.actors.read().unwrap();
+
+        let Some((actor_kind, threads)) = actors.get(id) else {
+            return f64::INFINITY;
+        };
+
+        let count = |actor_kind: ActorKind| -> usize {
+            actors
+                .values()
+                .filter(|(kind, _)| *kind == actor_kind)
+                .map(|(_, threads)| threads)
+                .sum()
+        };
+        let share = |actor_kind: ActorKind| match actor_kind {
+            ActorKind::Strategic => configuration.strategic_share,
+            ActorKind::Tactical => configuration.tactical_share,
+            ActorKind::Supervisor => configuration.supervisor_share,
+            ActorKind::Operational => configuration.operational_share,
+        };
+
+        // Only the kinds that have running actors take part in the split.
+        let total_share: f64 = ActorKind::ALL
+            .iter()
+            .filter(|actor_kind| count(**actor_kind) > 0)
+            .map(|actor_kind| share(*actor_kind))
+            .sum();
+
+        if total_share <= 0.0 {
+            return f64::INFINITY;
+        }
+
+        configuration.cores_per_asset * share(*actor_kind) / total_share * *threads as f64
+            / count(*actor_kind) as f64
+    }
+
+    /// How long the actor has to rest after having been busy for `busy` to
+    /// stay within its allowance.
+    pub fn rest(&self, id: &Id, busy: Duration, configuration: &CpuBudgetConfiguration)
+    -> Duration
+    {
+        let allowance = self.allowance(id, configuration);
+        let threads = self
+            .actors
+            .read()
+            .unwrap()
+            .get(id)
+            .map_or(1, |(_, threads)| *threads) as f64;
+
+        if allowance >= threads {
+            return Duration::ZERO;
+        }
+
+        busy.mul_f64(threads / allowance.max(f64::EPSILON) - 1.0)
+            .min(Duration::from_secs(60))
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::time::Duration;
+
+    use ordinator_configuration::throttling::CpuBudgetConfiguration;
+    use ordinator_scheduling_environment::Asset;
+    use ordinator_scheduling_environment::worker_environment::resources::Id;
+
+    use super::CpuBudget;
+    use crate::ActorKind;
+
+    #[test]
+    fn test_cpu_budget_split_between_actor_kinds()
+    {
+        let configuration = CpuBudgetConfiguration {
+            cores_per_asset: 1.0,
+            strategic_share: 0.5,
+            tactical_share: 0.25,
+            supervisor_share: 0.0,
+            operational_share: 0.25,
+        };
+
+        let strategic = Id::new("StrategicActor", vec![], vec![Asset::Test]);
+        let operational_1 = Id::new("OP-01-001", vec![], vec![Asset::Test]);
+        let operational_2 = Id::new("OP-01-002", vec![], vec![Asset::Test]);
+
+        let cpu_budget = CpuBudget::default();
+        cpu_budget.register(strategic.clone(), ActorKind::Strategic);
+        cpu_budget.register(operational_1.clone(), ActorKind::Operational);
+        cpu_budget.register(operational_2.clone(), ActorKind::Operational);
+
+        // There is no tactical actor, so its share goes to the others.
+        assert!((cpu_budget.allowance(&strategic, &configuration) - 2.0 / 3.0).abs() < 1e-9);
+        assert!((cpu_budget.allowance(&operational_1, &configuration) - 1.0 / 6.0).abs() < 1e-9);
+
+        let rest = cpu_budget.rest(&operational_1, Duration::from_millis(10), &configuration);
+        assert!(rest.abs_diff(Duration::from_millis(50)) < Duration::from_micros(1));
+
+        cpu_budget.deregister(&operational_1);
+        assert!(
+            cpu_budget
+                .allowance(&operational_1, &configuration)
+                .is_infinite()
+        );
+    }
+
+    #[test]
+    fn test_cpu_budget_reserves_a_share_per_thread()
+    {
+        let configuration = CpuBudgetConfiguration {
+            cores_per_asset: 2.0,
+            strategic_share: 0.5,
+            tactical_share: 0.5,
+            supervisor_share: 0.0,
+            operational_share: 0.0,
+        };
+
+        let strategic = Id::new("StrategicActor", vec![], vec![Asset::Test]);
+        let tactical = Id::new("TacticalActor", vec![], vec![Asset::Test]);
+
+        let cpu_budget = CpuBudget::default();
+        cpu_budget.register(strategic.clone(), ActorKind::Strategic);
+        cpu_budget.register(tactical.clone(), ActorKind::Tactical);
+        cpu_budget.set_threads(&strategic, 4);
+
+        // The strategic share is one core, which is split over the four threads.
+        assert!((cpu_budget.allowance(&strategic, &configuration) - 1.0).abs() < 1e-9);
+        assert!((cpu_budget.allowance(&tactical, &configuration) - 1.0).abs() < 1e-9);
+
+        let rest = cpu_budget.rest(&strategic, Duration::from_millis(10), &configuration);
+        assert!(rest.abs_diff(Duration::from_millis(30)) < Duration::from_micros(1));
+        assert!(
+            cpu_budget
+                .rest(&tactical, Duration::from_millis(10), &configuration)
+                .is_zero()
+        );
+    }
+}
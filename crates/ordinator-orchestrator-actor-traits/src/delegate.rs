@@ -1,6 +1,10 @@
 use ordinator_scheduling_environment::work_order::operation::Operation;
+use serde::Deserialize;
+use serde::Serialize;
 
-#[derive(Clone, Default, Hash, Eq, PartialEq, PartialOrd, Ord, Debug)]
+use crate::ActorError;
+
+#[derive(Clone, Default, Hash, Eq, PartialEq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
 pub enum Delegate {
     #[default]
     Assess,
@@ -11,6 +15,34 @@ pub enum Delegate {
     Fixed,
 }
 
+/// Why a `Delegate` changes state. Every transition of the
+/// `SupervisorSolution` is made, and logged, with one of these.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DelegateCause {
+    /// The supervisor auction decided on an assessed activity.
+    Auction,
+    /// The supervisor LNS released an activity for a new auction.
+    Unschedule,
+    /// A planner fixed the activity to the operational actor.
+    PlannerFixed,
+    /// A planner released a fixed activity.
+    PlannerReleased,
+    /// The strategic period of the work order moved in or out of the
+    /// supervisor periods.
+    StrategicPeriodMoved,
+    /// The `work_remaining` of the activity reached zero.
+    WorkCompleted,
+    /// The operational actor is no longer available.
+    OperationalUnavailable,
+}
+
+impl DelegateCause {
+    pub fn is_planner(&self) -> bool {
+        matches!(self, Self::PlannerFixed | Self::PlannerReleased)
+    }
+}
+
 impl Delegate {
     pub fn build(operation: &Operation) -> Delegate {
         if operation.operation_info.work_remaining.0.is_zero() {
@@ -35,25 +67,98 @@ impl Delegate {
         matches!(self, Self::Drop)
     }
 
-    pub fn state_change_to_unassign(&mut self) {
-        match self {
-            Delegate::Assess => *self = Delegate::Unassign,
-            Delegate::Assign => todo!(),
-            Delegate::Unassign => todo!(),
-            Delegate::Drop => todo!(),
-            Delegate::Done => todo!(),
-            Delegate::Fixed => todo!(),
-        }
+    pub fn is_fixed(&self) -> bool {
+        matches!(self, Self::Fixed)
     }
 
-    pub fn state_change_to_assign(&mut self) {
-        match self {
-            Delegate::Assess => *self = Delegate::Assign,
-            Delegate::Assign => todo!(),
-            Delegate::Unassign => todo!(),
-            Delegate::Drop => todo!(),
-            Delegate::Done => todo!(),
-            Delegate::Fixed => todo!(),
+    /// The operational actor is expected to carry out the activity.
+    pub fn is_assigned(&self) -> bool {
+        self.is_assign() || self.is_fixed()
+    }
+
+    /// The transition table of the `Delegate` state machine.
+    pub fn is_legal_transition(&self, to: &Delegate, cause: DelegateCause) -> bool {
+        use Delegate::*;
+        use DelegateCause::*;
+
+        matches!(
+            (cause, self, to),
+            (Auction, Assess, Assign | Unassign)
+                | (Unschedule, Assign | Unassign, Assess)
+                | (PlannerFixed, Assess | Assign | Unassign, Fixed)
+                | (PlannerReleased, Fixed, Assess)
+                | (
+                    StrategicPeriodMoved,
+                    Assess | Assign | Unassign | Fixed,
+                    Drop
+                )
+                | (StrategicPeriodMoved, Drop, Assess)
+                | (
+                    WorkCompleted,
+                    Assess | Assign | Unassign | Fixed | Drop,
+                    Done
+                )
+                | (OperationalUnavailable, Assign | Unassign | Fixed, Assess)
+        )
+    }
+
+    /// Moves the `Delegate` to `to` and returns the previous state. Illegal
+    /// transitions leave the `Delegate` unchanged.
+    pub fn transition(
+        &mut self,
+        to: Delegate,
+        cause: DelegateCause,
+    ) -> Result<Delegate, ActorError> {
+        if !self.is_legal_transition(&to, cause) {
+            let message = format!("Delegate cannot change from {self:?} to {to:?} on {cause:?}");
+            return Err(if cause.is_planner() {
+                ActorError::InvalidRequest(message)
+            } else {
+                ActorError::InvariantViolated(message)
+            });
         }
+        Ok(std::mem::replace(self, to))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Delegate;
+    use super::DelegateCause;
+    use crate::ActorError;
+
+    #[test]
+    fn test_delegate_transitions() {
+        let mut delegate = Delegate::Assess;
+        assert_eq!(
+            delegate.transition(Delegate::Assign, DelegateCause::Auction),
+            Ok(Delegate::Assess)
+        );
+        assert_eq!(
+            delegate.transition(Delegate::Fixed, DelegateCause::PlannerFixed),
+            Ok(Delegate::Assign)
+        );
+
+        // The LNS never releases a planner fixed activity.
+        assert!(matches!(
+            delegate.transition(Delegate::Assess, DelegateCause::Unschedule),
+            Err(ActorError::InvariantViolated(_))
+        ));
+        assert_eq!(delegate, Delegate::Fixed);
+
+        assert_eq!(
+            delegate.transition(Delegate::Drop, DelegateCause::StrategicPeriodMoved),
+            Ok(Delegate::Fixed)
+        );
+        assert_eq!(
+            delegate.transition(Delegate::Done, DelegateCause::WorkCompleted),
+            Ok(Delegate::Drop)
+        );
+
+        assert!(matches!(
+            delegate.transition(Delegate::Fixed, DelegateCause::PlannerFixed),
+            Err(ActorError::InvalidRequest(_))
+        ));
+        assert_eq!(delegate, Delegate::Done);
     }
 }
@@ -0,0 +1,425 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use anyhow::Result;
+use ordinator_scheduling_environment::SchedulingEnvironment;
+use ordinator_scheduling_environment::work_order::WorkOrderActivity;
+use ordinator_scheduling_environment::work_order::WorkOrderNumber;
+use ordinator_scheduling_environment::worker_environment::resources::Id;
+use ordinator_scheduling_environment::worker_environment::resources::Resources;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::Level;
+use tracing::event;
+
+use crate::ActorKind;
+
+/// A change to the `SchedulingEnvironment` of an `Asset`. The `Orchestrator`
+/// publishes each event only to the actors that it is routed to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DomainEvent
+{
+    WorkOrderCreated(Vec<WorkOrderNumber>),
+    WorkOrderUpdated(Vec<WorkOrderNumber>),
+    WorkOrderClosed(Vec<WorkOrderNumber>),
+    /// The `work_remaining` of the activity was confirmed.
+    OperationProgressConfirmed(WorkOrderActivity),
+    /// The availability of the worker behind the operational `Id` changed.
+    WorkerAvailabilityChanged(Id),
+    /// An operational actor was added to the `ActorSpecifications`.
+    WorkerAdded(Id),
+    /// An operational actor was removed from the `ActorSpecifications`.
+    WorkerRemoved(Id),
+    MaterialDateChanged(Vec<WorkOrderNumber>),
+    /// The periods of the `TimeEnvironment` moved forward.
+    PeriodRolled,
+}
+
+impl DomainEvent
+{
+    /// The routing rules by kind of actor. Only the strategic actor plans with
+    /// material dates, and the operational actors do not plan in periods.
+    pub fn is_routed_to(&self, actor_kind: ActorKind) -> bool
+    {
+        match self {
+            DomainEvent::WorkOrderCreated(_)
+            | DomainEvent::WorkOrderUpdated(_)
+            | DomainEvent::WorkOrderClosed(_)
+            | DomainEvent::OperationProgressConfirmed(_) => true,
+            DomainEvent::WorkerAvailabilityChanged(_) => actor_kind != ActorKind::Supervisor,
+            DomainEvent::WorkerAdded(_) | DomainEvent::WorkerRemoved(_) => {
+                actor_kind != ActorKind::Operational
+            }
+            DomainEvent::MaterialDateChanged(_) => actor_kind == ActorKind::Strategic,
+            DomainEvent::PeriodRolled => actor_kind != ActorKind::Operational,
+        }
+    }
+
+    /// The part of the event that reaches the operational actor. A changed
+    /// availability only reaches the operational actor of the worker, and a
+    /// work order only reaches the operational actors with the `Resources`
+    /// of one of its operations.
+    pub fn routed_to_operational(
+        &self,
+        id: &Id,
+        work_order_resources: &WorkOrderResources,
+    ) -> Option<DomainEvent>
+    {
+        let is_relevant = |work_order_number: &WorkOrderNumber| {
+            work_order_resources
+                .0
+                .get(work_order_number)
+                .is_some_and(|resources| id.1.iter().any(|resource| resources.contains(resource)))
+        };
+        let relevant = |work_order_numbers: &[WorkOrderNumber]| {
+            let work_order_numbers: Vec<WorkOrderNumber> = work_order_numbers
+                .iter()
+                .filter(|work_order_number| is_relevant(work_order_number))
+                .copied()
+                .collect();
+            (!work_order_numbers.is_empty()).then_some(work_order_numbers)
+        };
+
+        match self {
+            DomainEvent::WorkOrderCreated(work_order_numbers) => {
+                relevant(work_order_numbers).map(DomainEvent::WorkOrderCreated)
+            }
+            DomainEvent::WorkOrderUpdated(work_order_numbers) => {
+                relevant(work_order_numbers).map(DomainEvent::WorkOrderUpdated)
+            }
+            DomainEvent::WorkOrderClosed(work_order_numbers) => {
+                relevant(work_order_numbers).map(DomainEvent::WorkOrderClosed)
+            }
+            DomainEvent::OperationProgressConfirmed(work_order_activity) => {
+                is_relevant(&work_order_activity.0).then(|| self.clone())
+            }
+            DomainEvent::WorkerAvailabilityChanged(operational_id) => {
+                (operational_id == id).then(|| self.clone())
+            }
+            domain_event => domain_event
+                .is_routed_to(ActorKind::Operational)
+                .then(|| domain_event.clone()),
+        }
+    }
+
+    /// The work orders that the event is about.
+    pub fn work_order_numbers(&self) -> Vec<WorkOrderNumber>
+    {
+        match self {
+            DomainEvent::WorkOrderCreated(work_order_numbers)
+            | DomainEvent::WorkOrderUpdated(work_order_numbers)
+            | DomainEvent::WorkOrderClosed(work_order_numbers)
+            | DomainEvent::MaterialDateChanged(work_order_numbers) => work_order_numbers.clone(),
+            DomainEvent::OperationProgressConfirmed(work_order_activity) => {
+                vec![work_order_activity.0]
+            }
+            DomainEvent::WorkerAvailabilityChanged(_)
+            | DomainEvent::WorkerAdded(_)
+            | DomainEvent::WorkerRemoved(_)
+            | DomainEvent::PeriodRolled => vec![],
+        }
+    }
+}
+
+/// The `Resources` of the operations of the work orders of a `DomainEvent`.
+/// They are taken before the event is applied, so that a closed work order
+/// still reaches the operational actors that scheduled it.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WorkOrderResources(pub HashMap<WorkOrderNumber, HashSet<Resources>>);
+
+impl WorkOrderResources
+{
+    pub fn of_work_orders(
+        scheduling_environment: &SchedulingEnvironment,
+        work_order_numbers: &[WorkOrderNumber],
+    ) -> Self
+    {
+        Self(
+            work_order_numbers
+                .iter()
+                .filter_map(|work_order_number| {
+                    scheduling_environment
+                        .work_orders
+                        .inner
+                        .get(work_order_number)
+                })
+                .map(|work_order| {
+                    let resources = work_order
+                        .operations
+                        .0
+                        .values()
+                        .map(|operation| operation.resource)
+                        .collect();
+                    (work_order.work_order_number, resources)
+                })
+                .collect(),
+        )
+    }
+
+    /// Adds the `Resources` of the work orders that are not known yet.
+    pub fn extend(&mut self, work_order_resources: WorkOrderResources)
+    {
+        for (work_order_number, resources) in work_order_resources.0 {
+            self.0
+                .entry(work_order_number)
+                .or_default()
+                .extend(resources);
+        }
+    }
+}
+
+/// Every actor implements the handlers of the `DomainEvent`s that are routed
+/// to it. The remaining handlers only log the event.
+pub trait DomainEventHandler
+{
+    fn handle_domain_event(&mut self, domain_event: DomainEvent) -> Result<()>
+    {
+        match domain_event {
+            DomainEvent::WorkOrderCreated(work_order_numbers) => {
+                self.work_order_created(work_order_numbers)
+            }
+            DomainEvent::WorkOrderUpdated(work_order_numbers) => {
+                self.work_order_updated(work_order_numbers)
+            }
+            DomainEvent::WorkOrderClosed(work_order_numbers) => {
+                self.work_order_closed(work_order_numbers)
+            }
+            DomainEvent::OperationProgressConfirmed(work_order_activity) => {
+                self.operation_progress_confirmed(work_order_activity)
+            }
+            DomainEvent::WorkerAvailabilityChanged(id) => self.worker_availability_changed(id),
+            DomainEvent::WorkerAdded(id) => self.worker_added(id),
+            DomainEvent::WorkerRemoved(id) => self.worker_removed(id),
+            DomainEvent::MaterialDateChanged(work_order_numbers) => {
+                self.material_date_changed(work_order_numbers)
+            }
+            DomainEvent::PeriodRolled => self.period_rolled(),
+        }
+    }
+
+    fn work_order_created(&mut self, work_order_numbers: Vec<WorkOrderNumber>) -> Result<()>
+    {
+        not_handled::<Self>(DomainEvent::WorkOrderCreated(work_order_numbers))
+    }
+
+    fn work_order_updated(&mut self, work_order_numbers: Vec<WorkOrderNumber>) -> Result<()>
+    {
+        not_handled::<Self>(DomainEvent::WorkOrderUpdated(work_order_numbers))
+    }
+
+    fn work_order_closed(&mut self, work_order_numbers: Vec<WorkOrderNumber>) -> Result<()>
+    {
+        not_handled::<Self>(DomainEvent::WorkOrderClosed(work_order_numbers))
+    }
+
+    fn operation_progress_confirmed(&mut self, work_order_activity: WorkOrderActivity)
+    -> Result<()>
+    {
+        not_handled::<Self>(DomainEvent::OperationProgressConfirmed(work_order_activity))
+    }
+
+    fn worker_availability_changed(&mut self, id: Id) -> Result<()>
+    {
+        not_handled::<Self>(DomainEvent::WorkerAvailabilityChanged(id))
+    }
+
+    fn worker_added(&mut self, id: Id) -> Result<()>
+    {
+        not_handled::<Self>(DomainEvent::WorkerAdded(id))
+    }
+
+    fn worker_removed(&mut self, id: Id) -> Result<()>
+    {
+        not_handled::<Self>(DomainEvent::WorkerRemoved(id))
+    }
+
+    fn material_date_changed(&mut self, work_order_numbers: Vec<WorkOrderNumber>) -> Result<()>
+    {
+        not_handled::<Self>(DomainEvent::MaterialDateChanged(work_order_numbers))
+    }
+
+    fn period_rolled(&mut self) -> Result<()>
+    {
+        not_handled::<Self>(DomainEvent::PeriodRolled)
+    }
+}
+
+fn not_handled<Handler: ?Sized>(domain_event: DomainEvent) -> Result<()>
+{
+    event!(
+        Level::DEBUG,
+        ?domain_event,
+        handler = std::any::type_name::<Handler>(),
+        "domain event is not handled"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::collections::HashMap;
+    use std::collections::HashSet;
+
+    use anyhow::Result;
+    use ordinator_scheduling_environment::Asset;
+    use ordinator_scheduling_environment::work_order::WorkOrderNumber;
+    use ordinator_scheduling_environment::worker_environment::resources::Id;
+    use ordinator_scheduling_environment::worker_environment::resources::Resources;
+
+    use super::DomainEvent;
+    use super::DomainEventHandler;
+    use super::WorkOrderResources;
+    use crate::ActorKind;
+
+    fn work_order_resources() -> WorkOrderResources
+    {
+        WorkOrderResources(HashMap::from([
+            (
+                WorkOrderNumber(1),
+                HashSet::from([Resources::MtnMech, Resources::MtnElec]),
+            ),
+            (WorkOrderNumber(2), HashSet::from([Resources::MtnElec])),
+        ]))
+    }
+
+    #[test]
+    fn test_domain_event_routing()
+    {
+        let operational = Id::new("OP-01", vec![Resources::MtnMech], vec![Asset::Test]);
+        let other_operational = Id::new("OP-02", vec![], vec![Asset::Test]);
+        let work_order_resources = work_order_resources();
+
+        let availability_changed = DomainEvent::WorkerAvailabilityChanged(operational.clone());
+        assert!(
+            availability_changed
+                .routed_to_operational(&operational, &work_order_resources)
+                .is_some()
+        );
+        assert!(
+            availability_changed
+                .routed_to_operational(&other_operational, &work_order_resources)
+                .is_none()
+        );
+        assert!(!availability_changed.is_routed_to(ActorKind::Supervisor));
+        assert!(availability_changed.is_routed_to(ActorKind::Strategic));
+
+        let material_date_changed = DomainEvent::MaterialDateChanged(vec![WorkOrderNumber(1)]);
+        let routed_to: Vec<ActorKind> = ActorKind::ALL
+            .into_iter()
+            .filter(|actor_kind| material_date_changed.is_routed_to(*actor_kind))
+            .collect();
+        assert_eq!(routed_to, vec![ActorKind::Strategic]);
+        assert!(
+            material_date_changed
+                .routed_to_operational(&operational, &work_order_resources)
+                .is_none()
+        );
+
+        assert!(
+            DomainEvent::PeriodRolled
+                .routed_to_operational(&operational, &work_order_resources)
+                .is_none()
+        );
+
+        let worker_added = DomainEvent::WorkerAdded(operational.clone());
+        assert!(
+            worker_added
+                .routed_to_operational(&operational, &work_order_resources)
+                .is_none()
+        );
+        assert!(worker_added.is_routed_to(ActorKind::Supervisor));
+
+        assert!(ActorKind::ALL.into_iter().all(|actor_kind| {
+            DomainEvent::WorkOrderClosed(vec![WorkOrderNumber(1)]).is_routed_to(actor_kind)
+        }));
+    }
+
+    #[test]
+    fn test_work_order_events_reach_the_operational_actors_with_the_resources()
+    {
+        let mechanic = Id::new("OP-01", vec![Resources::MtnMech], vec![Asset::Test]);
+        let electrician = Id::new("OP-02", vec![Resources::MtnElec], vec![Asset::Test]);
+        let scaffolder = Id::new("OP-03", vec![Resources::MtnScaf], vec![Asset::Test]);
+        let work_order_resources = work_order_resources();
+
+        let work_order_closed =
+            DomainEvent::WorkOrderClosed(vec![WorkOrderNumber(1), WorkOrderNumber(2)]);
+
+        assert_eq!(
+            work_order_closed.routed_to_operational(&mechanic, &work_order_resources),
+            Some(DomainEvent::WorkOrderClosed(vec![WorkOrderNumber(1)]))
+        );
+        assert_eq!(
+            work_order_closed.routed_to_operational(&electrician, &work_order_resources),
+            Some(work_order_closed.clone())
+        );
+        assert_eq!(
+            work_order_closed.routed_to_operational(&scaffolder, &work_order_resources),
+            None
+        );
+        assert_eq!(
+            DomainEvent::WorkOrderCreated(vec![WorkOrderNumber(3)])
+                .routed_to_operational(&mechanic, &work_order_resources),
+            None
+        );
+    }
+
+    /// Only handles the created and closed work orders.
+    #[derive(Default)]
+    struct RecordingHandler
+    {
+        created: Vec<WorkOrderNumber>,
+        closed: Vec<WorkOrderNumber>,
+    }
+
+    impl DomainEventHandler for RecordingHandler
+    {
+        fn work_order_created(&mut self, work_order_numbers: Vec<WorkOrderNumber>) -> Result<()>
+        {
+            self.created.extend(work_order_numbers);
+            Ok(())
+        }
+
+        fn work_order_closed(&mut self, work_order_numbers: Vec<WorkOrderNumber>) -> Result<()>
+        {
+            self.closed.extend(work_order_numbers);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_domain_event_handler_dispatch()
+    {
+        let mut recording_handler = RecordingHandler::default();
+
+        recording_handler
+            .handle_domain_event(DomainEvent::WorkOrderCreated(vec![WorkOrderNumber(1)]))
+            .unwrap();
+        recording_handler
+            .handle_domain_event(DomainEvent::WorkOrderClosed(vec![WorkOrderNumber(2)]))
+            .unwrap();
+
+        assert_eq!(recording_handler.created, vec![WorkOrderNumber(1)]);
+        assert_eq!(recording_handler.closed, vec![WorkOrderNumber(2)]);
+    }
+
+    #[test]
+    fn test_unhandled_domain_events_are_ignored()
+    {
+        let mut recording_handler = RecordingHandler::default();
+
+        for domain_event in [
+            DomainEvent::WorkOrderUpdated(vec![WorkOrderNumber(1)]),
+            DomainEvent::MaterialDateChanged(vec![WorkOrderNumber(1)]),
+            DomainEvent::PeriodRolled,
+            DomainEvent::WorkerAdded(Id::new("OP-01", vec![], vec![Asset::Test])),
+        ] {
+            assert!(recording_handler.handle_domain_event(domain_event).is_ok());
+        }
+
+        assert!(recording_handler.created.is_empty());
+        assert!(recording_handler.closed.is_empty());
+    }
+}
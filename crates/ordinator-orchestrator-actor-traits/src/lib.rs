@@ -1,68 +1,295 @@
+pub mod checkpoint;
+pub mod cpu_budget;
 pub mod delegate;
+pub mod domain_event;
+pub mod lockstep;
 pub mod marginal_fitness;
+pub mod solution_feed;
+pub mod telemetry;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::fmt::Debug;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::MutexGuard;
+use std::thread::JoinHandle;
+use std::time::Duration;
 
 use anyhow::Context;
 use anyhow::Result;
 use anyhow::anyhow;
+use anyhow::bail;
 use arc_swap::ArcSwap;
+use checkpoint::CheckpointStore;
 use chrono::DateTime;
 use chrono::Utc;
+use cpu_budget::CpuBudget;
 use delegate::Delegate;
+use domain_event::DomainEvent;
+use domain_event::DomainEventHandler;
 use flume::Receiver;
 use flume::Sender;
+use lockstep::Lockstep;
 use marginal_fitness::MarginalFitness;
 use ordinator_configuration::SystemConfigurations;
 use ordinator_scheduling_environment::Asset;
 use ordinator_scheduling_environment::SchedulingEnvironment;
+use ordinator_scheduling_environment::audit_log::SchedulingEnvironmentMutation;
 use ordinator_scheduling_environment::time_environment::day::Day;
 use ordinator_scheduling_environment::time_environment::period::Period;
 use ordinator_scheduling_environment::work_order::WorkOrderActivity;
 use ordinator_scheduling_environment::work_order::WorkOrderNumber;
 use ordinator_scheduling_environment::work_order::operation::ActivityNumber;
+use ordinator_scheduling_environment::worker_environment::OperationalOptions;
+use ordinator_scheduling_environment::worker_environment::StrategicOptions;
+use ordinator_scheduling_environment::worker_environment::SupervisorOptions;
+use ordinator_scheduling_environment::worker_environment::TacticalOptions;
 use ordinator_scheduling_environment::worker_environment::resources::Id;
+use serde::Deserialize;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use telemetry::ActorMetrics;
 
 pub trait OrchestratorNotifier: Send + Sync + 'static
 {
-    fn notify_all_agents_of_work_order_change(
+    /// Sends the `DomainEvent` to every actor of the asset that it is routed
+    /// to.
+    fn publish_domain_event(&self, domain_event: DomainEvent, asset: &Asset) -> Result<()>;
+
+    /// Applies the planner's mutation to the `SchedulingEnvironment`, appends
+    /// it to the audit log and publishes `DomainEvent::WorkOrderUpdated`. The
+    /// caller must not hold the `SchedulingEnvironment` and gets the resulting
+    /// version back.
+    fn mutate_scheduling_environment(
         &self,
-        work_orders: Vec<WorkOrderNumber>,
+        mutation: SchedulingEnvironmentMutation,
+        user: &str,
         asset: &Asset,
-    ) -> Result<()>;
+    ) -> Result<u64>;
+
+    /// The receiver gets a message whenever another actor of the same asset
+    /// has published a new solution to the `SystemSolution`.
+    fn subscribe_to_system_solution(&self, id: &Id) -> Receiver<()>;
+
+    /// Only signals the change, it is called on the hot path of the actor.
+    fn notify_system_solution_changed(&self, id: &Id);
+
+    /// The actor has paused after the `SystemConfigurations::iteration_limit`.
+    fn iteration_limit_reached(&self, id: &Id);
+
+    fn cpu_budget(&self, asset: &Asset) -> Result<Arc<CpuBudget>>;
+
+    /// `None` unless the actors of the asset take turns, see
+    /// `SystemConfigurations::lockstep`.
+    fn lockstep(&self, asset: &Asset) -> Option<Arc<Lockstep>>;
+
+    fn actor_metrics(&self, id: &Id) -> Arc<ActorMetrics>;
+
+    /// `None` if checkpointing is disabled in the `SystemConfigurations`.
+    fn checkpoint_store(&self) -> Option<Arc<CheckpointStore>>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ActorKind
+{
+    Strategic,
+    Tactical,
+    Supervisor,
+    Operational,
+}
+
+impl ActorKind
+{
+    pub const ALL: [ActorKind; 4] = [
+        ActorKind::Strategic,
+        ActorKind::Tactical,
+        ActorKind::Supervisor,
+        ActorKind::Operational,
+    ];
 }
 use thiserror::Error;
-#[derive(Error, Debug)]
+
+/// The errors that an actor rejects a request with, so that the caller can
+/// tell them apart without parsing messages. They are usually carried inside
+/// an `anyhow::Error`, see `ActorError::find`. The `Orchestrator` fails with
+/// an `OrchestratorError` and the `Communication` with a `RequestError`.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
 pub enum ActorError
 {
-    #[error("{info:#?}")]
-    OptimizationError
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+    #[error("{0:?} is not part of the SchedulingEnvironment")]
+    UnknownWorkOrder(WorkOrderNumber),
+    #[error("Period {0} is not part of the SchedulingEnvironment")]
+    UnknownPeriod(String),
+    #[error("The lock cannot be satisfied: {0}")]
+    InfeasibleLock(String),
+    #[error("Internal invariant violated: {0}")]
+    InvariantViolated(String),
+}
+
+impl ActorError
+{
+    /// Machine readable name of the variant.
+    pub fn code(&self) -> &'static str
     {
-        info: ErrorInfo
-    },
+        match self {
+            ActorError::InvalidRequest(_) => "invalid_request",
+            ActorError::UnknownWorkOrder(_) => "unknown_work_order",
+            ActorError::UnknownPeriod(_) => "unknown_period",
+            ActorError::InfeasibleLock(_) => "infeasible_lock",
+            ActorError::InvariantViolated(_) => "invariant_violated",
+        }
+    }
+
+    /// The outermost `ActorError` in the chain of the `anyhow::Error`, also if
+    /// context has been added on top of it.
+    pub fn find(error: &anyhow::Error) -> Option<&ActorError>
+    {
+        error
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<ActorError>())
+    }
 }
 
-use std::fmt::Debug;
+/// The errors of the `Orchestrator` itself, before a request reaches an
+/// actor or when it is not sent to one.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum OrchestratorError
+{
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+    #[error("{0:?} is not part of the SchedulingEnvironment")]
+    UnknownWorkOrder(WorkOrderNumber),
+    #[error("{0} is not available")]
+    ActorUnavailable(String),
+    #[error("{0} does not exist")]
+    UnknownActor(String),
+    #[error("Illegal transition: {0}")]
+    IllegalTransition(String),
+    #[error("Internal invariant violated: {0}")]
+    InvariantViolated(String),
+}
+
+impl OrchestratorError
+{
+    /// Machine readable name of the variant.
+    pub fn code(&self) -> &'static str
+    {
+        match self {
+            OrchestratorError::InvalidRequest(_) => "invalid_request",
+            OrchestratorError::UnknownWorkOrder(_) => "unknown_work_order",
+            OrchestratorError::ActorUnavailable(_) => "actor_unavailable",
+            OrchestratorError::UnknownActor(_) => "unknown_actor",
+            OrchestratorError::IllegalTransition(_) => "illegal_transition",
+            OrchestratorError::InvariantViolated(_) => "invariant_violated",
+        }
+    }
+
+    /// The outermost `OrchestratorError` in the chain of the `anyhow::Error`.
+    pub fn find(error: &anyhow::Error) -> Option<&OrchestratorError>
+    {
+        error
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<OrchestratorError>())
+    }
+}
+
+/// An error reported by an actor on the error channel of the `Orchestrator`.
+/// The `Id` tells the `Orchestrator` which actor the restart policy applies to.
 #[derive(Debug)]
-pub struct ErrorInfo
+pub struct ActorFailure
 {
-    pub symptom: &'static str,
-    pub hypothesis: &'static str,
-    pub location: &'static std::panic::Location<'static>,
-    pub action: &'static str,
-    pub context: Box<dyn Debug + Send + Sync + 'static>,
+    pub id: Id,
+    pub error: anyhow::Error,
 }
+
 pub struct Communication<RequestMessage, Res>
 {
-    sender_to_actor: Sender<ActorMessage<RequestMessage>>,
-    pub receiver_from_actor: Receiver<Result<Res>>,
+    sender_to_actor: Sender<ActorMessage<RequestMessage, Res>>,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+/// The ways a request to an actor can fail. The caller decides what each of
+/// them means, e.g. which HTTP status to answer with.
+#[derive(Error, Debug)]
+pub enum RequestError
+{
+    #[error("The Actor has stopped running")]
+    ActorStopped,
+    #[error("The Actor did not reply within {0:?}")]
+    TimedOut(Duration),
+    #[error("The Actor rejected the request: {0:#}")]
+    Rejected(anyhow::Error),
+}
+
+impl RequestError
+{
+    /// The `ActorError` that the actor rejected the request with, if any.
+    pub fn actor_error(&self) -> Option<&ActorError>
+    {
+        match self {
+            RequestError::Rejected(error) => ActorError::find(error),
+            RequestError::ActorStopped | RequestError::TimedOut(_) => None,
+        }
+    }
+
+    /// Machine readable name of the failure.
+    pub fn code(&self) -> &'static str
+    {
+        match self {
+            RequestError::ActorStopped => "actor_stopped",
+            RequestError::TimedOut(_) => "timed_out",
+            RequestError::Rejected(_) => self.actor_error().map_or("rejected", ActorError::code),
+        }
+    }
+}
+
+/// Sends requests to a single actor. Each request carries its own reply
+/// channel, so concurrent requests cannot receive each others replies. The
+/// `Requester` can be cloned out of the `ActorRegistry`, so that no lock is
+/// held while waiting for the reply.
+pub struct Requester<RequestMessage, Res>
+{
+    sender_to_actor: Sender<ActorMessage<RequestMessage, Res>>,
 }
 
-// StateLink is not a request. It is something different
+impl<RequestMessage, Res> Clone for Requester<RequestMessage, Res>
+{
+    fn clone(&self) -> Self
+    {
+        Self {
+            sender_to_actor: self.sender_to_actor.clone(),
+        }
+    }
+}
+
+impl<RequestMessage, Res> Requester<RequestMessage, Res>
+{
+    pub async fn request(
+        &self,
+        request_message: RequestMessage,
+        timeout: Duration,
+    ) -> Result<Res, RequestError>
+    {
+        let (reply_to, reply) = flume::bounded(1);
+
+        self.sender_to_actor
+            .send(ActorMessage::Actor(request_message, reply_to))
+            .map_err(|_| RequestError::ActorStopped)?;
+
+        match tokio::time::timeout(timeout, reply.recv_async()).await {
+            Ok(Ok(Ok(response))) => Ok(response),
+            Ok(Ok(Err(error))) => Err(RequestError::Rejected(error)),
+            // The actor drops the reply channel if it stops before replying.
+            Ok(Err(_)) => Err(RequestError::ActorStopped),
+            Err(_) => Err(RequestError::TimedOut(timeout)),
+        }
+    }
+}
+
+// A DomainEvent is not a request. It is something different
 // Ahh this is good every Request message from each of the actors
 // should implement a `RequestMessage`. It is a little weird to
 // reuse the `Req` like this. You need to remember this to see
@@ -72,39 +299,79 @@ pub struct Communication<RequestMessage, Res>
 // generics in the correct way. There is something to learn here.
 impl<RequestMessage, Res> Communication<RequestMessage, Res>
 {
-    pub fn new(
-        sender: Sender<ActorMessage<RequestMessage>>,
-        receiver: Receiver<Result<Res>>,
-    ) -> Self
+    pub fn new(sender: Sender<ActorMessage<RequestMessage, Res>>) -> Self
     {
         Self {
             sender_to_actor: sender,
-            receiver_from_actor: receiver,
+            join_handle: None,
         }
     }
 
-    // This is being wrapped twice. I think that the best approach is to
-    // make the system function with.
-    pub fn from_agent(&self, message: RequestMessage) -> Result<()>
+    /// The `JoinHandle` of the thread running the actor. This is set by the
+    /// `ActorBuilder` when the thread is spawned.
+    pub fn set_join_handle(&mut self, join_handle: JoinHandle<()>)
     {
-        // What is it that you need to do here? You should
-        let message = ActorMessage::Actor(message);
-        self.sender_to_actor.send(message).map_err(|e| anyhow!(e.to_string() )).context("The Actor has stopped running. If the reason for this is not obvious, it means that the error handling should be extended.")
+        self.join_handle = Some(join_handle);
     }
 
-    pub fn from_actor(&self) -> Res
+    pub fn requester(&self) -> Requester<RequestMessage, Res>
     {
-        self.receiver_from_actor.recv().unwrap().unwrap()
+        Requester {
+            sender_to_actor: self.sender_to_actor.clone(),
+        }
     }
 
-    pub fn from_orchestrator(&self, state_link: StateLink)
+    pub async fn request(
+        &self,
+        request_message: RequestMessage,
+        timeout: Duration,
+    ) -> Result<Res, RequestError>
     {
-        let message = ActorMessage::State(state_link);
-        self.sender_to_actor.send(message).expect("The Actor has stopped running. If the reason for this is not obvious, it means that the error handling should be extended.");
+        self.requester().request(request_message, timeout).await
+    }
+
+    pub fn from_orchestrator(&self, domain_event: DomainEvent) -> Result<()>
+    {
+        let message = ActorMessage::Event(domain_event);
+        self.sender_to_actor.send(message).map_err(|e| anyhow!(e.to_string())).context("The Actor has stopped running. If the reason for this is not obvious, it means that the error handling should be extended.")
+    }
+
+    pub fn options(&self, actor_options: ActorOptions) -> Result<()>
+    {
+        self.sender_to_actor
+            .send(ActorMessage::Options(actor_options))
+            .map_err(|e| anyhow!(e.to_string()))
+            .context("Could not send the new options to the Actor")
+    }
+
+    pub fn lifecycle(&self, lifecycle: Lifecycle) -> Result<()>
+    {
+        self.sender_to_actor
+            .send(ActorMessage::Lifecycle(lifecycle))
+            .map_err(|e| anyhow!(e.to_string()))
+            .with_context(|| format!("Could not send {lifecycle:?} to the Actor"))
+    }
+
+    /// Sends `Lifecycle::Shutdown` or `Lifecycle::Abort` and hands out the
+    /// `JoinHandle` of the actor. The handle is returned instead of joined
+    /// here, so that the caller can release any locks that the actor might be
+    /// waiting on first.
+    pub fn stop(&mut self, lifecycle: Lifecycle) -> Option<JoinHandle<()>>
+    {
+        // The actor also shuts down if the channel is disconnected, so a failed
+        // send is not an error here.
+        let _ = self.lifecycle(lifecycle);
+
+        self.join_handle.take()
+    }
+
+    pub fn is_running(&self) -> bool
+    {
+        !self.sender_to_actor.is_disconnected()
     }
 }
 
-#[derive(PartialEq, Eq, Debug, Clone)]
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
 pub struct SystemSolution<S, T, U, V>
 where
     S: StrategicInterface,
@@ -116,13 +383,50 @@ where
 {
     pub strategic: Option<S>,
     pub tactical: Option<T>,
-    pub supervisor: Option<U>,
+    /// An `Asset` can have several supervisors, each with its own solution.
+    #[serde(
+        with = "serde_json_any_key::any_key_map",
+        bound(serialize = "U: Serialize", deserialize = "U: DeserializeOwned + 'de")
+    )]
+    pub supervisor: HashMap<Id, U>,
+    #[serde(
+        with = "serde_json_any_key::any_key_map",
+        bound(serialize = "V: Serialize", deserialize = "V: DeserializeOwned + 'de")
+    )]
     pub operational: HashMap<Id, V>,
+    /// The actor that last swapped in the strategic and tactical solution.
+    /// Only that actor can remove it again.
+    #[serde(default)]
+    pub owners: HashMap<ActorKind, Id>,
+    /// Bumped on every swap and removal of an actor solution.
+    #[serde(default)]
+    pub version: u64,
+}
+
+impl<S, T, U, V> SystemSolution<S, T, U, V>
+where
+    S: StrategicInterface,
+    T: TacticalInterface,
+    U: SupervisorInterface,
+    V: OperationalInterface + Solution,
+{
+    fn remove_owner(&mut self, actor_kind: ActorKind, id: &Id) -> Result<()>
+    {
+        match self.owners.get(&actor_kind) {
+            Some(owner) if owner != id => Err(anyhow!(
+                "{id} cannot remove the {actor_kind:?} solution, it belongs to {owner}"
+            )),
+            _ => {
+                self.owners.remove(&actor_kind);
+                Ok(())
+            }
+        }
+    }
 }
 
 // This is made completely wrong. I am not sure what the
 // best approach of solving it will be.
-pub trait SystemSolutions: Clone
+pub trait SystemSolutions: Clone + Serialize + DeserializeOwned + Send + Sync
 {
     type Strategic: StrategicInterface;
     type Tactical: TacticalInterface;
@@ -140,7 +444,25 @@ pub trait SystemSolutions: Clone
     fn tactical_swap(&mut self, id: &Id, solution: Self::Tactical)
     where
         Self::Tactical: Solution;
-    fn supervisor_actor_solutions(&self) -> Result<&Self::Supervisor>;
+    fn supervisor_actor_solution(&self, id: &Id) -> Result<&Self::Supervisor>;
+
+    /// The solutions of every supervisor of the `Asset`. Errors if no
+    /// supervisor has published a solution yet.
+    fn supervisor_actor_solutions(&self) -> Result<&HashMap<Id, Self::Supervisor>>;
+
+    /// The `Delegate`s of the operational actor in the solutions of all
+    /// supervisors.
+    fn delegates_for_operational(
+        &self,
+        operational_id: &Id,
+    ) -> Result<HashMap<WorkOrderActivity, Delegate>>
+    {
+        Ok(self
+            .supervisor_actor_solutions()?
+            .values()
+            .flat_map(|supervisor| supervisor.delegates_for_agent(operational_id))
+            .collect())
+    }
 
     fn supervisor_swap(&mut self, id: &Id, solution: Self::Supervisor)
     where
@@ -152,6 +474,18 @@ pub trait SystemSolutions: Clone
     fn operational_swap(&mut self, id: &Id, solution: Self::Operational)
     where
         Self::Operational: Solution;
+
+    /// Errors if the solution was swapped in by another actor than `id`.
+    fn strategic_remove(&mut self, id: &Id) -> Result<Option<Self::Strategic>>;
+
+    fn tactical_remove(&mut self, id: &Id) -> Result<Option<Self::Tactical>>;
+
+    fn supervisor_remove(&mut self, id: &Id) -> Result<Option<Self::Supervisor>>;
+
+    fn operational_remove(&mut self, id: &Id) -> Result<Option<Self::Operational>>;
+
+    /// Monotonic version of the `SystemSolution`, see `solution_feed`.
+    fn version(&self) -> u64;
 }
 
 // You are out in the woods here. You should keep up the work and focus on
@@ -163,10 +497,10 @@ pub trait SystemSolutions: Clone
 #[allow(dead_code, unused_variables)]
 impl<S, T, U, V> SystemSolutions for SystemSolution<S, T, U, V>
 where
-    S: StrategicInterface,
-    T: TacticalInterface,
-    U: SupervisorInterface,
-    V: OperationalInterface + Solution,
+    S: StrategicInterface + Serialize + DeserializeOwned + Send + Sync,
+    T: TacticalInterface + Serialize + DeserializeOwned + Send + Sync,
+    U: SupervisorInterface + Serialize + DeserializeOwned + Send + Sync + 'static,
+    V: OperationalInterface + Solution + Serialize + DeserializeOwned + Send + Sync + 'static,
 {
     type Operational = V;
     type Strategic = S;
@@ -178,8 +512,10 @@ where
         Self {
             strategic: None,
             tactical: None,
-            supervisor: None,
+            supervisor: HashMap::default(),
             operational: HashMap::default(),
+            owners: HashMap::default(),
+            version: 0,
         }
     }
 
@@ -197,11 +533,19 @@ where
             .with_context(|| "TacticalActor SystemSolution not found")
     }
 
-    fn supervisor_actor_solutions(&self) -> Result<&Self::Supervisor>
+    fn supervisor_actor_solution(&self, id: &Id) -> Result<&Self::Supervisor>
     {
         self.supervisor
-            .as_ref()
-            .with_context(|| "SupervisorActor SystemSolution not found")
+            .get(id)
+            .with_context(|| format!("SupervisorActor SystemSolution not found for {id}"))
+    }
+
+    fn supervisor_actor_solutions(&self) -> Result<&HashMap<Id, Self::Supervisor>>
+    {
+        if self.supervisor.is_empty() {
+            bail!("SupervisorActor SystemSolution not found");
+        }
+        Ok(&self.supervisor)
     }
 
     fn operational_actor_solutions(&self, id: &Id) -> Result<&Self::Operational>
@@ -216,6 +560,7 @@ where
     where
         Self::Operational: Solution,
     {
+        self.version += 1;
         self.operational.insert(id.clone(), solution);
     }
 
@@ -223,6 +568,8 @@ where
     where
         Self::Strategic: Solution,
     {
+        self.version += 1;
+        self.owners.insert(ActorKind::Strategic, id.clone());
         self.strategic = Some(solution);
     }
 
@@ -230,6 +577,8 @@ where
     where
         Self::Tactical: Solution,
     {
+        self.version += 1;
+        self.owners.insert(ActorKind::Tactical, id.clone());
         self.tactical = Some(solution);
     }
 
@@ -237,7 +586,8 @@ where
     where
         Self::Supervisor: Solution,
     {
-        self.supervisor = Some(solution);
+        self.version += 1;
+        self.supervisor.insert(id.clone(), solution);
     }
 
     fn all_operational(&self) -> HashSet<Id>
@@ -245,6 +595,40 @@ where
         self.operational.keys().cloned().collect()
     }
 
+    fn strategic_remove(&mut self, id: &Id) -> Result<Option<Self::Strategic>>
+    {
+        self.remove_owner(ActorKind::Strategic, id)?;
+        self.version += 1;
+        Ok(self.strategic.take())
+    }
+
+    fn tactical_remove(&mut self, id: &Id) -> Result<Option<Self::Tactical>>
+    {
+        self.remove_owner(ActorKind::Tactical, id)?;
+        self.version += 1;
+        Ok(self.tactical.take())
+    }
+
+    fn supervisor_remove(&mut self, id: &Id) -> Result<Option<Self::Supervisor>>
+    {
+        let supervisor = self.supervisor.remove(id);
+        if supervisor.is_some() {
+            self.version += 1;
+        }
+        Ok(supervisor)
+    }
+
+    fn operational_remove(&mut self, id: &Id) -> Result<Option<Self::Operational>>
+    {
+        self.version += 1;
+        Ok(self.operational.remove(id))
+    }
+
+    fn version(&self) -> u64
+    {
+        self.version
+    }
+
     // You could implement the pointer swapping here. Hmm... that might not be the
     // best idea.
 }
@@ -297,6 +681,8 @@ pub trait Solution: Sized
     // The solution should only contain the things that actually change.
     fn new(parameters: &Self::Parameters) -> Result<Self>;
 
+    fn objective_value(&self) -> &Self::ObjectiveValue;
+
     fn update_objective_value(&mut self, other_objective: Self::ObjectiveValue);
 }
 
@@ -306,7 +692,7 @@ pub trait Solution: Sized
 // through the `MassageHandler` channel.
 /// This trait should be implemented by every Actor so that it will be able to
 /// receive messages from the user and the [`Orchestrator`].  
-pub trait CommandHandler
+pub trait CommandHandler: DomainEventHandler
 {
     type Req;
     type Res;
@@ -315,16 +701,27 @@ pub trait CommandHandler
     // Maybe I should strive
     // Here it wraps the `Req` in the `ActorMessage` I do not think that this
     // is the best way of doing it
-    fn handle(&mut self, actor_message: ActorMessage<Self::Req>) -> Result<Self::Res>
+    //
+    // The reply to an `ActorMessage::Actor` goes back on its own channel, so
+    // only the errors from the `DomainEvent` are returned here.
+    fn handle(&mut self, actor_message: ActorMessage<Self::Req, Self::Res>) -> Result<()>
     {
         match actor_message {
-            ActorMessage::State(state_link) => self.handle_state_link(state_link),
-            ActorMessage::Actor(actor_request) => self.handle_request_message(actor_request),
+            ActorMessage::Event(domain_event) => self.handle_domain_event(domain_event),
+            ActorMessage::Actor(actor_request, reply_to) => {
+                // The requester has given up if the reply channel is disconnected.
+                let _ = reply_to.send(self.handle_request_message(actor_request));
+                Ok(())
+            }
+            ActorMessage::Lifecycle(lifecycle) => Err(anyhow!(
+                "{lifecycle:?} should be handled by the run loop of the Actor"
+            )),
+            ActorMessage::Options(_) => Err(anyhow!(
+                "Options should be handled by the run loop of the Actor"
+            )),
         }
     }
 
-    fn handle_state_link(&mut self, state_link: StateLink) -> Result<Self::Res>;
-
     fn handle_request_message(&mut self, request_message: Self::Req) -> Result<Self::Res>;
 }
 
@@ -371,7 +768,7 @@ where
 // custom logic internally depending on where they know the
 // work order to be located. This is crucial to respect
 // business logic.
-#[derive(PartialEq, Eq, Debug, Default, Clone)]
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
 pub enum WhereIsWorkOrder<T>
 {
     Strategic,
@@ -402,6 +799,10 @@ where
 {
     fn swap(id: &Id, solution: Self, system_solution: &mut Ss);
 
+    /// The solution of the actor in the `SystemSolution`, if there is one.
+    /// Actors are warm started from this when they are restarted.
+    fn from_system_solution(id: &Id, system_solution: &Ss) -> Option<Self>;
+
     // fn perform_swap(id: &Id, solution: Self, system_solution:
     // Self::SystemSolution) {
     //     Self::swap(id, solution, system_solution);
@@ -437,6 +838,8 @@ where
     // with evidence not blind faith.
     fn delegates_for_agent(&self, operational_agent: &Id) -> HashMap<WorkOrderActivity, Delegate>;
     fn count_delegate_types(&self, operational_agent: &Id) -> (u64, u64, u64);
+
+    fn all_delegates(&self) -> &HashMap<(Id, WorkOrderActivity), Delegate>;
 }
 // The `solution` should be updated on the `SharedSolution` not the
 // individual solution. These interfaces are implemented on the
@@ -458,47 +861,73 @@ where
         &'a self,
         work_order_activity: &WorkOrderActivity,
     ) -> Option<&'a MarginalFitness>;
+
+    /// The start of the first and the finish of the last `Assignment` of
+    /// every scheduled `WorkOrderActivity`.
+    fn scheduled_intervals(&self) -> HashMap<WorkOrderActivity, (DateTime<Utc>, DateTime<Utc>)>;
 }
 
 // You should make an API on the `Communication` struct. What other approach
 // should I take.
 #[derive(Clone)]
-pub enum ActorMessage<ActorRequest>
+pub enum ActorMessage<ActorRequest, ActorResponse>
+{
+    Event(DomainEvent),
+    /// A request together with the channel that the reply goes to.
+    Actor(ActorRequest, Sender<Result<ActorResponse>>),
+    Lifecycle(Lifecycle),
+    /// New options for the actor. The actor re-evaluates its objective with
+    /// the new options before it continues with its LNS iterations.
+    Options(ActorOptions),
+}
+
+/// The options of a single actor, tagged with the kind of actor that they
+/// belong to.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ActorOptions
 {
-    State(StateLink),
-    Actor(ActorRequest),
-    // Yes so options should be included here as part of what needs to be created for
-    // this to work. I believe that the best approach here will be to make something
-    // that
-    // FIX
-    // Add Options here so that every agent can have its options updated at run time.
-    // Options(),
+    Strategic(StrategicOptions),
+    Tactical(TacticalOptions),
+    Supervisor(SupervisorOptions),
+    Operational(OperationalOptions),
 }
 
-/// The StateLink is a generic type that each type of Agent will implement.
-/// The generics mean:
-///     S: Strategic
-///     T: Tactical
-///     Su: Supervisor
-///     O: Operational
-/// This means that each Agent in the system will need to implement how to
-/// understand messages from the other Agents in their own unique way.
-/// This allows us to get custom implementations for each of the
-/// Agent types creating a mesh of communication pathways that are still
-/// statically typed.
-#[allow(dead_code)]
-#[derive(Debug, Clone)]
-pub enum StateLink
+impl ActorOptions
 {
-    WorkOrders(ActorSpecific),
-    WorkerEnvironment,
-    TimeEnvironment,
+    pub fn actor_kind(&self) -> ActorKind
+    {
+        match self {
+            ActorOptions::Strategic(_) => ActorKind::Strategic,
+            ActorOptions::Tactical(_) => ActorKind::Tactical,
+            ActorOptions::Supervisor(_) => ActorKind::Supervisor,
+            ActorOptions::Operational(_) => ActorKind::Operational,
+        }
+    }
+
+    pub fn validate(&self) -> Result<()>
+    {
+        match self {
+            ActorOptions::Strategic(strategic_options) => strategic_options.validate(),
+            ActorOptions::Tactical(tactical_options) => tactical_options.validate(),
+            ActorOptions::Supervisor(supervisor_options) => supervisor_options.validate(),
+            ActorOptions::Operational(operational_options) => operational_options.validate(),
+        }
+    }
 }
 
-#[derive(Debug, Clone)]
-pub enum ActorSpecific
+/// Controls the run loop of an actor. A paused actor still handles messages
+/// but does not run any LNS iterations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lifecycle
 {
-    Strategic(Vec<WorkOrderNumber>),
+    Pause,
+    Resume,
+    Shutdown,
+    /// Shuts down without the `Orchestrator` writing a checkpoint. Used when
+    /// the actor is restarted after a failure, so that its state is not
+    /// persisted.
+    Abort,
 }
 
 pub trait ActorFactory<Ss>
@@ -513,6 +942,89 @@ where
         system_solution_arc_swap: Arc<ArcSwap<Ss>>,
         notify_orchestrator: Arc<dyn OrchestratorNotifier>,
         system_configurations: Arc<ArcSwap<SystemConfigurations>>,
-        error_channel: Sender<anyhow::Error>,
+        error_channel: Sender<ActorFailure>,
     ) -> Result<Self::Communication>;
 }
+
+#[cfg(test)]
+mod tests
+{
+    use std::time::Duration;
+
+    use anyhow::anyhow;
+
+    use super::ActorError;
+    use super::ActorMessage;
+    use super::Communication;
+    use super::RequestError;
+
+    #[tokio::test]
+    async fn test_request_reply_timeout_and_stopped_actor()
+    {
+        let (sender_to_actor, receiver_from_orchestrator) = flume::unbounded();
+        let communication = Communication::<u64, u64>::new(sender_to_actor);
+
+        let actor = std::thread::spawn(move || {
+            for message in receiver_from_orchestrator.iter() {
+                if let ActorMessage::Actor(request, reply_to) = message {
+                    match request {
+                        0 => reply_to
+                            .send(Err(anyhow::Error::new(ActorError::InvalidRequest(
+                                "zero is not allowed".to_string(),
+                            ))
+                            .context("The request was not handled")))
+                            .unwrap(),
+                        4 => reply_to.send(Err(anyhow!("four is not allowed"))).unwrap(),
+                        // The actor is busy and never replies to this request.
+                        1 => std::thread::sleep(Duration::from_millis(100)),
+                        request => reply_to.send(Ok(request * 2)).unwrap(),
+                    }
+                }
+            }
+        });
+
+        let timeout = Duration::from_millis(50);
+
+        assert_eq!(communication.request(21, timeout).await.unwrap(), 42);
+        let rejected = communication.request(0, timeout).await.unwrap_err();
+        assert_eq!(
+            rejected.actor_error(),
+            Some(&ActorError::InvalidRequest(
+                "zero is not allowed".to_string()
+            ))
+        );
+        assert_eq!(rejected.code(), "invalid_request");
+        let rejected = communication.request(4, timeout).await.unwrap_err();
+        assert!(matches!(rejected, RequestError::Rejected(_)));
+        assert_eq!(rejected.code(), "rejected");
+        assert!(matches!(
+            communication.request(1, timeout).await,
+            Err(RequestError::TimedOut(_))
+        ));
+
+        // Concurrent requests each get their own reply.
+        let requester = communication.requester();
+        let (a, b) = tokio::join!(
+            requester.request(2, Duration::from_secs(1)),
+            requester.request(3, Duration::from_secs(1))
+        );
+        assert_eq!((a.unwrap(), b.unwrap()), (4, 6));
+
+        drop(requester);
+        drop(communication);
+        actor.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_request_to_stopped_actor()
+    {
+        let (sender_to_actor, receiver_from_orchestrator) = flume::unbounded();
+        let communication = Communication::<u64, u64>::new(sender_to_actor);
+        drop(receiver_from_orchestrator);
+
+        assert!(matches!(
+            communication.request(1, Duration::from_secs(1)).await,
+            Err(RequestError::ActorStopped)
+        ));
+    }
+}
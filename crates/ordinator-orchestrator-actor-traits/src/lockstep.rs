@@ -0,0 +1,200 @@
+use std::collections::HashSet;
+use std::sync::Condvar;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use ordinator_scheduling_environment::worker_environment::resources::Id;
+
+/// Makes the actors of an asset take turns on their LNS iterations in a fixed
+/// order. Each actor reacts to the solutions that the actors before it have
+/// published, so the order of the turns decides the `SystemSolution`. With
+/// the turns fixed, the `SystemSolution` is reproducible from the
+/// `master_seed`, which is what a batch solve needs.
+#[derive(Debug)]
+pub struct Lockstep
+{
+    turns: Mutex<Turns>,
+    turn_changed: Condvar,
+}
+
+#[derive(Debug)]
+struct Turns
+{
+    order: Vec<Id>,
+    left: HashSet<Id>,
+    turn: usize,
+}
+
+impl Turns
+{
+    fn is_taking_turns(&self, id: &Id) -> bool
+    {
+        self.order.contains(id) && !self.left.contains(id)
+    }
+
+    /// Moves the turn on to the next actor that has not left.
+    fn advance(&mut self)
+    {
+        for _ in 0..self.order.len() {
+            self.turn = (self.turn + 1) % self.order.len();
+            if !self.left.contains(&self.order[self.turn]) {
+                return;
+            }
+        }
+    }
+}
+
+impl Lockstep
+{
+    /// The turns are taken in the order of the `Id`s.
+    pub fn new(order: Vec<Id>) -> Self
+    {
+        Self {
+            turns: Mutex::new(Turns {
+                order,
+                left: HashSet::new(),
+                turn: 0,
+            }),
+            turn_changed: Condvar::new(),
+        }
+    }
+
+    /// Waits at most `timeout` for the turn of the actor, so that it can still
+    /// handle its messages while the other actors iterate. An actor that is
+    /// not in the order, like one created after the asset was started, does
+    /// not wait.
+    pub fn wait_for_turn(&self, id: &Id, timeout: Duration) -> bool
+    {
+        let turns = self.turns.lock().unwrap();
+        let (turns, _) = self
+            .turn_changed
+            .wait_timeout_while(turns, timeout, |turns| {
+                turns.is_taking_turns(id) && &turns.order[turns.turn] != id
+            })
+            .unwrap();
+
+        !turns.is_taking_turns(id) || &turns.order[turns.turn] == id
+    }
+
+    /// Hands the turn to the next actor. Does nothing if it is not the turn
+    /// of the actor.
+    pub fn end_turn(&self, id: &Id)
+    {
+        let mut turns = self.turns.lock().unwrap();
+        if turns.is_taking_turns(id) && &turns.order[turns.turn] == id {
+            turns.advance();
+            self.turn_changed.notify_all();
+        }
+    }
+
+    /// The actor stops taking turns, because it has paused at the
+    /// `iteration_limit` or its thread has stopped.
+    pub fn leave(&self, id: &Id)
+    {
+        let mut turns = self.turns.lock().unwrap();
+        if !turns.is_taking_turns(id) {
+            return;
+        }
+
+        let has_turn = &turns.order[turns.turn] == id;
+        turns.left.insert(id.clone());
+        if has_turn {
+            turns.advance();
+        }
+        self.turn_changed.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::time::Duration;
+    use std::time::SystemTime;
+
+    use ordinator_scheduling_environment::Asset;
+    use ordinator_scheduling_environment::worker_environment::resources::Id;
+
+    use super::Lockstep;
+
+    /// Stands in for the actors of an asset. Every actor reads the shared
+    /// solution, changes it based on its own seeded state and rests for a
+    /// time that depends on the thread scheduling.
+    fn run_actors(master_seed: u64, iterations: usize) -> Vec<u64>
+    {
+        let ids: Vec<Id> = ["StrategicActor", "TacticalActor", "SUP-01", "OP-01-001"]
+            .into_iter()
+            .map(|name| Id::new(name, vec![], vec![Asset::Test]))
+            .collect();
+        let lockstep = Arc::new(Lockstep::new(ids.clone()));
+        let shared_solution = Arc::new(Mutex::new(vec![master_seed]));
+
+        let handles: Vec<_> = ids
+            .into_iter()
+            .enumerate()
+            .map(|(index, id)| {
+                let lockstep = Arc::clone(&lockstep);
+                let shared_solution = Arc::clone(&shared_solution);
+                std::thread::spawn(move || {
+                    let mut state = master_seed.wrapping_add(index as u64);
+                    for _ in 0..iterations {
+                        while !lockstep.wait_for_turn(&id, Duration::from_millis(1)) {}
+
+                        let last = *shared_solution.lock().unwrap().last().unwrap();
+                        state = state
+                            .wrapping_mul(6_364_136_223_846_793_005)
+                            .wrapping_add(last);
+                        shared_solution.lock().unwrap().push(state);
+
+                        let jitter = SystemTime::now()
+                            .duration_since(SystemTime::UNIX_EPOCH)
+                            .unwrap()
+                            .subsec_nanos()
+                            % 500;
+                        std::thread::sleep(Duration::from_micros(jitter as u64));
+
+                        lockstep.end_turn(&id);
+                    }
+                    lockstep.leave(&id);
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        Arc::try_unwrap(shared_solution)
+            .unwrap()
+            .into_inner()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_two_lockstep_runs_give_the_same_solution()
+    {
+        let first_run = run_actors(42, 20);
+
+        assert_eq!(first_run.len(), 1 + 4 * 20);
+        assert_eq!(first_run, run_actors(42, 20));
+        assert_ne!(first_run, run_actors(43, 20));
+    }
+
+    #[test]
+    fn test_an_actor_that_left_does_not_hold_up_the_others()
+    {
+        let strategic = Id::new("StrategicActor", vec![], vec![Asset::Test]);
+        let tactical = Id::new("TacticalActor", vec![], vec![Asset::Test]);
+        let created_later = Id::new("OP-01-002", vec![], vec![Asset::Test]);
+        let lockstep = Lockstep::new(vec![strategic.clone(), tactical.clone()]);
+
+        assert!(lockstep.wait_for_turn(&strategic, Duration::ZERO));
+        assert!(!lockstep.wait_for_turn(&tactical, Duration::ZERO));
+        assert!(lockstep.wait_for_turn(&created_later, Duration::ZERO));
+
+        lockstep.leave(&strategic);
+        assert!(lockstep.wait_for_turn(&tactical, Duration::ZERO));
+        lockstep.end_turn(&tactical);
+        assert!(lockstep.wait_for_turn(&tactical, Duration::ZERO));
+    }
+}
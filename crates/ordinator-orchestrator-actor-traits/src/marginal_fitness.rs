@@ -1,30 +1,162 @@
 use std::write;
 
-#[derive(Eq, PartialEq, PartialOrd, Ord, Clone, Default)]
+use ordinator_scheduling_environment::worker_environment::MarginalFitnessWeights;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Eq, PartialEq, PartialOrd, Ord, Clone, Default, Serialize, Deserialize)]
 pub enum MarginalFitness
 {
-    Scheduled(u64),
+    Scheduled(FitnessCriteria),
     #[default]
     None,
 }
 
+/// How well a `WorkOrderActivity` fits into the schedule of the operational
+/// actor that bids on it.
+#[derive(Eq, PartialEq, PartialOrd, Ord, Clone, Default, Debug, Serialize, Deserialize)]
+pub struct FitnessCriteria
+{
+    /// Seconds of non-productive time around the activity.
+    pub idle_seconds: u64,
+    /// Position of the required `Resources` among the skills of the
+    /// operational actor, 0 is its main skill.
+    pub skill_rank: u64,
+    /// Other activities of the same work order that the operational actor
+    /// has scheduled.
+    pub continuity: u64,
+    /// Seconds that the activity finishes after its tactical days.
+    pub lateness_seconds: u64,
+}
+
+impl FitnessCriteria
+{
+    /// Weighted cost of the bid in seconds, lower is better. The skill rank
+    /// and continuity are counted in hours. The score saturates instead of
+    /// overflowing.
+    pub fn score(&self, weights: &MarginalFitnessWeights) -> i64
+    {
+        let hour = 3600;
+        let cost = weights
+            .idle_time
+            .saturating_mul(self.idle_seconds)
+            .saturating_add(
+                weights
+                    .skill_match
+                    .saturating_mul(self.skill_rank)
+                    .saturating_mul(hour),
+            )
+            .saturating_add(weights.lateness.saturating_mul(self.lateness_seconds));
+        let bonus = weights
+            .continuity
+            .saturating_mul(self.continuity)
+            .saturating_mul(hour);
+
+        i64::try_from(cost)
+            .unwrap_or(i64::MAX)
+            .saturating_sub(i64::try_from(bonus).unwrap_or(i64::MAX))
+    }
+}
+
+impl MarginalFitness
+{
+    pub fn criteria(&self) -> Option<&FitnessCriteria>
+    {
+        match self {
+            MarginalFitness::Scheduled(fitness_criteria) => Some(fitness_criteria),
+            MarginalFitness::None => None,
+        }
+    }
+}
+
 impl std::fmt::Debug for MarginalFitness
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
     {
         match self {
-            MarginalFitness::Scheduled(time) => write!(
+            MarginalFitness::Scheduled(fitness_criteria) => write!(
                 f,
-                "{}::Scheduled({}, {:?}, {:?})",
+                "{}::Scheduled({}, {:?}, {:?}, skill_rank: {}, continuity: {}, lateness: {})",
                 std::any::type_name::<MarginalFitness>()
                     .split("::")
                     .last()
                     .unwrap(),
-                time,
-                time / 3600,
-                time / 3600 / 24,
+                fitness_criteria.idle_seconds,
+                fitness_criteria.idle_seconds / 3600,
+                fitness_criteria.idle_seconds / 3600 / 24,
+                fitness_criteria.skill_rank,
+                fitness_criteria.continuity,
+                fitness_criteria.lateness_seconds,
             ),
             MarginalFitness::None => write!(f, "MarginalFitness::None"),
         }
     }
 }
+
+#[cfg(test)]
+mod tests
+{
+    use ordinator_scheduling_environment::worker_environment::MarginalFitnessWeights;
+
+    use super::FitnessCriteria;
+
+    #[test]
+    fn test_fitness_criteria_score()
+    {
+        let gap_filler = FitnessCriteria {
+            idle_seconds: 1800,
+            skill_rank: 1,
+            continuity: 0,
+            lateness_seconds: 0,
+        };
+        let on_the_job = FitnessCriteria {
+            idle_seconds: 3600,
+            skill_rank: 0,
+            continuity: 2,
+            lateness_seconds: 0,
+        };
+
+        let idle_only = MarginalFitnessWeights::default();
+        assert!(gap_filler.score(&idle_only) < on_the_job.score(&idle_only));
+
+        let weights = MarginalFitnessWeights {
+            idle_time: 1,
+            skill_match: 1,
+            continuity: 1,
+            lateness: 1,
+        };
+        assert_eq!(gap_filler.score(&weights), 1800 + 3600);
+        assert_eq!(on_the_job.score(&weights), 3600 - 2 * 3600);
+        assert!(on_the_job.score(&weights) < gap_filler.score(&weights));
+    }
+
+    #[test]
+    fn test_fitness_criteria_score_saturates()
+    {
+        let weights = MarginalFitnessWeights {
+            idle_time: u64::MAX,
+            skill_match: u64::MAX,
+            continuity: 0,
+            lateness: 1,
+        };
+        let late = FitnessCriteria {
+            idle_seconds: u64::MAX,
+            skill_rank: 3,
+            continuity: 0,
+            lateness_seconds: u64::MAX,
+        };
+        assert_eq!(late.score(&weights), i64::MAX);
+
+        let weights = MarginalFitnessWeights {
+            idle_time: 0,
+            skill_match: 0,
+            continuity: u64::MAX,
+            lateness: 0,
+        };
+        let continuous = FitnessCriteria {
+            continuity: 2,
+            ..FitnessCriteria::default()
+        };
+        assert_eq!(continuous.score(&weights), -i64::MAX);
+    }
+}
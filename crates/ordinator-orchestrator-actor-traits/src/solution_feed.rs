@@ -0,0 +1,310 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use chrono::DateTime;
+use chrono::Utc;
+use ordinator_scheduling_environment::time_environment::day::Day;
+use ordinator_scheduling_environment::time_environment::period::Period;
+use ordinator_scheduling_environment::work_order::WorkOrderActivity;
+use ordinator_scheduling_environment::work_order::WorkOrderNumber;
+use ordinator_scheduling_environment::worker_environment::resources::Id;
+use serde::Serialize;
+
+use crate::OrchestratorError;
+use crate::OperationalInterface;
+use crate::StrategicInterface;
+use crate::SupervisorInterface;
+use crate::SystemSolutions;
+use crate::TacticalInterface;
+use crate::delegate::Delegate;
+
+/// A single change between two versions of a `SystemSolution`. `None`
+/// means that the work order or activity was not in the solution.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum SolutionChange
+{
+    StrategicPeriod
+    {
+        work_order_number: WorkOrderNumber,
+        from: Option<Period>,
+        to: Option<Period>,
+    },
+    TacticalDay
+    {
+        work_order_activity: WorkOrderActivity,
+        from: Option<Day>,
+        to: Option<Day>,
+    },
+    Delegate
+    {
+        operational_id: Id,
+        work_order_activity: WorkOrderActivity,
+        from: Option<Delegate>,
+        to: Option<Delegate>,
+    },
+    OperationalAssignment
+    {
+        operational_id: Id,
+        work_order_activity: WorkOrderActivity,
+        from: Option<(DateTime<Utc>, DateTime<Utc>)>,
+        to: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    },
+}
+
+/// The changes that took the `SystemSolution` from `from_version` to
+/// `to_version`.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct SolutionDiff
+{
+    pub from_version: u64,
+    pub to_version: u64,
+    pub recorded_at: DateTime<Utc>,
+    pub changes: Vec<SolutionChange>,
+}
+
+/// The latest `SolutionDiff`s of the `SystemSolution` of an `Asset`. The
+/// oldest diffs are dropped when more than `capacity` are recorded.
+pub struct SolutionFeed<Ss>
+{
+    last_recorded: Option<Arc<Ss>>,
+    diffs: VecDeque<SolutionDiff>,
+    capacity: usize,
+}
+
+impl<Ss> SolutionFeed<Ss>
+where
+    Ss: SystemSolutions,
+{
+    pub fn new(capacity: usize) -> Self
+    {
+        Self {
+            last_recorded: None,
+            diffs: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    /// The version of the last recorded `SystemSolution`.
+    pub fn version(&self) -> u64
+    {
+        self.last_recorded
+            .as_ref()
+            .map_or(0, |system_solution| system_solution.version())
+    }
+
+    /// Records the changes since the last recorded `SystemSolution`. Versions
+    /// that are not newer than the last recorded one are ignored, as several
+    /// actors can report the same swap.
+    pub fn record(&mut self, system_solution: Arc<Ss>)
+    {
+        let from_version = self.version();
+        if self.last_recorded.is_some() && system_solution.version() <= from_version {
+            return;
+        }
+
+        let changes = match &self.last_recorded {
+            Some(last_recorded) => diff_system_solutions(&**last_recorded, &*system_solution),
+            None => diff_system_solutions(&Ss::new(), &*system_solution),
+        };
+        let to_version = system_solution.version();
+        self.last_recorded = Some(system_solution);
+
+        if changes.is_empty() {
+            return;
+        }
+        if self.diffs.len() == self.capacity {
+            self.diffs.pop_front();
+        }
+        self.diffs.push_back(SolutionDiff {
+            from_version,
+            to_version,
+            recorded_at: Utc::now(),
+            changes,
+        });
+    }
+
+    /// The diffs after `version`. Fails if the diffs right after `version`
+    /// have been dropped, the caller then has to load the whole solution, or
+    /// if `version` has not been recorded yet.
+    pub fn diffs_since(&self, version: u64) -> Result<Vec<SolutionDiff>, OrchestratorError>
+    {
+        if version > self.version() {
+            return Err(OrchestratorError::InvalidRequest(format!(
+                "Version {version} is newer than the current version {}",
+                self.version()
+            )));
+        }
+        if let Some(oldest) = self.diffs.front()
+            && version < oldest.from_version
+        {
+            return Err(OrchestratorError::InvalidRequest(format!(
+                "The changes since version {version} are no longer available, the oldest is {}",
+                oldest.from_version
+            )));
+        }
+
+        Ok(self
+            .diffs
+            .iter()
+            .filter(|solution_diff| solution_diff.to_version > version)
+            .cloned()
+            .collect())
+    }
+}
+
+pub fn diff_system_solutions<Ss>(old: &Ss, new: &Ss) -> Vec<SolutionChange>
+where
+    Ss: SystemSolutions,
+{
+    let mut changes = vec![];
+
+    let strategic_periods = |system_solution: &Ss| {
+        system_solution
+            .strategic()
+            .map(|strategic| strategic.all_scheduled_tasks())
+            .unwrap_or_default()
+    };
+    let mut strategic_changes = diff_maps(&strategic_periods(old), &strategic_periods(new));
+    strategic_changes.sort_by_key(|(work_order_number, ..)| *work_order_number);
+    changes.extend(
+        strategic_changes
+            .into_iter()
+            .map(
+                |(work_order_number, from, to)| SolutionChange::StrategicPeriod {
+                    work_order_number,
+                    from,
+                    to,
+                },
+            ),
+    );
+
+    let tactical_days = |system_solution: &Ss| -> HashMap<WorkOrderActivity, Day> {
+        system_solution
+            .tactical_actor_solution()
+            .map(|tactical| {
+                tactical
+                    .all_scheduled_tasks()
+                    .into_iter()
+                    .flat_map(|(work_order_number, days)| {
+                        days.into_iter().map(move |(activity_number, day)| {
+                            ((work_order_number, activity_number), day)
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let mut tactical_changes = diff_maps(&tactical_days(old), &tactical_days(new));
+    tactical_changes.sort_by_key(|(work_order_activity, ..)| *work_order_activity);
+    changes.extend(
+        tactical_changes
+            .into_iter()
+            .map(
+                |(work_order_activity, from, to)| SolutionChange::TacticalDay {
+                    work_order_activity,
+                    from,
+                    to,
+                },
+            ),
+    );
+
+    let delegates = |system_solution: &Ss| {
+        system_solution
+            .supervisor_actor_solutions()
+            .map(|supervisors| {
+                supervisors
+                    .values()
+                    .flat_map(|supervisor| supervisor.all_delegates().clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let mut delegate_changes = diff_maps(&delegates(old), &delegates(new));
+    delegate_changes.sort_by(|(a, ..), (b, ..)| (&a.0.0, a.1).cmp(&(&b.0.0, b.1)));
+    changes.extend(delegate_changes.into_iter().map(
+        |((operational_id, work_order_activity), from, to)| SolutionChange::Delegate {
+            operational_id,
+            work_order_activity,
+            from,
+            to,
+        },
+    ));
+
+    let operational_intervals = |system_solution: &Ss| {
+        system_solution
+            .all_operational()
+            .into_iter()
+            .filter_map(|id| {
+                let operational = system_solution.operational_actor_solutions(&id).ok()?;
+                Some((id, operational.scheduled_intervals()))
+            })
+            .flat_map(|(id, intervals)| {
+                intervals
+                    .into_iter()
+                    .map(move |(work_order_activity, interval)| {
+                        ((id.clone(), work_order_activity), interval)
+                    })
+            })
+            .collect::<HashMap<_, _>>()
+    };
+    let mut operational_changes =
+        diff_maps(&operational_intervals(old), &operational_intervals(new));
+    operational_changes.sort_by(|(a, ..), (b, ..)| (&a.0.0, a.1).cmp(&(&b.0.0, b.1)));
+    changes.extend(operational_changes.into_iter().map(
+        |((operational_id, work_order_activity), from, to)| SolutionChange::OperationalAssignment {
+            operational_id,
+            work_order_activity,
+            from,
+            to,
+        },
+    ));
+
+    changes
+}
+
+fn diff_maps<K, V>(old: &HashMap<K, V>, new: &HashMap<K, V>) -> Vec<(K, Option<V>, Option<V>)>
+where
+    K: Eq + Hash + Clone,
+    V: PartialEq + Clone,
+{
+    old.keys()
+        .chain(new.keys())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .filter_map(|key| {
+            let (from, to) = (old.get(key), new.get(key));
+            (from != to).then(|| (key.clone(), from.cloned(), to.cloned()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::collections::HashMap;
+
+    use super::diff_maps;
+
+    #[test]
+    fn test_diff_maps()
+    {
+        let old = HashMap::from([(1, 'a'), (2, 'b'), (3, 'c')]);
+        let new = HashMap::from([(1, 'a'), (2, 'x'), (4, 'd')]);
+
+        let mut changes = diff_maps(&old, &new);
+        changes.sort();
+
+        assert_eq!(
+            changes,
+            vec![
+                (2, Some('b'), Some('x')),
+                (3, Some('c'), None),
+                (4, None, Some('d')),
+            ]
+        );
+    }
+}
@@ -0,0 +1,365 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::RwLock;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+
+use ordinator_scheduling_environment::Asset;
+use ordinator_scheduling_environment::worker_environment::resources::Id;
+
+/// The phases of an LNS iteration that are timed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IterationPhase
+{
+    Unschedule,
+    Schedule,
+    CalculateObjectiveValue,
+}
+
+impl IterationPhase
+{
+    const ALL: [IterationPhase; 3] = [
+        IterationPhase::Unschedule,
+        IterationPhase::Schedule,
+        IterationPhase::CalculateObjectiveValue,
+    ];
+
+    fn label(&self) -> &'static str
+    {
+        match self {
+            IterationPhase::Unschedule => "unschedule",
+            IterationPhase::Schedule => "schedule",
+            IterationPhase::CalculateObjectiveValue => "calculate_objective_value",
+        }
+    }
+}
+
+/// A `f64` that can be shared between the actor thread and the metrics
+/// route. The value is stored as its bit pattern.
+#[derive(Debug)]
+struct AtomicF64(AtomicU64);
+
+impl AtomicF64
+{
+    fn new(value: f64) -> Self
+    {
+        Self(AtomicU64::new(value.to_bits()))
+    }
+
+    fn load(&self) -> f64
+    {
+        f64::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    fn store(&self, value: f64)
+    {
+        self.0.store(value.to_bits(), Ordering::Relaxed)
+    }
+}
+
+#[derive(Debug)]
+struct RateWindow
+{
+    start: Instant,
+    iterations: u64,
+}
+
+/// The optimisation telemetry of a single actor. The actor writes to it on
+/// every LNS iteration and the `MetricsRegistry` reads it when rendering.
+#[derive(Debug)]
+pub struct ActorMetrics
+{
+    iterations: AtomicU64,
+    improved: AtomicU64,
+    accepted: AtomicU64,
+    iterations_per_second: AtomicF64,
+    rate_window: Mutex<RateWindow>,
+    best_objective_value: AtomicF64,
+    current_objective_value: AtomicF64,
+    phase_nanoseconds: [AtomicU64; 3],
+    queue_depth: AtomicU64,
+}
+
+impl Default for ActorMetrics
+{
+    fn default() -> Self
+    {
+        Self {
+            iterations: AtomicU64::new(0),
+            improved: AtomicU64::new(0),
+            accepted: AtomicU64::new(0),
+            iterations_per_second: AtomicF64::new(0.0),
+            rate_window: Mutex::new(RateWindow {
+                start: Instant::now(),
+                iterations: 0,
+            }),
+            best_objective_value: AtomicF64::new(f64::NAN),
+            current_objective_value: AtomicF64::new(f64::NAN),
+            phase_nanoseconds: Default::default(),
+            queue_depth: AtomicU64::new(0),
+        }
+    }
+}
+
+impl ActorMetrics
+{
+    /// Records a finished LNS iteration. `published` is true if the
+    /// candidate replaced the current solution, `current_cost` is the cost of
+    /// the solution that the actor continues from and `best_cost` the cost of
+    /// its best solution.
+    pub fn record_iteration(
+        &self,
+        improved: bool,
+        published: bool,
+        current_cost: f64,
+        best_cost: f64,
+    )
+    {
+        self.iterations.fetch_add(1, Ordering::Relaxed);
+        if improved {
+            self.improved.fetch_add(1, Ordering::Relaxed);
+        }
+        if published {
+            self.accepted.fetch_add(1, Ordering::Relaxed);
+        }
+
+        self.current_objective_value.store(current_cost);
+        if best_cost.is_finite() {
+            self.best_objective_value.store(best_cost);
+        }
+
+        // The rate is measured over windows of at least a second, so that it is
+        // not dominated by the length of a single iteration.
+        let mut rate_window = self.rate_window.lock().unwrap();
+        rate_window.iterations += 1;
+        let elapsed = rate_window.start.elapsed();
+        if elapsed >= Duration::from_secs(1) {
+            self.iterations_per_second
+                .store(rate_window.iterations as f64 / elapsed.as_secs_f64());
+            rate_window.start = Instant::now();
+            rate_window.iterations = 0;
+        }
+    }
+
+    pub fn record_phase(&self, iteration_phase: IterationPhase, duration: Duration)
+    {
+        let index = IterationPhase::ALL
+            .iter()
+            .position(|phase| *phase == iteration_phase)
+            .unwrap();
+        self.phase_nanoseconds[index].fetch_add(duration.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    pub fn record_queue_depth(&self, queue_depth: usize)
+    {
+        self.queue_depth
+            .store(queue_depth as u64, Ordering::Relaxed);
+    }
+
+    pub fn iterations(&self) -> u64
+    {
+        self.iterations.load(Ordering::Relaxed)
+    }
+
+    /// The share of the iterations where the candidate replaced the current
+    /// solution.
+    pub fn acceptance_ratio(&self) -> f64
+    {
+        let iterations = self.iterations.load(Ordering::Relaxed);
+        if iterations == 0 {
+            return 0.0;
+        }
+        self.accepted.load(Ordering::Relaxed) as f64 / iterations as f64
+    }
+}
+
+/// Holds the `ActorMetrics` of every actor and renders them in the
+/// Prometheus text format.
+#[derive(Debug, Default)]
+pub struct MetricsRegistry
+{
+    actors: RwLock<HashMap<Id, Arc<ActorMetrics>>>,
+}
+
+impl MetricsRegistry
+{
+    /// The metrics of a restarted actor continue from where the previous
+    /// actor left them, so that the counters stay monotonic.
+    pub fn actor_metrics(&self, id: &Id) -> Arc<ActorMetrics>
+    {
+        Arc::clone(self.actors.write().unwrap().entry(id.clone()).or_default())
+    }
+
+    pub fn remove(&self, id: &Id)
+    {
+        self.actors.write().unwrap().remove(id);
+    }
+
+    pub fn remove_asset(&self, asset: &Asset)
+    {
+        self.actors
+            .write()
+            .unwrap()
+            .retain(|id, _| id.asset() != asset);
+    }
+
+    /// The fewest LNS iterations that any actor of the asset has run. `None`
+    /// if the asset has no actors.
+    pub fn minimum_iterations(&self, asset: &Asset) -> Option<u64>
+    {
+        self.actors
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(id, _)| id.asset() == asset)
+            .map(|(_, actor_metrics)| actor_metrics.iterations())
+            .min()
+    }
+
+    pub fn render(&self) -> String
+    {
+        let actors = self.actors.read().unwrap();
+        let mut actors = actors.iter().collect::<Vec<_>>();
+        actors.sort_by(|(a, _), (b, _)| {
+            (a.asset().to_string(), &a.0).cmp(&(b.asset().to_string(), &b.0))
+        });
+
+        let mut output = String::new();
+
+        let mut family =
+            |name: &str, kind: &str, help: &str, value: &dyn Fn(&ActorMetrics) -> f64| {
+                writeln!(output, "# HELP {name} {help}").unwrap();
+                writeln!(output, "# TYPE {name} {kind}").unwrap();
+                for (id, actor_metrics) in &actors {
+                    writeln!(
+                        output,
+                        "{name}{{asset=\"{}\",actor=\"{}\"}} {}",
+                        id.asset(),
+                        id.0,
+                        value(actor_metrics)
+                    )
+                    .unwrap();
+                }
+            };
+
+        family(
+            "ordinator_actor_iterations_total",
+            "counter",
+            "LNS iterations run by the actor.",
+            &|metrics| metrics.iterations.load(Ordering::Relaxed) as f64,
+        );
+        family(
+            "ordinator_actor_improvements_total",
+            "counter",
+            "LNS iterations that improved the objective value.",
+            &|metrics| metrics.improved.load(Ordering::Relaxed) as f64,
+        );
+        family(
+            "ordinator_actor_iterations_per_second",
+            "gauge",
+            "LNS iterations per second over the last measured window.",
+            &|metrics| metrics.iterations_per_second.load(),
+        );
+        family(
+            "ordinator_actor_acceptance_ratio",
+            "gauge",
+            "Share of the LNS iterations where the candidate was accepted.",
+            &|metrics| metrics.acceptance_ratio(),
+        );
+        family(
+            "ordinator_actor_best_objective_value",
+            "gauge",
+            "Lowest cost seen by the actor.",
+            &|metrics| metrics.best_objective_value.load(),
+        );
+        family(
+            "ordinator_actor_current_objective_value",
+            "gauge",
+            "Cost of the solution that the actor is working from.",
+            &|metrics| metrics.current_objective_value.load(),
+        );
+        family(
+            "ordinator_actor_message_queue_depth",
+            "gauge",
+            "Messages waiting in the channel of the actor.",
+            &|metrics| metrics.queue_depth.load(Ordering::Relaxed) as f64,
+        );
+
+        writeln!(
+            output,
+            "# HELP ordinator_actor_phase_seconds_total Time spent in each phase of the LNS iterations."
+        )
+        .unwrap();
+        writeln!(output, "# TYPE ordinator_actor_phase_seconds_total counter").unwrap();
+        for (id, actor_metrics) in &actors {
+            for (iteration_phase, nanoseconds) in IterationPhase::ALL
+                .iter()
+                .zip(&actor_metrics.phase_nanoseconds)
+            {
+                writeln!(
+                    output,
+                    "ordinator_actor_phase_seconds_total{{asset=\"{}\",actor=\"{}\",phase=\"{}\"}} {}",
+                    id.asset(),
+                    id.0,
+                    iteration_phase.label(),
+                    Duration::from_nanos(nanoseconds.load(Ordering::Relaxed)).as_secs_f64()
+                )
+                .unwrap();
+            }
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::time::Duration;
+
+    use ordinator_scheduling_environment::Asset;
+    use ordinator_scheduling_environment::worker_environment::resources::Id;
+
+    use super::IterationPhase;
+    use super::MetricsRegistry;
+
+    #[test]
+    fn test_metrics_registry_render()
+    {
+        let metrics_registry = MetricsRegistry::default();
+        let id = Id::new("StrategicActor", vec![], vec![Asset::Test]);
+
+        let actor_metrics = metrics_registry.actor_metrics(&id);
+        actor_metrics.record_iteration(true, true, 10.0, 10.0);
+        actor_metrics.record_iteration(false, false, 10.0, 10.0);
+        actor_metrics.record_iteration(false, true, 12.0, 10.0);
+        actor_metrics.record_phase(IterationPhase::Schedule, Duration::from_millis(1500));
+
+        assert!((actor_metrics.acceptance_ratio() - 2.0 / 3.0).abs() < 1e-9);
+
+        let tactical_id = Id::new("TacticalActor", vec![], vec![Asset::Test]);
+        metrics_registry
+            .actor_metrics(&tactical_id)
+            .record_iteration(true, true, 5.0, 5.0);
+        assert_eq!(metrics_registry.minimum_iterations(&Asset::Test), Some(1));
+        assert_eq!(metrics_registry.minimum_iterations(&Asset::DF), None);
+        metrics_registry.remove(&tactical_id);
+
+        let rendered = metrics_registry.render();
+        let label = format!("{{asset=\"{}\",actor=\"StrategicActor\"}}", Asset::Test);
+
+        assert!(rendered.contains(&format!("ordinator_actor_iterations_total{label} 3")));
+        assert!(rendered.contains(&format!("ordinator_actor_best_objective_value{label} 10")));
+        assert!(rendered.contains(&format!(
+            "ordinator_actor_current_objective_value{label} 12"
+        )));
+        assert!(rendered.contains(&format!(
+            "ordinator_actor_phase_seconds_total{{asset=\"{}\",actor=\"StrategicActor\",phase=\"schedule\"}} 1.5",
+            Asset::Test
+        )));
+    }
+}
@@ -1,18 +1,26 @@
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::thread::JoinHandle;
 
 use anyhow::Context;
 use anyhow::Result;
+use anyhow::anyhow;
+use anyhow::bail;
 use arc_swap::ArcSwap;
 use ordinator_configuration::SystemConfigurations;
 use ordinator_operational_actor::OperationalApi;
 use ordinator_operational_actor::algorithm::operational_solution::OperationalSolution;
 use ordinator_orchestrator_actor_traits::ActorFactory;
+use ordinator_orchestrator_actor_traits::Lifecycle;
+use ordinator_orchestrator_actor_traits::OrchestratorError;
 use ordinator_orchestrator_actor_traits::OrchestratorNotifier;
 use ordinator_orchestrator_actor_traits::SystemSolution;
 use ordinator_orchestrator_actor_traits::SystemSolutions;
+use ordinator_orchestrator_actor_traits::domain_event::DomainEvent;
 use ordinator_scheduling_environment::Asset;
 use ordinator_scheduling_environment::SchedulingEnvironment;
+use ordinator_scheduling_environment::worker_environment::InputOperational;
+use ordinator_scheduling_environment::worker_environment::InputSupervisor;
 use ordinator_scheduling_environment::worker_environment::resources::Id;
 use ordinator_strategic_actor::StrategicApi;
 use ordinator_strategic_actor::algorithm::strategic_solution::StrategicSolution;
@@ -20,9 +28,12 @@ use ordinator_supervisor_actor::SupervisorApi;
 use ordinator_supervisor_actor::algorithm::supervisor_solution::SupervisorSolution;
 use ordinator_tactical_actor::TacticalApi;
 use ordinator_tactical_actor::algorithm::tactical_solution::TacticalSolution;
+use tracing::Level;
+use tracing::event;
 
 use crate::NotifyOrchestrator;
 use crate::Orchestrator;
+use crate::actor_registry::ActorKind;
 
 // This is not a good practice. You know that you will end up here again at some
 // point
@@ -83,7 +94,7 @@ where
     // So the `Id` is actually not only an ID, it specifies everything that is
     // unique to that specific actor. I think that is the reason that the system
     // works so well here.
-    pub fn start_strategic_actor(&mut self, id: &Id) -> Result<()>
+    pub fn start_strategic_actor(&self, id: &Id) -> Result<()>
     {
         // Insert an entry on the SchedulingEnvironment
         let build_dependencies = self.extract_factory_dependencies(id.asset())?;
@@ -98,6 +109,8 @@ where
         //
         //
         // TODO [ ] - Determine what to do about the `ID` here.
+        self.register_cpu_budget(ActorKind::Strategic, id)?;
+
         let communication = <StrategicApi as ActorFactory<Ss>>::construct_actor(
             id.clone(),
             build_dependencies.0,
@@ -119,12 +132,14 @@ where
         Ok(())
     }
 
-    pub fn start_tactical_actor(&mut self, id: &Id) -> Result<()>
+    pub fn start_tactical_actor(&self, id: &Id) -> Result<()>
     {
         // TODO [ ] - Insert entry into the `SchedulingEnvironment`
         let build_dependencies = self.extract_factory_dependencies(id.asset())?;
 
         // TODO [ ] - Determine what to do about the `ID` here.
+        self.register_cpu_budget(ActorKind::Tactical, id)?;
+
         let communication = <TacticalApi as ActorFactory<Ss>>::construct_actor(
             id.clone(),
             build_dependencies.0,
@@ -145,11 +160,13 @@ where
     }
 
     // TODO [ ] - Move the ActorSpecification into the SchedulingEnvironment.
-    pub fn start_supervisor_actor(&mut self, id: &Id) -> Result<()>
+    pub fn start_supervisor_actor(&self, id: &Id) -> Result<()>
     {
         // TODO [ ] - Insert entry into the `SchedulingEnvironment`
         let build_dependencies = self.extract_factory_dependencies(id.asset())?;
 
+        self.register_cpu_budget(ActorKind::Supervisor, id)?;
+
         let communication = <SupervisorApi as ActorFactory<Ss>>::construct_actor(
             id.clone(),
             build_dependencies.0,
@@ -174,12 +191,14 @@ where
     // the `SchedulingEnvironment` This actor is different. We have to insert a
     // different component into the system here. The best approach would
     // probably be to
-    pub fn start_operational_actor(&mut self, id: &Id) -> Result<()>
+    pub fn start_operational_actor(&self, id: &Id) -> Result<()>
     {
         // TODO [ ] - Insert entry into the `SchedulingEnvironment`
         let build_dependencies = self.extract_factory_dependencies(id.asset())?;
 
         // TODO [ ] - Determine what to do about the `ID` here.
+        self.register_cpu_budget(ActorKind::Operational, id)?;
+
         let communication = <OperationalApi as ActorFactory<Ss>>::construct_actor(
             id.clone(),
             build_dependencies.0,
@@ -199,4 +218,736 @@ where
             .insert(id.clone(), communication);
         Ok(())
     }
+
+    /// Adds the supervisor to the `ActorSpecifications` of a running asset and
+    /// starts it.
+    pub fn create_supervisor_actor(
+        &self,
+        asset: &Asset,
+        input_supervisor: InputSupervisor,
+    ) -> Result<()>
+    {
+        let id = input_supervisor.id.clone();
+        self.ensure_new_actor(asset, &id)?;
+
+        self.scheduling_environment
+            .lock()
+            .unwrap()
+            .worker_environment
+            .actor_specification
+            .get_mut(asset)
+            .with_context(|| format!("Missing ActorSpecifications for Asset {asset}"))?
+            .supervisors
+            .push(input_supervisor);
+
+        if let Err(error) = self.start_supervisor_actor(&id) {
+            self.remove_actor_specification(ActorKind::Supervisor, &id);
+            return Err(error);
+        }
+
+        self.database_connections
+            .save_worker_environment(
+                &self
+                    .scheduling_environment
+                    .lock()
+                    .unwrap()
+                    .worker_environment,
+            )
+            .with_context(|| format!("Could not persist {id}"))?;
+
+        event!(Level::INFO, actor = %id, "supervisor actor created");
+        Ok(())
+    }
+
+    /// Adds the operational actor to the `ActorSpecifications` of a running
+    /// asset and starts it. The strategic, tactical and supervisor actors
+    /// rebuild their capacities and operational actors when the
+    /// `DomainEvent::WorkerAdded` reaches them.
+    pub fn create_operational_actor(
+        &self,
+        asset: &Asset,
+        input_operational: InputOperational,
+    ) -> Result<()>
+    {
+        let id = input_operational.id.clone();
+        self.ensure_new_actor(asset, &id)?;
+
+        self.scheduling_environment
+            .lock()
+            .unwrap()
+            .worker_environment
+            .actor_specification
+            .get_mut(asset)
+            .with_context(|| format!("Missing ActorSpecifications for Asset {asset}"))?
+            .operational
+            .push(input_operational);
+
+        if let Err(error) = self.start_operational_actor(&id) {
+            self.remove_actor_specification(ActorKind::Operational, &id);
+            return Err(error);
+        }
+
+        self.database_connections
+            .save_worker_environment(
+                &self
+                    .scheduling_environment
+                    .lock()
+                    .unwrap()
+                    .worker_environment,
+            )
+            .with_context(|| format!("Could not persist {id}"))?;
+
+        self.notify_orchestrator()?
+            .publish_domain_event(DomainEvent::WorkerAdded(id.clone()), asset)?;
+
+        event!(Level::INFO, actor = %id, "operational actor created");
+        Ok(())
+    }
+
+    /// Stops the supervisor, removes its solution and its entry in the
+    /// `ActorSpecifications`.
+    pub fn delete_supervisor_actor(&self, asset: &Asset, id_string: &str) -> Result<Id>
+    {
+        let (actor_kind, id) = self.actor_by_id_string(asset, id_string)?;
+        if actor_kind != ActorKind::Supervisor {
+            bail!(OrchestratorError::InvalidRequest(format!(
+                "{id} is a {actor_kind:?} actor, not a supervisor actor"
+            )));
+        }
+
+        let is_started = self
+            .actor_registries
+            .lock()
+            .unwrap()
+            .get(asset)
+            .is_some_and(|actor_registry| {
+                actor_registry.supervisor_agent_senders.contains_key(&id)
+            });
+        if is_started {
+            self.stop_actor(ActorKind::Supervisor, &id)?;
+        }
+        self.remove_actor_specification(ActorKind::Supervisor, &id);
+        self.metrics_registry.remove(&id);
+
+        self.database_connections
+            .save_worker_environment(
+                &self
+                    .scheduling_environment
+                    .lock()
+                    .unwrap()
+                    .worker_environment,
+            )
+            .with_context(|| format!("Could not persist the removal of {id}"))?;
+
+        self.notify_orchestrator()?
+            .publish_domain_event(DomainEvent::WorkerRemoved(id.clone()), asset)?;
+
+        event!(Level::INFO, actor = %id, "supervisor actor deleted");
+        Ok(id)
+    }
+
+    /// Stops the operational actor, removes its solution and its entry in the
+    /// `ActorSpecifications`. The supervisors assess the remaining operational
+    /// actors for its activities when the `DomainEvent::WorkerRemoved` reaches
+    /// them.
+    pub fn delete_operational_actor(&self, asset: &Asset, id_string: &str) -> Result<Id>
+    {
+        let (actor_kind, id) = self.actor_by_id_string(asset, id_string)?;
+        if actor_kind != ActorKind::Operational {
+            bail!(OrchestratorError::InvalidRequest(format!(
+                "{id} is a {actor_kind:?} actor, not an operational actor"
+            )));
+        }
+
+        let is_started = self
+            .actor_registries
+            .lock()
+            .unwrap()
+            .get(asset)
+            .is_some_and(|actor_registry| {
+                actor_registry.operational_agent_senders.contains_key(&id)
+            });
+        if is_started {
+            self.stop_actor(ActorKind::Operational, &id)?;
+        }
+        self.remove_actor_specification(ActorKind::Operational, &id);
+        self.metrics_registry.remove(&id);
+
+        self.database_connections
+            .save_worker_environment(
+                &self
+                    .scheduling_environment
+                    .lock()
+                    .unwrap()
+                    .worker_environment,
+            )
+            .with_context(|| format!("Could not persist the removal of {id}"))?;
+
+        self.notify_orchestrator()?
+            .publish_domain_event(DomainEvent::WorkerRemoved(id.clone()), asset)?;
+
+        event!(Level::INFO, actor = %id, "operational actor deleted");
+        Ok(id)
+    }
+
+    /// New actors are only created on a running asset, and their `Id` has to
+    /// be unique within it.
+    fn ensure_new_actor(&self, asset: &Asset, id: &Id) -> Result<()>
+    {
+        if id.asset() != asset {
+            bail!(OrchestratorError::InvalidRequest(format!(
+                "{id} does not belong to Asset {asset}"
+            )));
+        }
+        if !self.actor_registries.lock().unwrap().contains_key(asset) {
+            bail!(OrchestratorError::ActorUnavailable(format!(
+                "The scheduling system for Asset {asset}"
+            )));
+        }
+        if self.actor_by_id_string(asset, &id.0).is_ok() {
+            bail!(OrchestratorError::InvalidRequest(format!(
+                "{id} already exists on Asset {asset}"
+            )));
+        }
+        Ok(())
+    }
+
+    fn remove_actor_specification(&self, actor_kind: ActorKind, id: &Id)
+    {
+        if let Some(cpu_budget) = self.cpu_budgets.lock().unwrap().get(id.asset()) {
+            cpu_budget.deregister(id);
+        }
+
+        let mut scheduling_environment_guard = self.scheduling_environment.lock().unwrap();
+        let Some(actor_specification) = scheduling_environment_guard
+            .worker_environment
+            .actor_specification
+            .get_mut(id.asset())
+        else {
+            return;
+        };
+
+        match actor_kind {
+            ActorKind::Supervisor => actor_specification
+                .supervisors
+                .retain(|supervisor| &supervisor.id != id),
+            ActorKind::Operational => actor_specification
+                .operational
+                .retain(|operational| &operational.id != id),
+            ActorKind::Strategic | ActorKind::Tactical => (),
+        }
+    }
+
+    /// Gives the actor its share of the `CpuBudget` of the asset. This has to
+    /// happen before the actor is built, as the actor looks up the budget
+    /// when it starts.
+    pub(crate) fn register_cpu_budget(&self, actor_kind: ActorKind, id: &Id) -> Result<()>
+    {
+        self.cpu_budgets
+            .lock()
+            .unwrap()
+            .get(id.asset())
+            .with_context(|| format!("Missing CpuBudget for Asset {}", id.asset()))?
+            .register(id.clone(), actor_kind);
+        Ok(())
+    }
+
+    /// Finds the `ActorKind` and `Id` of an actor in the `ActorSpecifications`
+    /// of the asset. Stopped actors are found here as well, so that they can be
+    /// restarted.
+    pub fn actor_by_id_string(&self, asset: &Asset, id_string: &str) -> Result<(ActorKind, Id)>
+    {
+        let scheduling_environment_guard = self.scheduling_environment.lock().unwrap();
+        let actor_specification = scheduling_environment_guard
+            .worker_environment
+            .actor_specification
+            .get(asset)
+            .with_context(|| format!("Missing ActorSpecifications for Asset {asset}"))?;
+
+        if actor_specification.strategic.id.0 == id_string {
+            return Ok((
+                ActorKind::Strategic,
+                actor_specification.strategic.id.clone(),
+            ));
+        }
+        if actor_specification.tactical.id.0 == id_string {
+            return Ok((ActorKind::Tactical, actor_specification.tactical.id.clone()));
+        }
+        if let Some(supervisor) = actor_specification
+            .supervisors
+            .iter()
+            .find(|supervisor| supervisor.id.0 == id_string)
+        {
+            return Ok((ActorKind::Supervisor, supervisor.id.clone()));
+        }
+        if let Some(operational) = actor_specification
+            .operational
+            .iter()
+            .find(|operational| operational.id.0 == id_string)
+        {
+            return Ok((ActorKind::Operational, operational.id.clone()));
+        }
+
+        bail!(OrchestratorError::UnknownActor(format!(
+            "{id_string} on Asset {asset}"
+        )))
+    }
+
+    pub fn is_actor_running(&self, actor_kind: ActorKind, id: &Id) -> bool
+    {
+        self.actor_registries
+            .lock()
+            .unwrap()
+            .get(id.asset())
+            .is_some_and(|actor_registry| actor_registry.is_running(actor_kind, id))
+    }
+
+    pub fn pause_actor(&self, actor_kind: ActorKind, id: &Id) -> Result<()>
+    {
+        self.send_lifecycle(actor_kind, id, Lifecycle::Pause)
+    }
+
+    pub fn resume_actor(&self, actor_kind: ActorKind, id: &Id) -> Result<()>
+    {
+        self.send_lifecycle(actor_kind, id, Lifecycle::Resume)
+    }
+
+    fn send_lifecycle(&self, actor_kind: ActorKind, id: &Id, lifecycle: Lifecycle) -> Result<()>
+    {
+        let actor_registries = self.actor_registries.lock().unwrap();
+        let actor_registry = actor_registries
+            .get(id.asset())
+            .with_context(|| format!("Missing ActorRegistry for Asset {}", id.asset()))?;
+
+        match actor_kind {
+            ActorKind::Strategic => actor_registry.strategic_agent_sender.lifecycle(lifecycle),
+            ActorKind::Tactical => actor_registry.tactical_agent_sender.lifecycle(lifecycle),
+            ActorKind::Supervisor => actor_registry
+                .supervisor_agent_senders
+                .get(id)
+                .ok_or_else(|| OrchestratorError::ActorUnavailable(id.to_string()))?
+                .lifecycle(lifecycle),
+            ActorKind::Operational => actor_registry
+                .operational_agent_senders
+                .get(id)
+                .ok_or_else(|| OrchestratorError::ActorUnavailable(id.to_string()))?
+                .lifecycle(lifecycle),
+        }
+    }
+
+    /// Shuts down the actor, joins its thread and removes its solution from
+    /// the `SystemSolution`. The solution is removed after the join so that
+    /// the actor cannot swap it back in. The metrics are kept, so that a
+    /// restarted actor continues its counters.
+    pub fn stop_actor(&self, actor_kind: ActorKind, id: &Id) -> Result<()>
+    {
+        self.shutdown_actor(actor_kind, id, Lifecycle::Shutdown)?;
+
+        if let Some(cpu_budget) = self.cpu_budgets.lock().unwrap().get(id.asset()) {
+            cpu_budget.deregister(id);
+        }
+        self.remove_solution(actor_kind, id)?;
+
+        event!(Level::INFO, actor = %id, ?actor_kind, "actor stopped");
+        Ok(())
+    }
+
+    /// Removes the solution of a stopped actor from the `SystemSolution` of
+    /// its asset.
+    pub(crate) fn remove_solution(&self, actor_kind: ActorKind, id: &Id) -> Result<()>
+    {
+        let system_solution = Arc::clone(
+            self.system_solutions
+                .lock()
+                .unwrap()
+                .get(id.asset())
+                .with_context(|| format!("Missing SystemSolution for Asset {}", id.asset()))?,
+        );
+
+        remove_actor_solution(&system_solution, actor_kind, id)?;
+        self.record_solution_changes(id.asset());
+        Ok(())
+    }
+
+    /// Stops the actor with `lifecycle` and joins its thread. The solution of
+    /// the actor is left in the `SystemSolution`, and written to the
+    /// checkpoint unless the actor was aborted.
+    pub(crate) fn shutdown_actor(
+        &self,
+        actor_kind: ActorKind,
+        id: &Id,
+        lifecycle: Lifecycle,
+    ) -> Result<()>
+    {
+        // The lock on the registries has to be released before joining, as the
+        // actor can be waiting on it in the `OrchestratorNotifier`.
+        let join_handle = self
+            .actor_registries
+            .lock()
+            .unwrap()
+            .get_mut(id.asset())
+            .with_context(|| format!("Missing ActorRegistry for Asset {}", id.asset()))?
+            .stop(actor_kind, id, lifecycle)?;
+
+        join_actor(join_handle, id)?;
+
+        // A failed checkpoint does not stop the shutdown.
+        if lifecycle == Lifecycle::Shutdown
+            && let Err(error) = self.write_checkpoint(id.asset())
+        {
+            event!(Level::WARN, actor = %id, ?error, "could not write checkpoint");
+        }
+        Ok(())
+    }
+
+    /// Stops the actor if it is running and starts it again from the state in
+    /// the `SchedulingEnvironment`.
+    pub fn restart_actor(&self, actor_kind: ActorKind, id: &Id) -> Result<()>
+    {
+        let is_running = self
+            .actor_registries
+            .lock()
+            .unwrap()
+            .get(id.asset())
+            .with_context(|| format!("Missing ActorRegistry for Asset {}", id.asset()))?
+            .is_running(actor_kind, id);
+
+        if is_running {
+            self.stop_actor(actor_kind, id)?;
+        }
+
+        match actor_kind {
+            ActorKind::Strategic => self.start_strategic_actor(id),
+            ActorKind::Tactical => self.start_tactical_actor(id),
+            ActorKind::Supervisor => self.start_supervisor_actor(id),
+            ActorKind::Operational => self.start_operational_actor(id),
+        }?;
+
+        event!(Level::INFO, actor = %id, ?actor_kind, "actor restarted");
+        Ok(())
+    }
+}
+
+pub(crate) fn join_actor(join_handle: Option<JoinHandle<()>>, id: &Id) -> Result<()>
+{
+    if let Some(join_handle) = join_handle {
+        join_handle
+            .join()
+            .map_err(|_| anyhow!("The thread of {id} panicked during shutdown"))?;
+    }
+    Ok(())
+}
+
+/// Removes the solution of the actor from the `SystemSolution`. This fails
+/// without changing anything if another actor owns the solution.
+fn remove_actor_solution<Ss>(
+    system_solution: &ArcSwap<Ss>,
+    actor_kind: ActorKind,
+    id: &Id,
+) -> Result<()>
+where
+    Ss: SystemSolutions,
+{
+    let mut removal = Ok(());
+    system_solution.rcu(|old| {
+        let mut system_solution = (**old).clone();
+        removal = match actor_kind {
+            ActorKind::Strategic => system_solution.strategic_remove(id).map(drop),
+            ActorKind::Tactical => system_solution.tactical_remove(id).map(drop),
+            ActorKind::Supervisor => system_solution.supervisor_remove(id).map(drop),
+            ActorKind::Operational => system_solution.operational_remove(id).map(drop),
+        };
+        match removal {
+            Ok(()) => Arc::new(system_solution),
+            Err(_) => Arc::clone(old),
+        }
+    });
+    removal.with_context(|| format!("Could not remove the solution of {id}"))
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use arc_swap::ArcSwap;
+    use ordinator_orchestrator_actor_traits::ActorMessage;
+    use ordinator_orchestrator_actor_traits::Communication;
+    use ordinator_orchestrator_actor_traits::Lifecycle;
+    use ordinator_orchestrator_actor_traits::SystemSolutions;
+    use ordinator_scheduling_environment::Asset;
+    use ordinator_scheduling_environment::worker_environment::resources::Id;
+    use ordinator_supervisor_actor::algorithm::supervisor_solution::SupervisorSolution;
+
+    use super::TotalSystemSolution;
+    use super::join_actor;
+    use super::remove_actor_solution;
+    use crate::Orchestrator;
+    use crate::OrchestratorError;
+    use crate::actor_registry::ActorKind;
+    use crate::actor_registry::ActorRegistry;
+    use crate::test_environment::test_asset_scheduling_environment;
+
+    /// Stands in for an actor thread. It runs `publish` when it is shut down,
+    /// so that the tests can check that a solution written before the join is
+    /// removed afterwards.
+    fn spawn_test_actor<Req, Res>(
+        publish: impl FnOnce() + Send + 'static,
+    ) -> Communication<Req, Res>
+    where
+        Req: Send + 'static,
+        Res: Send + 'static,
+    {
+        let (sender, receiver) = flume::unbounded::<ActorMessage<Req, Res>>();
+        let mut communication = Communication::new(sender);
+        communication.set_join_handle(std::thread::spawn(move || {
+            for message in receiver.iter() {
+                if let ActorMessage::Lifecycle(Lifecycle::Shutdown) = message {
+                    break;
+                }
+            }
+            publish();
+        }));
+        communication
+    }
+
+    fn publish_supervisor(
+        system_solution: &Arc<ArcSwap<TotalSystemSolution>>,
+        id: &Id,
+    ) -> impl FnOnce() + Send + 'static
+    {
+        let system_solution = Arc::clone(system_solution);
+        let id = id.clone();
+        move || {
+            system_solution.rcu(|old| {
+                let mut system_solution = (**old).clone();
+                system_solution.supervisor_swap(&id, SupervisorSolution::default());
+                Arc::new(system_solution)
+            });
+        }
+    }
+
+    #[test]
+    fn test_stop_start_and_restart_supervisor_actor()
+    {
+        let system_solution = Arc::new(ArcSwap::from_pointee(TotalSystemSolution::new()));
+        let id = Id::new("SUP-01", vec![], vec![Asset::Test]);
+
+        let mut actor_registry = ActorRegistry::new(
+            spawn_test_actor(|| ()),
+            spawn_test_actor(|| ()),
+            HashMap::from([(
+                id.clone(),
+                spawn_test_actor(publish_supervisor(&system_solution, &id)),
+            )]),
+            HashMap::new(),
+        );
+        assert!(actor_registry.is_running(ActorKind::Supervisor, &id));
+
+        // Stop
+        let join_handle = actor_registry
+            .stop(ActorKind::Supervisor, &id, Lifecycle::Shutdown)
+            .unwrap();
+        join_actor(join_handle, &id).unwrap();
+        assert!(!actor_registry.supervisor_agent_senders.contains_key(&id));
+        assert!(system_solution.load().supervisor_actor_solutions().is_ok());
+
+        remove_actor_solution(&system_solution, ActorKind::Supervisor, &id).unwrap();
+        assert!(system_solution.load().supervisor_actor_solutions().is_err());
+        assert!(
+            actor_registry
+                .stop(ActorKind::Supervisor, &id, Lifecycle::Shutdown)
+                .is_err()
+        );
+
+        // Start
+        actor_registry.add_supervisor_agent(
+            id.clone(),
+            spawn_test_actor(publish_supervisor(&system_solution, &id)),
+        );
+        assert!(actor_registry.is_running(ActorKind::Supervisor, &id));
+
+        // Restart
+        let join_handle = actor_registry
+            .stop(ActorKind::Supervisor, &id, Lifecycle::Shutdown)
+            .unwrap();
+        join_actor(join_handle, &id).unwrap();
+        remove_actor_solution(&system_solution, ActorKind::Supervisor, &id).unwrap();
+        actor_registry.add_supervisor_agent(
+            id.clone(),
+            spawn_test_actor(publish_supervisor(&system_solution, &id)),
+        );
+        assert!(actor_registry.is_running(ActorKind::Supervisor, &id));
+        assert!(system_solution.load().supervisor_actor_solutions().is_err());
+
+        // A stopped strategic actor keeps its slot in the registry.
+        let join_handle = actor_registry
+            .stop(ActorKind::Strategic, &id, Lifecycle::Shutdown)
+            .unwrap();
+        join_actor(join_handle, &id).unwrap();
+        assert!(!actor_registry.is_running(ActorKind::Strategic, &id));
+    }
+
+    #[test]
+    fn test_remove_actor_solution_keeps_the_other_supervisors()
+    {
+        let system_solution = ArcSwap::from_pointee(TotalSystemSolution::new());
+        let first = Id::new("SUP-01", vec![], vec![Asset::Test]);
+        let second = Id::new("SUP-02", vec![], vec![Asset::Test]);
+        let unknown = Id::new("SUP-03", vec![], vec![Asset::Test]);
+
+        for id in [&first, &second] {
+            system_solution.rcu(|old| {
+                let mut system_solution = (**old).clone();
+                system_solution.supervisor_swap(id, SupervisorSolution::default());
+                Arc::new(system_solution)
+            });
+        }
+        assert_eq!(
+            system_solution
+                .load()
+                .supervisor_actor_solutions()
+                .unwrap()
+                .len(),
+            2
+        );
+        let version = system_solution.load().version();
+
+        remove_actor_solution(&system_solution, ActorKind::Supervisor, &unknown).unwrap();
+        assert_eq!(system_solution.load().version(), version);
+
+        remove_actor_solution(&system_solution, ActorKind::Supervisor, &first).unwrap();
+        assert!(
+            system_solution
+                .load()
+                .supervisor_actor_solution(&first)
+                .is_err()
+        );
+        assert!(
+            system_solution
+                .load()
+                .supervisor_actor_solution(&second)
+                .is_ok()
+        );
+
+        remove_actor_solution(&system_solution, ActorKind::Supervisor, &second).unwrap();
+        assert!(system_solution.load().supervisor_actor_solutions().is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_delete_supervisor_actor()
+    {
+        let database_path = std::env::temp_dir().join(format!(
+            "ordinator_delete_supervisor_test_{}.redb",
+            std::process::id()
+        ));
+        let orchestrator =
+            Orchestrator::for_tests(&database_path, &test_asset_scheduling_environment());
+        orchestrator.start_asset(&Asset::Test).unwrap();
+
+        let error = orchestrator
+            .delete_supervisor_actor(&Asset::Test, "OP-01-001")
+            .unwrap_err();
+        assert!(matches!(
+            OrchestratorError::find(&error),
+            Some(OrchestratorError::InvalidRequest(_))
+        ));
+
+        let id = orchestrator
+            .delete_supervisor_actor(&Asset::Test, "SUP-01")
+            .unwrap();
+        assert!(
+            !orchestrator.actor_registries.lock().unwrap()[&Asset::Test]
+                .supervisor_agent_senders
+                .contains_key(&id)
+        );
+        assert!(
+            orchestrator.system_solutions.lock().unwrap()[&Asset::Test]
+                .load()
+                .supervisor_actor_solution(&id)
+                .is_err()
+        );
+
+        let persisted_scheduling_environment = orchestrator
+            .database_connections
+            .scheduling_environment(orchestrator.system_configurations.clone())
+            .unwrap();
+        for scheduling_environment in [
+            &orchestrator.scheduling_environment,
+            &persisted_scheduling_environment,
+        ] {
+            assert!(
+                scheduling_environment
+                    .lock()
+                    .unwrap()
+                    .worker_environment
+                    .actor_specification[&Asset::Test]
+                    .supervisors
+                    .is_empty()
+            );
+        }
+
+        let error = orchestrator
+            .delete_supervisor_actor(&Asset::Test, "SUP-01")
+            .unwrap_err();
+        assert!(matches!(
+            OrchestratorError::find(&error),
+            Some(OrchestratorError::UnknownActor(_))
+        ));
+
+        orchestrator.stop_asset(&Asset::Test).unwrap();
+        std::fs::remove_file(&database_path).unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_stop_and_restart_actor()
+    {
+        let database_path = std::env::temp_dir().join(format!(
+            "ordinator_stop_and_restart_actor_test_{}.redb",
+            std::process::id()
+        ));
+        let orchestrator =
+            Orchestrator::for_tests(&database_path, &test_asset_scheduling_environment());
+        orchestrator.start_asset(&Asset::Test).unwrap();
+        let (actor_kind, id) = orchestrator
+            .actor_by_id_string(&Asset::Test, "SUP-01")
+            .unwrap();
+        let supervisor_solution_exists = || {
+            orchestrator.system_solutions.lock().unwrap()[&Asset::Test]
+                .load()
+                .supervisor_actor_solution(&id)
+                .is_ok()
+        };
+
+        // Stop
+        orchestrator.stop_actor(actor_kind, &id).unwrap();
+        assert!(!orchestrator.is_actor_running(actor_kind, &id));
+        assert!(!supervisor_solution_exists());
+        assert!(orchestrator.stop_actor(actor_kind, &id).is_err());
+
+        // A stopped actor is started again.
+        orchestrator.restart_actor(actor_kind, &id).unwrap();
+        assert!(orchestrator.is_actor_running(actor_kind, &id));
+        assert_eq!(
+            orchestrator.actor_registries.lock().unwrap()[&Asset::Test]
+                .supervisor_by_id_string("SUP-01")
+                .unwrap(),
+            id
+        );
+
+        // A running actor is stopped first.
+        orchestrator.restart_actor(actor_kind, &id).unwrap();
+        assert!(orchestrator.is_actor_running(actor_kind, &id));
+
+        orchestrator.stop_actor(actor_kind, &id).unwrap();
+        assert!(matches!(
+            orchestrator.actor_registries.lock().unwrap()[&Asset::Test]
+                .supervisor_by_id_string("SUP-01"),
+            Err(OrchestratorError::UnknownActor(_))
+        ));
+
+        orchestrator.stop_asset(&Asset::Test).unwrap();
+        std::fs::remove_file(&database_path).unwrap();
+    }
 }
@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use anyhow::ensure;
+use chrono::DateTime;
+use chrono::Utc;
+use ordinator_operational_actor::algorithm::operational_solution::OperationalSolution;
+use ordinator_orchestrator_actor_traits::ActorOptions;
+use ordinator_orchestrator_actor_traits::OrchestratorError;
+use ordinator_orchestrator_actor_traits::SystemSolutions;
+use ordinator_scheduling_environment::Asset;
+use ordinator_scheduling_environment::worker_environment::resources::Id;
+use ordinator_strategic_actor::algorithm::strategic_solution::StrategicSolution;
+use ordinator_supervisor_actor::algorithm::supervisor_solution::SupervisorSolution;
+use ordinator_tactical_actor::algorithm::tactical_solution::TacticalSolution;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::Level;
+use tracing::event;
+
+use crate::Orchestrator;
+use crate::actor_registry::ActorKind;
+
+/// New options and/or throttling for a running actor. Throttling is shared by
+/// all actors of the same kind.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ActorOptionsUpdate
+{
+    pub options: Option<ActorOptions>,
+    pub throttling_milliseconds: Option<u64>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ActorOptionsRecord
+{
+    pub applied_at: DateTime<Utc>,
+    pub actor: String,
+    pub update: ActorOptionsUpdate,
+}
+
+/// Every options update that has been applied, so that a schedule can be
+/// traced back to the weights that produced it. The history is only kept in
+/// memory and starts empty when the orchestrator is restarted.
+#[derive(Default)]
+pub struct ActorOptionsHistory
+{
+    records: HashMap<Asset, Vec<ActorOptionsRecord>>,
+}
+
+impl ActorOptionsHistory
+{
+    pub fn record(&mut self, asset: &Asset, actor_options_record: ActorOptionsRecord)
+    {
+        self.records
+            .entry(asset.clone())
+            .or_default()
+            .push(actor_options_record);
+    }
+
+    pub fn records(&self, asset: &Asset) -> Vec<ActorOptionsRecord>
+    {
+        self.records.get(asset).cloned().unwrap_or_default()
+    }
+}
+
+impl<Ss> Orchestrator<Ss>
+where
+    Ss: SystemSolutions<
+            Strategic = StrategicSolution,
+            Tactical = TacticalSolution,
+            Supervisor = SupervisorSolution,
+            Operational = OperationalSolution,
+        > + Send
+        + Sync
+        + 'static,
+{
+    /// Validates the update and applies it to the running actor. The options
+    /// are also written to the `ActorSpecifications`, so that a restarted
+    /// actor keeps them.
+    pub fn update_actor_options(
+        &self,
+        asset: &Asset,
+        id_string: &str,
+        actor_options_update: ActorOptionsUpdate,
+    ) -> Result<ActorOptionsRecord>
+    {
+        let (actor_kind, id) = self.actor_by_id_string(asset, id_string)?;
+
+        ensure!(
+            actor_options_update.options.is_some()
+                || actor_options_update.throttling_milliseconds.is_some(),
+            OrchestratorError::InvalidRequest(format!("The options update for {id} is empty"))
+        );
+
+        if let Some(actor_options) = &actor_options_update.options {
+            if actor_options.actor_kind() != actor_kind {
+                bail!(OrchestratorError::InvalidRequest(format!(
+                    "{id} is a {actor_kind:?} actor and cannot use {:?} options",
+                    actor_options.actor_kind()
+                )));
+            }
+            actor_options.validate().map_err(|error| {
+                OrchestratorError::InvalidRequest(format!("Invalid options for {id}: {error:#}"))
+            })?;
+        }
+
+        if let Some(throttling_milliseconds) = actor_options_update.throttling_milliseconds {
+            let mut system_configurations = (**self.system_configurations.load()).clone();
+            system_configurations
+                .throttling
+                .set_throttling(&id.0, throttling_milliseconds)
+                .map_err(|error| {
+                    OrchestratorError::InvalidRequest(format!(
+                        "Invalid throttling for {id}: {error:#}"
+                    ))
+                })?;
+            self.system_configurations
+                .store(Arc::new(system_configurations));
+        }
+
+        if let Some(actor_options) = &actor_options_update.options {
+            self.store_actor_options(&id, actor_options.clone())?;
+            self.send_actor_options(actor_kind, &id, actor_options.clone())?;
+        }
+
+        let actor_options_record = ActorOptionsRecord {
+            applied_at: Utc::now(),
+            actor: id.0.clone(),
+            update: actor_options_update,
+        };
+
+        event!(Level::INFO, actor = %id, update = ?actor_options_record.update, "actor options updated");
+
+        self.actor_options_history
+            .lock()
+            .unwrap()
+            .record(asset, actor_options_record.clone());
+
+        Ok(actor_options_record)
+    }
+
+    pub fn actor_options_history(&self, asset: &Asset) -> Vec<ActorOptionsRecord>
+    {
+        self.actor_options_history.lock().unwrap().records(asset)
+    }
+
+    fn store_actor_options(&self, id: &Id, actor_options: ActorOptions) -> Result<()>
+    {
+        let mut scheduling_environment_guard = self.scheduling_environment.lock().unwrap();
+        let actor_specification = scheduling_environment_guard
+            .worker_environment
+            .actor_specification
+            .get_mut(id.asset())
+            .with_context(|| format!("Missing ActorSpecifications for Asset {}", id.asset()))?;
+
+        match actor_options {
+            ActorOptions::Strategic(strategic_options) => {
+                actor_specification.strategic.strategic_options = strategic_options
+            }
+            ActorOptions::Tactical(tactical_options) => {
+                actor_specification.tactical.tactical_options = tactical_options
+            }
+            ActorOptions::Supervisor(supervisor_options) => {
+                actor_specification
+                    .supervisors
+                    .iter_mut()
+                    .find(|input_supervisor| input_supervisor.id == *id)
+                    .with_context(|| format!("Missing a Supervisor entry for {id}"))?
+                    .supervisor_options = supervisor_options
+            }
+            ActorOptions::Operational(operational_options) => {
+                actor_specification
+                    .operational
+                    .iter_mut()
+                    .find(|input_operational| input_operational.id == *id)
+                    .with_context(|| format!("Missing an Operational entry for {id}"))?
+                    .operational_options = operational_options
+            }
+        }
+        Ok(())
+    }
+
+    fn send_actor_options(
+        &self,
+        actor_kind: ActorKind,
+        id: &Id,
+        actor_options: ActorOptions,
+    ) -> Result<()>
+    {
+        let actor_registries = self.actor_registries.lock().unwrap();
+        let actor_registry = actor_registries
+            .get(id.asset())
+            .with_context(|| format!("Missing ActorRegistry for Asset {}", id.asset()))?;
+
+        match actor_kind {
+            ActorKind::Strategic => actor_registry.strategic_agent_sender.options(actor_options),
+            ActorKind::Tactical => actor_registry.tactical_agent_sender.options(actor_options),
+            ActorKind::Supervisor => match actor_registry.supervisor_agent_senders.get(id) {
+                Some(communication) => communication.options(actor_options),
+                None => bail!(OrchestratorError::ActorUnavailable(id.to_string())),
+            },
+            ActorKind::Operational => match actor_registry.operational_agent_senders.get(id) {
+                Some(communication) => communication.options(actor_options),
+                None => bail!(OrchestratorError::ActorUnavailable(id.to_string())),
+            },
+        }
+    }
+}
@@ -1,8 +1,13 @@
 use std::collections::HashMap;
+use std::thread::JoinHandle;
 
+use anyhow::Result;
 use ordinator_operational_actor::messages::OperationalRequestMessage;
 use ordinator_operational_actor::messages::OperationalResponseMessage;
+pub use ordinator_orchestrator_actor_traits::ActorKind;
 use ordinator_orchestrator_actor_traits::Communication;
+use ordinator_orchestrator_actor_traits::Lifecycle;
+use ordinator_orchestrator_actor_traits::OrchestratorError;
 use ordinator_scheduling_environment::worker_environment::resources::Id;
 use ordinator_strategic_actor::messages::StrategicRequestMessage;
 use ordinator_strategic_actor::messages::StrategicResponseMessage;
@@ -23,6 +28,48 @@ pub struct ActorRegistry
 
 impl ActorRegistry
 {
+    pub fn is_running(&self, actor_kind: ActorKind, id: &Id) -> bool
+    {
+        match actor_kind {
+            ActorKind::Strategic => self.strategic_agent_sender.is_running(),
+            ActorKind::Tactical => self.tactical_agent_sender.is_running(),
+            ActorKind::Supervisor => self
+                .supervisor_agent_senders
+                .get(id)
+                .is_some_and(|communication| communication.is_running()),
+            ActorKind::Operational => self
+                .operational_agent_senders
+                .get(id)
+                .is_some_and(|communication| communication.is_running()),
+        }
+    }
+
+    /// Stops the actor with `lifecycle` and hands out its `JoinHandle`.
+    /// Supervisor and operational actors are removed from the registry, the
+    /// strategic and tactical slots stay until a restart replaces them.
+    pub fn stop(
+        &mut self,
+        actor_kind: ActorKind,
+        id: &Id,
+        lifecycle: Lifecycle,
+    ) -> Result<Option<JoinHandle<()>>>
+    {
+        Ok(match actor_kind {
+            ActorKind::Strategic => self.strategic_agent_sender.stop(lifecycle),
+            ActorKind::Tactical => self.tactical_agent_sender.stop(lifecycle),
+            ActorKind::Supervisor => self
+                .supervisor_agent_senders
+                .remove(id)
+                .ok_or_else(|| OrchestratorError::ActorUnavailable(id.to_string()))?
+                .stop(lifecycle),
+            ActorKind::Operational => self
+                .operational_agent_senders
+                .remove(id)
+                .ok_or_else(|| OrchestratorError::ActorUnavailable(id.to_string()))?
+                .stop(lifecycle),
+        })
+    }
+
     pub fn get_operational_addr(
         &self,
         operational_id: &String,
@@ -0,0 +1,266 @@
+use anyhow::Context;
+use anyhow::Result;
+use ordinator_operational_actor::algorithm::operational_solution::OperationalSolution;
+use ordinator_orchestrator_actor_traits::Lifecycle;
+use ordinator_orchestrator_actor_traits::OrchestratorError;
+use ordinator_orchestrator_actor_traits::SystemSolutions;
+use ordinator_scheduling_environment::Asset;
+use ordinator_scheduling_environment::worker_environment::resources::Id;
+use ordinator_strategic_actor::algorithm::strategic_solution::StrategicSolution;
+use ordinator_supervisor_actor::algorithm::supervisor_solution::SupervisorSolution;
+use ordinator_tactical_actor::algorithm::tactical_solution::TacticalSolution;
+use serde::Serialize;
+use tracing::Level;
+use tracing::event;
+
+use crate::Orchestrator;
+use crate::actor_registry::ActorKind;
+
+/// Whether the scheduling system of an `Asset` is running, and how many of
+/// its actors are.
+#[derive(Debug, Clone, Serialize)]
+pub struct AssetStatus
+{
+    pub asset: Asset,
+    pub running: bool,
+    pub specified_actors: usize,
+    pub running_actors: usize,
+}
+
+impl<Ss> Orchestrator<Ss>
+where
+    Ss: SystemSolutions<
+            Strategic = StrategicSolution,
+            Tactical = TacticalSolution,
+            Supervisor = SupervisorSolution,
+            Operational = OperationalSolution,
+        > + Send
+        + Sync
+        + 'static,
+{
+    /// Starts the `Asset`s that the `AssetsConfiguration` starts together
+    /// with the server. An `Asset` that fails to start is logged and left
+    /// stopped, so that it does not keep the other `Asset`s from starting.
+    pub fn start_configured_assets(&self) -> Result<()>
+    {
+        let started = self.system_configurations.load().assets.started.clone();
+        for asset in &started {
+            if let Err(error) = self.start_asset(asset) {
+                event!(
+                    Level::ERROR,
+                    %asset,
+                    error = format!("{error:#}"),
+                    "could not start the Asset"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Starts the actors of an available `Asset` with its own `ActorRegistry`
+    /// and `SystemSolution`.
+    pub fn start_asset(&self, asset: &Asset) -> Result<()>
+    {
+        // Two concurrent starts would otherwise both find the `Asset` stopped
+        // and each build a set of actors.
+        let _asset_transition = self.asset_transitions.lock().unwrap();
+
+        if !self
+            .scheduling_environment
+            .lock()
+            .unwrap()
+            .worker_environment
+            .actor_specification
+            .contains_key(asset)
+        {
+            return Err(OrchestratorError::InvalidRequest(format!(
+                "Asset {asset} is not available in the assets configuration"
+            ))
+            .into());
+        }
+        if self.actor_registries.lock().unwrap().contains_key(asset) {
+            return Err(OrchestratorError::IllegalTransition(format!(
+                "Asset {asset} is already running"
+            ))
+            .into());
+        }
+
+        self.supervision_tree.lock().unwrap().reset_asset(asset);
+
+        if let Err(error) = self.asset_factory(asset) {
+            self.system_solutions.lock().unwrap().remove(asset);
+            self.cpu_budgets.lock().unwrap().remove(asset);
+            self.locksteps.lock().unwrap().remove(asset);
+            return Err(error.context(format!("Could not start the actors of Asset {asset}")));
+        }
+
+        event!(Level::INFO, %asset, "asset started");
+        Ok(())
+    }
+
+    /// Shuts down the actors of the `Asset` and drops its `ActorRegistry` and
+    /// `SystemSolution`.
+    pub fn stop_asset(&self, asset: &Asset) -> Result<()>
+    {
+        let _asset_transition = self.asset_transitions.lock().unwrap();
+
+        if !self.actor_registries.lock().unwrap().contains_key(asset) {
+            return Err(OrchestratorError::IllegalTransition(format!(
+                "Asset {asset} is not running"
+            ))
+            .into());
+        }
+
+        self.shutdown_asset(asset)?;
+
+        self.actor_registries.lock().unwrap().remove(asset);
+        self.metrics_registry.remove_asset(asset);
+        self.system_solutions.lock().unwrap().remove(asset);
+        self.cpu_budgets.lock().unwrap().remove(asset);
+        self.locksteps.lock().unwrap().remove(asset);
+        self.solution_subscribers.lock().unwrap().remove(asset);
+        self.solution_feeds.lock().unwrap().remove(asset);
+        self.checkpointed_versions.lock().unwrap().remove(asset);
+        self.supervision_tree.lock().unwrap().reset_asset(asset);
+        self.undo_stacks.lock().unwrap().clear_asset(asset);
+
+        event!(Level::INFO, %asset, "asset stopped");
+        Ok(())
+    }
+
+    /// The status of every available `Asset`.
+    pub fn asset_statuses(&self) -> Vec<AssetStatus>
+    {
+        let scheduling_environment_guard = self.scheduling_environment.lock().unwrap();
+        let actor_registries = self.actor_registries.lock().unwrap();
+
+        let mut asset_statuses: Vec<AssetStatus> = scheduling_environment_guard
+            .worker_environment
+            .actor_specification
+            .iter()
+            .map(|(asset, actor_specification)| {
+                let actors: Vec<(ActorKind, &Id)> = [
+                    (ActorKind::Strategic, &actor_specification.strategic.id),
+                    (ActorKind::Tactical, &actor_specification.tactical.id),
+                ]
+                .into_iter()
+                .chain(
+                    actor_specification
+                        .supervisors
+                        .iter()
+                        .map(|supervisor| (ActorKind::Supervisor, &supervisor.id)),
+                )
+                .chain(
+                    actor_specification
+                        .operational
+                        .iter()
+                        .map(|operational| (ActorKind::Operational, &operational.id)),
+                )
+                .collect();
+
+                let actor_registry = actor_registries.get(asset);
+                AssetStatus {
+                    asset: asset.clone(),
+                    running: actor_registry.is_some(),
+                    specified_actors: actors.len(),
+                    running_actors: actors
+                        .iter()
+                        .filter(|(actor_kind, id)| {
+                            actor_registry.is_some_and(|actor_registry| {
+                                actor_registry.is_running(*actor_kind, id)
+                            })
+                        })
+                        .count(),
+                }
+            })
+            .collect();
+        asset_statuses.sort_by_key(|asset_status| asset_status.asset.to_string());
+        asset_statuses
+    }
+
+    /// Shuts down the actors of the `Asset` from the bottom of the hierarchy
+    /// and up, keeping their solutions in the `SystemSolution`.
+    pub(crate) fn shutdown_asset(&self, asset: &Asset) -> Result<()>
+    {
+        let (strategic_id, tactical_id) = {
+            let scheduling_environment_guard = self.scheduling_environment.lock().unwrap();
+            let actor_specification = scheduling_environment_guard
+                .worker_environment
+                .actor_specification
+                .get(asset)
+                .with_context(|| format!("Missing actor specification for Asset {asset}"))?;
+            (
+                actor_specification.strategic.id.clone(),
+                actor_specification.tactical.id.clone(),
+            )
+        };
+
+        let (supervisor_ids, operational_ids): (Vec<Id>, Vec<Id>) = {
+            let actor_registries = self.actor_registries.lock().unwrap();
+            let actor_registry = actor_registries
+                .get(asset)
+                .with_context(|| format!("Missing ActorRegistry for Asset {asset}"))?;
+            (
+                actor_registry
+                    .supervisor_agent_senders
+                    .keys()
+                    .cloned()
+                    .collect(),
+                actor_registry
+                    .operational_agent_senders
+                    .keys()
+                    .cloned()
+                    .collect(),
+            )
+        };
+
+        for operational_id in &operational_ids {
+            self.shutdown_actor(ActorKind::Operational, operational_id, Lifecycle::Shutdown)?;
+        }
+        for supervisor_id in &supervisor_ids {
+            self.shutdown_actor(ActorKind::Supervisor, supervisor_id, Lifecycle::Shutdown)?;
+        }
+        self.shutdown_actor(ActorKind::Tactical, &tactical_id, Lifecycle::Shutdown)?;
+        self.shutdown_actor(ActorKind::Strategic, &strategic_id, Lifecycle::Shutdown)?;
+
+        event!(Level::INFO, %asset, "actors of the asset shut down");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use ordinator_orchestrator_actor_traits::OrchestratorError;
+    use ordinator_scheduling_environment::Asset;
+
+    use crate::Orchestrator;
+    use crate::test_environment::test_asset_scheduling_environment;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_starting_a_running_and_stopping_a_stopped_asset_are_illegal()
+    {
+        let database_path = std::env::temp_dir().join(format!(
+            "ordinator_asset_transitions_test_{}.redb",
+            std::process::id()
+        ));
+        let orchestrator =
+            Orchestrator::for_tests(&database_path, &test_asset_scheduling_environment());
+        let is_illegal_transition = |result: anyhow::Result<()>| {
+            matches!(
+                OrchestratorError::find(&result.unwrap_err()),
+                Some(OrchestratorError::IllegalTransition(_))
+            )
+        };
+
+        assert!(is_illegal_transition(orchestrator.stop_asset(&Asset::Test)));
+        orchestrator.start_asset(&Asset::Test).unwrap();
+        assert!(is_illegal_transition(
+            orchestrator.start_asset(&Asset::Test)
+        ));
+        orchestrator.stop_asset(&Asset::Test).unwrap();
+        assert!(is_illegal_transition(orchestrator.stop_asset(&Asset::Test)));
+
+        std::fs::remove_file(&database_path).unwrap();
+    }
+}
@@ -0,0 +1,291 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use arc_swap::ArcSwap;
+use ordinator_configuration::SystemConfigurations;
+use ordinator_scheduling_environment::Asset;
+use tokio::task::JoinHandle;
+use tracing::Level;
+use tracing::event;
+
+use crate::Orchestrator;
+use crate::TotalSystemSolution;
+
+/// How long a batch solve runs before the solutions are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveBudget
+{
+    WallClock(Duration),
+    /// Every actor of the solved `Asset`s runs this many LNS iterations and
+    /// then pauses.
+    Iterations(u64),
+}
+
+/// Runs the scheduling system headless for the `Asset`s until the
+/// `SolveBudget` is spent, and writes the final `SystemSolution` of every
+/// `Asset` to the `output_directory` as JSON and xlsx. Checkpoints are not
+/// used. The actors of an `Asset` take turns in a `Lockstep`, so that an
+/// iteration budget and the same `master_seed` reproduce the `SystemSolution`.
+///
+/// The solve has its own database, initialized from the source data, so that
+/// it neither reads nor changes the planning of a running server.
+pub async fn solve(
+    assets: &[Asset],
+    budget: SolveBudget,
+    output_directory: &Path,
+    master_seed: Option<u64>,
+) -> Result<Vec<PathBuf>>
+{
+    let database_path =
+        std::env::temp_dir().join(format!("ordinator_batch_solve_{}.redb", std::process::id()));
+    if database_path.exists() {
+        std::fs::remove_file(&database_path)
+            .with_context(|| format!("Could not remove {}", database_path.display()))?;
+    }
+
+    let configurations = SystemConfigurations::read_all_configs()
+        .context("Could not read the SystemConfigurations")?;
+    configurations.rcu(|old| {
+        let mut system_configurations = (**old).clone();
+        system_configurations.database_config = database_path.clone();
+        batch_configurations(&mut system_configurations, budget, master_seed);
+        Arc::new(system_configurations)
+    });
+
+    let written_files =
+        solve_with_configurations(configurations, assets, budget, output_directory).await;
+    std::fs::remove_file(&database_path)
+        .with_context(|| format!("Could not remove {}", database_path.display()))?;
+    written_files
+}
+
+fn batch_configurations(
+    system_configurations: &mut SystemConfigurations,
+    budget: SolveBudget,
+    master_seed: Option<u64>,
+)
+{
+    if let Some(master_seed) = master_seed {
+        system_configurations.master_seed = master_seed;
+    }
+    system_configurations.checkpoint.enabled = false;
+    system_configurations.lockstep = true;
+    if let SolveBudget::Iterations(iterations) = budget {
+        system_configurations.iteration_limit = Some(iterations);
+    }
+}
+
+async fn solve_with_configurations(
+    configurations: Arc<ArcSwap<SystemConfigurations>>,
+    assets: &[Asset],
+    budget: SolveBudget,
+    output_directory: &Path,
+) -> Result<Vec<PathBuf>>
+{
+    let (orchestrator, error_handle) =
+        Orchestrator::<TotalSystemSolution>::with_configurations(configurations)
+            .context("Orchestrator could not be created")?;
+
+    orchestrator
+        .solve_assets(assets, budget, error_handle)
+        .await?;
+
+    let mut written_files = vec![];
+    std::fs::create_dir_all(output_directory).with_context(|| {
+        format!(
+            "Could not create the output directory {}",
+            output_directory.display()
+        )
+    })?;
+    for asset in assets {
+        written_files.extend(orchestrator.write_system_solution(asset, output_directory)?);
+    }
+
+    Ok(written_files)
+}
+
+impl Orchestrator<TotalSystemSolution>
+{
+    /// Starts the actors of the `Asset`s and shuts them down again once the
+    /// `SolveBudget` is spent. The final `SystemSolution`s stay in the
+    /// `system_solutions`.
+    async fn solve_assets(
+        &self,
+        assets: &[Asset],
+        budget: SolveBudget,
+        mut error_handle: JoinHandle<Result<()>>,
+    ) -> Result<()>
+    {
+        for asset in assets {
+            self.asset_factory(asset)
+                .with_context(|| format!("Could not start the actors of Asset {asset}"))?;
+        }
+
+        tokio::select! {
+            _ = self.wait_for_budget(assets, budget) => {}
+            error_task = &mut error_handle => {
+                error_task
+                    .context("The actor error handler panicked")?
+                    .context("The actor error handler stopped during the solve")?;
+            }
+        }
+        error_handle.abort();
+
+        for asset in assets {
+            self.shutdown_asset(asset)
+                .with_context(|| format!("Could not shut down the actors of Asset {asset}"))?;
+        }
+        Ok(())
+    }
+
+    async fn wait_for_budget(&self, assets: &[Asset], budget: SolveBudget)
+    {
+        match budget {
+            SolveBudget::WallClock(duration) => tokio::time::sleep(duration).await,
+            // The actors pause themselves at the `iteration_limit`, and wake
+            // this task every time one of them does.
+            SolveBudget::Iterations(iterations) => loop {
+                let iteration_limit_reached = self.iteration_limit_reached.notified();
+                if assets.iter().all(|asset| {
+                    self.metrics_registry
+                        .minimum_iterations(asset)
+                        .is_some_and(|minimum_iterations| minimum_iterations >= iterations)
+                }) {
+                    break;
+                }
+                iteration_limit_reached.await;
+            },
+        }
+    }
+
+    fn write_system_solution(&self, asset: &Asset, output_directory: &Path)
+    -> Result<Vec<PathBuf>>
+    {
+        let system_solution = self
+            .system_solutions
+            .lock()
+            .unwrap()
+            .get(asset)
+            .with_context(|| format!("Missing SystemSolution for Asset {asset}"))?
+            .load_full();
+
+        let json_path = output_directory.join(format!("{asset}_system_solution.json"));
+        let json = system_solution_to_json(&*system_solution)?;
+        std::fs::write(&json_path, serde_json::to_string_pretty(&json)?)
+            .with_context(|| format!("Could not write {}", json_path.display()))?;
+
+        let xlsx_path = output_directory.join(format!("{asset}_system_solution.xlsx"));
+        let (xlsx_buffer, _) = self.export_xlsx_solution(asset.clone())?;
+        std::fs::write(&xlsx_path, xlsx_buffer)
+            .with_context(|| format!("Could not write {}", xlsx_path.display()))?;
+
+        event!(
+            Level::INFO,
+            %asset,
+            json = %json_path.display(),
+            xlsx = %xlsx_path.display(),
+            "SystemSolution written"
+        );
+        Ok(vec![json_path, xlsx_path])
+    }
+}
+
+/// The maps of the solutions that are keyed by `Id`s, `Period`s and tuples
+/// serialize their keys as JSON strings. The `serde_json::Value` sorts the
+/// keys, so that the output does not depend on the iteration order of the
+/// `HashMap`s.
+fn system_solution_to_json<T: serde::Serialize>(system_solution: &T) -> Result<serde_json::Value>
+{
+    serde_json::to_value(system_solution).context("Could not serialize the SystemSolution")
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use ordinator_operational_actor::algorithm::operational_solution::OperationalSolution;
+    use ordinator_orchestrator_actor_traits::SystemSolutions;
+    use ordinator_scheduling_environment::Asset;
+    use ordinator_scheduling_environment::worker_environment::resources::Id;
+
+    use super::SolveBudget;
+    use super::batch_configurations;
+    use super::system_solution_to_json;
+    use crate::Orchestrator;
+    use crate::TotalSystemSolution;
+    use crate::test_environment::test_asset_scheduling_environment;
+
+    #[test]
+    fn test_system_solution_to_json_non_string_keys()
+    {
+        let id = Id::new("OP-01-001", vec![], vec![Asset::Test]);
+        let mut system_solution = TotalSystemSolution::new();
+        system_solution.operational_swap(&id, OperationalSolution::default());
+
+        let json = system_solution_to_json(&system_solution).unwrap();
+
+        let id_key = serde_json::to_string(&id).unwrap();
+        assert!(json["operational"][&id_key].is_object());
+
+        let deserialized: TotalSystemSolution = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            deserialized.operational_actor_solutions(&id).unwrap(),
+            &OperationalSolution::default()
+        );
+    }
+
+    /// Solves `Asset::Test` in lockstep like `solve`, on its own database.
+    async fn lockstep_solve(master_seed: u64, run: usize) -> serde_json::Value
+    {
+        let budget = SolveBudget::Iterations(20);
+        let database_path = std::env::temp_dir().join(format!(
+            "ordinator_lockstep_solve_test_{}_{run}.redb",
+            std::process::id()
+        ));
+        let orchestrator =
+            Orchestrator::for_tests(&database_path, &test_asset_scheduling_environment());
+        orchestrator.system_configurations.rcu(|old| {
+            let mut system_configurations = (**old).clone();
+            batch_configurations(&mut system_configurations, budget, Some(master_seed));
+            Arc::new(system_configurations)
+        });
+
+        orchestrator.start_asset(&Asset::Test).unwrap();
+        tokio::time::timeout(
+            Duration::from_secs(120),
+            orchestrator.wait_for_budget(&[Asset::Test], budget),
+        )
+        .await
+        .expect("The actors did not reach the iteration limit");
+        orchestrator.shutdown_asset(&Asset::Test).unwrap();
+
+        let system_solution =
+            orchestrator.system_solutions.lock().unwrap()[&Asset::Test].load_full();
+        let json = system_solution_to_json(&*system_solution).unwrap();
+
+        orchestrator.stop_asset(&Asset::Test).unwrap();
+        std::fs::remove_file(&database_path).unwrap();
+        json
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_lockstep_solves_with_the_same_seed_are_identical()
+    {
+        let first = lockstep_solve(17, 0).await;
+        let second = lockstep_solve(17, 1).await;
+
+        for layer in ["strategic", "tactical", "supervisor", "operational"] {
+            assert!(
+                !first[layer].is_null(),
+                "{layer} did not publish a solution"
+            );
+        }
+        assert_eq!(first, second);
+    }
+}
@@ -0,0 +1,67 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use clap::Parser;
+use ordinator_orchestrator::Asset;
+use ordinator_orchestrator::batch::SolveBudget;
+
+/// Solves the schedule of one or more assets without the API server and
+/// writes the final solutions to disk.
+#[derive(Parser)]
+#[command(name = "ordinator-solve", version, about, long_about = None)]
+struct Cli
+{
+    /// Asset to solve, can be given multiple times
+    #[arg(long = "asset", required = true)]
+    assets: Vec<String>,
+    /// Wall-clock budget of the solve in seconds
+    #[arg(
+        long,
+        conflicts_with = "iterations",
+        required_unless_present = "iterations"
+    )]
+    seconds: Option<u64>,
+    /// Number of LNS iterations that every actor has to run
+    #[arg(long)]
+    iterations: Option<u64>,
+    /// Master seed of the random number generators
+    #[arg(long)]
+    seed: Option<u64>,
+    #[arg(long, default_value = "./solutions")]
+    output_directory: PathBuf,
+}
+
+#[tokio::main]
+async fn main() -> Result<()>
+{
+    dotenvy::dotenv()
+        .context("You need to provide an .env file. Look at the .env.example for guidance")?;
+
+    let cli = Cli::parse();
+
+    let assets = cli
+        .assets
+        .iter()
+        .map(|asset| {
+            Asset::new_from_string(asset).with_context(|| format!("{asset} is not a valid Asset"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let budget = match (cli.seconds, cli.iterations) {
+        (Some(seconds), None) => SolveBudget::WallClock(Duration::from_secs(seconds)),
+        (None, Some(iterations)) => SolveBudget::Iterations(iterations),
+        _ => bail!("Give either --seconds or --iterations as the budget"),
+    };
+
+    let written_files =
+        ordinator_orchestrator::batch::solve(&assets, budget, &cli.output_directory, cli.seed)
+            .await?;
+
+    for written_file in written_files {
+        println!("{}", written_file.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use std::sync::Arc;
+use std::sync::Weak;
+
+use anyhow::Context;
+use anyhow::Result;
+use ordinator_orchestrator_actor_traits::SystemSolutions;
+use ordinator_orchestrator_actor_traits::checkpoint::scheduling_environment_fingerprint;
+use ordinator_scheduling_environment::Asset;
+use tracing::Level;
+use tracing::event;
+
+use crate::Orchestrator;
+
+/// The version of the `SystemSolution` in the last checkpoint of an asset.
+/// The lock is held while the checkpoint is written.
+pub type CheckpointedVersion = Arc<std::sync::Mutex<Option<u64>>>;
+
+impl<Ss> Orchestrator<Ss>
+where
+    Ss: SystemSolutions + Send + Sync + 'static,
+{
+    /// Writes the `SystemSolution` of the `Asset` to the `CheckpointStore`
+    /// unless its version is already written. The actors only publish their
+    /// solutions, so the `Orchestrator` is the single writer of the checkpoint
+    /// of an asset. Assets that are still starting are skipped, so that a
+    /// checkpoint is never replaced before every actor has warm started.
+    pub fn write_checkpoint(&self, asset: &Asset) -> Result<()>
+    {
+        let Some(checkpoint_store) = &self.checkpoint_store else {
+            return Ok(());
+        };
+
+        if !self.actor_registries.lock().unwrap().contains_key(asset) {
+            return Ok(());
+        }
+
+        // Held during the write, so that two writes of the same asset do not
+        // interleave and an older version never replaces a newer one.
+        let checkpointed_version = Arc::clone(
+            self.checkpointed_versions
+                .lock()
+                .unwrap()
+                .entry(asset.clone())
+                .or_default(),
+        );
+        let mut checkpointed_version = checkpointed_version.lock().unwrap();
+
+        let system_solution = self
+            .system_solutions
+            .lock()
+            .unwrap()
+            .get(asset)
+            .with_context(|| format!("Missing SystemSolution for Asset {asset}"))?
+            .load_full();
+
+        if *checkpointed_version == Some(system_solution.version()) {
+            return Ok(());
+        }
+
+        let fingerprint =
+            scheduling_environment_fingerprint(&self.scheduling_environment.lock().unwrap(), asset);
+
+        checkpoint_store.save(asset, fingerprint, &*system_solution)?;
+        *checkpointed_version = Some(system_solution.version());
+
+        event!(Level::DEBUG, %asset, version = system_solution.version(), "checkpoint written");
+        Ok(())
+    }
+
+    /// Writes the checkpoint of every `Asset` once per
+    /// `CheckpointConfiguration::interval`. Stops when the `Orchestrator` is
+    /// dropped.
+    pub(crate) async fn checkpoint_task(orchestrator: Weak<Self>)
+    {
+        loop {
+            let Some(interval) = orchestrator.upgrade().map(|orchestrator| {
+                orchestrator
+                    .system_configurations
+                    .load()
+                    .checkpoint
+                    .interval()
+            }) else {
+                return;
+            };
+            tokio::time::sleep(interval).await;
+
+            let Some(orchestrator) = orchestrator.upgrade() else {
+                return;
+            };
+
+            // The database write blocks, so it is kept off the async runtime.
+            let written = tokio::task::spawn_blocking(move || {
+                let assets: Vec<Asset> = orchestrator
+                    .system_solutions
+                    .lock()
+                    .unwrap()
+                    .keys()
+                    .cloned()
+                    .collect();
+
+                for asset in assets {
+                    if let Err(error) = orchestrator.write_checkpoint(&asset) {
+                        event!(Level::WARN, %asset, ?error, "could not write checkpoint");
+                    }
+                }
+            })
+            .await;
+
+            if let Err(error) = written {
+                event!(Level::ERROR, ?error, "the checkpoint task failed");
+            }
+        }
+    }
+}
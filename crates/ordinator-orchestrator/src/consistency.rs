@@ -0,0 +1,415 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::sync::Weak;
+
+use anyhow::Result;
+use chrono::DateTime;
+use chrono::Utc;
+use ordinator_orchestrator_actor_traits::OperationalInterface;
+use ordinator_orchestrator_actor_traits::OrchestratorError;
+use ordinator_orchestrator_actor_traits::StrategicInterface;
+use ordinator_orchestrator_actor_traits::SupervisorInterface;
+use ordinator_orchestrator_actor_traits::SystemSolutions;
+use ordinator_orchestrator_actor_traits::TacticalInterface;
+use ordinator_orchestrator_actor_traits::delegate::Delegate;
+use ordinator_scheduling_environment::Asset;
+use ordinator_scheduling_environment::time_environment::day::Day;
+use ordinator_scheduling_environment::time_environment::period::Period;
+use ordinator_scheduling_environment::work_order::WorkOrderActivity;
+use ordinator_scheduling_environment::work_order::WorkOrderNumber;
+use ordinator_scheduling_environment::work_order::operation::ActivityNumber;
+use ordinator_scheduling_environment::worker_environment::resources::Id;
+use serde::Serialize;
+use tracing::Level;
+use tracing::event;
+
+use crate::Orchestrator;
+
+/// A disagreement between the solutions of two actors in the same
+/// `SystemSolution`.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum ConsistencyViolation
+{
+    /// The tactical solution schedules a work order that the strategic
+    /// solution has not scheduled in any period.
+    TacticalWithoutStrategicPeriod
+    {
+        work_order_number: WorkOrderNumber
+    },
+    TacticalDayOutsideStrategicPeriod
+    {
+        work_order_activity: WorkOrderActivity,
+        day: Day,
+        strategic_period: Period,
+    },
+    /// The supervisor has assigned an activity that the operational actor
+    /// has not scheduled.
+    AssignWithoutOperationalAssignment
+    {
+        supervisor_id: Id,
+        operational_id: Id,
+        work_order_activity: WorkOrderActivity,
+    },
+    /// The operational actor has scheduled an activity that no supervisor
+    /// delegates to it, or that every supervisor has dropped.
+    OperationalAssignmentWithoutDelegate
+    {
+        operational_id: Id,
+        work_order_activity: WorkOrderActivity,
+        delegate: Option<Delegate>,
+    },
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ConsistencyReport
+{
+    pub asset: Asset,
+    /// The version of the `SystemSolution` that was validated.
+    pub version: u64,
+    pub checked_at: DateTime<Utc>,
+    pub violations: Vec<ConsistencyViolation>,
+}
+
+impl<Ss> Orchestrator<Ss>
+where
+    Ss: SystemSolutions,
+{
+    /// Validates the currently published `SystemSolution` of the `Asset`.
+    pub fn validate_consistency(&self, asset: &Asset) -> Result<ConsistencyReport>
+    {
+        let system_solution = self
+            .system_solutions
+            .lock()
+            .unwrap()
+            .get(asset)
+            .ok_or_else(|| {
+                OrchestratorError::ActorUnavailable(format!(
+                    "The scheduling system for Asset {asset}"
+                ))
+            })?
+            .load_full();
+
+        Ok(ConsistencyReport {
+            asset: asset.clone(),
+            version: system_solution.version(),
+            checked_at: Utc::now(),
+            violations: validate_system_solution(&*system_solution),
+        })
+    }
+
+    /// Validates every `Asset` once per `ValidationConfiguration::interval`
+    /// and logs the violations. Stops when the `Orchestrator` is dropped.
+    pub(crate) async fn consistency_validation_task(orchestrator: Weak<Self>)
+    {
+        loop {
+            let Some(interval) = orchestrator.upgrade().map(|orchestrator| {
+                orchestrator
+                    .system_configurations
+                    .load()
+                    .validation
+                    .interval()
+            }) else {
+                return;
+            };
+            tokio::time::sleep(interval).await;
+
+            let Some(orchestrator) = orchestrator.upgrade() else {
+                return;
+            };
+            let assets: Vec<Asset> = orchestrator
+                .system_solutions
+                .lock()
+                .unwrap()
+                .keys()
+                .cloned()
+                .collect();
+
+            for asset in assets {
+                match orchestrator.validate_consistency(&asset) {
+                    Ok(report) if !report.violations.is_empty() => event!(
+                        Level::WARN,
+                        %asset,
+                        version = report.version,
+                        violations = report.violations.len(),
+                        "the SystemSolution is inconsistent"
+                    ),
+                    Ok(_) => (),
+                    Err(error) => {
+                        event!(Level::ERROR, %asset, ?error, "the SystemSolution could not be validated")
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Checks the solutions in the `SystemSolution` against each other. Layers
+/// without a published solution are not checked.
+pub fn validate_system_solution<Ss>(system_solution: &Ss) -> Vec<ConsistencyViolation>
+where
+    Ss: SystemSolutions,
+{
+    let mut violations = vec![];
+
+    if let (Ok(strategic), Ok(tactical)) = (
+        system_solution.strategic(),
+        system_solution.tactical_actor_solution(),
+    ) {
+        violations.extend(tactical_violations(
+            &strategic.all_scheduled_tasks(),
+            &tactical.all_scheduled_tasks(),
+        ));
+    }
+
+    if let Ok(supervisors) = system_solution.supervisor_actor_solutions() {
+        let operational_intervals = system_solution
+            .all_operational()
+            .into_iter()
+            .filter_map(|id| {
+                let operational = system_solution.operational_actor_solutions(&id).ok()?;
+                Some((id, operational.scheduled_intervals()))
+            })
+            .collect();
+
+        let delegates = supervisors
+            .iter()
+            .map(|(supervisor_id, supervisor)| (supervisor_id.clone(), supervisor.all_delegates()))
+            .collect();
+
+        violations.extend(delegate_violations(&delegates, &operational_intervals));
+    }
+
+    violations
+}
+
+fn tactical_violations(
+    strategic_periods: &HashMap<WorkOrderNumber, Period>,
+    tactical_days: &HashMap<WorkOrderNumber, BTreeMap<ActivityNumber, Day>>,
+) -> Vec<ConsistencyViolation>
+{
+    let mut work_order_numbers: Vec<_> = tactical_days.keys().collect();
+    work_order_numbers.sort();
+
+    let mut violations = vec![];
+    for work_order_number in work_order_numbers {
+        let Some(strategic_period) = strategic_periods.get(work_order_number) else {
+            violations.push(ConsistencyViolation::TacticalWithoutStrategicPeriod {
+                work_order_number: *work_order_number,
+            });
+            continue;
+        };
+
+        for (activity_number, day) in &tactical_days[work_order_number] {
+            if !strategic_period.contains_date(day.date().date_naive()) {
+                violations.push(ConsistencyViolation::TacticalDayOutsideStrategicPeriod {
+                    work_order_activity: (*work_order_number, *activity_number),
+                    day: day.clone(),
+                    strategic_period: strategic_period.clone(),
+                });
+            }
+        }
+    }
+    violations
+}
+
+type OperationalIntervals = HashMap<WorkOrderActivity, (DateTime<Utc>, DateTime<Utc>)>;
+
+type Delegates = HashMap<(Id, WorkOrderActivity), Delegate>;
+
+/// The `Delegate`s are keyed by the supervisor that owns them, so that an
+/// activity delegated by one supervisor is not a violation for another.
+fn delegate_violations(
+    delegates: &HashMap<Id, &Delegates>,
+    operational_intervals: &HashMap<Id, OperationalIntervals>,
+) -> Vec<ConsistencyViolation>
+{
+    let mut violations = vec![];
+
+    let mut supervisor_ids: Vec<_> = delegates.keys().collect();
+    supervisor_ids.sort_by(|a, b| a.0.cmp(&b.0));
+
+    // An operational actor that has not published a solution yet is not
+    // checked against its assignments.
+    for supervisor_id in supervisor_ids {
+        for ((operational_id, work_order_activity), delegate) in delegates[supervisor_id] {
+            if let Some(intervals) = operational_intervals.get(operational_id)
+                && delegate.is_assign()
+                && !intervals.contains_key(work_order_activity)
+            {
+                violations.push((
+                    (&operational_id.0, *work_order_activity),
+                    ConsistencyViolation::AssignWithoutOperationalAssignment {
+                        supervisor_id: supervisor_id.clone(),
+                        operational_id: operational_id.clone(),
+                        work_order_activity: *work_order_activity,
+                    },
+                ));
+            }
+        }
+    }
+
+    for (operational_id, intervals) in operational_intervals {
+        for work_order_activity in intervals.keys() {
+            let key = (operational_id.clone(), *work_order_activity);
+            let supervisor_delegates: Vec<&Delegate> = delegates
+                .values()
+                .filter_map(|delegates| delegates.get(&key))
+                .collect();
+            if supervisor_delegates
+                .iter()
+                .all(|delegate| delegate.is_drop())
+            {
+                violations.push((
+                    (&operational_id.0, *work_order_activity),
+                    ConsistencyViolation::OperationalAssignmentWithoutDelegate {
+                        operational_id: operational_id.clone(),
+                        work_order_activity: *work_order_activity,
+                        delegate: supervisor_delegates.first().copied().cloned(),
+                    },
+                ));
+            }
+        }
+    }
+
+    violations.sort_by_key(|(key, _)| *key);
+    violations
+        .into_iter()
+        .map(|(_, violation)| violation)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::collections::BTreeMap;
+    use std::collections::HashMap;
+
+    use chrono::TimeZone;
+    use chrono::Utc;
+    use ordinator_orchestrator_actor_traits::OrchestratorError;
+    use ordinator_orchestrator_actor_traits::delegate::Delegate;
+    use ordinator_scheduling_environment::Asset;
+    use ordinator_scheduling_environment::time_environment::day::Day;
+    use ordinator_scheduling_environment::time_environment::period::Period;
+    use ordinator_scheduling_environment::work_order::WorkOrderNumber;
+    use ordinator_scheduling_environment::worker_environment::resources::Id;
+
+    use super::ConsistencyViolation;
+    use super::delegate_violations;
+    use super::tactical_violations;
+    use crate::Orchestrator;
+    use crate::test_environment::period;
+    use crate::test_environment::scheduling_environment;
+
+    #[test]
+    fn test_tactical_violations()
+    {
+        let period = Period::new(
+            0,
+            Utc.with_ymd_and_hms(2025, 1, 6, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 1, 19, 23, 59, 59).unwrap(),
+        );
+        let inside = Day::new(0, Utc.with_ymd_and_hms(2025, 1, 7, 0, 0, 0).unwrap());
+        let outside = Day::new(20, Utc.with_ymd_and_hms(2025, 1, 27, 0, 0, 0).unwrap());
+
+        let strategic_periods = HashMap::from([(WorkOrderNumber(1), period.clone())]);
+        let tactical_days = HashMap::from([
+            (
+                WorkOrderNumber(1),
+                BTreeMap::from([(10, inside), (20, outside.clone())]),
+            ),
+            (WorkOrderNumber(2), BTreeMap::new()),
+        ]);
+
+        assert_eq!(
+            tactical_violations(&strategic_periods, &tactical_days),
+            vec![
+                ConsistencyViolation::TacticalDayOutsideStrategicPeriod {
+                    work_order_activity: (WorkOrderNumber(1), 20),
+                    day: outside,
+                    strategic_period: period,
+                },
+                ConsistencyViolation::TacticalWithoutStrategicPeriod {
+                    work_order_number: WorkOrderNumber(2),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_delegate_violations()
+    {
+        let id = Id::new("OP-01", vec![], vec![]);
+        let supervisor_id = Id::new("SUP-01", vec![], vec![]);
+        let other_supervisor_id = Id::new("SUP-02", vec![], vec![]);
+        let interval = (
+            Utc.with_ymd_and_hms(2025, 1, 7, 8, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 1, 7, 12, 0, 0).unwrap(),
+        );
+
+        let supervisor_delegates = HashMap::from([
+            ((id.clone(), (WorkOrderNumber(1), 10)), Delegate::Assign),
+            ((id.clone(), (WorkOrderNumber(1), 20)), Delegate::Assign),
+            ((id.clone(), (WorkOrderNumber(2), 10)), Delegate::Drop),
+        ]);
+        // The activities delegated by another supervisor are not violations.
+        let other_supervisor_delegates = HashMap::from([
+            ((id.clone(), (WorkOrderNumber(2), 10)), Delegate::Drop),
+            ((id.clone(), (WorkOrderNumber(3), 10)), Delegate::Assign),
+        ]);
+        let delegates = HashMap::from([
+            (supervisor_id.clone(), &supervisor_delegates),
+            (other_supervisor_id, &other_supervisor_delegates),
+        ]);
+        let operational_intervals = HashMap::from([(
+            id.clone(),
+            HashMap::from([
+                ((WorkOrderNumber(1), 10), interval),
+                ((WorkOrderNumber(2), 10), interval),
+                ((WorkOrderNumber(3), 10), interval),
+                ((WorkOrderNumber(4), 10), interval),
+            ]),
+        )]);
+
+        assert_eq!(
+            delegate_violations(&delegates, &operational_intervals),
+            vec![
+                ConsistencyViolation::AssignWithoutOperationalAssignment {
+                    supervisor_id,
+                    operational_id: id.clone(),
+                    work_order_activity: (WorkOrderNumber(1), 20),
+                },
+                ConsistencyViolation::OperationalAssignmentWithoutDelegate {
+                    operational_id: id.clone(),
+                    work_order_activity: (WorkOrderNumber(2), 10),
+                    delegate: Some(Delegate::Drop),
+                },
+                ConsistencyViolation::OperationalAssignmentWithoutDelegate {
+                    operational_id: id,
+                    work_order_activity: (WorkOrderNumber(4), 10),
+                    delegate: None,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_consistency_of_a_stopped_asset()
+    {
+        let database_path = std::env::temp_dir().join(format!(
+            "ordinator_consistency_test_{}.redb",
+            std::process::id()
+        ));
+        let orchestrator =
+            Orchestrator::for_tests(&database_path, &scheduling_environment(vec![period(0)]));
+
+        let error = orchestrator.validate_consistency(&Asset::Test).unwrap_err();
+        assert!(matches!(
+            OrchestratorError::find(&error),
+            Some(OrchestratorError::ActorUnavailable(_))
+        ));
+
+        std::fs::remove_file(&database_path).unwrap();
+    }
+}
@@ -1,6 +1,6 @@
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::Read;
-use std::io::Write;
 use std::path::Path;
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -8,69 +8,499 @@ use std::sync::Mutex;
 use anyhow::Context;
 use anyhow::Result;
 use arc_swap::ArcSwap;
+use chrono::Utc;
 use ordinator_configuration::SystemConfigurations;
+use ordinator_orchestrator_actor_traits::domain_event::DomainEvent;
+use ordinator_scheduling_environment::Asset;
 use ordinator_scheduling_environment::SchedulingEnvironment;
+use ordinator_scheduling_environment::audit_log::SchedulingEnvironmentEvent;
+use ordinator_scheduling_environment::audit_log::SchedulingEnvironmentMutation;
+use ordinator_scheduling_environment::time_environment::TimeEnvironment;
+use ordinator_scheduling_environment::work_order::WorkOrderNumber;
+use ordinator_scheduling_environment::work_order::WorkOrders;
+use ordinator_scheduling_environment::worker_environment::WorkerEnvironment;
+use redb::Database;
+use redb::ReadableDatabase;
+use redb::ReadableTable;
+use redb::ReadableTableMetadata;
+use redb::TableDefinition;
+use redb::TableError;
+use redb::WriteTransaction;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tracing::Level;
+use tracing::event;
 
 use super::model_initializers;
 
-pub struct DataBaseConnection {}
+const WORK_ORDER_TABLE: TableDefinition<u64, &[u8]> = TableDefinition::new("work_orders");
+const ENVIRONMENT_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("environment");
+const WORKER_ENVIRONMENT_KEY: &str = "worker_environment";
+const TIME_ENVIRONMENT_KEY: &str = "time_environment";
+const AUDIT_LOG_TABLE: TableDefinition<u64, &[u8]> = TableDefinition::new("audit_log");
+
+/// The embedded database at `DATABASE_PATH`. It holds the
+/// `SchedulingEnvironment`, including the `PlannerDecision`s, the audit log of
+/// the planners and the checkpoints of the `CheckpointStore`. Every write is a
+/// single transaction, so a crash never leaves the database half written.
+pub struct DataBaseConnection
+{
+    database: Arc<Database>,
+}
 
-// At the moment you are simply reading everything into a
-// single struct and then you forget about the MongoDB
-// connection. I do not think that is a good approach
-// here. You should be able to interact with the data
-// continuously for this to work.
 impl DataBaseConnection
 {
-    // ISSUE #000 move-temp-scheduling-environment-into-mongodb
-    #[allow(clippy::new_without_default)]
-    pub fn new() -> Self
+    /// A `SchedulingEnvironment` that was dumped as JSON by earlier versions
+    /// is moved aside and imported into the new database.
+    pub fn open(database_path: &Path) -> Result<Self>
     {
-        Self {}
+        let legacy_scheduling_environment = if is_legacy_json(database_path)? {
+            let legacy_path = database_path.with_extension("legacy.json");
+            std::fs::rename(database_path, &legacy_path).with_context(|| {
+                format!(
+                    "Could not move the legacy database to {}",
+                    legacy_path.display()
+                )
+            })?;
+            event!(Level::INFO, legacy_path = %legacy_path.display(), "importing legacy database");
+            Some(read_legacy_json(&legacy_path)?)
+        } else {
+            None
+        };
+
+        if let Some(parent) = database_path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Could not create {}", parent.display()))?;
+        }
+
+        let database_connection = Self {
+            database: Arc::new(Database::create(database_path).with_context(|| {
+                format!("Could not open the database at {}", database_path.display())
+            })?),
+        };
+
+        if let Some(scheduling_environment) = legacy_scheduling_environment {
+            database_connection.save_scheduling_environment(&scheduling_environment)?;
+        }
+
+        Ok(database_connection)
     }
 
+    /// Shared with the `CheckpointStore`.
+    pub fn database(&self) -> Arc<Database>
+    {
+        Arc::clone(&self.database)
+    }
+
+    /// The `SchedulingEnvironment` is read from the database. Only an empty
+    /// database is initialized from the source data.
     pub fn scheduling_environment(
+        &self,
         system_configuration: Arc<ArcSwap<SystemConfigurations>>,
     ) -> Result<Arc<Mutex<SchedulingEnvironment>>>
     {
-        let database_path = &system_configuration.load().database_config;
-        if database_path.exists() {
-            initialize_from_database(database_path)
-        } else {
-            initialize_from_source_data_and_initialize_database(system_configuration.load())
-                .context("Could not write SchedulingEnvironment to database.")
+        if let Some(scheduling_environment) = self.load_scheduling_environment()? {
+            return Ok(scheduling_environment);
+        }
+
+        let scheduling_environment =
+            model_initializers::initialize_scheduling_environment(system_configuration.load())
+                .context("Could not initialize the SchedulingEnvironment from source data")?;
+
+        self.save_scheduling_environment(&scheduling_environment.lock().unwrap())
+            .context("Could not write SchedulingEnvironment to database.")?;
+        Ok(scheduling_environment)
+    }
+
+    /// Writes the parts of the `SchedulingEnvironment` that the `DomainEvent`
+    /// changed.
+    pub fn persist_domain_event(
+        &self,
+        scheduling_environment: &SchedulingEnvironment,
+        domain_event: &DomainEvent,
+    ) -> Result<()>
+    {
+        let write_transaction = self.database.begin_write()?;
+        match domain_event {
+            DomainEvent::WorkOrderCreated(work_order_numbers)
+            | DomainEvent::WorkOrderUpdated(work_order_numbers)
+            | DomainEvent::WorkOrderClosed(work_order_numbers)
+            | DomainEvent::MaterialDateChanged(work_order_numbers) => write_work_orders(
+                &write_transaction,
+                &scheduling_environment.work_orders,
+                work_order_numbers,
+            )?,
+            DomainEvent::OperationProgressConfirmed(work_order_activity) => write_work_orders(
+                &write_transaction,
+                &scheduling_environment.work_orders,
+                &[work_order_activity.0],
+            )?,
+            DomainEvent::WorkerAvailabilityChanged(_)
+            | DomainEvent::WorkerAdded(_)
+            | DomainEvent::WorkerRemoved(_) => write_environment(
+                &write_transaction,
+                WORKER_ENVIRONMENT_KEY,
+                &scheduling_environment.worker_environment,
+            )?,
+            DomainEvent::PeriodRolled => write_environment(
+                &write_transaction,
+                TIME_ENVIRONMENT_KEY,
+                &scheduling_environment.time_environment,
+            )?,
+        }
+        write_transaction
+            .commit()
+            .with_context(|| format!("Could not persist {domain_event:?}"))
+    }
+
+    /// For the changes to the `ActorSpecifications` that no `DomainEvent` is
+    /// published for.
+    pub fn save_worker_environment(&self, worker_environment: &WorkerEnvironment) -> Result<()>
+    {
+        let write_transaction = self.database.begin_write()?;
+        write_environment(
+            &write_transaction,
+            WORKER_ENVIRONMENT_KEY,
+            worker_environment,
+        )?;
+        write_transaction
+            .commit()
+            .context("Could not persist the WorkerEnvironment")
+    }
+
+    /// Appends the `SchedulingEnvironmentMutation` to the audit log. The
+    /// `work_orders` that it resulted in are written in the same transaction,
+    /// so the log and the state never disagree.
+    pub fn append_mutation(
+        &self,
+        work_orders: &WorkOrders,
+        user: &str,
+        asset: &Asset,
+        mutation: SchedulingEnvironmentMutation,
+    ) -> Result<SchedulingEnvironmentEvent>
+    {
+        let write_transaction = self.database.begin_write()?;
+        let scheduling_environment_event = {
+            let mut audit_log_table = write_transaction.open_table(AUDIT_LOG_TABLE)?;
+            let version = match audit_log_table.last()? {
+                Some((last_version, _)) => last_version.value() + 1,
+                None => 1,
+            };
+
+            let scheduling_environment_event = SchedulingEnvironmentEvent {
+                version,
+                timestamp: Utc::now(),
+                user: user.to_string(),
+                asset: asset.clone(),
+                mutation,
+            };
+            let bytes = serde_json::to_vec(&scheduling_environment_event)
+                .with_context(|| format!("Could not serialize version {version}"))?;
+            audit_log_table.insert(version, bytes.as_slice())?;
+            scheduling_environment_event
+        };
+
+        write_work_orders(
+            &write_transaction,
+            work_orders,
+            &scheduling_environment_event.mutation.work_order_numbers(),
+        )?;
+        write_transaction.commit().with_context(|| {
+            format!(
+                "Could not append version {} to the audit log",
+                scheduling_environment_event.version
+            )
+        })?;
+        Ok(scheduling_environment_event)
+    }
+
+    /// Every `SchedulingEnvironmentEvent` in the order of their versions.
+    pub fn audit_log(&self) -> Result<Vec<SchedulingEnvironmentEvent>>
+    {
+        let read_transaction = self.database.begin_read()?;
+        let audit_log_table = match read_transaction.open_table(AUDIT_LOG_TABLE) {
+            Ok(audit_log_table) => audit_log_table,
+            Err(TableError::TableDoesNotExist(_)) => return Ok(Vec::new()),
+            Err(error) => return Err(error.into()),
+        };
+
+        audit_log_table
+            .iter()?
+            .map(|entry| {
+                let (version, bytes) = entry?;
+                serde_json::from_slice(bytes.value()).with_context(|| {
+                    format!(
+                        "Version {} of the audit log is not a valid event",
+                        version.value()
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// The planning history of a single work order.
+    pub fn work_order_history(
+        &self,
+        work_order_number: WorkOrderNumber,
+    ) -> Result<Vec<SchedulingEnvironmentEvent>>
+    {
+        Ok(self
+            .audit_log()?
+            .into_iter()
+            .filter(|scheduling_environment_event| {
+                scheduling_environment_event
+                    .mutation
+                    .work_order_numbers()
+                    .contains(&work_order_number)
+            })
+            .collect())
+    }
+
+    pub(crate) fn save_scheduling_environment(
+        &self,
+        scheduling_environment: &SchedulingEnvironment,
+    ) -> Result<()>
+    {
+        let write_transaction = self.database.begin_write()?;
+        let work_order_numbers: Vec<WorkOrderNumber> = scheduling_environment
+            .work_orders
+            .inner
+            .keys()
+            .copied()
+            .collect();
+        write_work_orders(
+            &write_transaction,
+            &scheduling_environment.work_orders,
+            &work_order_numbers,
+        )?;
+        write_environment(
+            &write_transaction,
+            WORKER_ENVIRONMENT_KEY,
+            &scheduling_environment.worker_environment,
+        )?;
+        write_environment(
+            &write_transaction,
+            TIME_ENVIRONMENT_KEY,
+            &scheduling_environment.time_environment,
+        )?;
+        write_transaction
+            .commit()
+            .context("Could not persist the SchedulingEnvironment")
+    }
+
+    /// `None` if the `SchedulingEnvironment` was never written.
+    fn load_scheduling_environment(&self) -> Result<Option<Arc<Mutex<SchedulingEnvironment>>>>
+    {
+        let read_transaction = self.database.begin_read()?;
+        let environment_table = match read_transaction.open_table(ENVIRONMENT_TABLE) {
+            Ok(environment_table) => environment_table,
+            Err(TableError::TableDoesNotExist(_)) => return Ok(None),
+            Err(error) => return Err(error.into()),
+        };
+        if environment_table.is_empty()? {
+            return Ok(None);
+        }
+
+        let worker_environment: WorkerEnvironment =
+            read_environment(&environment_table, WORKER_ENVIRONMENT_KEY)?;
+        let time_environment: TimeEnvironment =
+            read_environment(&environment_table, TIME_ENVIRONMENT_KEY)?;
+
+        let mut work_orders = HashMap::new();
+        for entry in read_transaction.open_table(WORK_ORDER_TABLE)?.iter()? {
+            let (work_order_number, work_order) = entry?;
+            work_orders.insert(
+                WorkOrderNumber(work_order_number.value()),
+                serde_json::from_slice(work_order.value()).with_context(|| {
+                    format!(
+                        "{:?} in the database is not a valid WorkOrder",
+                        WorkOrderNumber(work_order_number.value())
+                    )
+                })?,
+            );
         }
+
+        Ok(Some(
+            SchedulingEnvironment::builder()
+                .work_orders(WorkOrders { inner: work_orders })
+                .worker_environment(worker_environment)
+                .time_environment(time_environment)
+                .build(),
+        ))
     }
 }
 
-fn initialize_from_database(path: &Path) -> Result<Arc<Mutex<SchedulingEnvironment>>>
+/// Work orders that are no longer in the `WorkOrders` are removed.
+fn write_work_orders(
+    write_transaction: &WriteTransaction,
+    work_orders: &WorkOrders,
+    work_order_numbers: &[WorkOrderNumber],
+) -> Result<()>
 {
-    let mut file = File::open(path)?;
-    let mut data = String::new();
+    let mut work_order_table = write_transaction.open_table(WORK_ORDER_TABLE)?;
+    for work_order_number in work_order_numbers {
+        match work_orders.inner.get(work_order_number) {
+            Some(work_order) => {
+                let bytes = serde_json::to_vec(work_order)
+                    .with_context(|| format!("Could not serialize {work_order_number:?}"))?;
+                work_order_table.insert(work_order_number.0, bytes.as_slice())?;
+            }
+            None => {
+                work_order_table.remove(work_order_number.0)?;
+            }
+        }
+    }
+    Ok(())
+}
 
-    file.read_to_string(&mut data)?;
+fn write_environment<T>(write_transaction: &WriteTransaction, key: &str, value: &T) -> Result<()>
+where
+    T: Serialize,
+{
+    let bytes = serde_json::to_vec(value).with_context(|| format!("Could not serialize {key}"))?;
+    write_transaction
+        .open_table(ENVIRONMENT_TABLE)?
+        .insert(key, bytes.as_slice())?;
+    Ok(())
+}
+
+fn read_environment<T>(
+    environment_table: &impl ReadableTable<&'static str, &'static [u8]>,
+    key: &str,
+) -> Result<T>
+where
+    T: DeserializeOwned,
+{
+    let bytes = environment_table
+        .get(key)?
+        .with_context(|| format!("The {key} is missing in the database"))?;
+    serde_json::from_slice(bytes.value())
+        .with_context(|| format!("The {key} in the database is not valid"))
+}
+
+fn is_legacy_json(path: &Path) -> Result<bool>
+{
+    if !path.exists() {
+        return Ok(false);
+    }
+    let mut first_byte = [0; 1];
+    let bytes_read = File::open(path)
+        .and_then(|mut file| file.read(&mut first_byte))
+        .with_context(|| format!("Could not read {}", path.display()))?;
+    Ok(bytes_read == 1 && first_byte[0] == b'{')
+}
 
-    Ok(Arc::new(Mutex::new(serde_json::from_str::<
-        SchedulingEnvironment,
-    >(&data)?)))
+fn read_legacy_json(path: &Path) -> Result<SchedulingEnvironment>
+{
+    let data = std::fs::read_to_string(path)
+        .with_context(|| format!("Could not read {}", path.display()))?;
+    serde_json::from_str(&data)
+        .with_context(|| format!("{} is not a valid SchedulingEnvironment", path.display()))
 }
 
-fn initialize_from_source_data_and_initialize_database(
-    system_configurations: arc_swap::Guard<Arc<SystemConfigurations>>,
-) -> Result<Arc<Mutex<SchedulingEnvironment>>>
+#[cfg(test)]
+mod tests
 {
-    let file_path = system_configurations.database_config.clone();
-    let scheduling_environment =
-        model_initializers::initialize_scheduling_environment(system_configurations)
-            .context("Could not initialize the SchedulingEnvironment from source data")?;
-
-    let json_scheduling_environment =
-        serde_json::to_string(&*scheduling_environment.lock().unwrap()).unwrap();
-    // TODO [ ]
-    // Make database integration here.
-    let mut file = File::create(file_path).unwrap();
-
-    file.write_all(json_scheduling_environment.as_bytes())
+    use std::collections::HashMap;
+
+    use ordinator_scheduling_environment::Asset;
+    use ordinator_scheduling_environment::SchedulingEnvironment;
+    use ordinator_scheduling_environment::audit_log::SchedulingEnvironmentMutation;
+    use ordinator_scheduling_environment::time_environment::period::Period;
+    use ordinator_scheduling_environment::work_order::WorkOrderNumber;
+    use ordinator_scheduling_environment::work_order::WorkOrders;
+
+    use super::DataBaseConnection;
+
+    #[test]
+    fn test_legacy_json_is_imported_and_reopened()
+    {
+        let directory =
+            std::env::temp_dir().join(format!("ordinator_database_test_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let database_path = directory.join("scheduling_environment.json");
+
+        let scheduling_environment = SchedulingEnvironment::builder()
+            .work_orders(WorkOrders {
+                inner: HashMap::new(),
+            })
+            .build();
+        std::fs::write(
+            &database_path,
+            serde_json::to_vec(&*scheduling_environment.lock().unwrap()).unwrap(),
+        )
         .unwrap();
-    Ok(scheduling_environment)
+
+        let database_connection = DataBaseConnection::open(&database_path).unwrap();
+        assert!(database_path.with_extension("legacy.json").exists());
+        drop(database_connection);
+
+        let database_connection = DataBaseConnection::open(&database_path).unwrap();
+        let loaded_scheduling_environment = database_connection
+            .load_scheduling_environment()
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&*loaded_scheduling_environment.lock().unwrap()).unwrap(),
+            serde_json::to_value(&*scheduling_environment.lock().unwrap()).unwrap()
+        );
+
+        drop(database_connection);
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn test_audit_log_is_appended_in_versions()
+    {
+        let database_path = std::env::temp_dir().join(format!(
+            "ordinator_audit_log_test_{}.redb",
+            std::process::id()
+        ));
+        let scheduling_environment = SchedulingEnvironment::builder()
+            .work_orders(WorkOrders {
+                inner: HashMap::new(),
+            })
+            .build();
+
+        let database_connection = DataBaseConnection::open(&database_path).unwrap();
+        assert!(database_connection.audit_log().unwrap().is_empty());
+
+        let mutations = [
+            SchedulingEnvironmentMutation::LockInPeriod {
+                work_order_numbers: vec![WorkOrderNumber(1), WorkOrderNumber(2)],
+                period: Period::default(),
+            },
+            SchedulingEnvironmentMutation::ExcludeFromPeriod {
+                work_order_numbers: vec![WorkOrderNumber(2)],
+                period: Period::default(),
+            },
+        ];
+        for mutation in mutations {
+            database_connection
+                .append_mutation(
+                    &scheduling_environment.lock().unwrap().work_orders,
+                    "planner",
+                    &Asset::Test,
+                    mutation,
+                )
+                .unwrap();
+        }
+        drop(database_connection);
+
+        let database_connection = DataBaseConnection::open(&database_path).unwrap();
+        let versions = |work_order_number| {
+            database_connection
+                .work_order_history(work_order_number)
+                .unwrap()
+                .iter()
+                .map(|scheduling_environment_event| scheduling_environment_event.version)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(versions(WorkOrderNumber(1)), vec![1]);
+        assert_eq!(versions(WorkOrderNumber(2)), vec![1, 2]);
+        assert!(versions(WorkOrderNumber(3)).is_empty());
+
+        drop(database_connection);
+        std::fs::remove_file(database_path).unwrap();
+    }
 }
@@ -1,8 +1,20 @@
 mod actor_factory;
+pub mod actor_options;
 pub mod actor_registry;
+pub mod assets;
+pub mod batch;
+pub mod checkpoint;
+pub mod consistency;
 pub mod database;
 pub mod logging;
 pub mod model_initializers;
+pub mod solution_changes;
+pub mod source_changes;
+pub mod supervision;
+#[cfg(test)]
+mod test_environment;
+pub mod undo;
+pub mod work_order_status;
 
 use std::collections::HashMap;
 use std::fs::File;
@@ -13,31 +25,44 @@ use std::sync::Weak;
 pub use actor_factory::TotalSystemSolution;
 use anyhow::Context;
 use anyhow::Result;
-use anyhow::anyhow;
 use anyhow::bail;
 use arc_swap::ArcSwap;
 use flume::Receiver;
 use flume::Sender;
 use ordinator_configuration::SystemConfigurations;
 use ordinator_contracts::orchestrator::OrchestratorResponse;
+use ordinator_contracts::orchestrator::WorkOrdersStatus;
 use ordinator_operational_actor::OperationalApi;
 use ordinator_operational_actor::algorithm::operational_solution::OperationalSolution;
 pub use ordinator_operational_actor::messages::OperationalRequestMessage;
 pub use ordinator_operational_actor::messages::OperationalResponseMessage;
 pub use ordinator_operational_actor::messages::requests::OperationalStatusRequest;
+pub use ordinator_orchestrator_actor_traits::ActorError;
 use ordinator_orchestrator_actor_traits::ActorFactory;
-use ordinator_orchestrator_actor_traits::ActorSpecific;
+use ordinator_orchestrator_actor_traits::ActorFailure;
 use ordinator_orchestrator_actor_traits::Communication;
+pub use ordinator_orchestrator_actor_traits::OrchestratorError;
 use ordinator_orchestrator_actor_traits::OrchestratorNotifier;
-use ordinator_orchestrator_actor_traits::StateLink;
+pub use ordinator_orchestrator_actor_traits::RequestError;
 pub use ordinator_orchestrator_actor_traits::SystemSolutions;
+use ordinator_orchestrator_actor_traits::checkpoint::CheckpointStore;
+use ordinator_orchestrator_actor_traits::cpu_budget::CpuBudget;
+use ordinator_orchestrator_actor_traits::domain_event::DomainEvent;
+use ordinator_orchestrator_actor_traits::domain_event::WorkOrderResources;
+use ordinator_orchestrator_actor_traits::lockstep::Lockstep;
+use ordinator_orchestrator_actor_traits::solution_feed::SolutionFeed;
+use ordinator_orchestrator_actor_traits::telemetry::ActorMetrics;
+use ordinator_orchestrator_actor_traits::telemetry::MetricsRegistry;
 pub use ordinator_scheduling_environment::Asset;
 use ordinator_scheduling_environment::SchedulingEnvironment;
+use ordinator_scheduling_environment::audit_log::SchedulingEnvironmentMutation;
 pub use ordinator_scheduling_environment::time_environment::day::Day;
 pub use ordinator_scheduling_environment::work_order::WorkOrderNumber;
-use ordinator_scheduling_environment::work_order::WorkOrders;
 pub use ordinator_scheduling_environment::work_order::operation::ActivityNumber;
+pub use ordinator_scheduling_environment::worker_environment::InputOperational;
+pub use ordinator_scheduling_environment::worker_environment::InputSupervisor;
 pub use ordinator_scheduling_environment::worker_environment::resources::Id;
+pub use ordinator_scheduling_environment::worker_environment::resources::Resources;
 use ordinator_strategic_actor::StrategicApi;
 use ordinator_strategic_actor::algorithm::strategic_solution::StrategicSolution;
 pub use ordinator_strategic_actor::messages::StrategicRequestMessage;
@@ -46,6 +71,8 @@ use ordinator_supervisor_actor::SupervisorApi;
 use ordinator_supervisor_actor::algorithm::supervisor_solution::SupervisorSolution;
 pub use ordinator_supervisor_actor::messages::SupervisorRequestMessage;
 pub use ordinator_supervisor_actor::messages::SupervisorResponseMessage;
+pub use ordinator_supervisor_actor::messages::requests::SupervisorRequestScheduling;
+pub use ordinator_supervisor_actor::messages::requests::SupervisorSchedulingMessage;
 pub use ordinator_supervisor_actor::messages::requests::SupervisorStatusMessage;
 pub use ordinator_supervisor_actor::messages::responses::SupervisorResponseStatus;
 use ordinator_tactical_actor::TacticalApi;
@@ -57,18 +84,48 @@ use ordinator_total_data_processing::excel_dumps::create_excel_dump;
 use serde::Deserialize;
 use serde::Serialize;
 use tokio::task::JoinHandle;
+use tracing::Level;
+use tracing::event;
 use tracing::instrument;
 
+use self::actor_options::ActorOptionsHistory;
+use self::actor_options::ActorOptionsUpdate;
+use self::actor_registry::ActorKind;
 use self::actor_registry::ActorRegistry;
+use self::checkpoint::CheckpointedVersion;
 use self::database::DataBaseConnection;
 use self::logging::LogHandles;
+use self::supervision::SupervisionTree;
+use self::undo::UndoStacks;
+use self::work_order_status::WorkOrderStatusFilter;
 
 pub struct Orchestrator<Ss>
 {
     pub scheduling_environment: Arc<std::sync::Mutex<SchedulingEnvironment>>,
     pub system_solutions: std::sync::Mutex<HashMap<Asset, Arc<ArcSwap<Ss>>>>,
     pub actor_registries: std::sync::Mutex<HashMap<Asset, ActorRegistry>>,
-    pub error_channels: (Sender<anyhow::Error>, Receiver<anyhow::Error>),
+    /// Held while an `Asset` is started or stopped. The `actor_registries`
+    /// cannot be held for this, as the actors lock them while they start and
+    /// shut down.
+    pub asset_transitions: std::sync::Mutex<()>,
+    pub error_channels: (Sender<ActorFailure>, Receiver<ActorFailure>),
+    pub supervision_tree: std::sync::Mutex<SupervisionTree>,
+    pub actor_options_history: std::sync::Mutex<ActorOptionsHistory>,
+    pub undo_stacks: std::sync::Mutex<UndoStacks>,
+    pub cpu_budgets: std::sync::Mutex<HashMap<Asset, Arc<CpuBudget>>>,
+    /// Only has the `Asset`s that are started with
+    /// `SystemConfigurations::lockstep`.
+    pub locksteps: std::sync::Mutex<HashMap<Asset, Arc<Lockstep>>>,
+    pub metrics_registry: MetricsRegistry,
+    /// Wakes a batch solve that waits for its iteration budget.
+    pub iteration_limit_reached: tokio::sync::Notify,
+    pub checkpoint_store: Option<Arc<CheckpointStore>>,
+    pub checkpointed_versions: std::sync::Mutex<HashMap<Asset, CheckpointedVersion>>,
+    pub solution_subscribers: std::sync::Mutex<HashMap<Asset, HashMap<Id, Sender<()>>>>,
+    pub solution_feeds: std::sync::Mutex<HashMap<Asset, Arc<std::sync::Mutex<SolutionFeed<Ss>>>>>,
+    /// Signals the `Asset`s whose `SystemSolution` changed to the solution
+    /// feed task.
+    pub solution_changed: Sender<Asset>,
     pub system_configurations: Arc<ArcSwap<SystemConfigurations>>,
     pub database_connections: DataBaseConnection,
     pub actor_notify: Option<Weak<Orchestrator<Ss>>>,
@@ -85,72 +142,251 @@ impl<Ss> Clone for NotifyOrchestrator<Ss>
     }
 }
 
-// WARNING: This should only take immutable references to self!
-impl<Ss> OrchestratorNotifier for NotifyOrchestrator<Ss>
+impl<Ss> Orchestrator<Ss>
+{
+    pub(crate) fn notify_orchestrator(&self) -> Result<NotifyOrchestrator<Ss>>
+    {
+        Ok(NotifyOrchestrator(
+            self.actor_notify
+                .as_ref()
+                .and_then(Weak::upgrade)
+                .context("The Orchestrator is shutting down")?,
+        ))
+    }
+}
+
+impl<Ss> NotifyOrchestrator<Ss>
 where
     Ss: SystemSolutions + Send + Sync + 'static,
 {
-    fn notify_all_agents_of_work_order_change(
+    /// Sends the `DomainEvent` to every actor of the asset that it is routed
+    /// to.
+    fn route_domain_event(
         &self,
-        work_orders: Vec<WorkOrderNumber>,
+        domain_event: &DomainEvent,
+        work_order_resources: &WorkOrderResources,
         asset: &Asset,
     ) -> Result<()>
-// The function should simply be a fire and forget. We should probably, just send a
-    // message to the Orchestrator.
     {
-        // It is too late to change this at the moment. You have to do something else
-        // instead.
-
         let actor_registries = self.0.actor_registries.lock().unwrap();
         let actor_registry = actor_registries
             .get(asset)
             .context("Asset should always be there")?;
 
-        let state_link = StateLink::WorkOrders(ActorSpecific::Strategic(work_orders.clone()));
-
-        //
-        actor_registry
-            .strategic_agent_sender
-            .from_orchestrator(state_link);
-
-        let state_link = StateLink::WorkOrders(ActorSpecific::Strategic(work_orders.clone()));
+        if domain_event.is_routed_to(ActorKind::Strategic)
+            && let Err(error) = actor_registry
+                .strategic_agent_sender
+                .from_orchestrator(domain_event.clone())
+        {
+            event!(Level::WARN, %error, ?domain_event, "StrategicActor was not notified");
+        }
 
-        actor_registry
-            .tactical_agent_sender
-            .from_orchestrator(state_link);
+        if domain_event.is_routed_to(ActorKind::Tactical)
+            && let Err(error) = actor_registry
+                .tactical_agent_sender
+                .from_orchestrator(domain_event.clone())
+        {
+            event!(Level::WARN, %error, ?domain_event, "TacticalActor was not notified");
+        }
 
-        for comm in actor_registry.supervisor_agent_senders.values() {
-            let state_link = StateLink::WorkOrders(ActorSpecific::Strategic(work_orders.clone()));
-            comm.from_orchestrator(state_link);
+        if domain_event.is_routed_to(ActorKind::Supervisor) {
+            for (id, comm) in actor_registry.supervisor_agent_senders.iter() {
+                if let Err(error) = comm.from_orchestrator(domain_event.clone()) {
+                    event!(Level::WARN, %error, actor = %id, ?domain_event, "SupervisorActor was not notified");
+                }
+            }
         }
 
-        for comm in actor_registry.operational_agent_senders.values() {
-            let state_link = StateLink::WorkOrders(ActorSpecific::Strategic(work_orders.clone()));
-            comm.from_orchestrator(state_link);
+        for (id, comm) in actor_registry.operational_agent_senders.iter() {
+            let Some(operational_event) =
+                domain_event.routed_to_operational(id, work_order_resources)
+            else {
+                continue;
+            };
+            if let Err(error) = comm.from_orchestrator(operational_event) {
+                event!(Level::WARN, %error, actor = %id, ?domain_event, "OperationalActor was not notified");
+            }
         }
 
         Ok(())
     }
+
+    /// Routes the `DomainEvent` and persists the parts of the
+    /// `SchedulingEnvironment` that it changed.
+    fn publish(
+        &self,
+        domain_event: DomainEvent,
+        work_order_resources: &WorkOrderResources,
+        asset: &Asset,
+    ) -> Result<()>
+    {
+        self.route_domain_event(&domain_event, work_order_resources, asset)?;
+
+        // The publisher must have released the `SchedulingEnvironment`.
+        self.0
+            .database_connections
+            .persist_domain_event(
+                &self.0.scheduling_environment.lock().unwrap(),
+                &domain_event,
+            )
+            .with_context(|| format!("Could not persist {domain_event:?} for Asset {asset}"))
+    }
+}
+
+// WARNING: This should only take immutable references to self!
+impl<Ss> OrchestratorNotifier for NotifyOrchestrator<Ss>
+where
+    Ss: SystemSolutions + Send + Sync + 'static,
+{
+    fn publish_domain_event(&self, domain_event: DomainEvent, asset: &Asset) -> Result<()>
+    {
+        let work_order_resources = WorkOrderResources::of_work_orders(
+            &self.0.scheduling_environment.lock().unwrap(),
+            &domain_event.work_order_numbers(),
+        );
+        self.publish(domain_event, &work_order_resources, asset)
+    }
+
+    fn mutate_scheduling_environment(
+        &self,
+        mutation: SchedulingEnvironmentMutation,
+        user: &str,
+        asset: &Asset,
+    ) -> Result<u64>
+    {
+        let (scheduling_environment_event, undo_entry) =
+            self.apply_mutation(mutation, user, asset, None)?;
+
+        self.0
+            .undo_stacks
+            .lock()
+            .unwrap()
+            .push(user, asset, undo_entry);
+        Ok(scheduling_environment_event.version)
+    }
+
+    fn subscribe_to_system_solution(&self, id: &Id) -> Receiver<()>
+    {
+        // A single pending notification is enough to wake the actor.
+        let (sender, receiver) = flume::bounded(1);
+
+        self.0
+            .solution_subscribers
+            .lock()
+            .unwrap()
+            .entry(id.asset().clone())
+            .or_default()
+            .insert(id.clone(), sender);
+
+        receiver
+    }
+
+    fn notify_system_solution_changed(&self, id: &Id)
+    {
+        // The feed is recorded by a background task, so the actor only signals.
+        let _ = self.0.solution_changed.send(id.asset().clone());
+
+        let mut solution_subscribers = self.0.solution_subscribers.lock().unwrap();
+
+        let Some(subscribers) = solution_subscribers.get_mut(id.asset()) else {
+            return;
+        };
+
+        subscribers.retain(|subscriber_id, sender| {
+            subscriber_id == id
+                || !matches!(
+                    sender.try_send(()),
+                    Err(flume::TrySendError::Disconnected(_))
+                )
+        });
+    }
+
+    fn iteration_limit_reached(&self, id: &Id)
+    {
+        event!(Level::INFO, actor = %id, "iteration limit reached");
+        self.0.iteration_limit_reached.notify_one();
+    }
+
+    fn cpu_budget(&self, asset: &Asset) -> Result<Arc<CpuBudget>>
+    {
+        self.0
+            .cpu_budgets
+            .lock()
+            .unwrap()
+            .get(asset)
+            .cloned()
+            .with_context(|| format!("Missing CpuBudget for Asset {asset}"))
+    }
+
+    fn lockstep(&self, asset: &Asset) -> Option<Arc<Lockstep>>
+    {
+        self.0.locksteps.lock().unwrap().get(asset).cloned()
+    }
+
+    fn actor_metrics(&self, id: &Id) -> Arc<ActorMetrics>
+    {
+        self.0.metrics_registry.actor_metrics(id)
+    }
+
+    fn checkpoint_store(&self) -> Option<Arc<CheckpointStore>>
+    {
+        self.0.checkpoint_store.clone()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum OrchestratorRequest
 {
     GetWorkOrderStatus(WorkOrderNumber),
-    GetWorkOrdersState(Asset),
+    GetWorkOrdersState(Asset, WorkOrderStatusFilter),
+    GetWorkOrderHistory(WorkOrderNumber),
+    /// Undoes the last planner command of the user on the asset.
+    Undo(Asset, String),
+    Redo(Asset, String),
     GetPeriods,
     GetDays,
     AgentStatusRequest,
     // InitializeSystemAgentsFromFile(Asset, ActorSpecifications),
-    CreateSupervisorAgent(Asset, u64, Id),
+    CreateSupervisorAgent(Asset, InputSupervisor),
     DeleteSupervisorAgent(Asset, String),
-
-    // This should be an API handle not simply
-    // CreateOperationalAgent(Asset, Id, f64, OperationalConfiguration),
+    CreateOperationalAgent(Asset, InputOperational),
     DeleteOperationalAgent(Asset, String),
+    ActorLifecycle(Asset, String, ActorLifecycleRequest),
+    UpdateActorOptions(Asset, String, ActorOptionsUpdate),
     Export(Asset),
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActorLifecycleRequest
+{
+    Pause,
+    Resume,
+    Stop,
+    Restart,
+}
+
+impl ActorLifecycleRequest
+{
+    /// Pausing, resuming and stopping only apply to a running actor. A stopped
+    /// actor can only be restarted.
+    pub fn check_transition(self, id: &Id, is_running: bool) -> Result<(), OrchestratorError>
+    {
+        match self {
+            ActorLifecycleRequest::Pause
+            | ActorLifecycleRequest::Resume
+            | ActorLifecycleRequest::Stop
+                if !is_running =>
+            {
+                Err(OrchestratorError::IllegalTransition(format!(
+                    "{id} is stopped and can only be restarted, not {self:?}"
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
 // These are basically handlers on the `Orchestrator` I think that they
 // should go into the. You have learned so much here but you have to
 // keep going. Remember to follow your guts here.
@@ -162,7 +398,8 @@ where
             Supervisor = SupervisorSolution,
             Operational = OperationalSolution,
         > + Send
-        + Sync,
+        + Sync
+        + 'static,
 {
     #[instrument(level = "info", skip_all)]
     pub async fn handle(
@@ -252,63 +489,44 @@ where
             // You should move the code into the SchedulingEnvironment. The TotalSap should handle
             // the initialization
             OrchestratorRequest::GetWorkOrderStatus(work_order_number) => {
-                let scheduling_environment_guard = self.scheduling_environment.lock().unwrap();
-
-                let cloned_work_orders: &WorkOrders = &scheduling_environment_guard.work_orders;
+                let work_order_response = self.work_order_status(work_order_number).await?;
 
-                let work_order = cloned_work_orders
-                    .inner
-                    .get(&work_order_number)
+                Ok(OrchestratorResponse::WorkOrderStatus(
+                    WorkOrdersStatus::Single(work_order_response),
+                ))
+            }
+            OrchestratorRequest::GetWorkOrdersState(asset, work_order_status_filter) => {
+                let work_order_responses = self
+                    .filtered_work_order_statuses(&asset, &work_order_status_filter)
+                    .await?;
+
+                Ok(OrchestratorResponse::WorkOrderStatus(
+                    WorkOrdersStatus::Multiple(work_order_responses),
+                ))
+            }
+            OrchestratorRequest::GetWorkOrderHistory(work_order_number) => {
+                let work_order_history = self
+                    .database_connections
+                    .work_order_history(work_order_number)
                     .with_context(|| {
-                        format!("{work_order_number:?} is not part of the SchedulingEnvironment")
+                        format!("Could not read the audit log of {work_order_number:?}")
                     })?;
 
-                let asset = &work_order.work_order_info.functional_location.asset;
-
-                let _api_solution = match self.system_solutions.lock().unwrap().get(asset) {
-                    Some(arc_swap_shared_solution) => (arc_swap_shared_solution).load(),
-                    None => bail!("Asset: {:?} is not initialzed", &asset),
-                };
+                Ok(OrchestratorResponse::WorkOrderHistory(work_order_history))
+            }
+            OrchestratorRequest::Undo(asset, user) => {
+                let scheduling_environment_event = self.undo(&asset, &user)?;
 
-                // let work_order_response = WorkOrderResponse::new(
-                //     work_order,
-                //     (**api_solution).clone().into(),
-                //     work_order_configurations,
-                // );
-                bail!("Implement this")
+                Ok(OrchestratorResponse::SchedulingEnvironmentEvent(
+                    scheduling_environment_event,
+                ))
             }
-            OrchestratorRequest::GetWorkOrdersState(asset) => {
-                let scheduling_environment_guard = self.scheduling_environment.lock().unwrap();
+            OrchestratorRequest::Redo(asset, user) => {
+                let scheduling_environment_event = self.redo(&asset, &user)?;
 
-                let cloned_work_orders: &WorkOrders = &scheduling_environment_guard.work_orders;
-                // This is not the correct implementation.
-                let _work_orders: Vec<_> = cloned_work_orders
-                    .inner
-                    .iter()
-                    .filter(|wo| wo.1.work_order_info.functional_location.asset == asset)
-                    .collect();
-
-                let _loaded_shared_solution =
-                    match self.system_solutions.lock().unwrap().get(&asset) {
-                        Some(arc_swap_shared_solution) => arc_swap_shared_solution.load(),
-                        None => bail!("Ordinator has not been initialized for asset: {}", &asset),
-                    };
-
-                // let work_order_configurations = &work_orders.work_order_configurations;
-                // let work_order_responses: HashMap<WorkOrderNumber, WorkOrderResponse> =
-                // work_orders     .inner
-                //     .iter()
-                //     .map(|(work_order_number, work_order)| {
-                //         let work_order_response = WorkOrderResponse::new(
-                //             work_order,
-                //             (**loaded_shared_solution).clone().into(),
-                //             work_order_configurations,
-                //         );
-                //         (*work_order_number, work_order_response)
-                //     })
-                //     .collect();
-
-                bail!("Implement this");
+                Ok(OrchestratorResponse::SchedulingEnvironmentEvent(
+                    scheduling_environment_event,
+                ))
             }
             OrchestratorRequest::GetPeriods => {
                 let scheduling_environment_guard = self.scheduling_environment.lock().unwrap();
@@ -329,127 +547,64 @@ where
                 let tactical_days = OrchestratorResponse::Days(days);
                 Ok(tactical_days)
             }
-            OrchestratorRequest::CreateSupervisorAgent(
-                _asset,
-                _number_of_supervisor_periods,
-                _id_string,
-            ) => {
-                // FIX
-                // Here you should create the system so that an entry in the
-                // `SchedulingEnvironment` is created.
-                // todo!();
-                // FIX
-                let _notify_orchestrator = NotifyOrchestrator(
-                    self.actor_notify
-                        .as_ref()
-                        .expect("Orchestrator is initialized with the Option::Some variant")
-                        .upgrade()
-                        .expect("This Weak reference should always be able to be upgraded."),
-                );
-
-                // The methods should be defined on the `actor_factory`
-                // This should be encapsulated. The factory method and the registry should be of
-                // the same process. Should this be inside of the `Orchestrator`
-                // or the `ActorFactory`? I think that the. So where should this
-                // be defined. I think that the best component is the Orchestrator itself.
-                // TODO [x] Make trait
-                // TODO [ ] Make method on Orchestrator
-                // TODO [ ] Integrate `ActorRegistry`
-                //
-                // FIX [ ] Make a `self.start_supervisor`
+            OrchestratorRequest::CreateSupervisorAgent(asset, input_supervisor) => {
+                let id = input_supervisor.id.clone();
+                self.create_supervisor_actor(&asset, input_supervisor)?;
 
-                // let orchestrator_response =
-                // OrchestratorResponse::RequestStatus(response_string);
-
-                Ok(OrchestratorResponse::Todo)
+                let response_string = format!("Supervisor agent created with id {id}");
+                Ok(OrchestratorResponse::RequestStatus(response_string))
             }
             OrchestratorRequest::DeleteSupervisorAgent(asset, id_string) => {
-                let id = self
-                    .actor_registries
-                    .lock()
-                    .unwrap()
-                    .get(&asset)
-                    .unwrap()
-                    .supervisor_by_id_string(id_string);
-
-                self.actor_registries
-                    .lock()
-                    .unwrap()
-                    .get_mut(&asset)
-                    .unwrap()
-                    .supervisor_agent_senders
-                    .remove(&id);
+                let id = self.delete_supervisor_actor(&asset, &id_string)?;
 
                 let response_string = format!("Supervisor agent deleted with id {id}");
                 let orchestrator_response = OrchestratorResponse::RequestStatus(response_string);
                 Ok(orchestrator_response)
             }
-            // Do we even want this?
-            // Yes it is crucial that `OperationalAgent`s can be created on demand. There is no
-            // excuse for not having that function.
-            // OrchestratorRequest::CreateOperationalAgent(
-            //     asset,
-            //     id,
-            //     hours_per_day,
-            //     operational_configuration,
-            // ) => {
-            //     // This function should update the scheduling environment and then create
-            //     // a function should be called on the scheduling environment to process the
-            //     // requests to create an agent.
-            //     // FIX
-            //     // QUESTION
-            //     // What should this function do?
-            //     // It creates an `OperationalAgent` but that is not enough.
-            //     let response_string = format!("Operational agent created with id {}", id);
-
-            //     let operational_configuration_all = OperationalConfigurationAll::new(
-            //         id.clone(),
-            //         hours_per_day,
-            //         operational_configuration,
-            //     );
-
-            //     // WARN
-            //     // You should create this so that the whole system is optimized
-            //     // you should create the configuration. Let `create_operational_agent`
-            //     // borrow it. And then insert it into the `SchedulingEnvironment`.
-            //     self.create_operational_agent(&operational_configuration_all)?;
-            //     // WARN
-            //     // Is this API fault tolerant enough? I am not really sure.
-            //     self.scheduling_environment
-            //         .lock()
-            //         .unwrap()
-            //         .worker_environment
-            //         .agent_environment
-            //         .operational
-            //         .insert(id, operational_configuration_all);
-
-            //     let orchestrator_response = OrchestratorResponse::RequestStatus(response_string);
-
-            //     Ok(orchestrator_response)
-            // }
+            OrchestratorRequest::CreateOperationalAgent(asset, input_operational) => {
+                let id = input_operational.id.clone();
+                self.create_operational_actor(&asset, input_operational)?;
+
+                let response_string = format!("Operational agent created with id {id}");
+                Ok(OrchestratorResponse::RequestStatus(response_string))
+            }
             OrchestratorRequest::DeleteOperationalAgent(asset, id_string) => {
-                let id = self
-                    .actor_registries
-                    .lock()
-                    .unwrap()
-                    .get(&asset)
-                    .unwrap()
-                    .supervisor_by_id_string(id_string.clone());
-
-                self.actor_registries
-                    .lock()
-                    .unwrap()
-                    .get_mut(&asset)
-                    .unwrap()
-                    .operational_agent_senders
-                    .remove(&id);
-
-                let response_string = format!("Operational agent deleted  with id {id_string}");
+                let id = self.delete_operational_actor(&asset, &id_string)?;
+
+                let response_string = format!("Operational agent deleted with id {id}");
                 let orchestrator_response = OrchestratorResponse::RequestStatus(response_string);
                 Ok(orchestrator_response)
             }
-            OrchestratorRequest::Export(_asset) => {
-                panic!();
+            OrchestratorRequest::ActorLifecycle(asset, id_string, actor_lifecycle_request) => {
+                let (actor_kind, id) = self.actor_by_id_string(&asset, &id_string)?;
+
+                actor_lifecycle_request
+                    .check_transition(&id, self.is_actor_running(actor_kind, &id))?;
+
+                match actor_lifecycle_request {
+                    ActorLifecycleRequest::Pause => self.pause_actor(actor_kind, &id),
+                    ActorLifecycleRequest::Resume => self.resume_actor(actor_kind, &id),
+                    ActorLifecycleRequest::Stop => self.stop_actor(actor_kind, &id),
+                    ActorLifecycleRequest::Restart => self.restart_actor(actor_kind, &id),
+                }?;
+
+                let response_string = format!("{actor_lifecycle_request:?} applied to {id}");
+                Ok(OrchestratorResponse::RequestStatus(response_string))
+            }
+            OrchestratorRequest::UpdateActorOptions(asset, id_string, actor_options_update) => {
+                let actor_options_record =
+                    self.update_actor_options(&asset, &id_string, actor_options_update)?;
+
+                let response_string = format!(
+                    "Options of {} updated at {}",
+                    actor_options_record.actor, actor_options_record.applied_at
+                );
+                Ok(OrchestratorResponse::RequestStatus(response_string))
+            }
+            OrchestratorRequest::Export(asset) => {
+                bail!(OrchestratorError::InvalidRequest(format!(
+                    "The xlsx export of Asset {asset} is not an OrchestratorResponse"
+                )))
             }
         }
     }
@@ -513,13 +668,15 @@ impl ActorRegistry
         self.operational_agent_senders.insert(id, communication);
     }
 
-    pub fn supervisor_by_id_string(&self, id_string: String) -> Id
+    pub fn supervisor_by_id_string(&self, id_string: &str) -> Result<Id, OrchestratorError>
     {
         self.supervisor_agent_senders
             .keys()
             .find(|id| id.0 == id_string)
-            .unwrap()
-            .clone()
+            .cloned()
+            .ok_or_else(|| {
+                OrchestratorError::UnknownActor(format!("The running supervisor actor {id_string}"))
+            })
     }
 }
 
@@ -538,13 +695,43 @@ where
     {
         let configurations = SystemConfigurations::read_all_configs().unwrap();
 
-        let log_handles = logging::setup_logging();
+        Self::with_configurations(configurations)
+    }
+
+    /// Like `new()`, but with `SystemConfigurations` that the caller may have
+    /// changed after reading them, e.g. to fix the master seed.
+    pub fn with_configurations(
+        configurations: Arc<ArcSwap<SystemConfigurations>>,
+    ) -> Result<(Arc<Self>, JoinHandle<Result<()>>)>
+    {
+        Self::with_log_handles(configurations, logging::setup_logging())
+    }
+
+    /// The global subscriber can only be set once, so the tests pass in
+    /// handles to layers that are not installed.
+    fn with_log_handles(
+        configurations: Arc<ArcSwap<SystemConfigurations>>,
+        log_handles: LogHandles,
+    ) -> Result<(Arc<Self>, JoinHandle<Result<()>>)>
+    {
+        event!(
+            Level::INFO,
+            master_seed = configurations.load().master_seed,
+            "master seed of the random number generators"
+        );
+
+        let database_connections = DataBaseConnection::open(&configurations.load().database_config)
+            .context("Could not open the database")?;
 
-        let scheduling_environment =
-            DataBaseConnection::scheduling_environment(configurations.clone())
-                .context("Could not build SchedulingEnvironment")?;
+        let scheduling_environment = database_connections
+            .scheduling_environment(configurations.clone())
+            .context("Could not build SchedulingEnvironment")?;
 
-        let database_connections = DataBaseConnection::new();
+        let checkpoint_store = configurations
+            .load()
+            .checkpoint
+            .enabled
+            .then(|| Arc::new(CheckpointStore::new(database_connections.database())));
 
         // The configurations are already in place, you should strive to make the system
         // as self contained as possible.
@@ -553,35 +740,54 @@ where
         //
         // This is a huge no go. you should have done this in an entirely different way
         // to make this system work.
-        let error_channels: (Sender<anyhow::Error>, Receiver<anyhow::Error>) = flume::bounded(0);
-
-        let error_task_handle: JoinHandle<Result<()>> =
-            tokio::spawn(Self::actor_error_handler(error_channels.1.clone()));
+        let error_channels: (Sender<ActorFailure>, Receiver<ActorFailure>) = flume::bounded(0);
+        let (solution_changed, solution_changed_receiver) = flume::unbounded();
 
         let orchestrator: Arc<Orchestrator<Ss>> = Arc::new_cyclic(|weak_self| Orchestrator {
             scheduling_environment,
             system_solutions: std::sync::Mutex::new(HashMap::new()),
             actor_registries: std::sync::Mutex::new(HashMap::new()),
+            asset_transitions: std::sync::Mutex::new(()),
             log_handles,
             actor_notify: Some(weak_self.clone()),
             system_configurations: configurations,
             database_connections,
             error_channels,
+            supervision_tree: std::sync::Mutex::new(SupervisionTree::default()),
+            actor_options_history: std::sync::Mutex::new(ActorOptionsHistory::default()),
+            undo_stacks: std::sync::Mutex::new(UndoStacks::default()),
+            cpu_budgets: std::sync::Mutex::new(HashMap::new()),
+            locksteps: std::sync::Mutex::new(HashMap::new()),
+            metrics_registry: MetricsRegistry::default(),
+            iteration_limit_reached: tokio::sync::Notify::new(),
+            checkpoint_store,
+            checkpointed_versions: std::sync::Mutex::new(HashMap::new()),
+            solution_subscribers: std::sync::Mutex::new(HashMap::new()),
+            solution_feeds: std::sync::Mutex::new(HashMap::new()),
+            solution_changed,
         });
-        Ok((orchestrator, error_task_handle))
-    }
 
-    async fn actor_error_handler(error_receiver: Receiver<anyhow::Error>) -> Result<()>
-    {
-        // This function will become important if [`ActorError`]s should
-        // not simply crash the Actors
-        // loop {
-        match error_receiver.recv_async().await {
-            // This is the actor error
-            Ok(actor_error) => Err(actor_error),
-            Err(_) => Err(anyhow!("All actors are down")),
+        // The handler only holds a `Weak` reference so that it does not keep the
+        // `Orchestrator` alive.
+        let error_task_handle: JoinHandle<Result<()>> = tokio::spawn(Self::actor_error_handler(
+            Arc::downgrade(&orchestrator),
+            orchestrator.error_channels.1.clone(),
+        ));
+
+        tokio::spawn(Self::consistency_validation_task(Arc::downgrade(
+            &orchestrator,
+        )));
+
+        tokio::spawn(Self::solution_feed_task(
+            Arc::downgrade(&orchestrator),
+            solution_changed_receiver,
+        ));
+
+        if orchestrator.checkpoint_store.is_some() {
+            tokio::spawn(Self::checkpoint_task(Arc::downgrade(&orchestrator)));
         }
-        // }
+
+        Ok((orchestrator, error_task_handle))
     }
 
     pub fn asset_factory(&self, asset: &Asset) -> Result<&Self>
@@ -595,6 +801,10 @@ where
             .lock()
             .unwrap()
             .insert(asset.clone(), system_solution);
+        self.cpu_budgets
+            .lock()
+            .unwrap()
+            .insert(asset.clone(), Arc::new(CpuBudget::default()));
         let dependencies = self.extract_factory_dependencies(asset)?;
 
         let (strategic_id, tactical_id, supervisors, operationals) = {
@@ -637,6 +847,20 @@ where
             (strategic_id, tactical_id, supervisors, operationals)
         };
 
+        // The actors take their turns in the order that they are created in.
+        if self.system_configurations.load().lockstep {
+            let order = [strategic_id.clone(), tactical_id.clone()]
+                .into_iter()
+                .chain(supervisors.iter().cloned())
+                .chain(operationals.iter().cloned())
+                .collect();
+            self.locksteps
+                .lock()
+                .unwrap()
+                .insert(asset.clone(), Arc::new(Lockstep::new(order)));
+        }
+
+        self.register_cpu_budget(ActorKind::Strategic, &strategic_id)?;
         let strategic_communication = StrategicApi::construct_actor(
             strategic_id.clone(),
             dependencies.0.clone(),
@@ -650,6 +874,7 @@ where
         // Where should their IDs come from? I think that the best approach is to
         // include them from
 
+        self.register_cpu_budget(ActorKind::Tactical, &tactical_id)?;
         let tactical_communication = TacticalApi::construct_actor(
             tactical_id.clone(),
             dependencies.0.clone(),
@@ -667,6 +892,7 @@ where
 
         let mut supervisor_communications = HashMap::default();
         for supervisor_id in supervisors {
+            self.register_cpu_budget(ActorKind::Supervisor, &supervisor_id)?;
             let supervisor_communication = SupervisorApi::construct_actor(
                 supervisor_id.clone(),
                 dependencies.0.clone(),
@@ -681,6 +907,7 @@ where
 
         let mut operational_communications = HashMap::default();
         for operational_id in operationals {
+            self.register_cpu_budget(ActorKind::Operational, &operational_id)?;
             let operational_communication = OperationalApi::construct_actor(
                 operational_id.clone(),
                 dependencies.0.clone(),
@@ -730,6 +957,15 @@ impl<Ss> Orchestrator<Ss>
 where
     Ss: SystemSolutions,
 {
+    /// How long a request to an actor waits for the reply.
+    pub fn request_timeout(&self) -> std::time::Duration
+    {
+        self.system_configurations
+            .load()
+            .communication
+            .request_timeout()
+    }
+
     pub fn export_xlsx_solution(&self, asset: Asset) -> Result<(Vec<u8>, String)>
     {
         // let system_solution = self
@@ -753,23 +989,26 @@ where
             scheduling_environment_lock.work_orders.clone()
         };
 
-        let xlsx_filename = create_excel_dump(
-            asset.clone(),
-            work_orders,
-            self.system_solutions
-                .lock()
-                .unwrap()
-                .get(&asset)
-                .with_context(|| {
-                    format!("You should start up a Scheduling System for Asset {asset}")
-                })?
-                .load(),
-        )
-        .unwrap();
+        let system_solution = self
+            .system_solutions
+            .lock()
+            .unwrap()
+            .get(&asset)
+            .ok_or_else(|| {
+                OrchestratorError::ActorUnavailable(format!(
+                    "The scheduling system for Asset {asset}"
+                ))
+            })?
+            .load();
+
+        let xlsx_filename = create_excel_dump(asset.clone(), work_orders, system_solution)
+            .with_context(|| format!("Could not create the xlsx dump for Asset {asset}"))?;
         let mut buffer = Vec::new();
-        let mut file = File::open(&xlsx_filename).unwrap();
-        file.read_to_end(&mut buffer).unwrap();
-        std::fs::remove_file(xlsx_filename).expect("The XLSX file could not be deleted");
+        File::open(&xlsx_filename)
+            .and_then(|mut file| file.read_to_end(&mut buffer))
+            .with_context(|| format!("Could not read {}", xlsx_filename.display()))?;
+        std::fs::remove_file(&xlsx_filename)
+            .with_context(|| format!("Could not delete {}", xlsx_filename.display()))?;
         let filename = format!("ordinator_xlsx_dump_for_{asset}");
         let http_header = format!("attachment; filename={filename}");
 
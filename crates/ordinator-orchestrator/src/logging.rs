@@ -159,3 +159,31 @@ pub fn setup_logging() -> LogHandles
         _flame_handle,
     }
 }
+
+/// Handles to layers that are never installed, for the `Orchestrator`s of the
+/// tests.
+#[cfg(test)]
+pub(crate) fn detached_log_handles() -> LogHandles
+{
+    let (non_blocking, _guard) = tracing_appender::non_blocking(std::io::sink());
+    let (_, file_handle) = reload::Layer::new(
+        fmt::layer()
+            .with_writer(non_blocking)
+            .json()
+            .with_filter(EnvFilter::new("off")),
+    );
+
+    let flame_file = env::temp_dir().join(format!("ordinator_test_{}.folded", std::process::id()));
+    let (_, _flame_handle) = reload::Layer::new(
+        FlameLayer::with_file(&flame_file)
+            .unwrap()
+            .0
+            .with_filter(EnvFilter::new("off")),
+    );
+    let _ = fs::remove_file(flame_file);
+
+    LogHandles {
+        file_handle,
+        _flame_handle,
+    }
+}
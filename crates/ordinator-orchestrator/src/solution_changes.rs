@@ -0,0 +1,166 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::Weak;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use flume::Receiver;
+use ordinator_orchestrator_actor_traits::SystemSolutions;
+use ordinator_orchestrator_actor_traits::solution_feed::SolutionDiff;
+use ordinator_orchestrator_actor_traits::solution_feed::SolutionFeed;
+use ordinator_scheduling_environment::Asset;
+use tracing::Level;
+use tracing::event;
+
+use crate::Orchestrator;
+
+/// The number of `SolutionDiff`s that are kept for each `Asset`.
+pub const SOLUTION_FEED_CAPACITY: usize = 1024;
+
+/// How long the feed waits for more changes before it records them, so
+/// that a burst of published solutions is diffed only once.
+pub const SOLUTION_FEED_DEBOUNCE: Duration = Duration::from_millis(50);
+
+impl<Ss> Orchestrator<Ss>
+where
+    Ss: SystemSolutions + Send + Sync + 'static,
+{
+    /// The `SolutionFeed` of the `Asset`. Each feed has its own lock so that
+    /// recording one `Asset` does not block the others.
+    fn solution_feed(&self, asset: &Asset) -> Arc<Mutex<SolutionFeed<Ss>>>
+    {
+        Arc::clone(
+            self.solution_feeds
+                .lock()
+                .unwrap()
+                .entry(asset.clone())
+                .or_insert_with(|| Arc::new(Mutex::new(SolutionFeed::new(SOLUTION_FEED_CAPACITY)))),
+        )
+    }
+
+    /// Records the changes of the `SystemSolution` of the `Asset` since it
+    /// was last recorded.
+    pub(crate) fn record_solution_changes(&self, asset: &Asset)
+    {
+        let Some(system_solution) = self
+            .system_solutions
+            .lock()
+            .unwrap()
+            .get(asset)
+            .map(|system_solution| system_solution.load_full())
+        else {
+            return;
+        };
+
+        self.solution_feed(asset)
+            .lock()
+            .unwrap()
+            .record(system_solution);
+    }
+
+    /// The changes of the `SystemSolution` of the `Asset` after `version`.
+    pub fn solution_changes_since(&self, asset: &Asset, version: u64) -> Result<Vec<SolutionDiff>>
+    {
+        let solution_feed = self
+            .solution_feeds
+            .lock()
+            .unwrap()
+            .get(asset)
+            .cloned()
+            .with_context(|| format!("There are no recorded changes for Asset {asset}"))?;
+
+        Ok(solution_feed.lock().unwrap().diffs_since(version)?)
+    }
+
+    /// Records the changes that the actors signal on `solution_changed`. The
+    /// diffing is kept off the actor threads. Stops when the `Orchestrator`
+    /// is dropped.
+    pub(crate) async fn solution_feed_task(
+        orchestrator: Weak<Self>,
+        solution_changed: Receiver<Asset>,
+    )
+    {
+        while let Ok(asset) = solution_changed.recv_async().await {
+            tokio::time::sleep(SOLUTION_FEED_DEBOUNCE).await;
+            let assets = changed_assets(asset, &solution_changed);
+
+            let Some(orchestrator) = orchestrator.upgrade() else {
+                return;
+            };
+
+            let recorded = tokio::task::spawn_blocking(move || {
+                for asset in assets {
+                    orchestrator.record_solution_changes(&asset);
+                }
+            })
+            .await;
+
+            if let Err(error) = recorded {
+                event!(Level::ERROR, ?error, "the solution feed task failed");
+            }
+        }
+    }
+}
+
+/// The `Asset`s that changed, with the signals that are already pending
+/// folded into one per `Asset`.
+fn changed_assets(asset: Asset, solution_changed: &Receiver<Asset>) -> HashSet<Asset>
+{
+    let mut assets: HashSet<Asset> = solution_changed.drain().collect();
+    assets.insert(asset);
+    assets
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::sync::Arc;
+
+    use ordinator_orchestrator_actor_traits::OrchestratorError;
+    use ordinator_orchestrator_actor_traits::SystemSolutions;
+    use ordinator_orchestrator_actor_traits::solution_feed::SolutionFeed;
+    use ordinator_scheduling_environment::Asset;
+    use ordinator_scheduling_environment::worker_environment::resources::Id;
+    use ordinator_supervisor_actor::algorithm::supervisor_solution::SupervisorSolution;
+
+    use super::changed_assets;
+    use crate::TotalSystemSolution;
+
+    #[test]
+    fn test_pending_signals_are_folded_per_asset()
+    {
+        let (sender, receiver) = flume::unbounded();
+        for _ in 0..100 {
+            sender.send(Asset::DF).unwrap();
+        }
+        sender.send(Asset::Test).unwrap();
+
+        let assets = changed_assets(Asset::DF, &receiver);
+
+        assert_eq!(assets.len(), 2);
+        assert!(assets.contains(&Asset::DF));
+        assert!(assets.contains(&Asset::Test));
+        assert!(receiver.is_empty());
+    }
+
+    #[test]
+    fn test_diffs_since_a_future_version_is_rejected()
+    {
+        let mut solution_feed = SolutionFeed::new(super::SOLUTION_FEED_CAPACITY);
+        let mut system_solution = TotalSystemSolution::new();
+        system_solution.supervisor_swap(
+            &Id::new("SUP-01", vec![], vec![Asset::Test]),
+            SupervisorSolution::default(),
+        );
+        let version = system_solution.version();
+        solution_feed.record(Arc::new(system_solution));
+
+        assert!(solution_feed.diffs_since(version).unwrap().is_empty());
+        assert!(matches!(
+            solution_feed.diffs_since(version + 1),
+            Err(OrchestratorError::InvalidRequest(_))
+        ));
+    }
+}
@@ -0,0 +1,372 @@
+use anyhow::Result;
+use anyhow::bail;
+use anyhow::ensure;
+use chrono::DateTime;
+use chrono::Utc;
+use ordinator_orchestrator_actor_traits::OrchestratorError;
+use ordinator_orchestrator_actor_traits::SystemSolutions;
+use ordinator_orchestrator_actor_traits::domain_event::DomainEvent;
+use ordinator_orchestrator_actor_traits::domain_event::WorkOrderResources;
+use ordinator_scheduling_environment::Asset;
+use ordinator_scheduling_environment::SchedulingEnvironment;
+use ordinator_scheduling_environment::work_order::WorkOrder;
+use ordinator_scheduling_environment::work_order::WorkOrderActivity;
+use ordinator_scheduling_environment::work_order::WorkOrderNumber;
+use ordinator_scheduling_environment::work_order::operation::Work;
+use ordinator_scheduling_environment::worker_environment::availability::Availability;
+use ordinator_scheduling_environment::worker_environment::resources::Id;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::Level;
+use tracing::event;
+
+use crate::Orchestrator;
+
+/// A change to the `SchedulingEnvironment` that comes from the source
+/// systems. The planners change it through `SchedulingEnvironmentMutation`s
+/// instead.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceChange
+{
+    /// New or changed work orders. The `PlannerDecision` of a changed work
+    /// order is kept.
+    WorkOrders(Vec<WorkOrder>),
+    WorkOrdersClosed(Vec<WorkOrderNumber>),
+    OperationProgress
+    {
+        work_order_activity: WorkOrderActivity,
+        work_remaining: Work,
+    },
+    MaterialExpectedDates(Vec<(WorkOrderNumber, Option<DateTime<Utc>>)>),
+    WorkerAvailability
+    {
+        id: Id,
+        availability: Availability,
+    },
+    /// Drops the first period and appends the next one.
+    PeriodRolled,
+}
+
+impl SourceChange
+{
+    pub fn work_order_numbers(&self) -> Vec<WorkOrderNumber>
+    {
+        match self {
+            SourceChange::WorkOrders(work_orders) => work_orders
+                .iter()
+                .map(|work_order| work_order.work_order_number)
+                .collect(),
+            SourceChange::WorkOrdersClosed(work_order_numbers) => work_order_numbers.clone(),
+            SourceChange::OperationProgress {
+                work_order_activity,
+                ..
+            } => vec![work_order_activity.0],
+            SourceChange::MaterialExpectedDates(material_expected_dates) => material_expected_dates
+                .iter()
+                .map(|(work_order_number, _)| *work_order_number)
+                .collect(),
+            SourceChange::WorkerAvailability { .. } | SourceChange::PeriodRolled => vec![],
+        }
+    }
+
+    /// Applies the change and returns the `DomainEvent`s that it caused.
+    /// Nothing is changed if the change is invalid.
+    pub fn apply(
+        self,
+        scheduling_environment: &mut SchedulingEnvironment,
+        asset: &Asset,
+    ) -> Result<Vec<DomainEvent>>
+    {
+        let work_orders = &mut scheduling_environment.work_orders.inner;
+        if !matches!(self, SourceChange::WorkOrders(_))
+            && let Some(work_order_number) = self
+                .work_order_numbers()
+                .into_iter()
+                .find(|work_order_number| !work_orders.contains_key(work_order_number))
+        {
+            bail!(OrchestratorError::UnknownWorkOrder(work_order_number));
+        }
+
+        let domain_events = match self {
+            SourceChange::WorkOrders(new_work_orders) => {
+                if let Some(work_order) = new_work_orders.iter().find(|work_order| {
+                    &work_order.work_order_info.functional_location.asset != asset
+                }) {
+                    bail!(OrchestratorError::InvalidRequest(format!(
+                        "{:?} is not on Asset {asset}",
+                        work_order.work_order_number
+                    )));
+                }
+
+                let (mut created, mut updated) = (vec![], vec![]);
+                for mut work_order in new_work_orders {
+                    let work_order_number = work_order.work_order_number;
+                    match work_orders.get(&work_order_number) {
+                        Some(existing_work_order) => {
+                            work_order.planner_decision =
+                                existing_work_order.planner_decision.clone();
+                            updated.push(work_order_number);
+                        }
+                        None => created.push(work_order_number),
+                    }
+                    work_orders.insert(work_order_number, work_order);
+                }
+
+                let mut domain_events = vec![];
+                if !created.is_empty() {
+                    domain_events.push(DomainEvent::WorkOrderCreated(created));
+                }
+                if !updated.is_empty() {
+                    domain_events.push(DomainEvent::WorkOrderUpdated(updated));
+                }
+                domain_events
+            }
+            SourceChange::WorkOrdersClosed(work_order_numbers) => {
+                for work_order_number in &work_order_numbers {
+                    work_orders.remove(work_order_number);
+                }
+                vec![DomainEvent::WorkOrderClosed(work_order_numbers)]
+            }
+            SourceChange::OperationProgress {
+                work_order_activity,
+                work_remaining,
+            } => {
+                let operation = work_orders
+                    .get_mut(&work_order_activity.0)
+                    .expect("The work orders were checked above")
+                    .operations
+                    .0
+                    .get_mut(&work_order_activity.1)
+                    .ok_or_else(|| {
+                        OrchestratorError::InvalidRequest(format!(
+                            "{work_order_activity:?} is not an operation"
+                        ))
+                    })?;
+                operation.operation_info.work_remaining = work_remaining;
+                vec![DomainEvent::OperationProgressConfirmed(work_order_activity)]
+            }
+            SourceChange::MaterialExpectedDates(material_expected_dates) => {
+                let mut work_order_numbers = vec![];
+                for (work_order_number, material_expected_date) in material_expected_dates {
+                    work_orders
+                        .get_mut(&work_order_number)
+                        .expect("The work orders were checked above")
+                        .work_order_dates
+                        .material_expected_date = material_expected_date;
+                    work_order_numbers.push(work_order_number);
+                }
+                vec![DomainEvent::MaterialDateChanged(work_order_numbers)]
+            }
+            SourceChange::WorkerAvailability { id, availability } => {
+                let input_operational = scheduling_environment
+                    .worker_environment
+                    .actor_specification
+                    .get_mut(asset)
+                    .and_then(|actor_specifications| {
+                        actor_specifications
+                            .operational
+                            .iter_mut()
+                            .find(|input_operational| input_operational.id == id)
+                    })
+                    .ok_or_else(|| {
+                        OrchestratorError::InvalidRequest(format!(
+                            "{id} is not an operational actor of Asset {asset}"
+                        ))
+                    })?;
+                input_operational.operational_configuration.availability = availability;
+                vec![DomainEvent::WorkerAvailabilityChanged(id)]
+            }
+            SourceChange::PeriodRolled => {
+                let periods = &mut scheduling_environment.time_environment.periods;
+                ensure!(
+                    periods.len() >= 2,
+                    OrchestratorError::InvariantViolated(
+                        "At least two periods are needed to roll the periods".to_string()
+                    )
+                );
+                let last_period = &periods[periods.len() - 1];
+                let period_length =
+                    *last_period.start_date() - *periods[periods.len() - 2].start_date();
+                let next_period = last_period.clone() + period_length;
+                periods.remove(0);
+                periods.push(next_period);
+                vec![DomainEvent::PeriodRolled]
+            }
+        };
+        Ok(domain_events)
+    }
+}
+
+impl<Ss> Orchestrator<Ss>
+where
+    Ss: SystemSolutions + Send + Sync + 'static,
+{
+    /// Applies a change from the source systems to a running asset and
+    /// publishes the `DomainEvent`s that it caused.
+    pub fn apply_source_change(&self, source_change: SourceChange, asset: &Asset) -> Result<()>
+    {
+        let notify_orchestrator = self.notify_orchestrator()?;
+        if !self.actor_registries.lock().unwrap().contains_key(asset) {
+            bail!(OrchestratorError::InvalidRequest(format!(
+                "Asset {asset} is not running"
+            )));
+        }
+
+        let work_order_numbers = source_change.work_order_numbers();
+        let (domain_events, work_order_resources) = {
+            let mut scheduling_environment_guard = self.scheduling_environment.lock().unwrap();
+            // Closed work orders are routed on the resources they had.
+            let mut work_order_resources = WorkOrderResources::of_work_orders(
+                &scheduling_environment_guard,
+                &work_order_numbers,
+            );
+            let domain_events = source_change.apply(&mut scheduling_environment_guard, asset)?;
+            work_order_resources.extend(WorkOrderResources::of_work_orders(
+                &scheduling_environment_guard,
+                &work_order_numbers,
+            ));
+            (domain_events, work_order_resources)
+        };
+
+        for domain_event in domain_events {
+            event!(Level::INFO, %asset, ?domain_event, "source change applied");
+            notify_orchestrator.publish(domain_event, &work_order_resources, asset)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use ordinator_orchestrator_actor_traits::OrchestratorError;
+    use ordinator_orchestrator_actor_traits::domain_event::DomainEvent;
+    use ordinator_scheduling_environment::Asset;
+    use ordinator_scheduling_environment::work_order::WorkOrderNumber;
+    use ordinator_scheduling_environment::work_order::operation::Work;
+
+    use super::SourceChange;
+    use crate::test_environment::period;
+    use crate::test_environment::scheduling_environment;
+    use crate::test_environment::work_order;
+
+    #[test]
+    fn test_work_orders_are_created_and_updated()
+    {
+        let mut scheduling_environment = scheduling_environment(vec![]);
+        let existing = WorkOrderNumber(2100000001);
+        scheduling_environment
+            .work_orders
+            .inner
+            .get_mut(&existing)
+            .unwrap()
+            .planner_decision
+            .lock_in_period(period(1));
+
+        let domain_events =
+            SourceChange::WorkOrders(vec![work_order(2100000001), work_order(2100000002)])
+                .apply(&mut scheduling_environment, &Asset::Unknown)
+                .unwrap();
+
+        assert_eq!(
+            domain_events,
+            vec![
+                DomainEvent::WorkOrderCreated(vec![WorkOrderNumber(2100000002)]),
+                DomainEvent::WorkOrderUpdated(vec![existing]),
+            ]
+        );
+        assert_eq!(scheduling_environment.work_orders.inner.len(), 2);
+        assert_eq!(
+            scheduling_environment.work_orders.inner[&existing]
+                .planner_decision
+                .locked_in_period,
+            Some(period(1))
+        );
+    }
+
+    #[test]
+    fn test_invalid_source_changes_change_nothing()
+    {
+        let mut scheduling_environment = scheduling_environment(vec![]);
+        let existing = WorkOrderNumber(2100000001);
+
+        let error = SourceChange::WorkOrdersClosed(vec![existing, WorkOrderNumber(1)])
+            .apply(&mut scheduling_environment, &Asset::Unknown)
+            .unwrap_err();
+        assert!(matches!(
+            OrchestratorError::find(&error),
+            Some(OrchestratorError::UnknownWorkOrder(WorkOrderNumber(1)))
+        ));
+        assert!(
+            scheduling_environment
+                .work_orders
+                .inner
+                .contains_key(&existing)
+        );
+
+        assert!(
+            SourceChange::WorkOrders(vec![work_order(2100000001)])
+                .apply(&mut scheduling_environment, &Asset::DF)
+                .is_err()
+        );
+
+        assert!(
+            SourceChange::PeriodRolled
+                .apply(&mut scheduling_environment, &Asset::Unknown)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_operation_progress_and_closed_work_orders()
+    {
+        let mut scheduling_environment = scheduling_environment(vec![]);
+        let work_order_activity = (WorkOrderNumber(2100000001), 10);
+
+        let domain_events = SourceChange::OperationProgress {
+            work_order_activity,
+            work_remaining: Work::from(2.0),
+        }
+        .apply(&mut scheduling_environment, &Asset::Unknown)
+        .unwrap();
+        assert_eq!(
+            domain_events,
+            vec![DomainEvent::OperationProgressConfirmed(work_order_activity)]
+        );
+        assert_eq!(
+            scheduling_environment.work_orders.inner[&work_order_activity.0]
+                .operations
+                .0[&work_order_activity.1]
+                .operation_info
+                .work_remaining,
+            Work::from(2.0)
+        );
+
+        let domain_events = SourceChange::WorkOrdersClosed(vec![work_order_activity.0])
+            .apply(&mut scheduling_environment, &Asset::Unknown)
+            .unwrap();
+        assert_eq!(
+            domain_events,
+            vec![DomainEvent::WorkOrderClosed(vec![work_order_activity.0])]
+        );
+        assert!(scheduling_environment.work_orders.inner.is_empty());
+    }
+
+    #[test]
+    fn test_periods_are_rolled()
+    {
+        let mut scheduling_environment = scheduling_environment(vec![period(0), period(1)]);
+
+        let domain_events = SourceChange::PeriodRolled
+            .apply(&mut scheduling_environment, &Asset::Unknown)
+            .unwrap();
+
+        assert_eq!(domain_events, vec![DomainEvent::PeriodRolled]);
+        let periods = &scheduling_environment.time_environment.periods;
+        assert_eq!(periods.len(), 2);
+        assert_eq!(periods[0], period(1));
+        assert_eq!(periods[1].start_date(), period(2).start_date());
+        assert_eq!(periods[1].end_date(), period(2).end_date());
+    }
+}
@@ -0,0 +1,583 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Weak;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use chrono::DateTime;
+use chrono::Utc;
+use flume::Receiver;
+use ordinator_configuration::supervision::RestartPolicy;
+use ordinator_configuration::supervision::RestartStrategy;
+use ordinator_operational_actor::algorithm::operational_solution::OperationalSolution;
+use ordinator_orchestrator_actor_traits::ActorFailure;
+use ordinator_orchestrator_actor_traits::Lifecycle;
+use ordinator_orchestrator_actor_traits::SystemSolutions;
+use ordinator_scheduling_environment::Asset;
+use ordinator_scheduling_environment::worker_environment::resources::Id;
+use ordinator_strategic_actor::algorithm::strategic_solution::StrategicSolution;
+use ordinator_supervisor_actor::algorithm::supervisor_solution::SupervisorSolution;
+use ordinator_tactical_actor::algorithm::tactical_solution::TacticalSolution;
+use serde::Serialize;
+use tracing::Level;
+use tracing::event;
+
+use crate::Orchestrator;
+use crate::actor_registry::ActorKind;
+
+const ERROR_HISTORY_LENGTH: usize = 100;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct ErrorRecord
+{
+    pub time: DateTime<Utc>,
+    pub error: String,
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct ErrorHistory
+{
+    pub errors: VecDeque<ErrorRecord>,
+    pub total_restarts: usize,
+    #[serde(skip)]
+    restarts_in_window: VecDeque<Instant>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SupervisionDecision
+{
+    Restart(Duration),
+    /// The actor is already being restarted because of an earlier error.
+    AlreadyRestarting,
+    Escalate,
+    /// The asset has been escalated and its actors are not restarted anymore.
+    AssetEscalated,
+}
+
+/// Keeps track of the errors and restarts of every actor, and of the assets
+/// where a failure has been escalated.
+#[derive(Default)]
+pub struct SupervisionTree
+{
+    error_histories: HashMap<Id, ErrorHistory>,
+    restarting: HashSet<Id>,
+    escalated_assets: HashMap<Asset, String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SupervisionReport
+{
+    pub escalated: Option<String>,
+    pub error_histories: HashMap<String, ErrorHistory>,
+}
+
+impl SupervisionTree
+{
+    pub fn record_error(&mut self, id: &Id, error: &anyhow::Error)
+    {
+        let error_history = self.error_histories.entry(id.clone()).or_default();
+
+        if error_history.errors.len() == ERROR_HISTORY_LENGTH {
+            error_history.errors.pop_front();
+        }
+        error_history.errors.push_back(ErrorRecord {
+            time: Utc::now(),
+            error: format!("{error:?}"),
+        });
+    }
+
+    /// Applies the `RestartPolicy` to an error from the actor. Only restarts
+    /// inside of the window of the policy count towards `max_restarts`.
+    pub fn decide(&mut self, id: &Id, policy: &RestartPolicy, now: Instant) -> SupervisionDecision
+    {
+        if self.escalated_assets.contains_key(id.asset()) {
+            return SupervisionDecision::AssetEscalated;
+        }
+        if self.restarting.contains(id) {
+            return SupervisionDecision::AlreadyRestarting;
+        }
+
+        let error_history = self.error_histories.entry(id.clone()).or_default();
+
+        while let Some(restart) = error_history.restarts_in_window.front() {
+            if now.duration_since(*restart) > policy.window() {
+                error_history.restarts_in_window.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        if error_history.restarts_in_window.len() >= policy.max_restarts {
+            return SupervisionDecision::Escalate;
+        }
+
+        let backoff = policy.backoff(error_history.restarts_in_window.len());
+        error_history.restarts_in_window.push_back(now);
+        error_history.total_restarts += 1;
+        self.restarting.insert(id.clone());
+
+        SupervisionDecision::Restart(backoff)
+    }
+
+    pub fn restart_finished(&mut self, id: &Id)
+    {
+        self.restarting.remove(id);
+    }
+
+    pub fn escalate(&mut self, asset: &Asset, reason: String)
+    {
+        self.escalated_assets.insert(asset.clone(), reason);
+    }
+
+    /// Forgets the escalation and the errors of the actors of the asset, so
+    /// that the actors of a restarted asset are supervised from scratch.
+    pub fn reset_asset(&mut self, asset: &Asset)
+    {
+        self.escalated_assets.remove(asset);
+        self.error_histories.retain(|id, _| id.asset() != asset);
+    }
+
+    pub fn is_escalated(&self, asset: &Asset) -> bool
+    {
+        self.escalated_assets.contains_key(asset)
+    }
+
+    pub fn report(&self, asset: &Asset) -> SupervisionReport
+    {
+        SupervisionReport {
+            escalated: self.escalated_assets.get(asset).cloned(),
+            error_histories: self
+                .error_histories
+                .iter()
+                .filter(|(id, _)| id.asset() == asset)
+                .map(|(id, error_history)| (id.0.clone(), error_history.clone()))
+                .collect(),
+        }
+    }
+}
+
+impl<Ss> Orchestrator<Ss>
+where
+    Ss: SystemSolutions<
+            Strategic = StrategicSolution,
+            Tactical = TacticalSolution,
+            Supervisor = SupervisorSolution,
+            Operational = OperationalSolution,
+        > + Send
+        + Sync
+        + 'static,
+{
+    // The supervision locks the `SupervisionTree` and the
+    // `SchedulingEnvironment`, so it is done on the blocking thread pool. The
+    // restarts are spawned from there, so that this loop keeps receiving. An
+    // actor that is blocked on the rendezvous error channel could otherwise
+    // never receive its `Lifecycle::Abort`.
+    pub(crate) async fn actor_error_handler(
+        orchestrator: Weak<Self>,
+        error_receiver: Receiver<ActorFailure>,
+    ) -> Result<()>
+    {
+        loop {
+            let actor_failure = error_receiver
+                .recv_async()
+                .await
+                .map_err(|_| anyhow!("All actors are down"))?;
+
+            let orchestrator = orchestrator
+                .upgrade()
+                .context("The Orchestrator has been dropped")?;
+
+            if let Err(error) =
+                tokio::task::spawn_blocking(move || orchestrator.supervise(actor_failure)).await
+            {
+                event!(Level::ERROR, %error, "supervision of an actor failure panicked");
+            }
+        }
+    }
+
+    fn supervise(self: Arc<Self>, actor_failure: ActorFailure)
+    {
+        let ActorFailure { id, error } = actor_failure;
+        event!(Level::ERROR, actor = %id, error = ?error, "actor reported an error");
+
+        let actor_kind = match self.actor_by_id_string(id.asset(), &id.0) {
+            Ok((actor_kind, _)) => actor_kind,
+            Err(error) => {
+                event!(Level::ERROR, actor = %id, %error, "actor cannot be supervised");
+                return;
+            }
+        };
+        let policy = self.restart_policy(actor_kind);
+
+        let decision = {
+            let mut supervision_tree = self.supervision_tree.lock().unwrap();
+            supervision_tree.record_error(&id, &error);
+            supervision_tree.decide(&id, &policy, Instant::now())
+        };
+
+        match decision {
+            SupervisionDecision::Restart(backoff) => {
+                // The failing actor is parked so that it does not publish or
+                // checkpoint anything during the backoff. A panicked actor has
+                // already stopped.
+                if let Err(error) = self.pause_actor(actor_kind, &id) {
+                    event!(Level::DEBUG, actor = %id, %error, "actor could not be paused");
+                }
+
+                tokio::task::spawn_blocking(move || {
+                    std::thread::sleep(backoff);
+
+                    let restart = match policy.strategy {
+                        RestartStrategy::OneForOne => self.restart_from_checkpoint(actor_kind, &id),
+                        RestartStrategy::OneForAll => self.restart_asset_actors(id.asset()),
+                    };
+
+                    self.supervision_tree.lock().unwrap().restart_finished(&id);
+
+                    if let Err(error) = restart {
+                        self.escalate(
+                            id.asset(),
+                            format!("{id} could not be restarted: {error:?}"),
+                        );
+                    }
+                });
+            }
+            SupervisionDecision::Escalate => {
+                let reason = format!(
+                    "{id} exceeded {} restarts within {} seconds",
+                    policy.max_restarts, policy.window_seconds
+                );
+                tokio::task::spawn_blocking(move || self.escalate(id.asset(), reason));
+            }
+            SupervisionDecision::AlreadyRestarting | SupervisionDecision::AssetEscalated => (),
+        }
+    }
+
+    fn restart_policy(&self, actor_kind: ActorKind) -> RestartPolicy
+    {
+        let restart_policies = &self.system_configurations.load().restart_policies;
+        match actor_kind {
+            ActorKind::Strategic => restart_policies.strategic.clone(),
+            ActorKind::Tactical => restart_policies.tactical.clone(),
+            ActorKind::Supervisor => restart_policies.supervisor.clone(),
+            ActorKind::Operational => restart_policies.operational.clone(),
+        }
+    }
+
+    /// Restarts the actor without the solution that it published before it
+    /// failed. The new actor is warm started from the last checkpoint of the
+    /// asset that passed the checks in `CheckpointStore::load`, or cold started
+    /// if there is none.
+    pub fn restart_from_checkpoint(&self, actor_kind: ActorKind, id: &Id) -> Result<()>
+    {
+        if self
+            .supervision_tree
+            .lock()
+            .unwrap()
+            .is_escalated(id.asset())
+        {
+            return Ok(());
+        }
+
+        // A panicked actor fails to join, but it should be restarted all the same.
+        if let Err(error) = self.shutdown_actor(actor_kind, id, Lifecycle::Abort) {
+            event!(Level::WARN, actor = %id, %error, "actor did not shut down cleanly");
+        }
+        self.remove_solution(actor_kind, id)?;
+
+        match actor_kind {
+            ActorKind::Strategic => self.start_strategic_actor(id),
+            ActorKind::Tactical => self.start_tactical_actor(id),
+            ActorKind::Supervisor => self.start_supervisor_actor(id),
+            ActorKind::Operational => self.start_operational_actor(id),
+        }?;
+
+        event!(Level::INFO, actor = %id, ?actor_kind, "actor restarted from the last checkpoint");
+        Ok(())
+    }
+
+    fn restart_asset_actors(&self, asset: &Asset) -> Result<()>
+    {
+        for (actor_kind, id) in self.asset_actors(asset)? {
+            self.restart_from_checkpoint(actor_kind, &id)?;
+        }
+        Ok(())
+    }
+
+    /// Stops every actor of the asset. The other assets and the process keep
+    /// running.
+    fn escalate(&self, asset: &Asset, reason: String)
+    {
+        event!(Level::ERROR, %asset, reason, "actor failure escalated to the asset");
+
+        self.supervision_tree
+            .lock()
+            .unwrap()
+            .escalate(asset, reason);
+
+        let asset_actors = match self.asset_actors(asset) {
+            Ok(asset_actors) => asset_actors,
+            Err(error) => {
+                event!(Level::ERROR, %asset, %error, "actors of the asset could not be found");
+                return;
+            }
+        };
+
+        for (actor_kind, id) in asset_actors {
+            if let Err(error) = self.stop_actor(actor_kind, &id) {
+                event!(Level::WARN, actor = %id, %error, "actor could not be stopped");
+            }
+        }
+    }
+
+    /// All running actors of the asset.
+    fn asset_actors(&self, asset: &Asset) -> Result<Vec<(ActorKind, Id)>>
+    {
+        let (strategic_id, tactical_id) = {
+            let scheduling_environment_guard = self.scheduling_environment.lock().unwrap();
+            let actor_specification = scheduling_environment_guard
+                .worker_environment
+                .actor_specification
+                .get(asset)
+                .with_context(|| format!("Missing ActorSpecifications for Asset {asset}"))?;
+            (
+                actor_specification.strategic.id.clone(),
+                actor_specification.tactical.id.clone(),
+            )
+        };
+
+        let actor_registries = self.actor_registries.lock().unwrap();
+        let actor_registry = actor_registries
+            .get(asset)
+            .with_context(|| format!("Missing ActorRegistry for Asset {asset}"))?;
+
+        let mut asset_actors = vec![];
+        if actor_registry.is_running(ActorKind::Strategic, &strategic_id) {
+            asset_actors.push((ActorKind::Strategic, strategic_id));
+        }
+        if actor_registry.is_running(ActorKind::Tactical, &tactical_id) {
+            asset_actors.push((ActorKind::Tactical, tactical_id));
+        }
+        asset_actors.extend(
+            actor_registry
+                .supervisor_agent_senders
+                .keys()
+                .map(|id| (ActorKind::Supervisor, id.clone())),
+        );
+        asset_actors.extend(
+            actor_registry
+                .operational_agent_senders
+                .keys()
+                .map(|id| (ActorKind::Operational, id.clone())),
+        );
+
+        Ok(asset_actors)
+    }
+
+    pub fn supervision_report(&self, asset: &Asset) -> SupervisionReport
+    {
+        self.supervision_tree.lock().unwrap().report(asset)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::collections::HashMap;
+    use std::time::Duration;
+    use std::time::Instant;
+
+    use anyhow::anyhow;
+    use ordinator_actor_core::catch_panic;
+    use ordinator_configuration::supervision::RestartPolicy;
+    use ordinator_configuration::supervision::RestartStrategy;
+    use ordinator_orchestrator_actor_traits::ActorFailure;
+    use ordinator_orchestrator_actor_traits::ActorMessage;
+    use ordinator_orchestrator_actor_traits::Communication;
+    use ordinator_orchestrator_actor_traits::Lifecycle;
+    use ordinator_scheduling_environment::Asset;
+    use ordinator_scheduling_environment::worker_environment::resources::Id;
+
+    use super::SupervisionDecision;
+    use super::SupervisionTree;
+    use crate::actor_factory::join_actor;
+    use crate::actor_registry::ActorKind;
+    use crate::actor_registry::ActorRegistry;
+
+    fn restart_policy() -> RestartPolicy
+    {
+        RestartPolicy {
+            strategy: RestartStrategy::OneForOne,
+            max_restarts: 2,
+            window_seconds: 60,
+            initial_backoff_milliseconds: 100,
+            max_backoff_milliseconds: 150,
+        }
+    }
+
+    #[test]
+    fn test_supervision_tree_backoff_and_escalation()
+    {
+        let id = Id::new("OP-01-001", vec![], vec![Asset::Test]);
+        let policy = restart_policy();
+        let mut supervision_tree = SupervisionTree::default();
+        let now = Instant::now();
+
+        supervision_tree.record_error(&id, &anyhow!("first"));
+        assert_eq!(
+            supervision_tree.decide(&id, &policy, now),
+            SupervisionDecision::Restart(Duration::from_millis(100))
+        );
+        assert_eq!(
+            supervision_tree.decide(&id, &policy, now),
+            SupervisionDecision::AlreadyRestarting
+        );
+        supervision_tree.restart_finished(&id);
+
+        assert_eq!(
+            supervision_tree.decide(&id, &policy, now),
+            SupervisionDecision::Restart(Duration::from_millis(150))
+        );
+        supervision_tree.restart_finished(&id);
+
+        assert_eq!(
+            supervision_tree.decide(&id, &policy, now),
+            SupervisionDecision::Escalate
+        );
+
+        // Restarts outside of the window do not count towards the maximum.
+        assert_eq!(
+            supervision_tree.decide(&id, &policy, now + Duration::from_secs(61)),
+            SupervisionDecision::Restart(Duration::from_millis(100))
+        );
+
+        supervision_tree.escalate(&Asset::Test, "test".to_string());
+        assert_eq!(
+            supervision_tree.decide(&id, &policy, now),
+            SupervisionDecision::AssetEscalated
+        );
+        assert_eq!(
+            supervision_tree.report(&Asset::Test).error_histories.len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_restarted_asset_is_supervised_again()
+    {
+        let id = Id::new("OP-01-001", vec![], vec![Asset::Test]);
+        let new_id = Id::new("OP-01-002", vec![], vec![Asset::Test]);
+        let policy = restart_policy();
+        let mut supervision_tree = SupervisionTree::default();
+        let now = Instant::now();
+
+        supervision_tree.record_error(&id, &anyhow!("first"));
+        supervision_tree.escalate(&Asset::Test, "test".to_string());
+        assert_eq!(
+            supervision_tree.decide(&new_id, &policy, now),
+            SupervisionDecision::AssetEscalated
+        );
+
+        // Stopping and starting the asset builds new actors.
+        supervision_tree.reset_asset(&Asset::Test);
+        let report = supervision_tree.report(&Asset::Test);
+        assert!(report.escalated.is_none());
+        assert!(report.error_histories.is_empty());
+
+        supervision_tree.record_error(&new_id, &anyhow!("second"));
+        assert_eq!(
+            supervision_tree.decide(&new_id, &policy, now),
+            SupervisionDecision::Restart(Duration::from_millis(100))
+        );
+    }
+
+    /// Runs like an actor thread, where the first LNS iteration panics if
+    /// `panics` is set.
+    fn spawn_test_actor<Req, Res>(
+        id: &Id,
+        panics: bool,
+        error_sender: flume::Sender<ActorFailure>,
+    ) -> Communication<Req, Res>
+    where
+        Req: Send + 'static,
+        Res: Send + 'static,
+    {
+        let (sender, receiver) = flume::unbounded::<ActorMessage<Req, Res>>();
+        let mut communication = Communication::new(sender);
+        let id = id.clone();
+        communication.set_join_handle(std::thread::spawn(move || {
+            let run = || {
+                if panics {
+                    panic!("no capacity left");
+                }
+                for message in receiver.iter() {
+                    if let ActorMessage::Lifecycle(Lifecycle::Shutdown | Lifecycle::Abort) = message
+                    {
+                        break;
+                    }
+                }
+            };
+            if let Err(error) = catch_panic(run) {
+                error_sender.send(ActorFailure { id, error }).unwrap();
+            }
+        }));
+        communication
+    }
+
+    #[test]
+    fn test_panicked_actor_is_restarted()
+    {
+        let strategic_id = Id::new("STRATEGIC", vec![], vec![Asset::Test]);
+        let tactical_id = Id::new("TACTICAL", vec![], vec![Asset::Test]);
+        let id = Id::new("OP-01-001", vec![], vec![Asset::Test]);
+        let (error_sender, error_receiver) = flume::unbounded();
+
+        let mut actor_registry = ActorRegistry::new(
+            spawn_test_actor(&strategic_id, false, error_sender.clone()),
+            spawn_test_actor(&tactical_id, false, error_sender.clone()),
+            HashMap::new(),
+            HashMap::from([(
+                id.clone(),
+                spawn_test_actor(&id, true, error_sender.clone()),
+            )]),
+        );
+
+        let actor_failure = error_receiver
+            .recv_timeout(Duration::from_secs(1))
+            .expect("The panic should be reported as an ActorFailure");
+        assert_eq!(actor_failure.id, id);
+        assert!(actor_failure.error.to_string().contains("no capacity left"));
+
+        let mut supervision_tree = SupervisionTree::default();
+        supervision_tree.record_error(&actor_failure.id, &actor_failure.error);
+        assert_eq!(
+            supervision_tree.decide(&id, &restart_policy(), Instant::now()),
+            SupervisionDecision::Restart(Duration::from_millis(100))
+        );
+
+        // The panic unwound the thread, so it joins without an error.
+        let join_handle = actor_registry
+            .stop(ActorKind::Operational, &id, Lifecycle::Abort)
+            .unwrap();
+        join_actor(join_handle, &id).unwrap();
+        assert!(!actor_registry.is_running(ActorKind::Operational, &id));
+
+        actor_registry.add_operational_agent(
+            id.clone(),
+            spawn_test_actor(&id, false, error_sender.clone()),
+        );
+        supervision_tree.restart_finished(&id);
+
+        assert!(actor_registry.is_running(ActorKind::Operational, &id));
+        assert!(error_receiver.try_recv().is_err());
+        assert_eq!(
+            supervision_tree.report(&Asset::Test).error_histories.len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,252 @@
+//! Hand-built data and an `Orchestrator` on top of it for the tests, as the
+//! source data is not available to them.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use arc_swap::ArcSwap;
+use chrono::Duration;
+use chrono::NaiveDate;
+use chrono::TimeZone;
+use chrono::Utc;
+use ordinator_configuration::SystemConfigurations;
+use ordinator_scheduling_environment::Asset;
+use ordinator_scheduling_environment::SchedulingEnvironment;
+use ordinator_scheduling_environment::time_environment::TimeEnvironment;
+use ordinator_scheduling_environment::time_environment::day::Day;
+use ordinator_scheduling_environment::time_environment::period::Period;
+use ordinator_scheduling_environment::work_order::WorkOrder;
+use ordinator_scheduling_environment::work_order::WorkOrderNumber;
+use ordinator_scheduling_environment::work_order::WorkOrders;
+use ordinator_scheduling_environment::work_order::work_order_info::WorkOrderInfoDetail;
+use ordinator_scheduling_environment::work_order::work_order_info::functional_location::FunctionalLocation;
+use ordinator_scheduling_environment::work_order::work_order_info::priority::Priority;
+use ordinator_scheduling_environment::work_order::work_order_info::revision::Revision;
+use ordinator_scheduling_environment::work_order::work_order_info::system_condition::SystemCondition;
+use ordinator_scheduling_environment::work_order::work_order_info::work_order_text::WorkOrderText;
+use ordinator_scheduling_environment::work_order::work_order_info::work_order_type::WorkOrderType;
+use ordinator_scheduling_environment::worker_environment::WorkerEnvironment;
+use ordinator_scheduling_environment::worker_environment::resources::Resources;
+
+use crate::Orchestrator;
+use crate::TotalSystemSolution;
+use crate::database::DataBaseConnection;
+use crate::logging;
+
+pub(crate) fn work_order(work_order_number: u64) -> WorkOrder
+{
+    WorkOrder::builder(WorkOrderNumber(work_order_number))
+        .main_work_center(Resources::MtnMech)
+        .operations_builder(10, Resources::MtnMech, |operation_builder| {
+            operation_builder
+                .operation_info(|operation_info_builder| {
+                    operation_info_builder
+                        .work_remaining(10.0)
+                        .work_actual(0.0)
+                        .work(10.0)
+                })
+                .operation_analytic(|operation_analytic_builder| {
+                    operation_analytic_builder
+                        .preparation_time(0.0)
+                        .duration(10.0)
+                })
+                .operation_dates(|operation_dates_builder| {
+                    let start_date = Utc.with_ymd_and_hms(2025, 1, 6, 7, 0, 0).unwrap();
+                    operation_dates_builder
+                        .earliest_start_datetime(start_date)
+                        .earliest_finish_datetime(start_date + Duration::hours(10))
+                })
+        })
+        .work_order_analytic_builder(|work_order_analytic_builder| {
+            work_order_analytic_builder
+                .system_status_codes(|system_status_codes_builder| system_status_codes_builder)
+                .user_status_codes(|user_status_codes_builder| user_status_codes_builder)
+        })
+        .work_order_info_builder(|work_order_info_builder| {
+            work_order_info_builder
+                .priority(Priority::Int(1))
+                .work_order_type(WorkOrderType::default())
+                .functional_location(FunctionalLocation::default())
+                .work_order_text(WorkOrderText::default())
+                .revision(Revision::default())
+                .system_condition(SystemCondition::default())
+                .work_order_info_detail(WorkOrderInfoDetail::default())
+        })
+        .work_order_dates_builder(|work_order_dates_builder| {
+            let start_date = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+            work_order_dates_builder
+                .earliest_allowed_start_date(start_date)
+                .latest_allowed_finish_date(start_date + Duration::weeks(4))
+                .basic_start_date(start_date)
+                .basic_finish_date(start_date + Duration::weeks(4))
+                .duration(Duration::weeks(4))
+        })
+        .build()
+}
+
+pub(crate) fn scheduling_environment(periods: Vec<Period>) -> SchedulingEnvironment
+{
+    let work_order = work_order(2100000001);
+    let scheduling_environment = SchedulingEnvironment::builder()
+        .work_orders(WorkOrders {
+            inner: HashMap::from([(work_order.work_order_number, work_order)]),
+        })
+        .time_environment(TimeEnvironment::new(periods, vec![]))
+        .build();
+
+    std::sync::Arc::into_inner(scheduling_environment)
+        .unwrap()
+        .into_inner()
+        .unwrap()
+}
+
+/// The actors of `Asset::Test`, in the format of the `ActorSpecifications`
+/// files.
+const TEST_ACTOR_SPECIFICATIONS: &str = r#"
+[material_to_period]
+nmat = 0
+smat = 0
+cmat = 2
+pmat = 3
+wmat = 3
+
+[strategic]
+number_of_strategic_periods = 6
+id = [ "StrategicActor", [], ["Test"] ]
+strategic_options.number_of_removed_work_orders = 2
+strategic_options.urgency_weight = 1
+strategic_options.resource_penalty_weight = 1000000
+# The work orders all share one functional location, and the clustering
+# term would outweigh the rest of the strategic objective value.
+strategic_options.clustering_weight = 0
+
+[tactical]
+number_of_tactical_days = 28
+id = [ "TacticalActor", [], ["Test"] ]
+tactical_options.number_of_removed_work_orders = 2
+tactical_options.urgency = 1
+tactical_options.resource_penalty = 10000000
+
+[[supervisors]]
+number_of_supervisor_periods = 3
+id = [ "SUP-01", [], ["Test"] ]
+supervisor_options.number_of_unassigned_work_orders = 2
+
+[[operational]]
+hours_per_day = 6.0
+operational_options.number_of_removed_activities = 2
+operational_configuration.off_shift_interval = { start = "19:00:00", end = "07:00:00" }
+operational_configuration.break_interval = { start = "11:00:00", end = "12:00:00" }
+operational_configuration.toolbox_interval = { start = "07:00:00", end = "08:00:00" }
+operational_configuration.availability.start_date = "2025-01-06T07:00:00Z"
+operational_configuration.availability.finish_date = "2025-01-20T15:00:00Z"
+id = [ "OP-01-001", ["MTN-MECH"], ["Test"] ]
+
+[[operational]]
+hours_per_day = 6.0
+operational_options.number_of_removed_activities = 2
+operational_configuration.off_shift_interval = { start = "19:00:00", end = "07:00:00" }
+operational_configuration.break_interval = { start = "11:00:00", end = "12:00:00" }
+operational_configuration.toolbox_interval = { start = "07:00:00", end = "08:00:00" }
+operational_configuration.availability.start_date = "2025-01-06T07:00:00Z"
+operational_configuration.availability.finish_date = "2025-01-20T15:00:00Z"
+id = [ "OP-01-002", ["MTN-MECH"], ["Test"] ]
+
+[work_order_configurations]
+operating_time = 6
+wdf_priority_map = { 1 = 500, 2 = 50, 3 = 5, 4 = 1 }
+wgn_priority_map = { 1 = 500, 2 = 50, 3 = 5, 4 = 1 }
+wpm_priority_map = { "A" = 500, "B" = 50, "C" = 5, "D" = 1 }
+vis_priority_map = { "V" = 100, "I" = 10, "S" = 10 }
+order_type_weights = { "WDF" = 10, "WGN" = 8, "WPM" = 5, "Other" = 0 }
+status_weights = { "SECE" = 7500, "PCNF_NMAT_SMAT" = 1500, "AWSC" = 10000 }
+clustering_weights = { asset = 10, sector = 5, system = 2, subsystem = 2, equipment_tag = 1 }
+"#;
+
+/// A small `Asset::Test` that the actors can be started on: two operational
+/// actors under one supervisor and a few work orders in the first periods.
+pub(crate) fn test_asset_scheduling_environment() -> SchedulingEnvironment
+{
+    let work_orders = (2100000001..=2100000004)
+        .map(|work_order_number| {
+            let mut work_order = work_order(work_order_number);
+            work_order.work_order_info.functional_location = FunctionalLocation {
+                string: "TEST".to_string(),
+                asset: Asset::Test,
+            };
+            (work_order.work_order_number, work_order)
+        })
+        .collect();
+
+    let periods: Vec<Period> = (0..8).map(period).collect();
+    let days = (0..28)
+        .map(|day_index| {
+            Day::new(
+                day_index,
+                *periods[0].start_date() + Duration::days(day_index as i64),
+            )
+        })
+        .collect();
+
+    let scheduling_environment = SchedulingEnvironment::builder()
+        .work_orders(WorkOrders { inner: work_orders })
+        .worker_environment(
+            WorkerEnvironment::builder()
+                .actor_specifications(
+                    Asset::Test,
+                    toml::from_str(TEST_ACTOR_SPECIFICATIONS).unwrap(),
+                )
+                .build(),
+        )
+        .time_environment(TimeEnvironment::new(periods, days))
+        .build();
+
+    Arc::into_inner(scheduling_environment)
+        .unwrap()
+        .into_inner()
+        .unwrap()
+}
+
+pub(crate) fn period(id: i32) -> Period
+{
+    let start_date =
+        Utc.with_ymd_and_hms(2025, 1, 6, 0, 0, 0).unwrap() + Duration::weeks(2 * id as i64);
+    Period::new(
+        id,
+        start_date,
+        start_date + Duration::weeks(2) - Duration::seconds(1),
+    )
+}
+
+impl Orchestrator<TotalSystemSolution>
+{
+    /// An `Orchestrator` on the `SchedulingEnvironment` with its own database
+    /// at `database_path`. It has to be created inside a tokio runtime.
+    pub(crate) fn for_tests(
+        database_path: &Path,
+        scheduling_environment: &SchedulingEnvironment,
+    ) -> Arc<Self>
+    {
+        let configuration_directory =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("../../configuration");
+        let mut configurations =
+            (**SystemConfigurations::read_configs(&configuration_directory, database_path)
+                .unwrap()
+                .load())
+            .clone();
+        configurations.checkpoint.enabled = false;
+
+        DataBaseConnection::open(database_path)
+            .unwrap()
+            .save_scheduling_environment(scheduling_environment)
+            .unwrap();
+
+        Self::with_log_handles(
+            Arc::new(ArcSwap::from_pointee(configurations)),
+            logging::detached_log_handles(),
+        )
+        .unwrap()
+        .0
+    }
+}
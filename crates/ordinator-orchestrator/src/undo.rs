@@ -0,0 +1,448 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use ordinator_operational_actor::algorithm::operational_solution::OperationalSolution;
+use ordinator_orchestrator_actor_traits::OrchestratorError;
+use ordinator_orchestrator_actor_traits::SystemSolutions;
+use ordinator_orchestrator_actor_traits::domain_event::DomainEvent;
+use ordinator_orchestrator_actor_traits::domain_event::WorkOrderResources;
+use ordinator_scheduling_environment::Asset;
+use ordinator_scheduling_environment::audit_log::PlannerState;
+use ordinator_scheduling_environment::audit_log::SchedulingEnvironmentEvent;
+use ordinator_scheduling_environment::audit_log::SchedulingEnvironmentMutation;
+use ordinator_scheduling_environment::work_order::WorkOrders;
+use ordinator_strategic_actor::algorithm::strategic_solution::StrategicSolution;
+use ordinator_supervisor_actor::algorithm::supervisor_solution::SupervisorSolution;
+use ordinator_tactical_actor::algorithm::tactical_solution::TacticalSolution;
+use tracing::Level;
+use tracing::event;
+
+use crate::NotifyOrchestrator;
+use crate::Orchestrator;
+
+/// An applied planner command, as the `PlannerState`s of its work orders
+/// before and after it.
+#[derive(Debug, Clone)]
+pub struct UndoEntry
+{
+    before: Vec<PlannerState>,
+    after: Vec<PlannerState>,
+}
+
+#[derive(Debug, Default)]
+struct UndoStack
+{
+    undo: Vec<UndoEntry>,
+    redo: Vec<UndoEntry>,
+}
+
+/// The undo and redo stacks of every user on every asset. They are only kept
+/// in memory, so a restart clears them.
+#[derive(Debug, Default)]
+pub struct UndoStacks
+{
+    stacks: HashMap<(String, Asset), UndoStack>,
+}
+
+impl UndoStacks
+{
+    /// A new command of the user makes the undone commands unavailable for
+    /// redo.
+    pub fn push(&mut self, user: &str, asset: &Asset, undo_entry: UndoEntry)
+    {
+        let undo_stack = self.stack(user, asset);
+        undo_stack.undo.push(undo_entry);
+        undo_stack.redo.clear();
+    }
+
+    /// Stopping an asset ends the planning on it, so its commands can no
+    /// longer be undone or redone.
+    pub fn clear_asset(&mut self, asset: &Asset)
+    {
+        self.stacks
+            .retain(|(_, stack_asset), _| stack_asset != asset);
+    }
+
+    fn stack(&mut self, user: &str, asset: &Asset) -> &mut UndoStack
+    {
+        self.stacks
+            .entry((user.to_string(), asset.clone()))
+            .or_default()
+    }
+}
+
+impl<Ss> NotifyOrchestrator<Ss>
+where
+    Ss: SystemSolutions + Send + Sync + 'static,
+{
+    /// Applies the mutation, appends it to the audit log and signals the
+    /// actors to update the work orders. With `expected_planner_states` the
+    /// work orders have to still be in those states, so that a later command
+    /// of another planner is never overwritten.
+    ///
+    /// The mutation is applied to a copy of the work orders, which only
+    /// replaces them once the audit log is committed. An `Err` therefore
+    /// always means that nothing was changed.
+    pub(crate) fn apply_mutation(
+        &self,
+        mutation: SchedulingEnvironmentMutation,
+        user: &str,
+        asset: &Asset,
+        expected_planner_states: Option<&[PlannerState]>,
+    ) -> Result<(SchedulingEnvironmentEvent, UndoEntry)>
+    {
+        let work_order_numbers = mutation.work_order_numbers();
+
+        let (scheduling_environment_event, undo_entry, work_order_resources) = {
+            let mut scheduling_environment_guard = self.0.scheduling_environment.lock().unwrap();
+            let before =
+                PlannerState::of_work_orders(&scheduling_environment_guard, &work_order_numbers);
+            if let Some(expected_planner_states) = expected_planner_states
+                && before != expected_planner_states
+            {
+                bail!(OrchestratorError::IllegalTransition(
+                    "The work orders were changed by a later command".to_string()
+                ));
+            }
+
+            if let Some(work_order_number) = work_order_numbers.iter().find(|work_order_number| {
+                !scheduling_environment_guard
+                    .work_orders
+                    .inner
+                    .contains_key(work_order_number)
+            }) {
+                bail!(OrchestratorError::UnknownWorkOrder(*work_order_number));
+            }
+
+            let mut work_orders = WorkOrders {
+                inner: work_order_numbers
+                    .iter()
+                    .filter_map(|work_order_number| {
+                        scheduling_environment_guard
+                            .work_orders
+                            .inner
+                            .get_key_value(work_order_number)
+                    })
+                    .map(|(work_order_number, work_order)| (*work_order_number, work_order.clone()))
+                    .collect(),
+            };
+            mutation
+                .apply_to_work_orders(&mut work_orders)
+                .context("Could not apply the SchedulingEnvironmentMutation")?;
+
+            let scheduling_environment_event =
+                self.0
+                    .database_connections
+                    .append_mutation(&work_orders, user, asset, mutation)?;
+            scheduling_environment_guard
+                .work_orders
+                .inner
+                .extend(work_orders.inner);
+
+            let after =
+                PlannerState::of_work_orders(&scheduling_environment_guard, &work_order_numbers);
+            let work_order_resources = WorkOrderResources::of_work_orders(
+                &scheduling_environment_guard,
+                &work_order_numbers,
+            );
+            (
+                scheduling_environment_event,
+                UndoEntry { before, after },
+                work_order_resources,
+            )
+        };
+
+        event!(
+            Level::INFO,
+            version = scheduling_environment_event.version,
+            user,
+            %asset,
+            mutation = ?scheduling_environment_event.mutation,
+            "SchedulingEnvironment mutated"
+        );
+
+        // The mutation is committed, so the actors of a stopped asset simply
+        // pick it up when the asset is started again.
+        if let Err(error) = self.route_domain_event(
+            &DomainEvent::WorkOrderUpdated(work_order_numbers),
+            &work_order_resources,
+            asset,
+        ) {
+            event!(
+                Level::WARN,
+                %error,
+                version = scheduling_environment_event.version,
+                %asset,
+                "The actors were not notified of the mutation"
+            );
+        }
+        Ok((scheduling_environment_event, undo_entry))
+    }
+}
+
+impl<Ss> Orchestrator<Ss>
+where
+    Ss: SystemSolutions<
+            Strategic = StrategicSolution,
+            Tactical = TacticalSolution,
+            Supervisor = SupervisorSolution,
+            Operational = OperationalSolution,
+        > + Send
+        + Sync
+        + 'static,
+{
+    /// Restores the work orders of the last command of the user on the asset.
+    pub fn undo(&self, asset: &Asset, user: &str) -> Result<SchedulingEnvironmentEvent>
+    {
+        let notify_orchestrator = self.notify_orchestrator()?;
+        let mut undo_stacks = self.undo_stacks.lock().unwrap();
+        let undo_stack = undo_stacks.stack(user, asset);
+
+        let undo_entry = undo_stack.undo.pop().ok_or_else(|| {
+            OrchestratorError::InvalidRequest(format!(
+                "{user} has nothing to undo on Asset {asset}"
+            ))
+        })?;
+
+        match notify_orchestrator.apply_mutation(
+            SchedulingEnvironmentMutation::Restore(undo_entry.before.clone()),
+            user,
+            asset,
+            Some(&undo_entry.after),
+        ) {
+            Ok((scheduling_environment_event, _)) => {
+                undo_stack.redo.push(undo_entry);
+                Ok(scheduling_environment_event)
+            }
+            // Nothing was changed, so the command can be tried again.
+            Err(error) => {
+                undo_stack.undo.push(undo_entry);
+                Err(error)
+            }
+        }
+    }
+
+    /// Applies the last undone command of the user on the asset again.
+    pub fn redo(&self, asset: &Asset, user: &str) -> Result<SchedulingEnvironmentEvent>
+    {
+        let notify_orchestrator = self.notify_orchestrator()?;
+        let mut undo_stacks = self.undo_stacks.lock().unwrap();
+        let undo_stack = undo_stacks.stack(user, asset);
+
+        let undo_entry = undo_stack.redo.pop().ok_or_else(|| {
+            OrchestratorError::InvalidRequest(format!(
+                "{user} has nothing to redo on Asset {asset}"
+            ))
+        })?;
+
+        match notify_orchestrator.apply_mutation(
+            SchedulingEnvironmentMutation::Restore(undo_entry.after.clone()),
+            user,
+            asset,
+            Some(&undo_entry.before),
+        ) {
+            Ok((scheduling_environment_event, _)) => {
+                undo_stack.undo.push(undo_entry);
+                Ok(scheduling_environment_event)
+            }
+            // Nothing was changed, so the command can be tried again.
+            Err(error) => {
+                undo_stack.redo.push(undo_entry);
+                Err(error)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use ordinator_orchestrator_actor_traits::OrchestratorError;
+    use ordinator_orchestrator_actor_traits::OrchestratorNotifier;
+    use ordinator_scheduling_environment::Asset;
+    use ordinator_scheduling_environment::audit_log::SchedulingEnvironmentMutation;
+    use ordinator_scheduling_environment::work_order::WorkOrderNumber;
+    use ordinator_scheduling_environment::work_order::planner_decision::PlannerDecision;
+    use ordinator_scheduling_environment::work_order::work_order_analytic::status_codes::StrategicUserStatusCodes;
+    use ordinator_scheduling_environment::work_order::work_order_analytic::status_codes::UserStatusCodes;
+
+    use super::UndoEntry;
+    use super::UndoStacks;
+    use crate::Orchestrator;
+    use crate::test_environment::period;
+    use crate::test_environment::scheduling_environment;
+
+    const WORK_ORDER_NUMBER: WorkOrderNumber = WorkOrderNumber(2100000001);
+
+    fn planner_state<Ss>(orchestrator: &Orchestrator<Ss>) -> (PlannerDecision, UserStatusCodes)
+    {
+        let scheduling_environment = orchestrator.scheduling_environment.lock().unwrap();
+        let work_order = &scheduling_environment.work_orders.inner[&WORK_ORDER_NUMBER];
+        (
+            work_order.planner_decision.clone(),
+            work_order.work_order_analytic.user_status_codes.clone(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_commands_are_undone_and_redone()
+    {
+        let database_path =
+            std::env::temp_dir().join(format!("ordinator_undo_test_{}.redb", std::process::id()));
+        let orchestrator =
+            Orchestrator::for_tests(&database_path, &scheduling_environment(vec![period(0)]));
+        let notify_orchestrator = orchestrator.notify_orchestrator().unwrap();
+        let initial = planner_state(&orchestrator);
+
+        // The asset is not started, so the actors cannot be notified.
+        notify_orchestrator
+            .mutate_scheduling_environment(
+                SchedulingEnvironmentMutation::UserStatus(StrategicUserStatusCodes {
+                    work_order_numbers: vec![WORK_ORDER_NUMBER],
+                    sch: Some(!initial.1.sch),
+                    awsc: None,
+                    sece: None,
+                }),
+                "planner",
+                &Asset::Test,
+            )
+            .unwrap();
+        let user_status = planner_state(&orchestrator);
+        assert_eq!(user_status.1.sch, !initial.1.sch);
+
+        notify_orchestrator
+            .mutate_scheduling_environment(
+                SchedulingEnvironmentMutation::LockInPeriod {
+                    work_order_numbers: vec![WORK_ORDER_NUMBER],
+                    period: period(0),
+                },
+                "planner",
+                &Asset::Test,
+            )
+            .unwrap();
+        let locked = planner_state(&orchestrator);
+        assert_eq!(locked.0.locked_in_period, Some(period(0)));
+
+        orchestrator.undo(&Asset::Test, "planner").unwrap();
+        assert_eq!(planner_state(&orchestrator), user_status);
+        orchestrator.undo(&Asset::Test, "planner").unwrap();
+        assert_eq!(planner_state(&orchestrator), initial);
+        assert!(orchestrator.undo(&Asset::Test, "planner").is_err());
+
+        orchestrator.redo(&Asset::Test, "planner").unwrap();
+        assert_eq!(planner_state(&orchestrator), user_status);
+        orchestrator.redo(&Asset::Test, "planner").unwrap();
+        assert_eq!(planner_state(&orchestrator), locked);
+        assert!(orchestrator.redo(&Asset::Test, "planner").is_err());
+
+        let error = notify_orchestrator
+            .mutate_scheduling_environment(
+                SchedulingEnvironmentMutation::LockInPeriod {
+                    work_order_numbers: vec![WORK_ORDER_NUMBER, WorkOrderNumber(1)],
+                    period: period(0),
+                },
+                "planner",
+                &Asset::Test,
+            )
+            .unwrap_err();
+        assert_eq!(
+            OrchestratorError::find(&error),
+            Some(&OrchestratorError::UnknownWorkOrder(WorkOrderNumber(1)))
+        );
+
+        assert_eq!(
+            orchestrator.database_connections.audit_log().unwrap().len(),
+            6
+        );
+        drop(notify_orchestrator);
+        drop(orchestrator);
+        std::fs::remove_file(database_path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_undo_never_overwrites_a_later_command()
+    {
+        let database_path = std::env::temp_dir().join(format!(
+            "ordinator_undo_conflict_test_{}.redb",
+            std::process::id()
+        ));
+        let orchestrator = Orchestrator::for_tests(
+            &database_path,
+            &scheduling_environment(vec![period(0), period(1)]),
+        );
+        let notify_orchestrator = orchestrator.notify_orchestrator().unwrap();
+
+        notify_orchestrator
+            .mutate_scheduling_environment(
+                SchedulingEnvironmentMutation::LockInPeriod {
+                    work_order_numbers: vec![WORK_ORDER_NUMBER],
+                    period: period(0),
+                },
+                "planner",
+                &Asset::Test,
+            )
+            .unwrap();
+        notify_orchestrator
+            .mutate_scheduling_environment(
+                SchedulingEnvironmentMutation::ExcludeFromPeriod {
+                    work_order_numbers: vec![WORK_ORDER_NUMBER],
+                    period: period(1),
+                },
+                "other_planner",
+                &Asset::Test,
+            )
+            .unwrap();
+        let later_command = planner_state(&orchestrator);
+
+        for _ in 0..2 {
+            let error = orchestrator.undo(&Asset::Test, "planner").unwrap_err();
+            assert!(matches!(
+                OrchestratorError::find(&error),
+                Some(OrchestratorError::IllegalTransition(_))
+            ));
+            assert_eq!(planner_state(&orchestrator), later_command);
+        }
+        assert_eq!(
+            orchestrator.database_connections.audit_log().unwrap().len(),
+            2
+        );
+
+        orchestrator.undo(&Asset::Test, "other_planner").unwrap();
+        orchestrator.undo(&Asset::Test, "planner").unwrap();
+        assert_eq!(planner_state(&orchestrator).0, PlannerDecision::default());
+
+        drop(notify_orchestrator);
+        drop(orchestrator);
+        std::fs::remove_file(database_path).unwrap();
+    }
+
+    #[test]
+    fn test_new_command_clears_redo_of_the_user()
+    {
+        let undo_entry = UndoEntry {
+            before: Vec::new(),
+            after: Vec::new(),
+        };
+        let mut undo_stacks = UndoStacks::default();
+        undo_stacks.push("planner", &Asset::Test, undo_entry.clone());
+        undo_stacks.push("other_planner", &Asset::Test, undo_entry.clone());
+
+        let undo_stack = undo_stacks.stack("planner", &Asset::Test);
+        let undone = undo_stack.undo.pop().unwrap();
+        undo_stack.redo.push(undone);
+
+        undo_stacks.push("other_planner", &Asset::Test, undo_entry.clone());
+        assert_eq!(undo_stacks.stack("planner", &Asset::Test).redo.len(), 1);
+
+        undo_stacks.push("planner", &Asset::Test, undo_entry);
+        let undo_stack = undo_stacks.stack("planner", &Asset::Test);
+        assert_eq!(undo_stack.undo.len(), 1);
+        assert!(undo_stack.redo.is_empty());
+        assert_eq!(
+            undo_stacks.stack("other_planner", &Asset::Test).undo.len(),
+            2
+        );
+    }
+}
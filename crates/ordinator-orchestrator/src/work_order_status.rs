@@ -0,0 +1,484 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use ordinator_contracts::orchestrator::ApiSolution;
+use ordinator_contracts::orchestrator::OperationalApiSolution;
+use ordinator_contracts::orchestrator::StrategicApiSolution;
+use ordinator_contracts::orchestrator::WorkOrderResponse;
+use ordinator_operational_actor::algorithm::operational_solution::OperationalSolution;
+use ordinator_orchestrator_actor_traits::OperationalInterface;
+use ordinator_orchestrator_actor_traits::OrchestratorError;
+use ordinator_orchestrator_actor_traits::StrategicInterface;
+use ordinator_orchestrator_actor_traits::SupervisorInterface;
+use ordinator_orchestrator_actor_traits::SystemSolutions;
+use ordinator_orchestrator_actor_traits::TacticalInterface;
+use ordinator_scheduling_environment::Asset;
+use ordinator_scheduling_environment::work_order::WorkOrder;
+use ordinator_scheduling_environment::work_order::WorkOrderNumber;
+use ordinator_scheduling_environment::worker_environment::resources::Resources;
+use ordinator_strategic_actor::algorithm::strategic_parameters::WorkOrderParameter;
+use ordinator_strategic_actor::algorithm::strategic_solution::StrategicSolution;
+use ordinator_strategic_actor::messages::StrategicRequestMessage;
+use ordinator_strategic_actor::messages::StrategicResponseMessage;
+use ordinator_strategic_actor::messages::requests::StrategicStatusMessage;
+use ordinator_supervisor_actor::algorithm::supervisor_solution::SupervisorSolution;
+use ordinator_tactical_actor::algorithm::tactical_solution::TacticalSolution;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::Orchestrator;
+
+/// Selects the work orders of an `Asset` in the work order status view.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkOrderStatusFilter
+{
+    /// A user or system status code, like `AWSC` or `REL`.
+    pub status: Option<String>,
+    /// Only work orders with an operation on the `Resources`.
+    pub resource: Option<Resources>,
+}
+
+impl WorkOrderStatusFilter
+{
+    pub fn matches(&self, work_order: &WorkOrder) -> bool
+    {
+        self.status
+            .as_ref()
+            .is_none_or(|status| work_order.work_order_analytic.has_status_code(status))
+            && self.resource.is_none_or(|resource| {
+                work_order
+                    .operations
+                    .0
+                    .values()
+                    .any(|operation| operation.resource == resource)
+            })
+    }
+}
+
+impl<Ss> Orchestrator<Ss>
+where
+    Ss: SystemSolutions<
+            Strategic = StrategicSolution,
+            Tactical = TacticalSolution,
+            Supervisor = SupervisorSolution,
+            Operational = OperationalSolution,
+        > + Send
+        + Sync
+        + 'static,
+{
+    /// The work order in the `SchedulingEnvironment` and in the solution of
+    /// every scheduling layer.
+    pub async fn work_order_status(
+        &self,
+        work_order_number: WorkOrderNumber,
+    ) -> Result<WorkOrderResponse>
+    {
+        let asset = self
+            .scheduling_environment
+            .lock()
+            .unwrap()
+            .work_orders
+            .inner
+            .get(&work_order_number)
+            .ok_or(OrchestratorError::UnknownWorkOrder(work_order_number))?
+            .work_order_info
+            .functional_location
+            .asset
+            .clone();
+
+        self.work_order_statuses(&asset, |work_order| {
+            work_order.work_order_number == work_order_number
+        })
+        .await?
+        .remove(&work_order_number)
+        .with_context(|| format!("{work_order_number:?} is missing in the status view"))
+    }
+
+    /// The work orders of the `Asset` that match the `WorkOrderStatusFilter`.
+    pub async fn filtered_work_order_statuses(
+        &self,
+        asset: &Asset,
+        work_order_status_filter: &WorkOrderStatusFilter,
+    ) -> Result<HashMap<WorkOrderNumber, WorkOrderResponse>>
+    {
+        self.work_order_statuses(asset, |work_order| {
+            work_order_status_filter.matches(work_order)
+        })
+        .await
+    }
+
+    async fn work_order_statuses(
+        &self,
+        asset: &Asset,
+        filter: impl Fn(&WorkOrder) -> bool,
+    ) -> Result<HashMap<WorkOrderNumber, WorkOrderResponse>>
+    {
+        let (work_orders, periods, work_order_configurations, material_to_period) = {
+            let scheduling_environment_guard = self.scheduling_environment.lock().unwrap();
+            let actor_specification = scheduling_environment_guard
+                .worker_environment
+                .actor_specification
+                .get(asset)
+                .with_context(|| format!("Missing actor specification for Asset {asset}"))?;
+
+            let work_orders: Vec<WorkOrder> = scheduling_environment_guard
+                .work_orders
+                .work_orders_by_asset(asset)
+                .into_values()
+                .filter(|work_order| filter(work_order))
+                .cloned()
+                .collect();
+            (
+                work_orders,
+                scheduling_environment_guard
+                    .time_environment
+                    .periods
+                    .clone(),
+                actor_specification.work_order_configurations.clone(),
+                actor_specification.material_to_period.clone(),
+            )
+        };
+
+        let system_solution = self
+            .system_solutions
+            .lock()
+            .unwrap()
+            .get(asset)
+            .ok_or_else(|| {
+                OrchestratorError::ActorUnavailable(format!(
+                    "The scheduling system for Asset {asset}"
+                ))
+            })?
+            .load_full();
+
+        let work_order_numbers: Vec<WorkOrderNumber> = work_orders
+            .iter()
+            .map(|work_order| work_order.work_order_number)
+            .collect();
+        let strategic_parameters = self
+            .strategic_work_order_parameters(asset, work_order_numbers.clone())
+            .await?;
+        let mut api_solutions =
+            api_solutions(&*system_solution, &work_order_numbers, strategic_parameters);
+
+        work_orders
+            .iter()
+            .map(|work_order| {
+                let work_order_response = WorkOrderResponse::new(
+                    work_order,
+                    api_solutions
+                        .remove(&work_order.work_order_number)
+                        .unwrap_or_default(),
+                    &periods,
+                    &work_order_configurations,
+                    &material_to_period,
+                )?;
+                Ok((work_order.work_order_number, work_order_response))
+            })
+            .collect()
+    }
+
+    /// The locked in and excluded periods are derived from the `WorkOrder`,
+    /// with its `planner_decision` taking precedence, and only the strategic
+    /// actor has them resolved against its periods.
+    async fn strategic_work_order_parameters(
+        &self,
+        asset: &Asset,
+        work_order_numbers: Vec<WorkOrderNumber>,
+    ) -> Result<HashMap<WorkOrderNumber, WorkOrderParameter>>
+    {
+        let requester = self
+            .actor_registries
+            .lock()
+            .unwrap()
+            .get(asset)
+            .ok_or_else(|| {
+                OrchestratorError::ActorUnavailable(format!(
+                    "The scheduling system for Asset {asset}"
+                ))
+            })?
+            .strategic_agent_sender
+            .requester();
+
+        let strategic_response = requester
+            .request(
+                StrategicRequestMessage::Status(StrategicStatusMessage::WorkOrders(
+                    work_order_numbers,
+                )),
+                self.request_timeout(),
+            )
+            .await?;
+
+        match strategic_response {
+            StrategicResponseMessage::WorkOrders(strategic_work_order_parameters) => {
+                Ok(strategic_work_order_parameters)
+            }
+            _ => bail!(OrchestratorError::InvariantViolated(
+                "The strategic actor did not respond with its WorkOrderParameters".to_string()
+            )),
+        }
+    }
+}
+
+/// Collects the work orders from the solution of each layer that has
+/// published one.
+fn api_solutions<Ss>(
+    system_solution: &Ss,
+    work_order_numbers: &[WorkOrderNumber],
+    mut strategic_parameters: HashMap<WorkOrderNumber, WorkOrderParameter>,
+) -> HashMap<WorkOrderNumber, ApiSolution>
+where
+    Ss: SystemSolutions,
+{
+    let strategic = system_solution.strategic().ok();
+    let mut tactical_days = system_solution
+        .tactical_actor_solution()
+        .map(|tactical| tactical.all_scheduled_tasks())
+        .unwrap_or_default();
+
+    let mut api_solutions: HashMap<WorkOrderNumber, ApiSolution> = work_order_numbers
+        .iter()
+        .map(|work_order_number| {
+            let strategic_parameter = strategic_parameters.remove(work_order_number);
+            let api_solution = ApiSolution {
+                strategic: StrategicApiSolution {
+                    solution: strategic
+                        .and_then(|strategic| strategic.scheduled_task(work_order_number))
+                        .cloned()
+                        .flatten(),
+                    locked_in_period: strategic_parameter
+                        .as_ref()
+                        .and_then(|parameter| parameter.locked_in_period.clone()),
+                    excluded_from_period: strategic_parameter
+                        .map(|parameter| parameter.excluded_periods)
+                        .unwrap_or_default(),
+                },
+                tactical: tactical_days.remove(work_order_number).unwrap_or_default(),
+                ..Default::default()
+            };
+            (*work_order_number, api_solution)
+        })
+        .collect();
+
+    if let Ok(supervisors) = system_solution.supervisor_actor_solutions() {
+        for ((operational_id, (work_order_number, activity_number)), delegate) in supervisors
+            .values()
+            .flat_map(|supervisor| supervisor.all_delegates())
+        {
+            if let Some(api_solution) = api_solutions.get_mut(work_order_number) {
+                api_solution
+                    .supervisor
+                    .entry(*activity_number)
+                    .or_default()
+                    .insert(operational_id.0.clone(), delegate.clone());
+            }
+        }
+    }
+
+    for operational_id in system_solution.all_operational() {
+        let Ok(operational) = system_solution.operational_actor_solutions(&operational_id) else {
+            continue;
+        };
+        for ((work_order_number, activity_number), (start, finish)) in
+            operational.scheduled_intervals()
+        {
+            if let Some(api_solution) = api_solutions.get_mut(&work_order_number) {
+                api_solution
+                    .operational
+                    .entry(activity_number)
+                    .or_default()
+                    .insert(
+                        operational_id.0.clone(),
+                        OperationalApiSolution { start, finish },
+                    );
+            }
+        }
+    }
+
+    api_solutions
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::collections::HashMap;
+    use std::collections::HashSet;
+
+    use chrono::Duration;
+    use chrono::TimeZone;
+    use chrono::Utc;
+    use ordinator_operational_actor::algorithm::operational_events::OperationalEvents;
+    use ordinator_operational_actor::algorithm::operational_solution::Assignment;
+    use ordinator_operational_actor::algorithm::operational_solution::OperationalAssignment;
+    use ordinator_operational_actor::algorithm::operational_solution::OperationalSolution;
+    use ordinator_orchestrator_actor_traits::SystemSolutions;
+    use ordinator_orchestrator_actor_traits::delegate::Delegate;
+    use ordinator_scheduling_environment::Asset;
+    use ordinator_scheduling_environment::time_environment::TimeInterval;
+    use ordinator_scheduling_environment::work_order::WorkOrderNumber;
+    use ordinator_scheduling_environment::worker_environment::resources::Id;
+    use ordinator_scheduling_environment::worker_environment::resources::Resources;
+    use ordinator_strategic_actor::algorithm::strategic_parameters::WorkOrderParameter;
+    use ordinator_strategic_actor::algorithm::strategic_solution::StrategicObjectiveValue;
+    use ordinator_strategic_actor::algorithm::strategic_solution::StrategicSolution;
+    use ordinator_supervisor_actor::algorithm::supervisor_solution::SupervisorSolution;
+
+    use super::WorkOrderStatusFilter;
+    use super::api_solutions;
+    use crate::TotalSystemSolution;
+    use crate::test_environment::period;
+    use crate::test_environment::work_order;
+
+    const SCHEDULED: WorkOrderNumber = WorkOrderNumber(2100000001);
+    const UNSCHEDULED: WorkOrderNumber = WorkOrderNumber(2100000002);
+
+    #[test]
+    fn test_work_order_status_filter_matches()
+    {
+        let mut work_order = work_order(SCHEDULED.0);
+        work_order.work_order_analytic.user_status_codes.awsc = true;
+        work_order.work_order_analytic.system_status_codes.rel = true;
+
+        let filter = |status: Option<&str>, resource: Option<Resources>| WorkOrderStatusFilter {
+            status: status.map(str::to_string),
+            resource,
+        };
+
+        assert!(WorkOrderStatusFilter::default().matches(&work_order));
+        assert!(filter(Some("AWSC"), None).matches(&work_order));
+        assert!(filter(Some("rel"), None).matches(&work_order));
+        assert!(!filter(Some("SMAT"), None).matches(&work_order));
+        assert!(!filter(Some("NOT-A-STATUS"), None).matches(&work_order));
+        assert!(filter(None, Some(Resources::MtnMech)).matches(&work_order));
+        assert!(!filter(None, Some(Resources::MtnElec)).matches(&work_order));
+        assert!(filter(Some("AWSC"), Some(Resources::MtnMech)).matches(&work_order));
+        assert!(!filter(Some("AWSC"), Some(Resources::MtnElec)).matches(&work_order));
+    }
+
+    /// The `SupervisorSolution` only creates its `Delegate`s in the auction,
+    /// so the test reads them in.
+    fn supervisor_solution(
+        delegates: &[(&Id, (WorkOrderNumber, u64), Delegate)],
+    ) -> SupervisorSolution
+    {
+        let operational_state_machine: serde_json::Map<String, serde_json::Value> = delegates
+            .iter()
+            .map(|(operational_id, work_order_activity, delegate)| {
+                (
+                    serde_json::to_string(&(operational_id, work_order_activity)).unwrap(),
+                    serde_json::to_value(delegate).unwrap(),
+                )
+            })
+            .collect();
+
+        serde_json::from_value(serde_json::json!({
+            "objective_value": 0,
+            "operational_state_machine": operational_state_machine,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_api_solutions_collect_every_layer()
+    {
+        let operational_id = Id::new("OP-01-001", vec![Resources::MtnMech], vec![Asset::Test]);
+        let start = Utc.with_ymd_and_hms(2025, 1, 6, 8, 0, 0).unwrap();
+        let finish = start + Duration::hours(3);
+
+        let mut system_solution = TotalSystemSolution::new();
+        system_solution.strategic_swap(
+            &Id::new("StrategicActor", vec![], vec![Asset::Test]),
+            StrategicSolution {
+                objective_value: StrategicObjectiveValue {
+                    objective_value: 0,
+                    urgency: (0, 0),
+                    resource_penalty: (0, 0),
+                    clustering_value: (0, 0),
+                },
+                strategic_scheduled_work_orders: HashMap::from([
+                    (SCHEDULED, Some(period(1))),
+                    (UNSCHEDULED, None),
+                ]),
+                strategic_loadings: Default::default(),
+                overloaded_resource_periods: Default::default(),
+            },
+        );
+        system_solution.supervisor_swap(
+            &Id::new("SUP-01", vec![], vec![Asset::Test]),
+            supervisor_solution(&[
+                (&operational_id, (SCHEDULED, 10), Delegate::Assign),
+                (
+                    &operational_id,
+                    (WorkOrderNumber(2100000003), 10),
+                    Delegate::Assess,
+                ),
+            ]),
+        );
+        system_solution.operational_swap(
+            &operational_id,
+            OperationalSolution {
+                objective_value: Default::default(),
+                scheduled_work_order_activities: vec![
+                    (
+                        (WorkOrderNumber(0), 0),
+                        OperationalAssignment::new(vec![Assignment {
+                            operational_events: OperationalEvents::Unavailable(
+                                TimeInterval::from_date_times(start, finish),
+                            ),
+                            start,
+                            finish,
+                        }]),
+                    ),
+                    (
+                        (SCHEDULED, 10),
+                        OperationalAssignment::new(vec![Assignment {
+                            operational_events: OperationalEvents::WrenchTime((
+                                TimeInterval::from_date_times(start, finish),
+                                (SCHEDULED, 10),
+                            )),
+                            start,
+                            finish,
+                        }]),
+                    ),
+                ],
+            },
+        );
+
+        let strategic_parameters = HashMap::from([(
+            SCHEDULED,
+            WorkOrderParameter {
+                locked_in_period: Some(period(1)),
+                excluded_periods: HashSet::from([period(2)]),
+                ..Default::default()
+            },
+        )]);
+
+        let mut api_solutions = api_solutions(
+            &system_solution,
+            &[SCHEDULED, UNSCHEDULED],
+            strategic_parameters,
+        );
+        assert_eq!(api_solutions.len(), 2);
+
+        let scheduled = api_solutions.remove(&SCHEDULED).unwrap();
+        assert_eq!(scheduled.strategic.solution, Some(period(1)));
+        assert_eq!(scheduled.strategic.locked_in_period, Some(period(1)));
+        assert_eq!(
+            scheduled.strategic.excluded_from_period,
+            HashSet::from([period(2)])
+        );
+        assert!(scheduled.tactical.is_empty());
+        assert_eq!(scheduled.supervisor[&10]["OP-01-001"], Delegate::Assign);
+        let operational = &scheduled.operational[&10]["OP-01-001"];
+        assert_eq!((operational.start, operational.finish), (start, finish));
+
+        let unscheduled = api_solutions.remove(&UNSCHEDULED).unwrap();
+        assert_eq!(unscheduled.strategic.solution, None);
+        assert_eq!(unscheduled.strategic.locked_in_period, None);
+        assert!(unscheduled.strategic.excluded_from_period.is_empty());
+        assert!(unscheduled.supervisor.is_empty());
+        assert!(unscheduled.operational.is_empty());
+    }
+}
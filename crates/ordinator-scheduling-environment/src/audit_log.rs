@@ -0,0 +1,389 @@
+use anyhow::Result;
+use anyhow::bail;
+use chrono::DateTime;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::Asset;
+use crate::SchedulingEnvironment;
+use crate::time_environment::period::Period;
+use crate::work_order::WorkOrderNumber;
+use crate::work_order::WorkOrders;
+use crate::work_order::planner_decision::PlannerDecision;
+use crate::work_order::work_order_analytic::status_codes::StrategicUserStatusCodes;
+use crate::work_order::work_order_analytic::status_codes::UserStatusCodes;
+
+/// A change that a planner makes to the `SchedulingEnvironment`. Applying the
+/// logged mutations in order to the same initial `SchedulingEnvironment`
+/// always gives the same state.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum SchedulingEnvironmentMutation
+{
+    UserStatus(StrategicUserStatusCodes),
+    LockInPeriod
+    {
+        work_order_numbers: Vec<WorkOrderNumber>,
+        period: Period,
+    },
+    ExcludeFromPeriod
+    {
+        work_order_numbers: Vec<WorkOrderNumber>,
+        period: Period,
+    },
+    /// Sets the work orders back to an earlier `PlannerState`, which is how
+    /// commands are undone and redone.
+    Restore(Vec<PlannerState>),
+}
+
+/// The parts of a `WorkOrder` that the planners change.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PlannerState
+{
+    pub work_order_number: WorkOrderNumber,
+    pub planner_decision: PlannerDecision,
+    pub user_status_codes: UserStatusCodes,
+}
+
+impl SchedulingEnvironmentMutation
+{
+    pub fn work_order_numbers(&self) -> Vec<WorkOrderNumber>
+    {
+        match self {
+            SchedulingEnvironmentMutation::UserStatus(strategic_user_status_codes) => {
+                strategic_user_status_codes.work_order_numbers.clone()
+            }
+            SchedulingEnvironmentMutation::LockInPeriod {
+                work_order_numbers, ..
+            }
+            | SchedulingEnvironmentMutation::ExcludeFromPeriod {
+                work_order_numbers, ..
+            } => work_order_numbers.clone(),
+            SchedulingEnvironmentMutation::Restore(planner_states) => planner_states
+                .iter()
+                .map(|planner_state| planner_state.work_order_number)
+                .collect(),
+        }
+    }
+
+    /// Nothing is changed if one of the work orders is unknown.
+    pub fn apply(&self, scheduling_environment: &mut SchedulingEnvironment) -> Result<()>
+    {
+        self.apply_to_work_orders(&mut scheduling_environment.work_orders)
+    }
+
+    /// A mutation only changes the work orders, so it can be applied to a
+    /// copy of the ones that it names.
+    pub fn apply_to_work_orders(&self, work_orders: &mut WorkOrders) -> Result<()>
+    {
+        let work_orders = &mut work_orders.inner;
+        if let Some(work_order_number) = self
+            .work_order_numbers()
+            .iter()
+            .find(|work_order_number| !work_orders.contains_key(work_order_number))
+        {
+            bail!("{work_order_number:?} is not in the SchedulingEnvironment");
+        }
+
+        if let SchedulingEnvironmentMutation::Restore(planner_states) = self {
+            for planner_state in planner_states {
+                let work_order = work_orders
+                    .get_mut(&planner_state.work_order_number)
+                    .expect("The work orders were checked above");
+                work_order.planner_decision = planner_state.planner_decision.clone();
+                work_order.work_order_analytic.user_status_codes =
+                    planner_state.user_status_codes.clone();
+            }
+            return Ok(());
+        }
+
+        for work_order_number in self.work_order_numbers() {
+            let work_order = work_orders
+                .get_mut(&work_order_number)
+                .expect("The work orders were checked above");
+
+            match self {
+                SchedulingEnvironmentMutation::UserStatus(strategic_user_status_codes) => {
+                    let user_status_codes = &mut work_order.work_order_analytic.user_status_codes;
+                    if let Some(sece) = strategic_user_status_codes.sece {
+                        user_status_codes.sece = sece;
+                    }
+                    if let Some(sch) = strategic_user_status_codes.sch {
+                        user_status_codes.sch = sch;
+                    }
+                    if let Some(awsc) = strategic_user_status_codes.awsc {
+                        user_status_codes.awsc = awsc;
+                    }
+                }
+                SchedulingEnvironmentMutation::LockInPeriod { period, .. } => {
+                    work_order.planner_decision.lock_in_period(period.clone())
+                }
+                SchedulingEnvironmentMutation::ExcludeFromPeriod { period, .. } => {
+                    work_order.planner_decision.exclude_period(period.clone())
+                }
+                SchedulingEnvironmentMutation::Restore(_) => unreachable!("Restored above"),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl PlannerState
+{
+    /// The current `PlannerState`s of the work orders that exist.
+    pub fn of_work_orders(
+        scheduling_environment: &SchedulingEnvironment,
+        work_order_numbers: &[WorkOrderNumber],
+    ) -> Vec<PlannerState>
+    {
+        work_order_numbers
+            .iter()
+            .filter_map(|work_order_number| {
+                scheduling_environment
+                    .work_orders
+                    .inner
+                    .get(work_order_number)
+            })
+            .map(|work_order| PlannerState {
+                work_order_number: work_order.work_order_number,
+                planner_decision: work_order.planner_decision.clone(),
+                user_status_codes: work_order.work_order_analytic.user_status_codes.clone(),
+            })
+            .collect()
+    }
+}
+
+/// An entry of the append-only audit log. The `version` counts the mutations
+/// of the `SchedulingEnvironment`, so it is the version that the mutation
+/// resulted in.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SchedulingEnvironmentEvent
+{
+    pub version: u64,
+    pub timestamp: DateTime<Utc>,
+    pub user: String,
+    pub asset: Asset,
+    pub mutation: SchedulingEnvironmentMutation,
+}
+
+impl SchedulingEnvironment
+{
+    /// Rebuilds the state by applying the events, in the order of their
+    /// versions, to the `SchedulingEnvironment` that they were logged on.
+    pub fn replay<'a>(
+        &mut self,
+        events: impl IntoIterator<Item = &'a SchedulingEnvironmentEvent>,
+    ) -> Result<()>
+    {
+        for event in events {
+            event.mutation.apply(self).map_err(|error| {
+                error.context(format!("Could not replay version {}", event.version))
+            })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use chrono::Duration;
+    use chrono::NaiveDate;
+    use chrono::TimeZone;
+    use chrono::Utc;
+
+    use super::PlannerState;
+    use super::SchedulingEnvironmentEvent;
+    use super::SchedulingEnvironmentMutation;
+    use crate::Asset;
+    use crate::SchedulingEnvironment;
+    use crate::time_environment::period::Period;
+    use crate::work_order::WorkOrder;
+    use crate::work_order::WorkOrderNumber;
+    use crate::work_order::WorkOrders;
+    use crate::work_order::work_order_analytic::status_codes::StrategicUserStatusCodes;
+    use crate::work_order::work_order_info::WorkOrderInfoDetail;
+    use crate::work_order::work_order_info::functional_location::FunctionalLocation;
+    use crate::work_order::work_order_info::priority::Priority;
+    use crate::work_order::work_order_info::revision::Revision;
+    use crate::work_order::work_order_info::system_condition::SystemCondition;
+    use crate::work_order::work_order_info::work_order_text::WorkOrderText;
+    use crate::work_order::work_order_info::work_order_type::WorkOrderType;
+    use crate::worker_environment::resources::Resources;
+
+    fn work_order(work_order_number: WorkOrderNumber) -> WorkOrder
+    {
+        WorkOrder::builder(work_order_number)
+            .main_work_center(Resources::MtnMech)
+            .operations_builder(10, Resources::MtnMech, |operation_builder| {
+                operation_builder
+                    .operation_info(|operation_info_builder| {
+                        operation_info_builder
+                            .work_remaining(10.0)
+                            .work_actual(0.0)
+                            .work(10.0)
+                    })
+                    .operation_analytic(|operation_analytic_builder| {
+                        operation_analytic_builder
+                            .preparation_time(0.0)
+                            .duration(10.0)
+                    })
+                    .operation_dates(|operation_dates_builder| {
+                        let start_date = Utc.with_ymd_and_hms(2025, 1, 6, 7, 0, 0).unwrap();
+                        operation_dates_builder
+                            .earliest_start_datetime(start_date)
+                            .earliest_finish_datetime(start_date + Duration::hours(10))
+                    })
+            })
+            .work_order_analytic_builder(|work_order_analytic_builder| {
+                work_order_analytic_builder
+                    .system_status_codes(|system_status_codes_builder| system_status_codes_builder)
+                    .user_status_codes(|user_status_codes_builder| user_status_codes_builder)
+            })
+            .work_order_info_builder(|work_order_info_builder| {
+                work_order_info_builder
+                    .priority(Priority::Int(1))
+                    .work_order_type(WorkOrderType::default())
+                    .functional_location(FunctionalLocation::default())
+                    .work_order_text(WorkOrderText::default())
+                    .revision(Revision::default())
+                    .system_condition(SystemCondition::default())
+                    .work_order_info_detail(WorkOrderInfoDetail::default())
+            })
+            .work_order_dates_builder(|work_order_dates_builder| {
+                let start_date = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+                work_order_dates_builder
+                    .earliest_allowed_start_date(start_date)
+                    .latest_allowed_finish_date(start_date + Duration::weeks(4))
+                    .basic_start_date(start_date)
+                    .basic_finish_date(start_date + Duration::weeks(4))
+                    .duration(Duration::weeks(4))
+            })
+            .build()
+    }
+
+    fn scheduling_environment(work_order_numbers: &[WorkOrderNumber]) -> SchedulingEnvironment
+    {
+        let scheduling_environment = SchedulingEnvironment::builder()
+            .work_orders(WorkOrders {
+                inner: work_order_numbers
+                    .iter()
+                    .map(|work_order_number| (*work_order_number, work_order(*work_order_number)))
+                    .collect::<HashMap<_, _>>(),
+            })
+            .build();
+
+        Arc::into_inner(scheduling_environment)
+            .unwrap()
+            .into_inner()
+            .unwrap()
+    }
+
+    fn period(id: i32) -> Period
+    {
+        let start_date =
+            Utc.with_ymd_and_hms(2025, 1, 6, 0, 0, 0).unwrap() + Duration::weeks(2 * id as i64);
+        Period::new(
+            id,
+            start_date,
+            start_date + Duration::weeks(2) - Duration::seconds(1),
+        )
+    }
+
+    fn events(mutations: Vec<SchedulingEnvironmentMutation>) -> Vec<SchedulingEnvironmentEvent>
+    {
+        mutations
+            .into_iter()
+            .zip(1..)
+            .map(|(mutation, version)| SchedulingEnvironmentEvent {
+                version,
+                timestamp: Utc::now(),
+                user: "planner".to_string(),
+                asset: Asset::Test,
+                mutation,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_replay_gives_the_directly_applied_state()
+    {
+        let first = WorkOrderNumber(2100000001);
+        let second = WorkOrderNumber(2100000002);
+        let mut scheduling_environment = scheduling_environment(&[first, second]);
+        let initial_planner_states =
+            PlannerState::of_work_orders(&scheduling_environment, &[first]);
+
+        let events = events(vec![
+            SchedulingEnvironmentMutation::LockInPeriod {
+                work_order_numbers: vec![first],
+                period: period(0),
+            },
+            SchedulingEnvironmentMutation::ExcludeFromPeriod {
+                work_order_numbers: vec![first, second],
+                period: period(1),
+            },
+            SchedulingEnvironmentMutation::UserStatus(StrategicUserStatusCodes {
+                work_order_numbers: vec![second],
+                sch: Some(true),
+                awsc: None,
+                sece: Some(true),
+            }),
+            SchedulingEnvironmentMutation::Restore(initial_planner_states.clone()),
+        ]);
+        for event in &events {
+            event.mutation.apply(&mut scheduling_environment).unwrap();
+        }
+
+        let mut replayed_scheduling_environment = self::scheduling_environment(&[first, second]);
+        replayed_scheduling_environment.replay(&events).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&replayed_scheduling_environment.work_orders).unwrap(),
+            serde_json::to_value(&scheduling_environment.work_orders).unwrap()
+        );
+        assert_eq!(
+            PlannerState::of_work_orders(&replayed_scheduling_environment, &[first]),
+            initial_planner_states
+        );
+        let replayed_second = &replayed_scheduling_environment.work_orders.inner[&second];
+        assert!(
+            replayed_second
+                .planner_decision
+                .excluded_periods
+                .contains(&period(1))
+        );
+        assert!(replayed_second.work_order_analytic.user_status_codes.sch);
+        assert!(replayed_second.work_order_analytic.user_status_codes.sece);
+    }
+
+    #[test]
+    fn test_replay_stops_at_an_unknown_work_order()
+    {
+        let known = WorkOrderNumber(2100000001);
+        let mut scheduling_environment = scheduling_environment(&[known]);
+
+        let events = events(vec![
+            SchedulingEnvironmentMutation::LockInPeriod {
+                work_order_numbers: vec![known],
+                period: period(0),
+            },
+            SchedulingEnvironmentMutation::ExcludeFromPeriod {
+                work_order_numbers: vec![known, WorkOrderNumber(1)],
+                period: period(1),
+            },
+        ]);
+
+        let error = scheduling_environment.replay(&events).unwrap_err();
+        assert_eq!(error.to_string(), "Could not replay version 2");
+        assert!(
+            scheduling_environment.work_orders.inner[&known]
+                .planner_decision
+                .excluded_periods
+                .is_empty()
+        );
+    }
+}
@@ -1,4 +1,5 @@
 #![feature(iter_map_windows)]
+pub mod audit_log;
 pub mod time_environment;
 pub mod work_order;
 pub mod worker_environment;
@@ -12,7 +12,7 @@ use crate::work_order::operation::Work;
 
 #[derive(Default, PartialEq, Eq, Serialize, Deserialize, Debug, Clone)]
 pub struct Days {
-    // #[serde(with = "any_key_map")]
+    #[serde(with = "serde_json_any_key::any_key_map")]
     pub days: HashMap<Day, Work>,
 }
 
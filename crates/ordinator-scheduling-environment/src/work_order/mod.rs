@@ -1,5 +1,6 @@
 pub mod display;
 pub mod operation;
+pub mod planner_decision;
 pub mod work_order_analytic;
 pub mod work_order_dates;
 pub mod work_order_info;
@@ -26,6 +27,7 @@ use self::operation::Operation;
 use self::operation::OperationBuilder;
 use self::operation::Operations;
 use self::operation::Work;
+use self::planner_decision::PlannerDecision;
 use self::work_order_analytic::WorkOrderAnalytic;
 use self::work_order_analytic::WorkOrderAnalyticBuilder;
 use self::work_order_analytic::status_codes::MaterialStatus;
@@ -155,6 +157,8 @@ pub struct WorkOrder
     pub work_order_analytic: WorkOrderAnalytic,
     pub work_order_dates: WorkOrderDates,
     pub work_order_info: WorkOrderInfo,
+    #[serde(default)]
+    pub planner_decision: PlannerDecision,
 }
 
 pub struct WorkOrderBuilder
@@ -189,6 +193,7 @@ impl WorkOrderBuilder
             work_order_info: self
                 .work_order_info
                 .expect("Missing field initializations on the WorkOrderBuilder"),
+            planner_decision: PlannerDecision::default(),
         }
     }
 
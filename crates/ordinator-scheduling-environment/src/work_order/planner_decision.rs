@@ -0,0 +1,33 @@
+use std::collections::HashSet;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::time_environment::period::Period;
+
+/// The periods that a planner has locked a work order into or excluded it
+/// from. They are kept on the `WorkOrder` so that they survive a restart of
+/// the actors.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlannerDecision
+{
+    pub locked_in_period: Option<Period>,
+    pub excluded_periods: HashSet<Period>,
+}
+
+impl PlannerDecision
+{
+    pub fn lock_in_period(&mut self, period: Period)
+    {
+        self.excluded_periods.remove(&period);
+        self.locked_in_period = Some(period);
+    }
+
+    pub fn exclude_period(&mut self, period: Period)
+    {
+        if self.locked_in_period.as_ref() == Some(&period) {
+            self.locked_in_period = None;
+        }
+        self.excluded_periods.insert(period);
+    }
+}
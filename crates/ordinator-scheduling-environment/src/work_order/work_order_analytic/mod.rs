@@ -82,6 +82,11 @@ impl WorkOrderAnalytic {
         }
     }
 
+    /// Whether the user or system status code, like `AWSC` or `REL`, is set.
+    pub fn has_status_code(&self, status_code: &str) -> bool {
+        self.user_status_codes.is_set(status_code) || self.system_status_codes.is_set(status_code)
+    }
+
     // TODO [ ]
     pub fn fixed(&self) -> bool {
         todo!(
@@ -89,3 +94,21 @@ impl WorkOrderAnalytic {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::WorkOrderAnalytic;
+
+    #[test]
+    fn test_has_status_code() {
+        let work_order_analytic = WorkOrderAnalytic::builder()
+            .user_status_codes(|user_status_codes| user_status_codes.smat(true))
+            .system_status_codes(|system_status_codes| system_status_codes.rel(true))
+            .build();
+
+        assert!(work_order_analytic.has_status_code("SMAT"));
+        assert!(work_order_analytic.has_status_code("rel"));
+        assert!(!work_order_analytic.has_status_code("AWSC"));
+        assert!(!work_order_analytic.has_status_code("NOT-A-STATUS"));
+    }
+}
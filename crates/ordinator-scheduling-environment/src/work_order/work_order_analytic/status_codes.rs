@@ -86,9 +86,39 @@ impl SystemStatusCodes {
         //
         SystemStatusCodesBuilder(SystemStatusCodes::default())
     }
+
+    /// Whether the status code, like `AWSC`, is set. Unknown status codes are
+    /// never set.
+    pub fn is_set(&self, status_code: &str) -> bool {
+        match status_code.to_uppercase().as_str() {
+            "REL" => self.rel,
+            "PRC" => self.prc,
+            "SETC" => self.setc,
+            "SSAP" => self.ssap,
+            "GMPS" => self.gmps,
+            "MANC" => self.manc,
+            "CRTD" => self.crtd,
+            "NMAT" => self.nmat,
+            "TECO" => self.teco,
+            "MACM" => self.macm,
+            "MSPT" => self.mspt,
+            "PPRT" => self.pprt,
+            "NCMP" => self.ncmp,
+            "CLSD" => self.clsd,
+            "PCNF" => self.pcnf,
+            "CSER" => self.cser,
+            "PRT" => self.prt,
+            "CNF" => self.cnf,
+            "NTUP" => self.ntup,
+            "ESTC" => self.estc,
+            "RELR" => self.relr,
+            "GMCO" => self.gmco,
+            _ => false,
+        }
+    }
 }
 
-#[derive(Default, Args, Clone, Serialize, Deserialize, Debug)]
+#[derive(Default, Args, Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
 pub struct UserStatusCodes {
     #[arg(long)]
     pub appr: bool,
@@ -181,6 +211,57 @@ impl UserStatusCodes {
     pub(crate) fn builder() -> UserStatusCodesBuilder {
         UserStatusCodesBuilder(UserStatusCodes::default())
     }
+
+    /// Whether the status code, like `AWSC`, is set. Unknown status codes are
+    /// never set.
+    pub fn is_set(&self, status_code: &str) -> bool {
+        match status_code.to_uppercase().as_str() {
+            "APPR" => self.appr,
+            "SMAT" => self.smat,
+            "INIT" => self.init,
+            "RDBL" => self.rdbl,
+            "QCAP" => self.qcap,
+            "RFRZ" => self.rfrz,
+            "WMAT" => self.wmat,
+            "CMAT" => self.cmat,
+            "PMAT" => self.pmat,
+            "APOG" => self.apog,
+            "PROK" => self.prok,
+            "WREA" => self.wrea,
+            "EXDO" => self.exdo,
+            "SWE" => self.swe,
+            "AWDO" => self.awdo,
+            "ROUT" => self.rout,
+            "WTA" => self.wta,
+            "SCH" => self.sch,
+            "SECE" => self.sece,
+            "REL" => self.rel,
+            "REES" => self.rees,
+            "REAP" => self.reap,
+            "WREL" => self.wrel,
+            "AWSD" => self.awsd,
+            "SRAA" => self.sraa,
+            "QCRJ" => self.qcrj,
+            "AWSC" => self.awsc,
+            "LPRQ" => self.lprq,
+            "RREV" => self.rrev,
+            "AWCA" => self.awca,
+            "RREQ" => self.rreq,
+            "VFAL" => self.vfal,
+            "SREQ" => self.sreq,
+            "AMCR" => self.amcr,
+            "DFRJ" => self.dfrj,
+            "VPAS" => self.vpas,
+            "DFCR" => self.dfcr,
+            "IREQ" => self.ireq,
+            "ATVD" => self.atvd,
+            "AWMD" => self.awmd,
+            "DFEX" => self.dfex,
+            "DFAP" => self.dfap,
+            "AWPR" => self.awpr,
+            _ => false,
+        }
+    }
 }
 
 pub struct UserStatusCodesBuilder(UserStatusCodes);
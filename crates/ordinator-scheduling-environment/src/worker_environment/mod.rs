@@ -4,9 +4,12 @@ pub mod resources;
 pub mod worker;
 
 use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
 
 use anyhow::Context;
 use anyhow::Result;
+use anyhow::ensure;
 use crew::OperationalConfiguration;
 use resources::Id;
 use serde::Deserialize;
@@ -67,7 +70,7 @@ impl WorkerEnvironmentBuilder
     // Ideally we need to provide a resource file for each of the different.
     // assets. That means that this should be callable many times over for
     // this to work.
-    pub fn actor_environment(mut self, asset: Asset) -> Result<Self>
+    pub fn actor_environment(self, asset: Asset) -> Result<Self>
     {
         // This should then be changed into something different for this to
         // work. You need to put it into the Asset and the ... I think that
@@ -107,31 +110,25 @@ impl WorkerEnvironmentBuilder
         //     ),
         // ];
 
-        // You should put the data into the toml? Yes I think that is the best approach
-        // here.
-        let asset_string = asset.to_string().to_lowercase();
-
-        let path = format!(
-            "./temp_scheduling_environment_database/actor_specifications/actor_specification_{asset_string}.toml",
-        );
+        let actor_specifications = ActorSpecifications::from_asset(&asset)?;
 
-        let contents = std::fs::read_to_string(&path).with_context(|| {
-            format!("Could not read string for ActorSpecification\nPath: {path}")
-        })?;
-        let actor_specifications: ActorSpecifications =
-            toml::from_str(&contents).with_context(|| {
-                format!(
-                    "Could not deserialize into ActorSpecification. File: {}, Line: {}\nContent String\n{contents}",
-                    file!(),
-                    line!()
-                )
-            })?;
+        Ok(self.actor_specifications(asset, actor_specifications))
+    }
 
+    pub fn actor_specifications(
+        mut self,
+        asset: Asset,
+        actor_specifications: ActorSpecifications,
+    ) -> Self
+    {
         self.actor_environment.insert(asset, actor_specifications);
-        Ok(self)
+        self
     }
 }
 
+pub const ACTOR_SPECIFICATIONS_DIRECTORY: &str =
+    "./temp_scheduling_environment_database/actor_specifications";
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ActorSpecifications
 {
@@ -152,6 +149,43 @@ pub struct ActorSpecifications
     pub material_to_period: MaterialToPeriod,
 }
 
+impl ActorSpecifications
+{
+    /// The actor specification file of the `Asset` in `directory`.
+    pub fn path(directory: &Path, asset: &Asset) -> PathBuf
+    {
+        let asset_string = asset.to_string().to_lowercase();
+
+        directory.join(format!("actor_specification_{asset_string}.toml"))
+    }
+
+    // You should put the data into the toml? Yes I think that is the best approach
+    // here.
+    pub fn from_asset(asset: &Asset) -> Result<Self>
+    {
+        Self::from_file(&Self::path(
+            Path::new(ACTOR_SPECIFICATIONS_DIRECTORY),
+            asset,
+        ))
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self>
+    {
+        let contents = std::fs::read_to_string(path).with_context(|| {
+            format!(
+                "Could not read string for ActorSpecification\nPath: {}",
+                path.display()
+            )
+        })?;
+        toml::from_str(&contents).with_context(|| {
+            format!(
+                "Could not deserialize into ActorSpecification. Path: {}\nContent String\n{contents}",
+                path.display()
+            )
+        })
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TimeInput
 {
@@ -165,7 +199,7 @@ pub struct TimeInput
 
 // TODO #00 #00 #03 [x] Move the `./configuration/work_order_parameters.json`
 // here. Is this
-#[derive(Eq, PartialEq, Serialize, Deserialize, Debug)]
+#[derive(PartialEq, Serialize, Deserialize, Debug)]
 pub struct InputStrategic
 {
     pub id: Id,
@@ -173,7 +207,7 @@ pub struct InputStrategic
     pub strategic_options: StrategicOptions,
 }
 
-#[derive(Eq, PartialEq, Serialize, Deserialize, Debug)]
+#[derive(PartialEq, Serialize, Deserialize, Debug)]
 pub struct InputTactical
 {
     pub id: Id,
@@ -181,7 +215,7 @@ pub struct InputTactical
     pub tactical_options: TacticalOptions,
 }
 
-#[derive(Eq, Hash, PartialEq, Serialize, Deserialize, Debug)]
+#[derive(PartialEq, Serialize, Deserialize, Debug)]
 pub struct InputSupervisor
 {
     pub id: Id,
@@ -221,13 +255,17 @@ pub struct InputOperational
 // main thing that you need to have more decoupling.
 //
 // This has to be Clone. Otherwise you will not be able to understand the
-#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
 pub struct StrategicOptions
 {
     pub number_of_removed_work_orders: usize,
     pub urgency_weight: usize,
     pub resource_penalty_weight: usize,
     pub clustering_weight: usize,
+    #[serde(default)]
+    pub acceptance_criterion: AcceptanceCriterion,
+    #[serde(default)]
+    pub portfolio: StrategicPortfolioOptions,
     // These two should go into the `SchedulingEnvironment` that means that
     // the code should strive to... This means that the StrategicAgent, would
     // simply import this directly into itself. There is no need for a
@@ -238,29 +276,319 @@ pub struct StrategicOptions
     // I think that is the correct answer.
 }
 
+/// Runs the strategic LNS as a portfolio of independent workers, each with its
+/// own seed. The workers exchange the best solution every `exchange_interval`
+/// iterations, and only that solution is published. Worker `i` uses
+/// `acceptance_criteria[i % len]`, or the `acceptance_criterion` of the
+/// `StrategicOptions` if the list is empty.
+///
+/// ```toml
+/// strategic_options.portfolio = { number_of_workers = 4, exchange_interval = 20 }
+/// ```
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct StrategicPortfolioOptions
+{
+    pub number_of_workers: usize,
+    pub exchange_interval: usize,
+    pub acceptance_criteria: Vec<AcceptanceCriterion>,
+}
+
+impl Default for StrategicPortfolioOptions
+{
+    /// A single worker is the plain sequential LNS.
+    fn default() -> Self
+    {
+        Self {
+            number_of_workers: 1,
+            exchange_interval: 20,
+            acceptance_criteria: vec![],
+        }
+    }
+}
+
+impl StrategicPortfolioOptions
+{
+    pub fn validate(&self) -> Result<()>
+    {
+        ensure!(
+            self.number_of_workers > 0,
+            "number_of_workers has to be positive"
+        );
+        ensure!(
+            self.exchange_interval > 0,
+            "exchange_interval has to be positive"
+        );
+        self.acceptance_criteria
+            .iter()
+            .try_for_each(AcceptanceCriterion::validate)
+    }
+}
+
 // The `rng` should not be inside of the `ordinator-scheduling-environment`
-#[derive(Eq, Hash, PartialEq, Serialize, Deserialize, Debug, Clone)]
+#[derive(PartialEq, Serialize, Deserialize, Debug, Clone)]
 pub struct TacticalOptions
 {
     pub number_of_removed_work_orders: usize,
     pub urgency: usize,
     pub resource_penalty: usize,
+    #[serde(default)]
+    pub acceptance_criterion: AcceptanceCriterion,
 }
 
-#[derive(Eq, Hash, PartialEq, Serialize, Deserialize, Debug, Clone)]
+#[derive(PartialEq, Serialize, Deserialize, Debug, Clone)]
 pub struct SupervisorOptions
 {
     pub number_of_unassigned_work_orders: usize,
+    #[serde(default)]
+    pub acceptance_criterion: AcceptanceCriterion,
+    #[serde(default)]
+    pub marginal_fitness_weights: MarginalFitnessWeights,
 }
 
+/// Weights of the criteria that the supervisor combines when it ranks the
+/// bids of the operational actors on an activity. The default only looks at
+/// the idle time, as the supervisor did before the other criteria existed.
+///
+/// ```toml
+/// supervisor_options.marginal_fitness_weights = { idle_time = 1, skill_match = 2, continuity = 4, lateness = 1 }
+/// ```
 #[derive(Eq, Hash, PartialEq, Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct MarginalFitnessWeights
+{
+    pub idle_time: u64,
+    pub skill_match: u64,
+    pub continuity: u64,
+    pub lateness: u64,
+}
+
+impl Default for MarginalFitnessWeights
+{
+    fn default() -> Self
+    {
+        Self {
+            idle_time: 1,
+            skill_match: 0,
+            continuity: 0,
+            lateness: 0,
+        }
+    }
+}
+
+#[derive(PartialEq, Serialize, Deserialize, Debug, Clone)]
 pub struct OperationalOptions
 {
     pub number_of_removed_activities: usize,
+    #[serde(default)]
+    pub acceptance_criterion: AcceptanceCriterion,
 }
+
+impl StrategicOptions
+{
+    /// Checks options that are sent to a running actor. The weights may be
+    /// zero, but not all of them at once, as the objective would be constant.
+    pub fn validate(&self) -> Result<()>
+    {
+        ensure!(
+            self.number_of_removed_work_orders > 0,
+            "number_of_removed_work_orders has to be positive"
+        );
+        ensure!(
+            self.urgency_weight + self.resource_penalty_weight + self.clustering_weight > 0,
+            "At least one of the strategic weights has to be positive"
+        );
+        self.portfolio.validate()?;
+        self.acceptance_criterion.validate()
+    }
+}
+
+impl TacticalOptions
+{
+    pub fn validate(&self) -> Result<()>
+    {
+        ensure!(
+            self.number_of_removed_work_orders > 0,
+            "number_of_removed_work_orders has to be positive"
+        );
+        ensure!(
+            self.urgency + self.resource_penalty > 0,
+            "At least one of the tactical weights has to be positive"
+        );
+        self.acceptance_criterion.validate()
+    }
+}
+
+impl SupervisorOptions
+{
+    pub fn validate(&self) -> Result<()>
+    {
+        ensure!(
+            self.number_of_unassigned_work_orders > 0,
+            "number_of_unassigned_work_orders has to be positive"
+        );
+        let weights = &self.marginal_fitness_weights;
+        let weight_sum = [weights.skill_match, weights.continuity, weights.lateness]
+            .into_iter()
+            .try_fold(weights.idle_time, u64::checked_add)
+            .context("The marginal fitness weights overflow")?;
+        ensure!(
+            weight_sum > 0,
+            "At least one of the marginal fitness weights has to be positive"
+        );
+        self.acceptance_criterion.validate()
+    }
+}
+
+impl OperationalOptions
+{
+    pub fn validate(&self) -> Result<()>
+    {
+        ensure!(
+            self.number_of_removed_activities > 0,
+            "number_of_removed_activities has to be positive"
+        );
+        self.acceptance_criterion.validate()
+    }
+}
+
+/// Decides whether a candidate solution from an LNS iteration replaces the
+/// current solution of an `Actor`. Each actor selects its own criterion in its
+/// options, and leaving it out of the `actor_specification_*.toml` keeps the
+/// old hill climbing behavior.
+///
+/// ```toml
+/// strategic_options.acceptance_criterion = { simulated_annealing = { initial_temperature = 1000.0, cooling_rate = 0.999, minimum_temperature = 0.1 } }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AcceptanceCriterion
+{
+    /// Only strictly better candidates are accepted.
+    #[default]
+    HillClimbing,
+    /// Worse candidates are accepted with probability `exp(-delta /
+    /// temperature)`. The temperature is multiplied by `cooling_rate` on
+    /// every iteration and never goes below `minimum_temperature`.
+    SimulatedAnnealing
+    {
+        initial_temperature: f64,
+        cooling_rate: f64,
+        minimum_temperature: f64,
+    },
+    /// A candidate is accepted if it is no worse than the current solution
+    /// or the current solution from `history_length` iterations ago.
+    LateAcceptance
+    {
+        history_length: usize
+    },
+    /// A candidate is accepted if it is within `deviation` (relative) of
+    /// the best objective value found so far.
+    RecordToRecord
+    {
+        deviation: f64
+    },
+}
+
+impl AcceptanceCriterion
+{
+    pub fn validate(&self) -> Result<()>
+    {
+        match self {
+            AcceptanceCriterion::HillClimbing => (),
+            AcceptanceCriterion::SimulatedAnnealing {
+                initial_temperature,
+                cooling_rate,
+                minimum_temperature,
+            } => {
+                ensure!(
+                    *minimum_temperature >= 0.0 && initial_temperature >= minimum_temperature,
+                    "The temperatures have to satisfy 0 <= minimum_temperature <= initial_temperature"
+                );
+                ensure!(
+                    *cooling_rate > 0.0 && *cooling_rate <= 1.0,
+                    "cooling_rate has to be in (0, 1]"
+                );
+            }
+            AcceptanceCriterion::LateAcceptance { history_length } => {
+                ensure!(*history_length > 0, "history_length has to be positive")
+            }
+            AcceptanceCriterion::RecordToRecord { deviation } => {
+                ensure!(
+                    deviation.is_finite() && *deviation >= 0.0,
+                    "deviation has to be a non-negative number"
+                )
+            }
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests
 {
+    use super::AcceptanceCriterion;
+    use super::MarginalFitnessWeights;
+    use super::StrategicOptions;
+    use super::StrategicPortfolioOptions;
+    use super::SupervisorOptions;
+
+    #[test]
+    fn test_strategic_options_validation()
+    {
+        let mut strategic_options = StrategicOptions {
+            number_of_removed_work_orders: 10,
+            urgency_weight: 1,
+            resource_penalty_weight: 0,
+            clustering_weight: 0,
+            acceptance_criterion: AcceptanceCriterion::HillClimbing,
+            portfolio: StrategicPortfolioOptions::default(),
+        };
+        assert!(strategic_options.validate().is_ok());
+
+        strategic_options.portfolio.number_of_workers = 0;
+        assert!(strategic_options.validate().is_err());
+
+        strategic_options.portfolio.number_of_workers = 4;
+        strategic_options.portfolio.acceptance_criteria =
+            vec![AcceptanceCriterion::LateAcceptance { history_length: 0 }];
+        assert!(strategic_options.validate().is_err());
+
+        strategic_options.portfolio = StrategicPortfolioOptions::default();
+
+        strategic_options.urgency_weight = 0;
+        assert!(strategic_options.validate().is_err());
+
+        strategic_options.urgency_weight = 1;
+        strategic_options.acceptance_criterion = AcceptanceCriterion::SimulatedAnnealing {
+            initial_temperature: 10.0,
+            cooling_rate: 1.5,
+            minimum_temperature: 0.1,
+        };
+        assert!(strategic_options.validate().is_err());
+    }
+
+    #[test]
+    fn test_supervisor_options_validation()
+    {
+        let mut supervisor_options = SupervisorOptions {
+            number_of_unassigned_work_orders: 10,
+            acceptance_criterion: AcceptanceCriterion::HillClimbing,
+            marginal_fitness_weights: MarginalFitnessWeights::default(),
+        };
+        assert!(supervisor_options.validate().is_ok());
+
+        supervisor_options.marginal_fitness_weights.idle_time = 0;
+        assert!(supervisor_options.validate().is_err());
+
+        supervisor_options.marginal_fitness_weights = MarginalFitnessWeights {
+            idle_time: u64::MAX,
+            skill_match: 1,
+            continuity: 0,
+            lateness: 0,
+        };
+        assert!(supervisor_options.validate().is_err());
+    }
 
     // #[test]
     // fn test_toml_operational_parsing()
@@ -10,17 +10,19 @@ use std::sync::Mutex;
 use anyhow::Context;
 use anyhow::Result;
 use ordinator_configuration::SystemConfigurations;
-use ordinator_scheduling_environment::Asset;
 use ordinator_scheduling_environment::IntoSchedulingEnvironment;
 use ordinator_scheduling_environment::SchedulingEnvironment;
 use ordinator_scheduling_environment::work_order::WorkOrderNumber;
 use ordinator_scheduling_environment::work_order::operation::ActivityNumber;
 use ordinator_scheduling_environment::work_order::operation::operation_info::NumberOfPeople;
 use ordinator_scheduling_environment::work_order::work_order_info::work_order_type::WorkOrderType;
+use ordinator_scheduling_environment::worker_environment::ActorSpecifications;
 use ordinator_scheduling_environment::worker_environment::TimeInput;
 use ordinator_scheduling_environment::worker_environment::WorkerEnvironment;
 use serde::Deserialize;
 use serde::de::DeserializeOwned;
+use tracing::Level;
+use tracing::event;
 
 use super::baptiste_csv_reader_merges::load_csv_data;
 use super::create_time_environment;
@@ -66,12 +68,26 @@ impl IntoSchedulingEnvironment for TotalSap
             format!("Could not deserialize the TimeInput config. Input:\n{time_input_string}")
         })?;
 
+        // An `Asset` with an invalid `ActorSpecifications` file is left out, so
+        // that it cannot be started, instead of stopping every other `Asset`.
+        let mut worker_environment = WorkerEnvironment::builder();
+        for asset in &system_configuration.assets.available {
+            match ActorSpecifications::from_asset(asset) {
+                Ok(actor_specifications) => {
+                    worker_environment =
+                        worker_environment.actor_specifications(asset.clone(), actor_specifications)
+                }
+                Err(error) => event!(
+                    Level::ERROR,
+                    %asset,
+                    error = format!("{error:#}"),
+                    "skipping the Asset as its ActorSpecifications could not be loaded"
+                ),
+            }
+        }
+
         Ok(SchedulingEnvironment::builder()
-            .worker_environment(
-                WorkerEnvironment::builder()
-                    .actor_environment(Asset::DF)?
-                    .build(), // Add more assets here.
-            )
+            .worker_environment(worker_environment.build())
             .time_environment(create_time_environment(&time_input))
             .work_orders(
                 load_csv_data(&system_configuration.data_locations)
@@ -475,6 +491,10 @@ impl WorkOperations
 #[cfg(test)]
 mod tests
 {
+    use std::path::Path;
+
+    use ordinator_configuration::assets::AssetsConfiguration;
+    use ordinator_scheduling_environment::worker_environment::ACTOR_SPECIFICATIONS_DIRECTORY;
 
     use super::*;
 
@@ -487,4 +507,18 @@ mod tests
 
         populate_csv_structures::<WorkOperationsCsv>(&path).unwrap();
     }
+
+    #[test]
+    fn test_actor_specifications_of_available_assets_are_valid()
+    {
+        let assets_contents =
+            std::fs::read_to_string("../../configuration/assets/assets.toml").unwrap();
+        let assets_configuration: AssetsConfiguration = toml::from_str(&assets_contents).unwrap();
+
+        let directory = Path::new("../..").join(ACTOR_SPECIFICATIONS_DIRECTORY);
+        for asset in &assets_configuration.available {
+            ActorSpecifications::from_file(&ActorSpecifications::path(&directory, asset))
+                .unwrap_or_else(|error| panic!("Asset {asset}: {error:#}"));
+        }
+    }
 }